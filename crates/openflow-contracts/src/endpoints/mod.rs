@@ -344,6 +344,50 @@ pub static ENDPOINTS: &[Endpoint] = &[
         description: "Duplicate a task",
         tags: &["tasks", "duplicate"],
     },
+    Endpoint {
+        command: "cleanup_task_worktrees",
+        method: HttpMethod::Post,
+        path: "/api/tasks/:id/cleanup",
+        request_type: Some("CleanupWorktreeRequest"),
+        response_type: "Chat[]",
+        path_params: &["id"],
+        query_params: &[],
+        description: "Run the cleanup script and remove the worktrees of a task's chats",
+        tags: &["tasks", "worktree"],
+    },
+    Endpoint {
+        command: "get_task_workflow",
+        method: HttpMethod::Get,
+        path: "/api/tasks/:id/workflow",
+        request_type: None,
+        response_type: "WorkflowRun",
+        path_params: &["id"],
+        query_params: &[],
+        description: "Get the workflow run of a task with the status and chat of every step",
+        tags: &["tasks", "workflow", "read"],
+    },
+    Endpoint {
+        command: "start_task_workflow",
+        method: HttpMethod::Post,
+        path: "/api/tasks/:id/workflow/start",
+        request_type: None,
+        response_type: "WorkflowRun",
+        path_params: &["id"],
+        query_params: &[],
+        description: "Start or resume the workflow of a task",
+        tags: &["tasks", "workflow"],
+    },
+    Endpoint {
+        command: "run_task_workflow_step",
+        method: HttpMethod::Post,
+        path: "/api/tasks/:id/workflow/steps/:stepIndex/run",
+        request_type: None,
+        response_type: "ExecutionProcess",
+        path_params: &["id", "stepIndex"],
+        query_params: &[],
+        description: "Run a step of a task's workflow in its chat",
+        tags: &["tasks", "workflow"],
+    },
     // ==========================================================================
     // ARTIFACTS (Task artifacts)
     // ==========================================================================
@@ -504,6 +548,72 @@ pub static ENDPOINTS: &[Endpoint] = &[
         description: "Start a workflow step by creating its worktree",
         tags: &["chats", "workflow"],
     },
+    Endpoint {
+        command: "cleanup_chat_worktree",
+        method: HttpMethod::Post,
+        path: "/api/chats/:id/cleanup",
+        request_type: Some("CleanupWorktreeRequest"),
+        response_type: "Chat",
+        path_params: &["id"],
+        query_params: &[],
+        description: "Run the cleanup script and remove a chat's worktree",
+        tags: &["chats", "worktree"],
+    },
+    Endpoint {
+        command: "verify_chat",
+        method: HttpMethod::Post,
+        path: "/api/chats/:id/verify",
+        request_type: None,
+        response_type: "VerificationResult",
+        path_params: &["id"],
+        query_params: &[],
+        description: "Run the project's verification checks in a chat's worktree",
+        tags: &["chats", "verification"],
+    },
+    Endpoint {
+        command: "list_chat_verifications",
+        method: HttpMethod::Get,
+        path: "/api/chats/:id/verifications",
+        request_type: None,
+        response_type: "VerificationResult[]",
+        path_params: &["id"],
+        query_params: &[],
+        description: "List the verification results of a chat, newest first",
+        tags: &["chats", "verification"],
+    },
+    Endpoint {
+        command: "get_dev_server",
+        method: HttpMethod::Get,
+        path: "/api/chats/:id/dev-server",
+        request_type: None,
+        response_type: "ExecutionProcess",
+        path_params: &["id"],
+        query_params: &[],
+        description: "Get the running dev server of a chat, if any",
+        tags: &["chats", "dev-server"],
+    },
+    Endpoint {
+        command: "start_dev_server",
+        method: HttpMethod::Post,
+        path: "/api/chats/:id/dev-server",
+        request_type: None,
+        response_type: "ExecutionProcess",
+        path_params: &["id"],
+        query_params: &[],
+        description: "Start the project's dev server in a chat's worktree",
+        tags: &["chats", "dev-server"],
+    },
+    Endpoint {
+        command: "stop_dev_server",
+        method: HttpMethod::Delete,
+        path: "/api/chats/:id/dev-server",
+        request_type: None,
+        response_type: "ExecutionProcess",
+        path_params: &["id"],
+        query_params: &[],
+        description: "Stop the dev server of a chat",
+        tags: &["chats", "dev-server"],
+    },
     // ==========================================================================
     // MESSAGES
     // ==========================================================================
@@ -620,6 +730,39 @@ pub static ENDPOINTS: &[Endpoint] = &[
         description: "Get a process by ID",
         tags: &["processes", "read"],
     },
+    Endpoint {
+        command: "get_process_output",
+        method: HttpMethod::Get,
+        path: "/api/processes/:id/output",
+        request_type: None,
+        response_type: "ProcessOutputChunk[]",
+        path_params: &["id"],
+        query_params: &["since", "limit"],
+        description: "Replay persisted output chunks for a process",
+        tags: &["processes", "read"],
+    },
+    Endpoint {
+        command: "get_process_snapshot",
+        method: HttpMethod::Get,
+        path: "/api/processes/:id/snapshot",
+        request_type: None,
+        response_type: "ProcessSnapshot",
+        path_params: &["id"],
+        query_params: &[],
+        description: "Get recent terminal output and size of a running process, for reattaching",
+        tags: &["processes", "read"],
+    },
+    Endpoint {
+        command: "export_process_recording",
+        method: HttpMethod::Get,
+        path: "/api/processes/:id/recording.cast",
+        request_type: None,
+        response_type: "string",
+        path_params: &["id"],
+        query_params: &[],
+        description: "Download the asciicast v2 recording of a PTY process",
+        tags: &["processes", "read"],
+    },
     Endpoint {
         command: "kill_process",
         method: HttpMethod::Post,
@@ -675,6 +818,17 @@ pub static ENDPOINTS: &[Endpoint] = &[
         description: "Get count of running processes",
         tags: &["processes", "status"],
     },
+    Endpoint {
+        command: "list_run_queue",
+        method: HttpMethod::Get,
+        path: "/api/processes/queue",
+        request_type: None,
+        response_type: "QueuedProcess[]",
+        path_params: &[],
+        query_params: &[],
+        description: "List coding agent runs waiting in the run queue",
+        tags: &["processes", "queue"],
+    },
     // ==========================================================================
     // EXECUTOR PROFILES
     // ==========================================================================
@@ -839,6 +993,42 @@ pub static ENDPOINTS: &[Endpoint] = &[
         tags: &["settings", "read"],
     },
     // ==========================================================================
+    // SECRETS
+    // ==========================================================================
+    Endpoint {
+        command: "list_secrets",
+        method: HttpMethod::Get,
+        path: "/api/secrets",
+        request_type: None,
+        response_type: "Secret[]",
+        path_params: &[],
+        query_params: &[],
+        description: "List all secrets by name",
+        tags: &["secrets", "list"],
+    },
+    Endpoint {
+        command: "set_secret",
+        method: HttpMethod::Put,
+        path: "/api/secrets/:name",
+        request_type: Some("SetSecretRequest"),
+        response_type: "Secret",
+        path_params: &["name"],
+        query_params: &[],
+        description: "Create or replace a secret",
+        tags: &["secrets", "update"],
+    },
+    Endpoint {
+        command: "delete_secret",
+        method: HttpMethod::Delete,
+        path: "/api/secrets/:name",
+        request_type: None,
+        response_type: "void",
+        path_params: &["name"],
+        query_params: &[],
+        description: "Delete a secret",
+        tags: &["secrets", "delete"],
+    },
+    // ==========================================================================
     // GIT
     // ==========================================================================
     Endpoint {
@@ -1059,6 +1249,28 @@ pub static ENDPOINTS: &[Endpoint] = &[
         description: "Parse workflow content into a template",
         tags: &["workflows", "parse"],
     },
+    Endpoint {
+        command: "list_workflow_variables",
+        method: HttpMethod::Post,
+        path: "/api/workflows/variables",
+        request_type: None,
+        response_type: "WorkflowVariableInfo[]",
+        path_params: &[],
+        query_params: &["content"],
+        description: "List the variables a workflow declares or uses",
+        tags: &["workflows", "parse"],
+    },
+    Endpoint {
+        command: "lint_workflow",
+        method: HttpMethod::Post,
+        path: "/api/workflows/lint",
+        request_type: None,
+        response_type: "WorkflowDiagnostic[]",
+        path_params: &[],
+        query_params: &["content"],
+        description: "Lint workflow content, with line-numbered diagnostics",
+        tags: &["workflows", "parse"],
+    },
     // ==========================================================================
     // SYSTEM
     // ==========================================================================
@@ -1101,6 +1313,7 @@ pub fn endpoints_by_domain() -> std::collections::HashMap<&'static str, Vec<&'st
         "executor",
         "terminal",
         "settings",
+        "secrets",
         "git",
         "github",
         "search",
@@ -7,6 +7,7 @@
 use serde::{Deserialize, Serialize};
 use typeshare::typeshare;
 
+use crate::entities::executor::CliToolType;
 use crate::validation::{
     validate_required_string, validate_string_length, Validate, ValidationCollector,
     ValidationResult,
@@ -212,6 +213,10 @@ pub struct Chat {
     /// @validate: max_length=255
     pub claude_session_id: Option<String>,
 
+    /// CLI tool that created the session (null if unknown, in which case
+    /// the session is never resumed)
+    pub session_cli_tool: Option<CliToolType>,
+
     /// Timestamp when the chat was archived (soft-delete)
     /// null means the chat is active
     pub archived_at: Option<String>,
@@ -259,6 +264,14 @@ impl Chat {
         self.claude_session_id.is_some()
     }
 
+    /// Get the session ID to resume with a CLI tool, if the session was
+    /// created by that tool
+    pub fn session_for(&self, tool: CliToolType) -> Option<&str> {
+        self.claude_session_id
+            .as_deref()
+            .filter(|_| self.session_cli_tool == Some(tool))
+    }
+
     /// Get the branch name, generating one if not set
     pub fn get_branch(&self) -> Option<&str> {
         self.branch.as_deref()
@@ -407,6 +420,10 @@ pub struct ChatWithMessages {
 
     /// All messages in this chat, ordered by created_at ASC
     pub messages: Vec<super::message::Message>,
+
+    /// Latest verification result of the chat (null if it was never verified)
+    #[serde(default)]
+    pub latest_verification: Option<super::verification::VerificationResult>,
 }
 
 impl ChatWithMessages {
@@ -415,12 +432,26 @@ impl ChatWithMessages {
         Self {
             chat,
             messages: Vec::new(),
+            latest_verification: None,
         }
     }
 
     /// Create with messages
     pub fn with_messages(chat: Chat, messages: Vec<super::message::Message>) -> Self {
-        Self { chat, messages }
+        Self {
+            chat,
+            messages,
+            latest_verification: None,
+        }
+    }
+
+    /// Set the latest verification result
+    pub fn with_latest_verification(
+        mut self,
+        verification: Option<super::verification::VerificationResult>,
+    ) -> Self {
+        self.latest_verification = verification;
+        self
     }
 
     /// Get the number of messages
@@ -528,6 +559,7 @@ mod tests {
             main_chat_id: None,
             workflow_step_index: Some(0),
             claude_session_id: Some("session-123".to_string()),
+            session_cli_tool: Some(CliToolType::ClaudeCode),
             archived_at: None,
             created_at: "2024-01-15T10:30:00Z".to_string(),
             updated_at: "2024-01-15T10:30:00Z".to_string(),
@@ -553,6 +585,7 @@ mod tests {
             main_chat_id: None,
             workflow_step_index: None,
             claude_session_id: None,
+            session_cli_tool: None,
             archived_at: None,
             created_at: "2024-01-15T10:30:00Z".to_string(),
             updated_at: "2024-01-15T10:30:00Z".to_string(),
@@ -694,6 +727,19 @@ mod tests {
         assert!(!chat.has_session());
     }
 
+    #[test]
+    fn test_chat_session_for() {
+        let mut chat = create_test_chat();
+        assert_eq!(
+            chat.session_for(CliToolType::ClaudeCode),
+            Some("session-123")
+        );
+        assert_eq!(chat.session_for(CliToolType::CodexCli), None);
+
+        chat.session_cli_tool = None;
+        assert_eq!(chat.session_for(CliToolType::ClaudeCode), None);
+    }
+
     #[test]
     fn test_chat_get_branch() {
         let mut chat = create_test_chat();
@@ -18,6 +18,11 @@
 //!     args: Some(r#"["--output-format", "stream-json"]"#.to_string()),
 //!     env: None,
 //!     model: Some("claude-sonnet-4".to_string()),
+//!     cli_tool: None,
+//!     max_runtime_secs: None,
+//!     idle_timeout_secs: None,
+//!     resource_limits: None,
+//!     sandbox: None,
 //!     is_default: true,
 //!     created_at: "2024-01-01T00:00:00Z".to_string(),
 //!     updated_at: "2024-01-01T00:00:00Z".to_string(),
@@ -27,6 +32,9 @@
 use serde::{Deserialize, Serialize};
 use typeshare::typeshare;
 
+use super::limits::ResourceLimits;
+use super::sandbox::SandboxSettings;
+
 /// Executor profile representing a CLI tool configuration for AI coding agents.
 ///
 /// Profiles store the command, arguments, environment variables, and model
@@ -38,6 +46,11 @@ use typeshare::typeshare;
 /// * `args` - JSON array of default arguments passed to the CLI
 /// * `env` - JSON object of environment variables to set when running
 /// * `model` - Optional model identifier for AI APIs (e.g., "claude-sonnet-4")
+/// * `cli_tool` - CLI tool type selecting the executor adapter (inferred from `command` if unset)
+/// * `max_runtime_secs` - Wall-clock limit for a run, after which it is killed
+/// * `idle_timeout_secs` - Limit on time without output, after which a run is killed
+/// * `resource_limits` - JSON object of `ResourceLimits` applied to each run
+/// * `sandbox` - JSON object of `SandboxSettings` confining each run
 /// * `is_default` - Whether this is the default profile for new tasks (only one can be default)
 ///
 /// @entity
@@ -66,7 +79,9 @@ pub struct ExecutorProfile {
     /// @validate: max_length=10000, format=json_array
     pub args: Option<String>,
 
-    /// JSON object of environment variables to set when running
+    /// JSON object of environment variables to set when running.
+    /// Values may reference OpenFlow's own environment as `${env:NAME}` and
+    /// stored secrets as `${secret:NAME}`.
     /// @validate: max_length=10000, format=json_object
     pub env: Option<String>,
 
@@ -74,6 +89,26 @@ pub struct ExecutorProfile {
     /// @validate: max_length=255
     pub model: Option<String>,
 
+    /// CLI tool type used to drive the command
+    /// If not set, the tool type is inferred from the command
+    pub cli_tool: Option<CliToolType>,
+
+    /// Maximum run time in seconds before a run is killed as timed out
+    /// None or 0 means no limit
+    pub max_runtime_secs: Option<u32>,
+
+    /// Maximum time in seconds without output before a run is killed as
+    /// timed out. None or 0 means no limit
+    pub idle_timeout_secs: Option<u32>,
+
+    /// JSON object of resource limits (`ResourceLimits`) for each run
+    /// @validate: max_length=1000, format=json_object
+    pub resource_limits: Option<String>,
+
+    /// JSON object of sandbox settings (`SandboxSettings`) for each run
+    /// @validate: max_length=10000, format=json_object
+    pub sandbox: Option<String>,
+
     /// Whether this is the default profile for new tasks
     /// Only one profile can be default at a time
     pub is_default: bool,
@@ -101,6 +136,36 @@ impl ExecutorProfile {
         self.model.as_ref().is_some_and(|m| !m.is_empty())
     }
 
+    /// Get the CLI tool type for this profile
+    /// Falls back to inferring the tool from the command if not set explicitly
+    pub fn tool_type(&self) -> CliToolType {
+        self.cli_tool
+            .unwrap_or_else(|| CliToolType::from_command(&self.command))
+    }
+
+    /// Get the wall-clock limit for a run, if one is set
+    pub fn max_runtime(&self) -> Option<std::time::Duration> {
+        positive_secs(self.max_runtime_secs)
+    }
+
+    /// Get the idle-output limit for a run, if one is set
+    pub fn idle_timeout(&self) -> Option<std::time::Duration> {
+        positive_secs(self.idle_timeout_secs)
+    }
+
+    /// Parse the resource_limits JSON string
+    /// Returns no limits if resource_limits is None or invalid JSON
+    pub fn parse_resource_limits(&self) -> ResourceLimits {
+        ResourceLimits::from_json(self.resource_limits.as_deref())
+    }
+
+    /// Parse the sandbox JSON string
+    /// Returns disabled settings if sandbox is None, and an error if it is
+    /// invalid JSON
+    pub fn parse_sandbox(&self) -> Result<SandboxSettings, serde_json::Error> {
+        SandboxSettings::from_json(self.sandbox.as_deref())
+    }
+
     /// Parse the args JSON string into a Vec of strings
     /// Returns an empty Vec if args is None or invalid JSON
     pub fn parse_args(&self) -> Vec<String> {
@@ -120,6 +185,12 @@ impl ExecutorProfile {
     }
 }
 
+/// Convert an optional seconds value to a duration, treating 0 as unset
+fn positive_secs(secs: Option<u32>) -> Option<std::time::Duration> {
+    secs.filter(|secs| *secs > 0)
+        .map(|secs| std::time::Duration::from_secs(secs.into()))
+}
+
 /// Summary view of an executor profile for list views
 ///
 /// Contains only the essential fields needed for selection UIs
@@ -175,8 +246,9 @@ impl From<&ExecutorProfile> for ExecutorProfileSummary {
 /// These are the common CLI tools that OpenFlow supports.
 /// Custom tools can use any command string in the profile.
 #[typeshare]
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, sqlx::Type)]
 #[serde(rename_all = "snake_case")]
+#[sqlx(rename_all = "snake_case")]
 pub enum CliToolType {
     /// Anthropic's Claude Code CLI
     ClaudeCode,
@@ -242,6 +314,11 @@ mod tests {
             args: Some(r#"["--verbose", "--no-confirm"]"#.to_string()),
             env: Some(r#"{"API_KEY": "test123"}"#.to_string()),
             model: Some("claude-sonnet-4".to_string()),
+            cli_tool: None,
+            max_runtime_secs: None,
+            idle_timeout_secs: None,
+            resource_limits: None,
+            sandbox: None,
             is_default: true,
             created_at: "2024-01-01T00:00:00Z".to_string(),
             updated_at: "2024-01-01T00:00:00Z".to_string(),
@@ -309,6 +386,18 @@ mod tests {
         assert!(!profile.has_model());
     }
 
+    #[test]
+    fn test_tool_type() {
+        let mut profile = sample_profile();
+        assert_eq!(profile.tool_type(), CliToolType::ClaudeCode);
+
+        profile.command = "/usr/local/bin/codex".to_string();
+        assert_eq!(profile.tool_type(), CliToolType::CodexCli);
+
+        profile.cli_tool = Some(CliToolType::GeminiCli);
+        assert_eq!(profile.tool_type(), CliToolType::GeminiCli);
+    }
+
     #[test]
     fn test_parse_args() {
         let profile = sample_profile();
@@ -348,6 +437,32 @@ mod tests {
         assert!(profile.parse_env().is_empty());
     }
 
+    #[test]
+    fn test_parse_resource_limits() {
+        let mut profile = sample_profile();
+        assert!(profile.parse_resource_limits().is_empty());
+
+        profile.resource_limits = Some(r#"{"cpuSecs": 60, "openFiles": 256}"#.to_string());
+        let limits = profile.parse_resource_limits();
+        assert_eq!(limits.cpu_secs, Some(60));
+        assert_eq!(limits.open_files, Some(256));
+        assert!(limits.address_space_mb.is_none());
+    }
+
+    #[test]
+    fn test_parse_sandbox() {
+        let mut profile = sample_profile();
+        assert!(!profile.parse_sandbox().unwrap().enabled);
+
+        profile.sandbox = Some(r#"{"enabled": true, "allowNetwork": true}"#.to_string());
+        let sandbox = profile.parse_sandbox().unwrap();
+        assert!(sandbox.enabled);
+        assert!(sandbox.allow_network);
+
+        profile.sandbox = Some("{enabled: true}".to_string());
+        assert!(profile.parse_sandbox().is_err());
+    }
+
     #[test]
     fn test_executor_profile_summary_from() {
         let profile = sample_profile();
@@ -407,4 +522,23 @@ mod tests {
         let parsed: CliToolType = serde_json::from_str(&json).unwrap();
         assert_eq!(parsed, CliToolType::ClaudeCode);
     }
+
+    #[test]
+    fn test_timeouts() {
+        let mut profile = sample_profile();
+        assert!(profile.max_runtime().is_none());
+        assert!(profile.idle_timeout().is_none());
+
+        profile.max_runtime_secs = Some(600);
+        profile.idle_timeout_secs = Some(0);
+        assert_eq!(
+            profile.max_runtime(),
+            Some(std::time::Duration::from_secs(600))
+        );
+        assert!(profile.idle_timeout().is_none());
+
+        let json = serde_json::to_string(&profile).unwrap();
+        assert!(json.contains(r#""maxRuntimeSecs":600"#));
+        assert!(json.contains(r#""idleTimeoutSecs":0"#));
+    }
 }
@@ -0,0 +1,148 @@
+//! Resource Limits
+//!
+//! Optional caps on what a spawned process tree may use, enforced with
+//! rlimits. Limits are configured per executor profile
+//! (`executor_profiles.resource_limits`) and per project script
+//! (`projects.script_limits`), both stored as JSON.
+//!
+//! A run that hits a limit is recorded as failed, with a status reason
+//! naming the limit.
+
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+// =============================================================================
+// ResourceLimits
+// =============================================================================
+
+/// Resource limits for a process and everything it starts
+///
+/// Unset and zero values mean no limit.
+///
+/// # Example
+/// ```json
+/// {
+///   "addressSpaceMb": 4096,
+///   "cpuSecs": 1800,
+///   "openFiles": 1024,
+///   "processes": 512
+/// }
+/// ```
+#[typeshare]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceLimits {
+    /// Maximum address space (virtual memory) per process, in MB
+    pub address_space_mb: Option<u32>,
+
+    /// Maximum CPU time per process, in seconds
+    pub cpu_secs: Option<u32>,
+
+    /// Maximum number of open files per process
+    pub open_files: Option<u32>,
+
+    /// Maximum number of processes for the user running the app
+    pub processes: Option<u32>,
+}
+
+impl ResourceLimits {
+    /// Check if no limit is set
+    pub fn is_empty(&self) -> bool {
+        [
+            self.address_space_mb,
+            self.cpu_secs,
+            self.open_files,
+            self.processes,
+        ]
+        .iter()
+        .all(|value| value.unwrap_or(0) == 0)
+    }
+
+    /// Parse limits from a JSON column
+    /// Returns no limits if the JSON is missing or invalid
+    pub fn from_json(json: Option<&str>) -> Self {
+        json.and_then(|json| serde_json::from_str(json).ok())
+            .unwrap_or_default()
+    }
+}
+
+// =============================================================================
+// ProjectScriptLimits
+// =============================================================================
+
+/// Resource limits for each of a project's scripts
+///
+/// # Example
+/// ```json
+/// {
+///   "setup": { "cpuSecs": 600 },
+///   "dev": { "addressSpaceMb": 8192 },
+///   "cleanup": null
+/// }
+/// ```
+#[typeshare]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectScriptLimits {
+    /// Limits for the setup script
+    pub setup: Option<ResourceLimits>,
+
+    /// Limits for the dev server script
+    pub dev: Option<ResourceLimits>,
+
+    /// Limits for the cleanup script
+    pub cleanup: Option<ResourceLimits>,
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resource_limits_serialization() {
+        let limits = ResourceLimits {
+            address_space_mb: Some(4096),
+            cpu_secs: Some(60),
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&limits).unwrap();
+        assert!(json.contains("\"addressSpaceMb\":4096"));
+        assert!(json.contains("\"cpuSecs\":60"));
+
+        let parsed = ResourceLimits::from_json(Some(&json));
+        assert_eq!(parsed, limits);
+    }
+
+    #[test]
+    fn test_resource_limits_is_empty() {
+        assert!(ResourceLimits::default().is_empty());
+        assert!(ResourceLimits {
+            open_files: Some(0),
+            ..Default::default()
+        }
+        .is_empty());
+        assert!(!ResourceLimits {
+            processes: Some(64),
+            ..Default::default()
+        }
+        .is_empty());
+    }
+
+    #[test]
+    fn test_from_json_invalid() {
+        assert!(ResourceLimits::from_json(None).is_empty());
+        assert!(ResourceLimits::from_json(Some("not json")).is_empty());
+    }
+
+    #[test]
+    fn test_project_script_limits_partial() {
+        let limits: ProjectScriptLimits =
+            serde_json::from_str(r#"{"dev": {"cpuSecs": 10}}"#).unwrap();
+        assert!(limits.setup.is_none());
+        assert_eq!(limits.dev.unwrap().cpu_secs, Some(10));
+    }
+}
@@ -8,12 +8,16 @@ pub mod artifact;
 pub mod chat;
 pub mod executor;
 pub mod git;
+pub mod limits;
 pub mod message;
 pub mod process;
 pub mod project;
+pub mod sandbox;
 pub mod search;
+pub mod secret;
 pub mod settings;
 pub mod task;
+pub mod verification;
 pub mod workflow;
 
 // Re-export entity types for convenience
@@ -25,16 +29,21 @@ pub use git::{
     GitFileStatus, GitStatus, GitStatusFile, PullRequestResult, Worktree, WorktreeStatus,
     WorktreeSummary,
 };
+pub use limits::{ProjectScriptLimits, ResourceLimits};
 pub use message::{Message, MessageRole, MessageSummary};
 pub use process::{
-    ExecutionProcess, OutputType, ProcessOutputEvent, ProcessStatus, ProcessStatusEvent,
-    ProcessSummary, RunReason,
+    ExecutionProcess, OutputType, ProcessOutputChunk, ProcessOutputEvent, ProcessSnapshot,
+    ProcessStatus, ProcessStatusEvent, ProcessSummary, QueuedProcess, RunReason,
 };
 pub use project::{Project, ProjectSummary, ProjectWithStats};
+pub use sandbox::{ProcessSandbox, SandboxSettings};
 pub use search::{SearchResult, SearchResultType};
+pub use secret::Secret;
 pub use settings::{Setting, SettingsMap};
 pub use task::{Task, TaskStatus, TaskSummary, TaskWithChatCount, TaskWithChats};
+pub use verification::{VerificationCheck, VerificationCheckStatus, VerificationResult};
 pub use workflow::{
-    WorkflowContext, WorkflowStep, WorkflowStepStatus, WorkflowTemplate, WorkflowTemplateSummary,
-    WorkflowVariable,
+    WorkflowContext, WorkflowDiagnostic, WorkflowDiagnosticCode, WorkflowDiagnosticSeverity,
+    WorkflowRun, WorkflowStep, WorkflowStepStatus, WorkflowTemplate, WorkflowTemplateSummary,
+    WorkflowVariable, WorkflowVariableInfo, WorkflowVariableType,
 };
@@ -21,12 +21,15 @@ use crate::validation::{
 /// Tracks the lifecycle state of a running or completed process.
 ///
 /// # Serialization
-/// Serialized as lowercase strings: "running", "completed", "failed", "killed"
+/// Serialized as lowercase strings: "queued", "running", "completed", "failed", "killed",
+/// "timedout"
 #[typeshare]
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Hash, sqlx::Type)]
 #[serde(rename_all = "lowercase")]
 #[sqlx(rename_all = "lowercase")]
 pub enum ProcessStatus {
+    /// Process is waiting in the run queue for a free slot
+    Queued,
     /// Process is currently running
     #[default]
     Running,
@@ -36,15 +39,19 @@ pub enum ProcessStatus {
     Failed,
     /// Process was manually killed/terminated
     Killed,
+    /// Process was killed for exceeding its max runtime or idle timeout
+    TimedOut,
 }
 
 impl std::fmt::Display for ProcessStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            ProcessStatus::Queued => write!(f, "queued"),
             ProcessStatus::Running => write!(f, "running"),
             ProcessStatus::Completed => write!(f, "completed"),
             ProcessStatus::Failed => write!(f, "failed"),
             ProcessStatus::Killed => write!(f, "killed"),
+            ProcessStatus::TimedOut => write!(f, "timedout"),
         }
     }
 }
@@ -54,10 +61,12 @@ impl std::str::FromStr for ProcessStatus {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_lowercase().as_str() {
+            "queued" => Ok(ProcessStatus::Queued),
             "running" => Ok(ProcessStatus::Running),
             "completed" => Ok(ProcessStatus::Completed),
             "failed" => Ok(ProcessStatus::Failed),
             "killed" => Ok(ProcessStatus::Killed),
+            "timedout" => Ok(ProcessStatus::TimedOut),
             _ => Err(format!("Invalid process status: {}", s)),
         }
     }
@@ -72,6 +81,11 @@ impl TryFrom<String> for ProcessStatus {
 }
 
 impl ProcessStatus {
+    /// Check if the process is waiting in the run queue
+    pub fn is_queued(&self) -> bool {
+        matches!(self, ProcessStatus::Queued)
+    }
+
     /// Check if the process is currently running
     pub fn is_running(&self) -> bool {
         matches!(self, ProcessStatus::Running)
@@ -92,23 +106,33 @@ impl ProcessStatus {
         matches!(self, ProcessStatus::Killed)
     }
 
-    /// Check if the process has finished (not running)
+    /// Check if the process was killed for timing out
+    pub fn is_timed_out(&self) -> bool {
+        matches!(self, ProcessStatus::TimedOut)
+    }
+
+    /// Check if the process has finished (not queued or running)
     pub fn is_finished(&self) -> bool {
-        !self.is_running()
+        !self.is_running() && !self.is_queued()
     }
 
-    /// Check if the process ended with an error (failed or killed)
+    /// Check if the process ended with an error (failed, killed or timed out)
     pub fn is_error(&self) -> bool {
-        matches!(self, ProcessStatus::Failed | ProcessStatus::Killed)
+        matches!(
+            self,
+            ProcessStatus::Failed | ProcessStatus::Killed | ProcessStatus::TimedOut
+        )
     }
 
     /// Get all possible process status values
     pub fn all() -> &'static [ProcessStatus] {
         &[
+            ProcessStatus::Queued,
             ProcessStatus::Running,
             ProcessStatus::Completed,
             ProcessStatus::Failed,
             ProcessStatus::Killed,
+            ProcessStatus::TimedOut,
         ]
     }
 }
@@ -241,8 +265,9 @@ impl RunReason {
 /// # Serialization
 /// Serialized as lowercase strings: "stdout", "stderr"
 #[typeshare]
-#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Hash, sqlx::Type)]
 #[serde(rename_all = "lowercase")]
+#[sqlx(rename_all = "lowercase")]
 pub enum OutputType {
     /// Standard output stream
     #[default]
@@ -319,11 +344,13 @@ impl OutputType {
 ///   "executorProfileId": "770e8400-e29b-41d4-a716-446655440002",
 ///   "status": "completed",
 ///   "exitCode": 0,
+///   "statusReason": null,
 ///   "executorAction": "Run claude-code with task instructions",
 ///   "runReason": "codingagent",
 ///   "beforeHeadCommit": "abc123",
 ///   "afterHeadCommit": "def456",
 ///   "pid": 12345,
+///   "devServerUrl": null,
 ///   "startedAt": "2024-01-15T10:30:00Z",
 ///   "completedAt": "2024-01-15T10:35:00Z",
 ///   "createdAt": "2024-01-15T10:30:00Z",
@@ -353,6 +380,16 @@ pub struct ExecutionProcess {
     /// 0 = success, non-zero = failure
     pub exit_code: Option<i32>,
 
+    /// Why the process reached its final status, when not evident from
+    /// the exit code (e.g. orphaned by a previous session)
+    /// @validate: max_length=1000
+    pub status_reason: Option<String>,
+
+    /// Last signal sent to the process group when it was stopped
+    /// (`SIGINT`, `SIGTERM` or `SIGKILL`); None if it exited on its own
+    /// @validate: max_length=16
+    pub termination_signal: Option<String>,
+
     /// Description of what action triggered this process
     /// @validate: max_length=1000
     pub executor_action: String,
@@ -373,6 +410,10 @@ pub struct ExecutionProcess {
     /// Available while the process is running
     pub pid: Option<i32>,
 
+    /// URL the process is serving on (dev servers only)
+    /// @validate: max_length=2000
+    pub dev_server_url: Option<String>,
+
     /// When the process started (ISO 8601)
     pub started_at: String,
 
@@ -549,6 +590,12 @@ pub struct ProcessOutputEvent {
 
     /// Timestamp when output was received (ISO 8601)
     pub timestamp: String,
+
+    /// Bytes of terminal output up to the end of this chunk (PTY output
+    /// only). Events at or below a `ProcessSnapshot`'s offset are already
+    /// part of the snapshot.
+    #[serde(default)]
+    pub offset: Option<i64>,
 }
 
 impl ProcessOutputEvent {
@@ -559,6 +606,7 @@ impl ProcessOutputEvent {
             output_type: OutputType::Stdout,
             content: content.into(),
             timestamp: chrono::Utc::now().to_rfc3339(),
+            offset: None,
         }
     }
 
@@ -569,6 +617,7 @@ impl ProcessOutputEvent {
             output_type: OutputType::Stderr,
             content: content.into(),
             timestamp: chrono::Utc::now().to_rfc3339(),
+            offset: None,
         }
     }
 
@@ -583,6 +632,108 @@ impl ProcessOutputEvent {
     }
 }
 
+// =============================================================================
+// Process Output Chunk
+// =============================================================================
+
+/// A persisted chunk of process output
+///
+/// Output is stored as it arrives so that clients which connect late,
+/// reload, or reconnect can replay the full log of a run. Chunks are
+/// ordered by `id`, which is also used as the replay cursor.
+///
+/// # Database
+/// @entity
+/// @table: process_outputs
+///
+/// # Example
+/// ```json
+/// {
+///   "id": 42,
+///   "processId": "550e8400-e29b-41d4-a716-446655440000",
+///   "outputType": "stdout",
+///   "content": "Compiling openflow v0.1.0\n",
+///   "createdAt": "2024-01-15T10:30:01Z"
+/// }
+/// ```
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessOutputChunk {
+    /// Sequence number of the chunk (monotonically increasing)
+    pub id: i64,
+
+    /// Process ID this output belongs to
+    pub process_id: String,
+
+    /// Type of output (stdout or stderr)
+    pub output_type: OutputType,
+
+    /// Output content
+    pub content: String,
+
+    /// When the chunk was received (ISO 8601)
+    pub created_at: String,
+}
+
+impl ProcessOutputChunk {
+    /// Check if this is stdout
+    pub fn is_stdout(&self) -> bool {
+        self.output_type.is_stdout()
+    }
+
+    /// Check if this is stderr
+    pub fn is_stderr(&self) -> bool {
+        self.output_type.is_stderr()
+    }
+}
+
+// =============================================================================
+// Process Snapshot
+// =============================================================================
+
+/// Recent terminal output and size of a running PTY process
+///
+/// Lets a terminal or agent view opened mid-run render the screen before it
+/// starts following live output events. Clients should subscribe to the
+/// process's output before requesting the snapshot, so that nothing is
+/// missed in between, then skip output events whose `offset` is not past
+/// the snapshot's.
+///
+/// # Example
+/// ```json
+/// {
+///   "processId": "550e8400-e29b-41d4-a716-446655440000",
+///   "content": "\u001b[32m$\u001b[0m cargo build\r\n",
+///   "cols": 120,
+///   "rows": 32,
+///   "truncated": false,
+///   "offset": 31
+/// }
+/// ```
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessSnapshot {
+    /// Process ID the snapshot belongs to
+    pub process_id: String,
+
+    /// Most recent terminal output, including escape sequences
+    pub content: String,
+
+    /// Current terminal width in columns
+    pub cols: u16,
+
+    /// Current terminal height in rows
+    pub rows: u16,
+
+    /// Whether earlier output no longer fits in the scrollback
+    pub truncated: bool,
+
+    /// Bytes of terminal output up to the end of `content`
+    pub offset: i64,
+}
+
 // =============================================================================
 // Process Status Event
 // =============================================================================
@@ -640,6 +791,42 @@ impl ProcessStatusEvent {
     }
 }
 
+// =============================================================================
+// Queued Process
+// =============================================================================
+
+/// A coding agent run waiting in the run queue
+///
+/// Runs are queued when starting them would exceed the global or
+/// per-project concurrency limit, and are started in order as running
+/// agents finish.
+///
+/// # Example
+/// ```json
+/// {
+///   "processId": "550e8400-e29b-41d4-a716-446655440000",
+///   "chatId": "550e8400-e29b-41d4-a716-446655440001",
+///   "projectId": "550e8400-e29b-41d4-a716-446655440002",
+///   "position": 1
+/// }
+/// ```
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct QueuedProcess {
+    /// Process ID of the queued run
+    pub process_id: String,
+
+    /// Chat the run belongs to
+    pub chat_id: String,
+
+    /// Project the run counts against for per-project limits
+    pub project_id: String,
+
+    /// Position in the queue, starting at 1
+    pub position: u32,
+}
+
 // =============================================================================
 // Validation Implementation
 // =============================================================================
@@ -652,6 +839,27 @@ impl Validate for ExecutionProcess {
             .validate(|| {
                 validate_string_length("executor_action", &self.executor_action, None, Some(1000))
             })
+            .validate(|| {
+                if let Some(ref reason) = self.status_reason {
+                    validate_string_length("status_reason", reason, None, Some(1000))
+                } else {
+                    Ok(())
+                }
+            })
+            .validate(|| {
+                if let Some(ref signal) = self.termination_signal {
+                    validate_string_length("termination_signal", signal, None, Some(16))
+                } else {
+                    Ok(())
+                }
+            })
+            .validate(|| {
+                if let Some(ref url) = self.dev_server_url {
+                    validate_string_length("dev_server_url", url, None, Some(2000))
+                } else {
+                    Ok(())
+                }
+            })
             .validate(|| {
                 if let Some(ref commit) = self.before_head_commit {
                     validate_string_length("before_head_commit", commit, None, Some(40))
@@ -685,11 +893,14 @@ mod tests {
             executor_profile_id: Some("770e8400-e29b-41d4-a716-446655440002".to_string()),
             status: ProcessStatus::Running,
             exit_code: None,
+            status_reason: None,
+            termination_signal: None,
             executor_action: "Run claude-code with task instructions".to_string(),
             run_reason: RunReason::Codingagent,
             before_head_commit: Some("abc123".to_string()),
             after_head_commit: None,
             pid: Some(12345),
+            dev_server_url: None,
             started_at: "2024-01-15T10:30:00Z".to_string(),
             completed_at: None,
             created_at: "2024-01-15T10:30:00Z".to_string(),
@@ -704,11 +915,14 @@ mod tests {
             executor_profile_id: Some("770e8400-e29b-41d4-a716-446655440002".to_string()),
             status: ProcessStatus::Completed,
             exit_code: Some(0),
+            status_reason: None,
+            termination_signal: None,
             executor_action: "Run setup script".to_string(),
             run_reason: RunReason::Setupscript,
             before_head_commit: Some("abc123".to_string()),
             after_head_commit: Some("def456".to_string()),
             pid: None,
+            dev_server_url: None,
             started_at: "2024-01-15T10:30:00Z".to_string(),
             completed_at: Some("2024-01-15T10:35:00Z".to_string()),
             created_at: "2024-01-15T10:30:00Z".to_string(),
@@ -722,10 +936,12 @@ mod tests {
 
     #[test]
     fn test_process_status_display() {
+        assert_eq!(ProcessStatus::Queued.to_string(), "queued");
         assert_eq!(ProcessStatus::Running.to_string(), "running");
         assert_eq!(ProcessStatus::Completed.to_string(), "completed");
         assert_eq!(ProcessStatus::Failed.to_string(), "failed");
         assert_eq!(ProcessStatus::Killed.to_string(), "killed");
+        assert_eq!(ProcessStatus::TimedOut.to_string(), "timedout");
     }
 
     #[test]
@@ -746,6 +962,14 @@ mod tests {
             "killed".parse::<ProcessStatus>().unwrap(),
             ProcessStatus::Killed
         );
+        assert_eq!(
+            "queued".parse::<ProcessStatus>().unwrap(),
+            ProcessStatus::Queued
+        );
+        assert_eq!(
+            "timedout".parse::<ProcessStatus>().unwrap(),
+            ProcessStatus::TimedOut
+        );
         assert_eq!(
             "RUNNING".parse::<ProcessStatus>().unwrap(),
             ProcessStatus::Running
@@ -758,6 +982,10 @@ mod tests {
         assert!(ProcessStatus::Running.is_running());
         assert!(!ProcessStatus::Running.is_finished());
 
+        assert!(ProcessStatus::Queued.is_queued());
+        assert!(!ProcessStatus::Queued.is_running());
+        assert!(!ProcessStatus::Queued.is_finished());
+
         assert!(ProcessStatus::Completed.is_completed());
         assert!(ProcessStatus::Completed.is_finished());
 
@@ -766,12 +994,18 @@ mod tests {
 
         assert!(ProcessStatus::Killed.is_killed());
         assert!(ProcessStatus::Killed.is_error());
+
+        assert!(ProcessStatus::TimedOut.is_timed_out());
+        assert!(ProcessStatus::TimedOut.is_finished());
+        assert!(ProcessStatus::TimedOut.is_error());
     }
 
     #[test]
     fn test_process_status_all() {
         let all = ProcessStatus::all();
-        assert_eq!(all.len(), 4);
+        assert_eq!(all.len(), 6);
+        assert!(all.contains(&ProcessStatus::Queued));
+        assert!(all.contains(&ProcessStatus::TimedOut));
         assert!(all.contains(&ProcessStatus::Running));
         assert!(all.contains(&ProcessStatus::Completed));
         assert!(all.contains(&ProcessStatus::Failed));
@@ -1167,4 +1401,21 @@ mod tests {
         let deserialized: ProcessStatusEvent = serde_json::from_str(&json).unwrap();
         assert_eq!(event, deserialized);
     }
+
+    #[test]
+    fn test_queued_process_serialization() {
+        let queued = QueuedProcess {
+            process_id: "process-123".to_string(),
+            chat_id: "chat-456".to_string(),
+            project_id: "project-789".to_string(),
+            position: 2,
+        };
+        let json = serde_json::to_string(&queued).unwrap();
+
+        assert!(json.contains("\"processId\":\"process-123\""));
+        assert!(json.contains("\"position\":2"));
+
+        let deserialized: QueuedProcess = serde_json::from_str(&json).unwrap();
+        assert_eq!(queued, deserialized);
+    }
 }
@@ -11,6 +11,8 @@
 use serde::{Deserialize, Serialize};
 use typeshare::typeshare;
 
+use super::limits::ProjectScriptLimits;
+
 use crate::validation::{
     validate_required_string, validate_string_length, Validate, ValidationCollector,
     ValidationResult,
@@ -106,6 +108,11 @@ pub struct Project {
     /// @validate: max_length=50000
     pub verification_config: Option<String>,
 
+    /// JSON object with resource limits for the setup, dev and cleanup
+    /// scripts (`ProjectScriptLimits`)
+    /// @validate: max_length=5000
+    pub script_limits: Option<String>,
+
     /// Timestamp when the project was archived (soft-delete)
     /// null means the project is active
     pub archived_at: Option<String>,
@@ -146,6 +153,22 @@ impl Project {
             .and_then(|json| serde_json::from_str(json).ok())
             .unwrap_or_default()
     }
+
+    /// Parse verification_config JSON into a map of check name to command
+    pub fn get_verification_config(&self) -> std::collections::BTreeMap<String, String> {
+        self.verification_config
+            .as_ref()
+            .and_then(|json| serde_json::from_str(json).ok())
+            .unwrap_or_default()
+    }
+
+    /// Parse script_limits JSON into per-script resource limits
+    pub fn get_script_limits(&self) -> ProjectScriptLimits {
+        self.script_limits
+            .as_ref()
+            .and_then(|json| serde_json::from_str(json).ok())
+            .unwrap_or_default()
+    }
 }
 
 // =============================================================================
@@ -310,6 +333,7 @@ mod tests {
             always_included_rules: None,
             workflows_folder: ".openflow/workflows".to_string(),
             verification_config: None,
+            script_limits: None,
             archived_at: None,
             created_at: "2024-01-15T10:30:00Z".to_string(),
             updated_at: "2024-01-15T10:30:00Z".to_string(),
@@ -325,6 +349,37 @@ mod tests {
         assert!(project.is_archived());
     }
 
+    #[test]
+    fn test_project_get_verification_config() {
+        let mut project = create_test_project();
+        assert!(project.get_verification_config().is_empty());
+
+        project.verification_config =
+            Some(r#"{"test": "pnpm test", "lint": "pnpm lint"}"#.to_string());
+        let checks: Vec<_> = project.get_verification_config().into_iter().collect();
+        assert_eq!(
+            checks,
+            vec![
+                ("lint".to_string(), "pnpm lint".to_string()),
+                ("test".to_string(), "pnpm test".to_string()),
+            ]
+        );
+
+        project.verification_config = Some("not json".to_string());
+        assert!(project.get_verification_config().is_empty());
+    }
+
+    #[test]
+    fn test_project_get_script_limits() {
+        let mut project = create_test_project();
+        assert_eq!(project.get_script_limits(), ProjectScriptLimits::default());
+
+        project.script_limits = Some(r#"{"dev": {"addressSpaceMb": 2048}}"#.to_string());
+        let limits = project.get_script_limits();
+        assert_eq!(limits.dev.unwrap().address_space_mb, Some(2048));
+        assert!(limits.setup.is_none());
+    }
+
     #[test]
     fn test_project_get_copy_files() {
         let project = create_test_project();
@@ -0,0 +1,115 @@
+//! Sandbox Settings
+//!
+//! Optional filesystem and network confinement for agent runs. An executor
+//! profile opts in through `executor_profiles.sandbox` (JSON). Sandboxed runs
+//! can only write to the chat worktree (and the repository's git directory),
+//! get a private HOME and tmp, and have no network unless the profile
+//! allows it.
+
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+// =============================================================================
+// SandboxSettings
+// =============================================================================
+
+/// Sandbox settings of an executor profile
+///
+/// # Example
+/// ```json
+/// {
+///   "enabled": true,
+///   "allowNetwork": true,
+///   "readOnlyPaths": ["/home/me/.nvm"]
+/// }
+/// ```
+#[typeshare]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
+pub struct SandboxSettings {
+    /// Whether runs of the profile are sandboxed
+    pub enabled: bool,
+
+    /// Whether sandboxed runs may use the network
+    pub allow_network: bool,
+
+    /// Paths under HOME to keep visible (read-only), e.g. where the CLI or
+    /// its runtime is installed
+    pub read_only_paths: Vec<String>,
+}
+
+impl SandboxSettings {
+    /// Parse settings from a JSON column
+    /// Returns disabled settings if the JSON is missing, and an error if it
+    /// is invalid (a typo must not silently turn the sandbox off)
+    pub fn from_json(json: Option<&str>) -> Result<Self, serde_json::Error> {
+        match json {
+            Some(json) if !json.trim().is_empty() => serde_json::from_str(json),
+            _ => Ok(Self::default()),
+        }
+    }
+}
+
+// =============================================================================
+// ProcessSandbox
+// =============================================================================
+
+/// Sandbox to start a single process in
+#[typeshare]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ProcessSandbox {
+    /// Paths the process may write to
+    pub writable_paths: Vec<String>,
+
+    /// Paths hidden by the private HOME or tmp to keep visible (read-only)
+    pub read_only_paths: Vec<String>,
+
+    /// Paths under a writable path that stay read-only (e.g. git hooks)
+    pub protected_paths: Vec<String>,
+
+    /// Whether the process may use the network
+    pub allow_network: bool,
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sandbox_settings_defaults() {
+        let settings = SandboxSettings::from_json(Some(r#"{"enabled": true}"#)).unwrap();
+        assert!(settings.enabled);
+        assert!(!settings.allow_network);
+        assert!(settings.read_only_paths.is_empty());
+
+        assert!(!SandboxSettings::from_json(None).unwrap().enabled);
+        assert!(!SandboxSettings::from_json(Some("")).unwrap().enabled);
+    }
+
+    #[test]
+    fn test_sandbox_settings_rejects_invalid_json() {
+        assert!(SandboxSettings::from_json(Some("not json")).is_err());
+        assert!(SandboxSettings::from_json(Some(r#"{"enabled": "yes"}"#)).is_err());
+        assert!(SandboxSettings::from_json(Some(r#"{"enable": true}"#)).is_err());
+    }
+
+    #[test]
+    fn test_process_sandbox_serialization() {
+        let sandbox = ProcessSandbox {
+            writable_paths: vec!["/tmp/worktree".to_string()],
+            allow_network: true,
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&sandbox).unwrap();
+        assert!(json.contains(r#""writablePaths":["/tmp/worktree"]"#));
+        assert!(json.contains(r#""allowNetwork":true"#));
+
+        let parsed: ProcessSandbox = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, sandbox);
+    }
+}
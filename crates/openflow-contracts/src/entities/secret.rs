@@ -0,0 +1,99 @@
+//! Secret Entity
+//!
+//! Secrets are named values such as API keys and tokens, stored encrypted in
+//! the `secrets` table. They are write-only: only names and timestamps are
+//! ever returned. Executor profile env, project scripts and verification
+//! commands reference them as `${secret:NAME}`; the value is resolved when
+//! the process is spawned. Scripts receive it as the environment variable
+//! `NAME`.
+
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+/// Maximum length of a secret name
+pub const SECRET_NAME_MAX_LENGTH: usize = 255;
+
+/// A stored secret (the value is never exposed)
+///
+/// @entity
+/// @table: secrets
+///
+/// # Example
+/// ```json
+/// {
+///   "name": "ANTHROPIC_API_KEY",
+///   "createdAt": "2024-01-15T10:30:00Z",
+///   "updatedAt": "2024-01-15T10:30:00Z"
+/// }
+/// ```
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Secret {
+    /// Secret name, referenced as `${secret:NAME}`
+    /// @validate: required, min_length=1, max_length=255, pattern=^[A-Za-z_][A-Za-z0-9_]*$
+    pub name: String,
+
+    /// When the secret was first stored
+    pub created_at: String,
+
+    /// When the value was last changed
+    pub updated_at: String,
+}
+
+impl Secret {
+    /// Check if a name can be used for a secret.
+    ///
+    /// Names follow environment variable rules: letters, digits and
+    /// underscores, not starting with a digit.
+    pub fn is_valid_name(name: &str) -> bool {
+        let mut chars = name.chars();
+        let starts_ok = chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_');
+        starts_ok
+            && name.len() <= SECRET_NAME_MAX_LENGTH
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+    }
+
+    /// Get the placeholder that references this secret
+    pub fn placeholder(&self) -> String {
+        format!("${{secret:{}}}", self.name)
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_valid_name() {
+        assert!(Secret::is_valid_name("ANTHROPIC_API_KEY"));
+        assert!(Secret::is_valid_name("_token2"));
+        assert!(!Secret::is_valid_name(""));
+        assert!(!Secret::is_valid_name("2FA"));
+        assert!(!Secret::is_valid_name("MY-KEY"));
+        assert!(!Secret::is_valid_name("KEY}"));
+        assert!(!Secret::is_valid_name(
+            &"A".repeat(SECRET_NAME_MAX_LENGTH + 1)
+        ));
+    }
+
+    #[test]
+    fn test_placeholder_and_serialization() {
+        let secret = Secret {
+            name: "GITHUB_TOKEN".to_string(),
+            created_at: "2024-01-15T10:30:00Z".to_string(),
+            updated_at: "2024-01-15T10:30:00Z".to_string(),
+        };
+
+        assert_eq!(secret.placeholder(), "${secret:GITHUB_TOKEN}");
+        let json = serde_json::to_string(&secret).unwrap();
+        assert!(json.contains("\"createdAt\""));
+        assert!(!json.contains("value"));
+    }
+}
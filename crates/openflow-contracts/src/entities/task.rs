@@ -123,6 +123,7 @@ impl TaskStatus {
 ///   "description": "Add login and registration functionality",
 ///   "status": "inprogress",
 ///   "workflowTemplate": ".openflow/workflows/feature.md",
+///   "workflowVariables": "{\"ticket_url\":\"https://example.com/T-12\"}",
 ///   "actionsRequiredCount": 2,
 ///   "parentTaskId": null,
 ///   "autoStartNextStep": true,
@@ -160,6 +161,9 @@ pub struct Task {
     /// @validate: max_length=1000
     pub workflow_template: Option<String>,
 
+    /// JSON object of values for the workflow template's variables
+    pub workflow_variables: Option<String>,
+
     /// Number of pending actions requiring user attention
     pub actions_required_count: i32,
 
@@ -418,6 +422,7 @@ mod tests {
             description: Some("Test description".to_string()),
             status: TaskStatus::Todo,
             workflow_template: Some(".openflow/workflows/feature.md".to_string()),
+            workflow_variables: None,
             actions_required_count: 0,
             parent_task_id: None,
             auto_start_next_step: true,
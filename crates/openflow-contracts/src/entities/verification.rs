@@ -0,0 +1,224 @@
+//! Verification Entity
+//!
+//! Verification results describe a run of a project's verification checks
+//! (the commands in `projects.verification_config`, e.g. tests and lint)
+//! in a chat's worktree. Each check runs as its own execution process with
+//! `RunReason::Verification`.
+
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+// =============================================================================
+// VerificationCheckStatus Enum
+// =============================================================================
+
+/// Outcome of a single verification check
+#[typeshare]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum VerificationCheckStatus {
+    /// The command exited with code 0
+    Passed,
+    /// The command exited with a non-zero code or was killed
+    #[default]
+    Failed,
+    /// The command could not be started
+    Error,
+}
+
+impl std::fmt::Display for VerificationCheckStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VerificationCheckStatus::Passed => write!(f, "passed"),
+            VerificationCheckStatus::Failed => write!(f, "failed"),
+            VerificationCheckStatus::Error => write!(f, "error"),
+        }
+    }
+}
+
+// =============================================================================
+// VerificationCheck Entity
+// =============================================================================
+
+/// Result of one configured verification command
+///
+/// # Example
+/// ```json
+/// {
+///   "name": "test",
+///   "command": "pnpm test",
+///   "status": "passed",
+///   "exitCode": 0,
+///   "processId": "550e8400-e29b-41d4-a716-446655440000",
+///   "error": null
+/// }
+/// ```
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationCheck {
+    /// Check name (key in the verification config)
+    pub name: String,
+
+    /// Shell command that was run
+    pub command: String,
+
+    /// Outcome of the check
+    pub status: VerificationCheckStatus,
+
+    /// Exit code of the command, if it exited
+    pub exit_code: Option<i32>,
+
+    /// Execution process that ran the command (output can be replayed from it)
+    pub process_id: Option<String>,
+
+    /// Error message if the command could not be started
+    pub error: Option<String>,
+}
+
+impl VerificationCheck {
+    /// Check if this check passed
+    pub fn is_passed(&self) -> bool {
+        self.status == VerificationCheckStatus::Passed
+    }
+}
+
+// =============================================================================
+// VerificationResult Entity
+// =============================================================================
+
+/// Result of running all verification checks for a chat
+///
+/// # Example
+/// ```json
+/// {
+///   "chatId": "660e8400-e29b-41d4-a716-446655440001",
+///   "passed": false,
+///   "checks": [
+///     { "name": "lint", "command": "pnpm lint", "status": "passed", "exitCode": 0,
+///       "processId": "...", "error": null },
+///     { "name": "test", "command": "pnpm test", "status": "failed", "exitCode": 1,
+///       "processId": "...", "error": null }
+///   ],
+///   "startedAt": "2024-01-15T10:30:00Z",
+///   "completedAt": "2024-01-15T10:32:10Z"
+/// }
+/// ```
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationResult {
+    /// Chat whose worktree was verified
+    pub chat_id: String,
+
+    /// Whether every check passed
+    pub passed: bool,
+
+    /// Per-check results, ordered by check name
+    pub checks: Vec<VerificationCheck>,
+
+    /// When verification started
+    pub started_at: String,
+
+    /// When the last check finished
+    pub completed_at: String,
+}
+
+impl VerificationResult {
+    /// Build a result from its checks; `passed` is true only if all checks passed
+    pub fn new(
+        chat_id: impl Into<String>,
+        checks: Vec<VerificationCheck>,
+        started_at: impl Into<String>,
+        completed_at: impl Into<String>,
+    ) -> Self {
+        Self {
+            chat_id: chat_id.into(),
+            passed: checks.iter().all(VerificationCheck::is_passed),
+            checks,
+            started_at: started_at.into(),
+            completed_at: completed_at.into(),
+        }
+    }
+
+    /// Get the checks that did not pass
+    pub fn failed_checks(&self) -> impl Iterator<Item = &VerificationCheck> {
+        self.checks.iter().filter(|check| !check.is_passed())
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(name: &str, status: VerificationCheckStatus) -> VerificationCheck {
+        VerificationCheck {
+            name: name.to_string(),
+            command: format!("pnpm {}", name),
+            status,
+            exit_code: Some(if status == VerificationCheckStatus::Passed {
+                0
+            } else {
+                1
+            }),
+            process_id: Some(format!("process-{}", name)),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_verification_result_passed() {
+        let result = VerificationResult::new(
+            "chat-1",
+            vec![
+                check("lint", VerificationCheckStatus::Passed),
+                check("test", VerificationCheckStatus::Passed),
+            ],
+            "2024-01-15T10:30:00Z",
+            "2024-01-15T10:31:00Z",
+        );
+
+        assert!(result.passed);
+        assert_eq!(result.failed_checks().count(), 0);
+    }
+
+    #[test]
+    fn test_verification_result_failed() {
+        let result = VerificationResult::new(
+            "chat-1",
+            vec![
+                check("lint", VerificationCheckStatus::Passed),
+                check("test", VerificationCheckStatus::Failed),
+            ],
+            "2024-01-15T10:30:00Z",
+            "2024-01-15T10:31:00Z",
+        );
+
+        assert!(!result.passed);
+        let failed: Vec<_> = result.failed_checks().map(|c| c.name.as_str()).collect();
+        assert_eq!(failed, vec!["test"]);
+    }
+
+    #[test]
+    fn test_verification_result_serialization() {
+        let result = VerificationResult::new(
+            "chat-1",
+            vec![check("test", VerificationCheckStatus::Error)],
+            "2024-01-15T10:30:00Z",
+            "2024-01-15T10:31:00Z",
+        );
+
+        let json = serde_json::to_string(&result).unwrap();
+        assert!(json.contains("\"chatId\":\"chat-1\""));
+        assert!(json.contains("\"status\":\"error\""));
+        assert!(json.contains("\"exitCode\":1"));
+        assert!(json.contains("\"processId\":\"process-test\""));
+
+        let deserialized: VerificationResult = serde_json::from_str(&json).unwrap();
+        assert_eq!(result, deserialized);
+    }
+}
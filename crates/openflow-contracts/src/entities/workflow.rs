@@ -6,6 +6,8 @@
 use serde::{Deserialize, Serialize};
 use typeshare::typeshare;
 
+use super::chat::ChatRole;
+
 /// Status of a workflow step
 ///
 /// @entity_enum
@@ -53,6 +55,8 @@ impl std::str::FromStr for WorkflowStepStatus {
 ///
 /// Steps are parsed from markdown workflow definition files.
 /// Each step represents a distinct phase of work with its own chat session.
+/// The optional settings come from the template's front-matter defaults and
+/// the step's own metadata block; unset settings fall back to the task's.
 ///
 /// @entity
 #[typeshare]
@@ -77,6 +81,40 @@ pub struct WorkflowStep {
     /// Associated chat ID if step has been started
     /// @validate: format=uuid
     pub chat_id: Option<String>,
+
+    /// Executor profile to run the step with (profile name or ID)
+    #[serde(default)]
+    pub executor_profile: Option<String>,
+
+    /// Role of the step's chat
+    #[serde(default)]
+    pub chat_role: Option<ChatRole>,
+
+    /// Model to run the step with, instead of the profile's model
+    #[serde(default)]
+    pub model: Option<String>,
+
+    /// Instructions given to the agent ahead of the step's prompt
+    #[serde(default)]
+    pub hidden_prompt: Option<String>,
+
+    /// Files the step must produce in the task's artifacts folder
+    #[serde(default)]
+    pub required_artifacts: Vec<String>,
+
+    /// Whether to start the next step once this one completes, instead of
+    /// the task's auto-start setting
+    #[serde(default)]
+    pub auto_advance: Option<bool>,
+
+    /// Maximum runtime of the step's agent in seconds, instead of the
+    /// profile's
+    #[serde(default)]
+    pub timeout_secs: Option<u32>,
+
+    /// Indices of the steps that must be done before this step can start
+    #[serde(default)]
+    pub depends_on: Vec<i32>,
 }
 
 impl WorkflowStep {
@@ -88,6 +126,14 @@ impl WorkflowStep {
             description: description.into(),
             status: WorkflowStepStatus::Pending,
             chat_id: None,
+            executor_profile: None,
+            chat_role: None,
+            model: None,
+            hidden_prompt: None,
+            required_artifacts: Vec::new(),
+            auto_advance: None,
+            timeout_secs: None,
+            depends_on: Vec::new(),
         }
     }
 
@@ -109,6 +155,22 @@ impl WorkflowStep {
         )
     }
 
+    /// Check if the steps this step depends on are all done
+    pub fn dependencies_met(&self, steps: &[WorkflowStep]) -> bool {
+        self.depends_on.iter().all(|index| {
+            steps
+                .iter()
+                .find(|s| s.index == *index)
+                .is_some_and(|s| s.is_complete())
+        })
+    }
+
+    /// Set the steps this step depends on
+    pub fn with_depends_on(mut self, depends_on: Vec<i32>) -> Self {
+        self.depends_on = depends_on;
+        self
+    }
+
     /// Associate a chat with this step
     pub fn with_chat(mut self, chat_id: impl Into<String>) -> Self {
         self.chat_id = Some(chat_id.into());
@@ -164,6 +226,10 @@ pub struct WorkflowTemplate {
     /// Parsed steps from the workflow content
     pub steps: Vec<WorkflowStep>,
 
+    /// Number of errors linting the workflow content reports
+    #[serde(default)]
+    pub error_count: i32,
+
     /// When the template was created (ISO 8601)
     pub created_at: String,
 
@@ -187,6 +253,7 @@ impl WorkflowTemplate {
             content: content.into(),
             is_builtin,
             steps: Vec::new(),
+            error_count: 0,
             created_at: now.clone(),
             updated_at: now,
         }
@@ -269,6 +336,10 @@ pub struct WorkflowTemplateSummary {
 
     /// Number of steps in the workflow
     pub step_count: i32,
+
+    /// Number of errors linting the workflow reports
+    #[serde(default)]
+    pub error_count: i32,
 }
 
 impl From<&WorkflowTemplate> for WorkflowTemplateSummary {
@@ -279,6 +350,7 @@ impl From<&WorkflowTemplate> for WorkflowTemplateSummary {
             description: template.description.clone(),
             is_builtin: template.is_builtin,
             step_count: template.steps.len() as i32,
+            error_count: template.error_count,
         }
     }
 }
@@ -289,6 +361,64 @@ impl From<WorkflowTemplate> for WorkflowTemplateSummary {
     }
 }
 
+/// A task's workflow being run step by step
+///
+/// When a task's workflow is started, its template is copied into a workflow
+/// document in the task's artifacts folder. The step markers in that document
+/// record progress, and each step runs in its own chat.
+///
+/// @entity
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowRun {
+    /// Task the workflow belongs to
+    /// @validate: required, format=uuid
+    pub task_id: String,
+
+    /// Template the workflow was created from (the task's workflow template)
+    pub template: String,
+
+    /// Absolute path of the task's workflow document
+    pub path: String,
+
+    /// Steps with their status and chat
+    pub steps: Vec<WorkflowStep>,
+}
+
+impl WorkflowRun {
+    /// Get a step by index
+    pub fn step(&self, index: i32) -> Option<&WorkflowStep> {
+        self.steps.iter().find(|s| s.index == index)
+    }
+
+    /// Get the current step (first in-progress or pending step)
+    pub fn current_step(&self) -> Option<&WorkflowStep> {
+        self.steps
+            .iter()
+            .find(|s| s.is_active())
+            .or_else(|| self.steps.iter().find(|s| s.can_start()))
+    }
+
+    /// Get the pending steps whose dependencies are all done
+    pub fn ready_steps(&self) -> Vec<&WorkflowStep> {
+        self.steps
+            .iter()
+            .filter(|s| s.can_start() && s.dependencies_met(&self.steps))
+            .collect()
+    }
+
+    /// Get the first pending step after the given index
+    pub fn next_pending_step(&self, after: i32) -> Option<&WorkflowStep> {
+        self.steps.iter().find(|s| s.index > after && s.can_start())
+    }
+
+    /// Check if the workflow is complete (all steps done)
+    pub fn is_complete(&self) -> bool {
+        !self.steps.is_empty() && self.steps.iter().all(|s| s.is_complete())
+    }
+}
+
 /// Available workflow variables that can be substituted in workflow content
 ///
 /// Variables use the format {@variable_name} in markdown.
@@ -357,6 +487,186 @@ impl std::fmt::Display for WorkflowVariable {
     }
 }
 
+impl WorkflowVariable {
+    /// Get the built-in variable with the given name, if any
+    pub fn from_name(name: &str) -> Option<WorkflowVariable> {
+        WorkflowVariable::all()
+            .into_iter()
+            .find(|v| v.name() == name)
+    }
+}
+
+/// Type of a template-defined workflow variable's value
+///
+/// @entity_enum
+#[typeshare]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum WorkflowVariableType {
+    /// Any text
+    #[default]
+    String,
+    /// An integer or decimal number
+    Number,
+    /// `true` or `false`
+    Boolean,
+}
+
+impl WorkflowVariableType {
+    /// Check if a value is valid for this type
+    pub fn accepts(&self, value: &str) -> bool {
+        match self {
+            WorkflowVariableType::String => true,
+            WorkflowVariableType::Number => value.trim().parse::<f64>().is_ok_and(f64::is_finite),
+            WorkflowVariableType::Boolean => matches!(value.trim(), "true" | "false"),
+        }
+    }
+}
+
+impl std::fmt::Display for WorkflowVariableType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WorkflowVariableType::String => write!(f, "string"),
+            WorkflowVariableType::Number => write!(f, "number"),
+            WorkflowVariableType::Boolean => write!(f, "boolean"),
+        }
+    }
+}
+
+/// A variable of a workflow template
+///
+/// Reported for every variable a template declares in its front-matter and
+/// every `{@name}` placeholder it uses. Built-in variables are filled in
+/// when the workflow runs; the others take their value from the task.
+///
+/// @entity
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowVariableInfo {
+    /// Variable name, as in `{@name}`
+    pub name: String,
+
+    /// Type of the variable's value
+    pub value_type: WorkflowVariableType,
+
+    /// Value used when the task doesn't set one
+    pub default_value: Option<String>,
+
+    /// What the variable is for
+    pub description: Option<String>,
+
+    /// Whether tasks must set a value (declared without a default, or used
+    /// without being declared)
+    pub required: bool,
+
+    /// Whether this is one of the built-in `WorkflowVariable`s
+    pub builtin: bool,
+
+    /// Whether the template declares the variable in its front-matter
+    pub declared: bool,
+
+    /// Whether the template uses the variable in a placeholder
+    pub used: bool,
+}
+
+/// Severity of a workflow template diagnostic
+///
+/// @entity_enum
+#[typeshare]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum WorkflowDiagnosticSeverity {
+    /// The template can't be used as written (e.g. a step would be dropped)
+    Error,
+    /// The template works but probably not as intended
+    Warning,
+}
+
+/// Kind of problem a workflow template diagnostic reports
+///
+/// @entity_enum
+#[typeshare]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkflowDiagnosticCode {
+    /// A line that looks like a step header but isn't parsed as one
+    MalformedStepHeader,
+    /// Two steps with the same name
+    DuplicateStepName,
+    /// A step without a description
+    EmptyStep,
+    /// A placeholder that is neither built-in nor declared
+    UnknownVariable,
+    /// Neither a front-matter title nor a `#` heading
+    MissingTitle,
+    /// Front-matter that is not closed or not valid
+    InvalidFrontMatter,
+    /// A `step` block that is not closed or not valid
+    InvalidStepConfig,
+    /// A `depends_on` reference to an unknown step, or a dependency cycle
+    InvalidDependency,
+}
+
+/// A problem found by linting a workflow template
+///
+/// @entity
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowDiagnostic {
+    /// Line of the problem (1-based)
+    pub line: u32,
+
+    /// Column of the problem (1-based, in characters)
+    pub column: u32,
+
+    /// How serious the problem is
+    pub severity: WorkflowDiagnosticSeverity,
+
+    /// Kind of problem
+    pub code: WorkflowDiagnosticCode,
+
+    /// Description of the problem
+    pub message: String,
+}
+
+impl WorkflowDiagnostic {
+    /// Create an error diagnostic
+    pub fn error(
+        line: u32,
+        column: u32,
+        code: WorkflowDiagnosticCode,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            line,
+            column,
+            severity: WorkflowDiagnosticSeverity::Error,
+            code,
+            message: message.into(),
+        }
+    }
+
+    /// Create a warning diagnostic
+    pub fn warning(
+        line: u32,
+        column: u32,
+        code: WorkflowDiagnosticCode,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            severity: WorkflowDiagnosticSeverity::Warning,
+            ..Self::error(line, column, code, message)
+        }
+    }
+
+    /// Check if this is an error
+    pub fn is_error(&self) -> bool {
+        self.severity == WorkflowDiagnosticSeverity::Error
+    }
+}
+
 /// Context for substituting workflow variables
 ///
 /// Holds the actual values for workflow variables that will be
@@ -574,6 +884,19 @@ mod tests {
         assert_eq!(step.chat_id, Some("chat-123".to_string()));
     }
 
+    #[test]
+    fn test_workflow_step_config_defaults() {
+        let json =
+            r#"{"index":1,"name":"Review","description":"","status":"pending","chatId":null}"#;
+        let step: WorkflowStep = serde_json::from_str(json).unwrap();
+
+        assert!(step.executor_profile.is_none());
+        assert!(step.chat_role.is_none());
+        assert!(step.required_artifacts.is_empty());
+        assert!(step.auto_advance.is_none());
+        assert!(step.timeout_secs.is_none());
+    }
+
     #[test]
     fn test_workflow_template_creation() {
         let template = WorkflowTemplate::new("builtin:feature", "Feature", "# Content", true);
@@ -664,6 +987,100 @@ mod tests {
         assert_eq!(current.name, "Step 3");
     }
 
+    #[test]
+    fn test_workflow_run_progress() {
+        let mut steps = vec![
+            WorkflowStep::new(0, "Step 1", ""),
+            WorkflowStep::new(1, "Step 2", ""),
+            WorkflowStep::new(2, "Step 3", ""),
+        ];
+        steps[0].complete();
+        steps[1].start();
+
+        let mut run = WorkflowRun {
+            task_id: "task-1".to_string(),
+            template: "builtin:feature".to_string(),
+            path: "/repo/.zenflow/tasks/task-1/workflow.md".to_string(),
+            steps,
+        };
+        assert_eq!(run.current_step().unwrap().index, 1);
+        assert_eq!(run.next_pending_step(0).unwrap().index, 2);
+        assert!(run.next_pending_step(2).is_none());
+        assert!(!run.is_complete());
+
+        run.steps[1].complete();
+        run.steps[2].skip();
+        assert!(run.current_step().is_none());
+        assert!(run.is_complete());
+        assert_eq!(run.step(2).unwrap().name, "Step 3");
+    }
+
+    #[test]
+    fn test_workflow_run_ready_steps() {
+        let mut run = WorkflowRun {
+            task_id: "task-1".to_string(),
+            template: "fan-out.md".to_string(),
+            path: "/repo/.zenflow/tasks/task-1/workflow.md".to_string(),
+            steps: vec![
+                WorkflowStep::new(0, "Implement", ""),
+                WorkflowStep::new(1, "Tests", "").with_depends_on(vec![0]),
+                WorkflowStep::new(2, "Docs", "").with_depends_on(vec![0]),
+                WorkflowStep::new(3, "Review", "").with_depends_on(vec![1, 2]),
+            ],
+        };
+        let ready =
+            |run: &WorkflowRun| -> Vec<i32> { run.ready_steps().iter().map(|s| s.index).collect() };
+        assert_eq!(ready(&run), vec![0]);
+
+        run.steps[0].complete();
+        assert_eq!(ready(&run), vec![1, 2]);
+
+        run.steps[1].complete();
+        run.steps[2].start();
+        assert!(ready(&run).is_empty());
+
+        run.steps[2].skip();
+        assert_eq!(ready(&run), vec![3]);
+    }
+
+    #[test]
+    fn test_workflow_variable_type_accepts() {
+        assert!(WorkflowVariableType::String.accepts("anything"));
+        assert!(WorkflowVariableType::Number.accepts("42"));
+        assert!(WorkflowVariableType::Number.accepts("-1.5"));
+        assert!(!WorkflowVariableType::Number.accepts("forty"));
+        assert!(!WorkflowVariableType::Number.accepts("NaN"));
+        assert!(WorkflowVariableType::Boolean.accepts("true"));
+        assert!(!WorkflowVariableType::Boolean.accepts("yes"));
+
+        assert_eq!(
+            WorkflowVariable::from_name("task_title"),
+            Some(WorkflowVariable::TaskTitle)
+        );
+        assert_eq!(WorkflowVariable::from_name("ticket_url"), None);
+    }
+
+    #[test]
+    fn test_workflow_diagnostic_serialization() {
+        let diagnostic = WorkflowDiagnostic::warning(
+            3,
+            5,
+            WorkflowDiagnosticCode::EmptyStep,
+            "Step 'Plan' has no description",
+        );
+        assert!(!diagnostic.is_error());
+        assert_eq!(
+            serde_json::to_value(&diagnostic).unwrap(),
+            serde_json::json!({
+                "line": 3,
+                "column": 5,
+                "severity": "warning",
+                "code": "empty_step",
+                "message": "Step 'Plan' has no description",
+            })
+        );
+    }
+
     #[test]
     fn test_workflow_template_summary() {
         let steps = vec![
@@ -165,8 +165,14 @@ pub enum EntityType {
     Setting,
     /// Workflow template
     WorkflowTemplate,
+    /// Workflow run of a task
+    Workflow,
     /// Git worktree
     Worktree,
+    /// Verification result for a chat
+    Verification,
+    /// Stored secret (names only)
+    Secret,
 }
 
 impl std::fmt::Display for EntityType {
@@ -180,7 +186,10 @@ impl std::fmt::Display for EntityType {
             EntityType::Process => write!(f, "process"),
             EntityType::Setting => write!(f, "setting"),
             EntityType::WorkflowTemplate => write!(f, "workflow_template"),
+            EntityType::Workflow => write!(f, "workflow"),
             EntityType::Worktree => write!(f, "worktree"),
+            EntityType::Verification => write!(f, "verification"),
+            EntityType::Secret => write!(f, "secret"),
         }
     }
 }
@@ -198,7 +207,10 @@ impl std::str::FromStr for EntityType {
             "process" => Ok(EntityType::Process),
             "setting" => Ok(EntityType::Setting),
             "workflow_template" | "workflowtemplate" => Ok(EntityType::WorkflowTemplate),
+            "workflow" => Ok(EntityType::Workflow),
             "worktree" => Ok(EntityType::Worktree),
+            "verification" => Ok(EntityType::Verification),
+            "secret" => Ok(EntityType::Secret),
             _ => Err(format!("Invalid entity type: {}", s)),
         }
     }
@@ -226,7 +238,10 @@ impl EntityType {
             EntityType::Process => "processes",
             EntityType::Setting => "settings",
             EntityType::WorkflowTemplate => "workflowTemplates",
+            EntityType::Workflow => "workflows",
             EntityType::Worktree => "worktrees",
+            EntityType::Verification => "verifications",
+            EntityType::Secret => "secrets",
         }
     }
 
@@ -241,7 +256,10 @@ impl EntityType {
             EntityType::Process,
             EntityType::Setting,
             EntityType::WorkflowTemplate,
+            EntityType::Workflow,
             EntityType::Worktree,
+            EntityType::Verification,
+            EntityType::Secret,
         ]
     }
 }
@@ -799,7 +817,10 @@ mod tests {
             EntityType::WorkflowTemplate.to_string(),
             "workflow_template"
         );
+        assert_eq!(EntityType::Workflow.to_string(), "workflow");
         assert_eq!(EntityType::Worktree.to_string(), "worktree");
+        assert_eq!(EntityType::Verification.to_string(), "verification");
+        assert_eq!(EntityType::Secret.to_string(), "secret");
     }
 
     #[test]
@@ -831,9 +852,12 @@ mod tests {
     #[test]
     fn test_entity_type_all() {
         let all = EntityType::all();
-        assert_eq!(all.len(), 9);
+        assert_eq!(all.len(), 12);
         assert!(all.contains(&EntityType::Project));
+        assert!(all.contains(&EntityType::Workflow));
         assert!(all.contains(&EntityType::Worktree));
+        assert!(all.contains(&EntityType::Verification));
+        assert!(all.contains(&EntityType::Secret));
     }
 
     #[test]
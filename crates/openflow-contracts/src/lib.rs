@@ -38,6 +38,7 @@ pub mod validation;
 // Re-export commonly used types for convenience
 // Entity types
 pub use entities::ArtifactFile;
+pub use entities::Secret;
 pub use entities::{
     Branch, Commit, CommitSummary, DiffHunk, FileChangeType, FileDiff, FileDiffSummary,
     GitFileStatus, GitStatus, GitStatusFile, PullRequestResult, Worktree, WorktreeStatus,
@@ -46,20 +47,25 @@ pub use entities::{
 pub use entities::{Chat, ChatRole, ChatSummary, ChatWithMessageCount, ChatWithMessages};
 pub use entities::{CliToolType, ExecutorProfile, ExecutorProfileSummary};
 pub use entities::{
-    ExecutionProcess, OutputType, ProcessOutputEvent, ProcessStatus, ProcessStatusEvent,
-    ProcessSummary, RunReason,
+    ExecutionProcess, OutputType, ProcessOutputChunk, ProcessOutputEvent, ProcessSnapshot,
+    ProcessStatus, ProcessStatusEvent, ProcessSummary, QueuedProcess, RunReason,
 };
 pub use entities::{Message, MessageRole, MessageSummary};
+pub use entities::{ProcessSandbox, SandboxSettings};
 pub use entities::{Project, ProjectSummary, ProjectWithStats};
+pub use entities::{ProjectScriptLimits, ResourceLimits};
 pub use entities::{SearchResult, SearchResultType};
 pub use entities::{Setting, SettingsMap};
 pub use entities::{Task, TaskStatus, TaskSummary, TaskWithChatCount, TaskWithChats};
+pub use entities::{VerificationCheck, VerificationCheckStatus, VerificationResult};
 pub use entities::{
-    WorkflowContext, WorkflowStep, WorkflowStepStatus, WorkflowTemplate, WorkflowTemplateSummary,
-    WorkflowVariable,
+    WorkflowContext, WorkflowDiagnostic, WorkflowDiagnosticCode, WorkflowDiagnosticSeverity,
+    WorkflowRun, WorkflowStep, WorkflowStepStatus, WorkflowTemplate, WorkflowTemplateSummary,
+    WorkflowVariable, WorkflowVariableInfo, WorkflowVariableType,
 };
 // Request types
 pub use requests::SearchRequest;
+pub use requests::SetSecretRequest;
 pub use requests::{
     AppendMessageContentRequest, CreateMessageRequest, SetMessageStreamingRequest,
     UpdateMessageRequest,
@@ -69,14 +75,14 @@ pub use requests::{
     GetWorkflowTemplateRequest, ListWorkflowTemplatesRequest, ParseWorkflowRequest,
     SubstituteWorkflowVariablesRequest, UpdateWorkflowStepRequest, UpdateWorkflowTemplateRequest,
 };
-pub use requests::{CreateChatRequest, UpdateChatRequest};
+pub use requests::{CleanupWorktreeRequest, CreateChatRequest, UpdateChatRequest};
 pub use requests::{
     CreateExecutorProfileRequest, RunExecutorRequest, SetDefaultExecutorProfileRequest,
     UpdateExecutorProfileRequest,
 };
 pub use requests::{
-    CreateProcessRequest, KillProcessRequest, ListProcessesRequest, ResizeProcessRequest,
-    SendProcessInputRequest, StartProcessRequest, UpdateProcessRequest,
+    CreateProcessRequest, GetProcessOutputRequest, KillProcessRequest, ListProcessesRequest,
+    ResizeProcessRequest, SendProcessInputRequest, StartProcessRequest, UpdateProcessRequest,
 };
 pub use requests::{CreateProjectRequest, UpdateProjectRequest};
 pub use requests::{
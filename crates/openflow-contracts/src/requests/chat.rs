@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 use typeshare::typeshare;
 
 use crate::entities::chat::ChatRole;
+use crate::entities::executor::CliToolType;
 use crate::validation::{
     validate_required_string, validate_string_length, Validate, ValidationCollector,
     ValidationResult,
@@ -238,6 +239,10 @@ pub struct UpdateChatRequest {
     /// @validate: format=uuid
     pub executor_profile_id: Option<String>,
 
+    /// Updated base branch the chat branch is created from
+    /// @validate: min_length=1, max_length=255
+    pub base_branch: Option<String>,
+
     /// Updated branch name
     /// @validate: max_length=255
     pub branch: Option<String>,
@@ -263,6 +268,10 @@ pub struct UpdateChatRequest {
     /// Claude Code session ID for resuming conversations
     /// @validate: max_length=255
     pub claude_session_id: Option<String>,
+
+    /// CLI tool that created the session. A session ID set without it is
+    /// never resumed.
+    pub session_cli_tool: Option<CliToolType>,
 }
 
 impl UpdateChatRequest {
@@ -270,6 +279,7 @@ impl UpdateChatRequest {
     pub fn has_updates(&self) -> bool {
         self.title.is_some()
             || self.executor_profile_id.is_some()
+            || self.base_branch.is_some()
             || self.branch.is_some()
             || self.worktree_path.is_some()
             || self.worktree_deleted.is_some()
@@ -277,6 +287,7 @@ impl UpdateChatRequest {
             || self.initial_prompt.is_some()
             || self.hidden_prompt.is_some()
             || self.claude_session_id.is_some()
+            || self.session_cli_tool.is_some()
     }
 
     /// Create a request to update only the title
@@ -287,10 +298,12 @@ impl UpdateChatRequest {
         }
     }
 
-    /// Create a request to update the session ID
-    pub fn with_session_id(session_id: impl Into<String>) -> Self {
+    /// Create a request to update the session ID and the CLI tool that
+    /// created it
+    pub fn with_session_id(session_id: impl Into<String>, tool: CliToolType) -> Self {
         Self {
             claude_session_id: Some(session_id.into()),
+            session_cli_tool: Some(tool),
             ..Default::default()
         }
     }
@@ -331,6 +344,13 @@ impl Validate for UpdateChatRequest {
                     Ok(())
                 }
             })
+            .validate(|| {
+                if let Some(ref base_branch) = self.base_branch {
+                    validate_string_length("base_branch", base_branch, Some(1), Some(255))
+                } else {
+                    Ok(())
+                }
+            })
             .validate(|| {
                 if let Some(ref branch) = self.branch {
                     validate_string_length("branch", branch, None, Some(255))
@@ -370,6 +390,45 @@ impl Validate for UpdateChatRequest {
     }
 }
 
+// =============================================================================
+// Cleanup Worktree Request
+// =============================================================================
+
+/// Request to clean up the worktree of a chat, or of every chat of a task
+///
+/// Runs the project's cleanup script in the worktree and removes it. The
+/// branch is kept unless `deleteBranch` is set.
+///
+/// # Endpoint
+/// @endpoint: POST /api/chats/:id/cleanup
+/// @endpoint: POST /api/tasks/:id/cleanup
+/// @command: cleanup_chat_worktree
+/// @command: cleanup_task_worktrees
+///
+/// # Example
+/// ```json
+/// {
+///   "deleteBranch": false
+/// }
+/// ```
+#[typeshare]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CleanupWorktreeRequest {
+    /// Also delete the chat's branch
+    #[serde(default)]
+    pub delete_branch: bool,
+}
+
+impl CleanupWorktreeRequest {
+    /// Create a request that also deletes the branch
+    pub fn deleting_branch() -> Self {
+        Self {
+            delete_branch: true,
+        }
+    }
+}
+
 // =============================================================================
 // Tests
 // =============================================================================
@@ -512,6 +571,7 @@ mod tests {
         let request = UpdateChatRequest {
             title: Some("Updated Title".to_string()),
             executor_profile_id: Some("exec-123".to_string()),
+            base_branch: None,
             branch: Some("feature/test".to_string()),
             worktree_path: Some("/tmp/worktree".to_string()),
             worktree_deleted: Some(false),
@@ -519,6 +579,7 @@ mod tests {
             initial_prompt: Some("Updated prompt".to_string()),
             hidden_prompt: None,
             claude_session_id: Some("session-456".to_string()),
+            session_cli_tool: Some(CliToolType::ClaudeCode),
         };
 
         assert!(request.validate().is_ok());
@@ -544,11 +605,12 @@ mod tests {
 
     #[test]
     fn test_update_chat_request_with_session_id() {
-        let request = UpdateChatRequest::with_session_id("session-789");
+        let request = UpdateChatRequest::with_session_id("session-789", CliToolType::CodexCli);
 
         assert!(request.validate().is_ok());
         assert!(request.has_updates());
         assert_eq!(request.claude_session_id, Some("session-789".to_string()));
+        assert_eq!(request.session_cli_tool, Some(CliToolType::CodexCli));
     }
 
     #[test]
@@ -602,6 +664,16 @@ mod tests {
         assert!(request.validate().is_err());
     }
 
+    #[test]
+    fn test_update_chat_request_empty_base_branch() {
+        let request = UpdateChatRequest {
+            base_branch: Some(String::new()),
+            ..Default::default()
+        };
+
+        assert!(request.validate().is_err());
+    }
+
     #[test]
     fn test_update_chat_request_has_updates() {
         // Test each field individually
@@ -617,6 +689,12 @@ mod tests {
         }
         .has_updates());
 
+        assert!(UpdateChatRequest {
+            base_branch: Some("develop".to_string()),
+            ..Default::default()
+        }
+        .has_updates());
+
         assert!(UpdateChatRequest {
             branch: Some("main".to_string()),
             ..Default::default()
@@ -694,5 +772,20 @@ mod tests {
         assert!(request.initial_prompt.is_none());
         assert!(request.hidden_prompt.is_none());
         assert!(request.claude_session_id.is_none());
+        assert!(request.session_cli_tool.is_none());
+    }
+
+    // =========================================================================
+    // CleanupWorktreeRequest Tests
+    // =========================================================================
+
+    #[test]
+    fn test_cleanup_worktree_request_defaults_to_keeping_branch() {
+        let request: CleanupWorktreeRequest = serde_json::from_str("{}").unwrap();
+        assert!(!request.delete_branch);
+
+        let request: CleanupWorktreeRequest =
+            serde_json::from_str(r#"{"deleteBranch": true}"#).unwrap();
+        assert_eq!(request, CleanupWorktreeRequest::deleting_branch());
     }
 }
@@ -20,6 +20,10 @@
 use serde::{Deserialize, Serialize};
 use typeshare::typeshare;
 
+use crate::entities::executor::CliToolType;
+use crate::entities::limits::ResourceLimits;
+use crate::entities::sandbox::SandboxSettings;
+
 /// Request to create a new executor profile.
 ///
 /// # Required Fields
@@ -31,6 +35,11 @@ use typeshare::typeshare;
 /// * `args` - JSON array of CLI arguments
 /// * `env` - JSON object of environment variables
 /// * `model` - AI model identifier
+/// * `cli_tool` - CLI tool type (inferred from `command` if not set)
+/// * `max_runtime_secs` - Wall-clock limit for a run (0 for none)
+/// * `idle_timeout_secs` - Limit on time without output (0 for none)
+/// * `resource_limits` - JSON object of `ResourceLimits` for each run
+/// * `sandbox` - JSON object of `SandboxSettings` for each run
 /// * `is_default` - Whether this should be the default profile
 ///
 /// @endpoint: POST /api/executor-profiles
@@ -63,6 +72,24 @@ pub struct CreateExecutorProfileRequest {
     /// @validate: max_length=255
     pub model: Option<String>,
 
+    /// CLI tool type selecting the executor adapter
+    /// If not set, the tool type is inferred from the command
+    pub cli_tool: Option<CliToolType>,
+
+    /// Maximum run time in seconds (0 for no limit)
+    pub max_runtime_secs: Option<u32>,
+
+    /// Maximum time in seconds without output (0 for no limit)
+    pub idle_timeout_secs: Option<u32>,
+
+    /// JSON object of resource limits (`ResourceLimits`) for each run
+    /// @validate: max_length=1000, format=json_object
+    pub resource_limits: Option<String>,
+
+    /// JSON object of sandbox settings (`SandboxSettings`) for each run
+    /// @validate: max_length=10000, format=json_object
+    pub sandbox: Option<String>,
+
     /// Whether this should be the default profile
     /// If true, clears default from all other profiles
     pub is_default: Option<bool>,
@@ -78,6 +105,11 @@ impl CreateExecutorProfileRequest {
             args: None,
             env: None,
             model: None,
+            cli_tool: None,
+            max_runtime_secs: None,
+            idle_timeout_secs: None,
+            resource_limits: None,
+            sandbox: None,
             is_default: None,
         }
     }
@@ -119,6 +151,36 @@ impl CreateExecutorProfileRequest {
         self
     }
 
+    /// Set the CLI tool type
+    pub fn with_cli_tool(mut self, cli_tool: CliToolType) -> Self {
+        self.cli_tool = Some(cli_tool);
+        self
+    }
+
+    /// Set the maximum run time in seconds
+    pub fn with_max_runtime_secs(mut self, secs: u32) -> Self {
+        self.max_runtime_secs = Some(secs);
+        self
+    }
+
+    /// Set the idle-output timeout in seconds
+    pub fn with_idle_timeout_secs(mut self, secs: u32) -> Self {
+        self.idle_timeout_secs = Some(secs);
+        self
+    }
+
+    /// Set the resource limits for each run
+    pub fn with_resource_limits(mut self, limits: &ResourceLimits) -> Self {
+        self.resource_limits = serde_json::to_string(limits).ok();
+        self
+    }
+
+    /// Set the sandbox settings for each run
+    pub fn with_sandbox(mut self, sandbox: &SandboxSettings) -> Self {
+        self.sandbox = serde_json::to_string(sandbox).ok();
+        self
+    }
+
     /// Set whether this is the default profile
     pub fn with_is_default(mut self, is_default: bool) -> Self {
         self.is_default = Some(is_default);
@@ -161,6 +223,23 @@ pub struct UpdateExecutorProfileRequest {
     /// @validate: max_length=255
     pub model: Option<String>,
 
+    /// Updated CLI tool type
+    pub cli_tool: Option<CliToolType>,
+
+    /// Updated maximum run time in seconds (0 removes the limit)
+    pub max_runtime_secs: Option<u32>,
+
+    /// Updated idle-output timeout in seconds (0 removes the limit)
+    pub idle_timeout_secs: Option<u32>,
+
+    /// Updated resource limits as a JSON object (`{}` removes all limits)
+    /// @validate: max_length=1000, format=json_object
+    pub resource_limits: Option<String>,
+
+    /// Updated sandbox settings as a JSON object
+    /// @validate: max_length=10000, format=json_object
+    pub sandbox: Option<String>,
+
     /// Updated default status
     /// If true, clears default from all other profiles
     pub is_default: Option<bool>,
@@ -208,6 +287,36 @@ impl UpdateExecutorProfileRequest {
         self
     }
 
+    /// Set the CLI tool type to update
+    pub fn with_cli_tool(mut self, cli_tool: CliToolType) -> Self {
+        self.cli_tool = Some(cli_tool);
+        self
+    }
+
+    /// Set the maximum run time to update
+    pub fn with_max_runtime_secs(mut self, secs: u32) -> Self {
+        self.max_runtime_secs = Some(secs);
+        self
+    }
+
+    /// Set the idle-output timeout to update
+    pub fn with_idle_timeout_secs(mut self, secs: u32) -> Self {
+        self.idle_timeout_secs = Some(secs);
+        self
+    }
+
+    /// Set the resource limits to update
+    pub fn with_resource_limits(mut self, limits: &ResourceLimits) -> Self {
+        self.resource_limits = serde_json::to_string(limits).ok();
+        self
+    }
+
+    /// Set the sandbox settings to update
+    pub fn with_sandbox(mut self, sandbox: &SandboxSettings) -> Self {
+        self.sandbox = serde_json::to_string(sandbox).ok();
+        self
+    }
+
     /// Set the default status to update
     pub fn with_is_default(mut self, is_default: bool) -> Self {
         self.is_default = Some(is_default);
@@ -222,6 +331,11 @@ impl UpdateExecutorProfileRequest {
             || self.args.is_some()
             || self.env.is_some()
             || self.model.is_some()
+            || self.cli_tool.is_some()
+            || self.max_runtime_secs.is_some()
+            || self.idle_timeout_secs.is_some()
+            || self.resource_limits.is_some()
+            || self.sandbox.is_some()
             || self.is_default.is_some()
     }
 }
@@ -305,6 +419,11 @@ mod tests {
         assert!(request.args.is_none());
         assert!(request.env.is_none());
         assert!(request.model.is_none());
+        assert!(request.cli_tool.is_none());
+        assert!(request.max_runtime_secs.is_none());
+        assert!(request.idle_timeout_secs.is_none());
+        assert!(request.resource_limits.is_none());
+        assert!(request.sandbox.is_none());
         assert!(request.is_default.is_none());
     }
 
@@ -375,6 +494,49 @@ mod tests {
         assert!(request.description.is_none());
     }
 
+    #[test]
+    fn test_cli_tool_serialization() {
+        let request = CreateExecutorProfileRequest::new("Gemini", "gemini")
+            .with_cli_tool(CliToolType::GeminiCli);
+
+        let json = serde_json::to_string(&request).unwrap();
+        assert!(json.contains(r#""cliTool":"gemini_cli""#));
+
+        let update = UpdateExecutorProfileRequest::new().with_cli_tool(CliToolType::CodexCli);
+        assert!(update.has_updates());
+    }
+
+    #[test]
+    fn test_timeouts_serialization() {
+        let request = CreateExecutorProfileRequest::new("Claude", "claude")
+            .with_max_runtime_secs(3600)
+            .with_idle_timeout_secs(300);
+
+        let json = serde_json::to_string(&request).unwrap();
+        assert!(json.contains(r#""maxRuntimeSecs":3600"#));
+        assert!(json.contains(r#""idleTimeoutSecs":300"#));
+
+        let update = UpdateExecutorProfileRequest::new().with_idle_timeout_secs(0);
+        assert!(update.has_updates());
+    }
+
+    #[test]
+    fn test_resource_limits_builder() {
+        let limits = ResourceLimits {
+            cpu_secs: Some(600),
+            ..Default::default()
+        };
+        let request =
+            CreateExecutorProfileRequest::new("Claude", "claude").with_resource_limits(&limits);
+
+        let stored = request.resource_limits.unwrap();
+        assert!(stored.contains(r#""cpuSecs":600"#));
+        assert_eq!(ResourceLimits::from_json(Some(&stored)), limits);
+
+        let update = UpdateExecutorProfileRequest::new().with_resource_limits(&limits);
+        assert!(update.has_updates());
+    }
+
     #[test]
     fn test_update_request_has_updates() {
         let mut request = UpdateExecutorProfileRequest::new();
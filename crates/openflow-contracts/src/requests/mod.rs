@@ -10,13 +10,14 @@ pub mod message;
 pub mod process;
 pub mod project;
 pub mod search;
+pub mod secret;
 pub mod settings;
 pub mod task;
 pub mod terminal;
 pub mod workflow;
 
 // Re-export request types for convenience
-pub use chat::{CreateChatRequest, UpdateChatRequest};
+pub use chat::{CleanupWorktreeRequest, CreateChatRequest, UpdateChatRequest};
 pub use executor::{
     CreateExecutorProfileRequest, RunExecutorRequest, SetDefaultExecutorProfileRequest,
     UpdateExecutorProfileRequest,
@@ -32,11 +33,12 @@ pub use message::{
     UpdateMessageRequest,
 };
 pub use process::{
-    CreateProcessRequest, KillProcessRequest, ListProcessesRequest, ResizeProcessRequest,
-    SendProcessInputRequest, StartProcessRequest, UpdateProcessRequest,
+    CreateProcessRequest, GetProcessOutputRequest, KillProcessRequest, ListProcessesRequest,
+    ResizeProcessRequest, SendProcessInputRequest, StartProcessRequest, UpdateProcessRequest,
 };
 pub use project::{CreateProjectRequest, UpdateProjectRequest};
 pub use search::SearchRequest;
+pub use secret::SetSecretRequest;
 pub use settings::{
     DeleteAllSettingsRequest, DeleteAllSettingsResponse, DeleteSettingRequest,
     GetAllSettingsRequest, GetSettingOrDefaultRequest, GetSettingRequest, SetSettingRequest,
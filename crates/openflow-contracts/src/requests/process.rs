@@ -6,7 +6,9 @@
 use serde::{Deserialize, Serialize};
 use typeshare::typeshare;
 
+use crate::entities::limits::ResourceLimits;
 use crate::entities::process::{ProcessStatus, RunReason};
+use crate::entities::sandbox::ProcessSandbox;
 use crate::validation::{
     validate_required_string, validate_string_length, Validate, ValidationCollector,
     ValidationResult,
@@ -588,6 +590,91 @@ impl Validate for ListProcessesRequest {
     }
 }
 
+// =============================================================================
+// Get Process Output Request (Query Parameters)
+// =============================================================================
+
+/// Query parameters for replaying persisted process output
+///
+/// Output chunks are returned in arrival order. Pass the `id` of the last
+/// chunk received as `since` to fetch only newer chunks.
+///
+/// # Endpoint
+/// @endpoint: GET /api/processes/:id/output
+/// @command: get_process_output
+///
+/// # Example URL
+/// `GET /api/processes/550e8400-.../output?since=42&limit=500`
+#[typeshare]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GetProcessOutputRequest {
+    /// Only return chunks with an id greater than this cursor
+    /// @validate: min=0
+    pub since: Option<i64>,
+
+    /// Maximum number of chunks to return
+    /// @validate: min=1, max=10000
+    pub limit: Option<i32>,
+}
+
+impl GetProcessOutputRequest {
+    /// Maximum number of chunks that can be requested at once
+    pub const MAX_LIMIT: i32 = 10000;
+
+    /// Create a request for all chunks after the given cursor
+    pub fn since(cursor: i64) -> Self {
+        Self {
+            since: Some(cursor),
+            ..Default::default()
+        }
+    }
+
+    /// Add a limit to the request
+    pub fn with_limit(mut self, limit: i32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+}
+
+impl Validate for GetProcessOutputRequest {
+    fn validate(&self) -> ValidationResult<()> {
+        ValidationCollector::new()
+            .validate(|| {
+                if let Some(since) = self.since {
+                    if since < 0 {
+                        return Err(crate::validation::ValidationError::NumberMin {
+                            field: "since".to_string(),
+                            min: 0.0,
+                            actual: since as f64,
+                        });
+                    }
+                }
+                Ok(())
+            })
+            .validate(|| {
+                if let Some(limit) = self.limit {
+                    if limit < 1 {
+                        return Err(crate::validation::ValidationError::NumberMin {
+                            field: "limit".to_string(),
+                            min: 1.0,
+                            actual: limit as f64,
+                        });
+                    }
+                    if limit > Self::MAX_LIMIT {
+                        return Err(crate::validation::ValidationError::NumberMax {
+                            field: "limit".to_string(),
+                            max: Self::MAX_LIMIT as f64,
+                            actual: limit as f64,
+                        });
+                    }
+                }
+                Ok(())
+            })
+            .finish()
+    }
+}
+
 // =============================================================================
 // Start Process Request
 // =============================================================================
@@ -637,6 +724,18 @@ pub struct StartProcessRequest {
 
     /// PTY rows (height) - only used if use_pty is true
     pub pty_rows: Option<u16>,
+
+    /// Kill the process as timed out after this many seconds
+    pub max_runtime_secs: Option<u32>,
+
+    /// Kill the process as timed out after this many seconds without output
+    pub idle_timeout_secs: Option<u32>,
+
+    /// Resource limits for the process and everything it starts
+    pub resource_limits: Option<ResourceLimits>,
+
+    /// Sandbox to confine the process to
+    pub sandbox: Option<ProcessSandbox>,
 }
 
 impl StartProcessRequest {
@@ -664,6 +763,16 @@ impl StartProcessRequest {
         Self::pty(command, 120, 40)
     }
 
+    /// Create a request that runs a shell script (`sh -c` or `cmd /C` on Windows)
+    pub fn script(script: impl Into<String>) -> Self {
+        let (shell, flag) = if cfg!(windows) {
+            ("cmd", "/C")
+        } else {
+            ("sh", "-c")
+        };
+        Self::new(shell).with_args([flag.to_string(), script.into()])
+    }
+
     /// Add arguments to the command
     pub fn with_args<I, S>(mut self, args: I) -> Self
     where
@@ -699,6 +808,30 @@ impl StartProcessRequest {
         self.pty_rows = Some(rows);
         self
     }
+
+    /// Set the wall-clock limit in seconds
+    pub fn with_max_runtime_secs(mut self, secs: u32) -> Self {
+        self.max_runtime_secs = Some(secs);
+        self
+    }
+
+    /// Set the idle-output limit in seconds
+    pub fn with_idle_timeout_secs(mut self, secs: u32) -> Self {
+        self.idle_timeout_secs = Some(secs);
+        self
+    }
+
+    /// Set resource limits for the process
+    pub fn with_resource_limits(mut self, limits: ResourceLimits) -> Self {
+        self.resource_limits = Some(limits);
+        self
+    }
+
+    /// Run the process in a sandbox
+    pub fn with_sandbox(mut self, sandbox: ProcessSandbox) -> Self {
+        self.sandbox = Some(sandbox);
+        self
+    }
 }
 
 impl Validate for StartProcessRequest {
@@ -873,6 +1006,41 @@ mod tests {
         assert_eq!(request, deserialized);
     }
 
+    // =========================================================================
+    // GetProcessOutputRequest Tests
+    // =========================================================================
+
+    #[test]
+    fn test_get_process_output_request_default() {
+        let request = GetProcessOutputRequest::default();
+
+        assert!(request.validate().is_ok());
+        assert!(request.since.is_none());
+        assert!(request.limit.is_none());
+    }
+
+    #[test]
+    fn test_get_process_output_request_since_with_limit() {
+        let request = GetProcessOutputRequest::since(42).with_limit(100);
+
+        assert!(request.validate().is_ok());
+        assert_eq!(request.since, Some(42));
+        assert_eq!(request.limit, Some(100));
+    }
+
+    #[test]
+    fn test_get_process_output_request_invalid_values() {
+        assert!(GetProcessOutputRequest::since(-1).validate().is_err());
+        assert!(GetProcessOutputRequest::default()
+            .with_limit(0)
+            .validate()
+            .is_err());
+        assert!(GetProcessOutputRequest::default()
+            .with_limit(GetProcessOutputRequest::MAX_LIMIT + 1)
+            .validate()
+            .is_err());
+    }
+
     // =========================================================================
     // UpdateProcessRequest Tests
     // =========================================================================
@@ -1263,6 +1431,17 @@ mod tests {
         assert_eq!(request.pty_rows, Some(40));
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn test_start_process_request_script() {
+        let request = StartProcessRequest::script("npm install && npm run build");
+
+        assert!(request.validate().is_ok());
+        assert_eq!(request.command, "sh");
+        assert_eq!(request.args, vec!["-c", "npm install && npm run build"]);
+        assert!(!request.use_pty);
+    }
+
     #[test]
     fn test_start_process_request_with_args() {
         let request = StartProcessRequest::new("claude").with_args(["--verbose", "-p", "hello"]);
@@ -1316,6 +1495,28 @@ mod tests {
         assert_eq!(request.pty_rows, Some(24));
     }
 
+    #[test]
+    fn test_start_process_request_with_timeouts() {
+        let request = StartProcessRequest::new("claude")
+            .with_max_runtime_secs(600)
+            .with_idle_timeout_secs(60);
+
+        assert_eq!(request.max_runtime_secs, Some(600));
+        assert_eq!(request.idle_timeout_secs, Some(60));
+    }
+
+    #[test]
+    fn test_start_process_request_resource_limits() {
+        let request = StartProcessRequest::script("make").with_resource_limits(ResourceLimits {
+            cpu_secs: Some(120),
+            ..Default::default()
+        });
+
+        assert_eq!(request.resource_limits.unwrap().cpu_secs, Some(120));
+        let json = serde_json::to_string(&request).unwrap();
+        assert!(json.contains(r#""resourceLimits":{"#));
+    }
+
     #[test]
     fn test_start_process_request_empty_command() {
         let request = StartProcessRequest::new("");
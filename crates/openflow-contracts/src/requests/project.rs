@@ -82,6 +82,10 @@ pub struct CreateProjectRequest {
     /// JSON object with verification command configuration
     /// @validate: max_length=50000
     pub verification_config: Option<String>,
+
+    /// JSON object with resource limits for each script
+    /// @validate: max_length=5000
+    pub script_limits: Option<String>,
 }
 
 impl Validate for CreateProjectRequest {
@@ -208,6 +212,10 @@ pub struct UpdateProjectRequest {
     /// Updated verification config JSON object
     /// @validate: max_length=50000
     pub verification_config: Option<String>,
+
+    /// Updated script resource limits JSON object
+    /// @validate: max_length=5000
+    pub script_limits: Option<String>,
 }
 
 impl UpdateProjectRequest {
@@ -225,6 +233,7 @@ impl UpdateProjectRequest {
             || self.always_included_rules.is_some()
             || self.workflows_folder.is_some()
             || self.verification_config.is_some()
+            || self.script_limits.is_some()
     }
 }
 
@@ -377,6 +386,7 @@ mod tests {
             always_included_rules: None,
             workflows_folder: None,
             verification_config: None,
+            script_limits: None,
         };
 
         let json = serde_json::to_string(&request).unwrap();
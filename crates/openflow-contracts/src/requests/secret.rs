@@ -0,0 +1,66 @@
+//! Secret Request Types
+//!
+//! Request types for secret operations. Secrets are write-only, so there is
+//! no request to read a value back.
+
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+/// Request to create or replace a secret
+///
+/// @endpoint: PUT /api/secrets/:name
+/// @command: set_secret
+#[typeshare]
+#[derive(Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SetSecretRequest {
+    /// Secret name, referenced as `${secret:NAME}`
+    /// @validate: required, min_length=1, max_length=255, pattern=^[A-Za-z_][A-Za-z0-9_]*$
+    pub name: String,
+
+    /// Secret value (stored encrypted, never returned)
+    /// @validate: required, min_length=1, max_length=65535
+    pub value: String,
+}
+
+impl SetSecretRequest {
+    /// Create a new set secret request
+    pub fn new(name: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            value: value.into(),
+        }
+    }
+}
+
+// The value is left out so requests can be logged safely
+impl std::fmt::Debug for SetSecretRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SetSecretRequest")
+            .field("name", &self.name)
+            .field("value", &"<redacted>")
+            .finish()
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_secret_request_debug_hides_value() {
+        let request = SetSecretRequest::new("API_KEY", "sk-very-secret");
+
+        let debug = format!("{:?}", request);
+        assert!(debug.contains("API_KEY"));
+        assert!(!debug.contains("sk-very-secret"));
+
+        let json = serde_json::to_string(&request).unwrap();
+        let deserialized: SetSecretRequest = serde_json::from_str(&json).unwrap();
+        assert_eq!(request, deserialized);
+    }
+}
@@ -29,6 +29,7 @@ use crate::validation::{
 ///   "title": "Implement user authentication",
 ///   "description": "Add login and registration functionality",
 ///   "workflowTemplate": ".openflow/workflows/feature.md",
+///   "workflowVariables": { "ticket_url": "https://example.com/T-12" },
 ///   "parentTaskId": null,
 ///   "baseBranch": "main"
 /// }
@@ -53,6 +54,9 @@ pub struct CreateTaskRequest {
     /// @validate: max_length=1000
     pub workflow_template: Option<String>,
 
+    /// Values for the workflow template's variables (name -> value)
+    pub workflow_variables: Option<std::collections::HashMap<String, String>>,
+
     /// Parent task ID for creating sub-tasks
     /// @validate: format=uuid
     pub parent_task_id: Option<String>,
@@ -84,6 +88,18 @@ impl CreateTaskRequest {
         self
     }
 
+    /// Set the value of a workflow variable
+    pub fn with_workflow_variable(
+        mut self,
+        name: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        self.workflow_variables
+            .get_or_insert_with(Default::default)
+            .insert(name.into(), value.into());
+        self
+    }
+
     /// Set the parent task ID
     pub fn with_parent(mut self, parent_id: impl Into<String>) -> Self {
         self.parent_task_id = Some(parent_id.into());
@@ -248,6 +264,7 @@ mod tests {
             title: "Test Task".to_string(),
             description: Some("Test description".to_string()),
             workflow_template: Some(".openflow/workflows/feature.md".to_string()),
+            workflow_variables: None,
             parent_task_id: None,
             base_branch: Some("main".to_string()),
         };
@@ -322,6 +339,7 @@ mod tests {
             title: "Test Task".to_string(),
             description: Some("Description".to_string()),
             workflow_template: Some(".openflow/workflows/feature.md".to_string()),
+            workflow_variables: None,
             parent_task_id: None,
             base_branch: Some("main".to_string()),
         };
@@ -36,5 +36,7 @@
 mod broadcaster;
 mod types;
 
+#[cfg(test)]
+pub(crate) use broadcaster::CollectingBroadcaster;
 pub use broadcaster::{ChannelBroadcaster, EventBroadcaster, NullBroadcaster};
 pub use types::{DataAction, EntityType, Event, OutputType, ProcessStatus};
@@ -24,6 +24,12 @@ pub enum EntityType {
     Process,
     /// Worktree entity
     Worktree,
+    /// Verification result entity
+    Verification,
+    /// Secret entity (names only)
+    Secret,
+    /// Workflow run of a task
+    Workflow,
 }
 
 impl EntityType {
@@ -38,6 +44,9 @@ impl EntityType {
             Self::Setting => "setting",
             Self::Process => "process",
             Self::Worktree => "worktree",
+            Self::Verification => "verification",
+            Self::Secret => "secret",
+            Self::Workflow => "workflow",
         }
     }
 }
@@ -79,6 +88,8 @@ pub enum OutputType {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ProcessStatus {
+    /// Process is waiting in the run queue
+    Queued,
     /// Process is starting
     Starting,
     /// Process is running
@@ -89,6 +100,8 @@ pub enum ProcessStatus {
     Failed,
     /// Process was killed
     Killed,
+    /// Process was killed for exceeding a timeout
+    TimedOut,
 }
 
 /// Events for real-time synchronization
@@ -108,6 +121,8 @@ pub enum Event {
         content: String,
         /// Timestamp (ISO 8601)
         timestamp: String,
+        /// Bytes of terminal output up to the end of this chunk (PTY only)
+        offset: Option<i64>,
     },
 
     /// Process status change event
@@ -160,6 +175,25 @@ impl Event {
             output_type,
             content: content.into(),
             timestamp: chrono::Utc::now().to_rfc3339(),
+            offset: None,
+        }
+    }
+
+    /// Create a process output event for terminal (PTY) output
+    ///
+    /// `offset` is the number of bytes of terminal output up to the end of
+    /// `content`, so clients can skip output a snapshot already contains.
+    pub fn terminal_output(
+        process_id: impl Into<String>,
+        content: impl Into<String>,
+        offset: u64,
+    ) -> Self {
+        Self::ProcessOutput {
+            process_id: process_id.into(),
+            output_type: OutputType::Stdout,
+            content: content.into(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            offset: Some(offset as i64),
         }
     }
 
@@ -23,7 +23,7 @@ use openflow_contracts::{
     Chat, ChatRole, ChatWithMessages, CreateChatRequest, Message, UpdateChatRequest,
 };
 
-use super::{ServiceError, ServiceResult};
+use super::{verification, ServiceError, ServiceResult};
 
 /// List chats for a task.
 ///
@@ -42,7 +42,7 @@ pub async fn list(pool: &SqlitePool, task_id: &str) -> ServiceResult<Vec<Chat>>
             base_branch, branch, worktree_path, worktree_deleted,
             setup_completed_at, initial_prompt, hidden_prompt,
             is_plan_container, main_chat_id, workflow_step_index,
-            claude_session_id, archived_at, created_at, updated_at
+            claude_session_id, session_cli_tool, archived_at, created_at, updated_at
         FROM chats
         WHERE task_id = ? AND archived_at IS NULL
         ORDER BY workflow_step_index ASC, created_at ASC
@@ -77,7 +77,7 @@ pub async fn list_standalone(pool: &SqlitePool, project_id: &str) -> ServiceResu
             base_branch, branch, worktree_path, worktree_deleted,
             setup_completed_at, initial_prompt, hidden_prompt,
             is_plan_container, main_chat_id, workflow_step_index,
-            claude_session_id, archived_at, created_at, updated_at
+            claude_session_id, session_cli_tool, archived_at, created_at, updated_at
         FROM chats
         WHERE project_id = ? AND task_id IS NULL AND archived_at IS NULL
         ORDER BY created_at DESC
@@ -119,7 +119,7 @@ pub async fn list_by_project(pool: &SqlitePool, project_id: &str) -> ServiceResu
             base_branch, branch, worktree_path, worktree_deleted,
             setup_completed_at, initial_prompt, hidden_prompt,
             is_plan_container, main_chat_id, workflow_step_index,
-            claude_session_id, archived_at, created_at, updated_at
+            claude_session_id, session_cli_tool, archived_at, created_at, updated_at
         FROM chats
         WHERE project_id = ? AND archived_at IS NULL
         ORDER BY created_at DESC
@@ -150,7 +150,7 @@ pub async fn list_archived(pool: &SqlitePool) -> ServiceResult<Vec<Chat>> {
             base_branch, branch, worktree_path, worktree_deleted,
             setup_completed_at, initial_prompt, hidden_prompt,
             is_plan_container, main_chat_id, workflow_step_index,
-            claude_session_id, archived_at, created_at, updated_at
+            claude_session_id, session_cli_tool, archived_at, created_at, updated_at
         FROM chats
         WHERE archived_at IS NOT NULL
         ORDER BY archived_at DESC
@@ -167,7 +167,7 @@ pub async fn list_archived(pool: &SqlitePool) -> ServiceResult<Vec<Chat>> {
     Ok(chats)
 }
 
-/// Get a chat by ID with its messages.
+/// Get a chat by ID with its messages and latest verification result.
 pub async fn get(pool: &SqlitePool, id: &str) -> ServiceResult<ChatWithMessages> {
     debug!("Getting chat id={}", id);
 
@@ -178,7 +178,7 @@ pub async fn get(pool: &SqlitePool, id: &str) -> ServiceResult<ChatWithMessages>
             base_branch, branch, worktree_path, worktree_deleted,
             setup_completed_at, initial_prompt, hidden_prompt,
             is_plan_container, main_chat_id, workflow_step_index,
-            claude_session_id, archived_at, created_at, updated_at
+            claude_session_id, session_cli_tool, archived_at, created_at, updated_at
         FROM chats
         WHERE id = ?
         "#,
@@ -219,8 +219,11 @@ pub async fn get(pool: &SqlitePool, id: &str) -> ServiceResult<ChatWithMessages>
         ServiceError::Database(e)
     })?;
 
+    let latest_verification = verification::get_latest(pool, id).await?;
+
     debug!("Found chat id={} with {} messages", id, messages.len());
-    Ok(ChatWithMessages::with_messages(chat, messages))
+    Ok(ChatWithMessages::with_messages(chat, messages)
+        .with_latest_verification(latest_verification))
 }
 
 /// Get a chat by ID (without messages).
@@ -234,7 +237,7 @@ pub async fn get_chat(pool: &SqlitePool, id: &str) -> ServiceResult<Chat> {
             base_branch, branch, worktree_path, worktree_deleted,
             setup_completed_at, initial_prompt, hidden_prompt,
             is_plan_container, main_chat_id, workflow_step_index,
-            claude_session_id, archived_at, created_at, updated_at
+            claude_session_id, session_cli_tool, archived_at, created_at, updated_at
         FROM chats
         WHERE id = ?
         "#,
@@ -336,6 +339,7 @@ pub async fn update(
     // Apply updates, falling back to existing values
     let title = request.title.or(existing.title);
     let executor_profile_id = request.executor_profile_id.or(existing.executor_profile_id);
+    let base_branch = request.base_branch.unwrap_or(existing.base_branch);
     let branch = request.branch.or(existing.branch);
     let worktree_path = request.worktree_path.or(existing.worktree_path);
     let worktree_deleted = request
@@ -344,6 +348,13 @@ pub async fn update(
     let setup_completed_at = request.setup_completed_at.or(existing.setup_completed_at);
     let initial_prompt = request.initial_prompt.or(existing.initial_prompt);
     let hidden_prompt = request.hidden_prompt.or(existing.hidden_prompt);
+    // A new session ID replaces the tool too, so it's never attributed to
+    // the tool of the previous session
+    let session_cli_tool = if request.claude_session_id.is_some() {
+        request.session_cli_tool
+    } else {
+        request.session_cli_tool.or(existing.session_cli_tool)
+    };
     let claude_session_id = request.claude_session_id.or(existing.claude_session_id);
 
     sqlx::query(
@@ -352,6 +363,7 @@ pub async fn update(
         SET
             title = ?,
             executor_profile_id = ?,
+            base_branch = ?,
             branch = ?,
             worktree_path = ?,
             worktree_deleted = ?,
@@ -359,12 +371,14 @@ pub async fn update(
             initial_prompt = ?,
             hidden_prompt = ?,
             claude_session_id = ?,
+            session_cli_tool = ?,
             updated_at = datetime('now', 'subsec')
         WHERE id = ?
         "#,
     )
     .bind(&title)
     .bind(&executor_profile_id)
+    .bind(&base_branch)
     .bind(&branch)
     .bind(&worktree_path)
     .bind(worktree_deleted)
@@ -372,6 +386,7 @@ pub async fn update(
     .bind(&initial_prompt)
     .bind(&hidden_prompt)
     .bind(&claude_session_id)
+    .bind(session_cli_tool)
     .bind(id)
     .execute(pool)
     .await
@@ -599,6 +614,7 @@ mod tests {
             always_included_rules: None,
             workflows_folder: None,
             verification_config: None,
+            script_limits: None,
         };
         project::create(pool, request)
             .await
@@ -613,6 +629,7 @@ mod tests {
             title: title.to_string(),
             description: None,
             workflow_template: None,
+            workflow_variables: None,
             parent_task_id: None,
             base_branch: None,
         };
@@ -965,6 +982,7 @@ mod tests {
         let update_request = UpdateChatRequest {
             title: Some("Updated Title".to_string()),
             executor_profile_id: None,
+            base_branch: None,
             branch: Some("feature/test".to_string()),
             worktree_path: Some("/tmp/worktree/test".to_string()),
             worktree_deleted: None,
@@ -972,6 +990,7 @@ mod tests {
             initial_prompt: None,
             hidden_prompt: None,
             claude_session_id: None,
+            session_cli_tool: None,
         };
 
         let updated = update(&test_db.pool, &created.id, update_request)
@@ -1019,6 +1038,7 @@ mod tests {
         let update_request = UpdateChatRequest {
             title: None,
             executor_profile_id: None,
+            base_branch: None,
             branch: Some("feature/update".to_string()),
             worktree_path: None,
             worktree_deleted: None,
@@ -1026,6 +1046,7 @@ mod tests {
             initial_prompt: None,
             hidden_prompt: None,
             claude_session_id: None,
+            session_cli_tool: None,
         };
 
         let updated = update(&test_db.pool, &created.id, update_request)
@@ -1057,6 +1078,7 @@ mod tests {
         let update_request = UpdateChatRequest {
             title: None,
             executor_profile_id: None,
+            base_branch: None,
             branch: None,
             worktree_path: None,
             worktree_deleted: Some(true),
@@ -1064,6 +1086,7 @@ mod tests {
             initial_prompt: None,
             hidden_prompt: None,
             claude_session_id: None,
+            session_cli_tool: None,
         };
 
         let updated = update(&test_db.pool, &created.id, update_request)
@@ -1080,6 +1103,7 @@ mod tests {
         let update_request = UpdateChatRequest {
             title: Some("New Title".to_string()),
             executor_profile_id: None,
+            base_branch: None,
             branch: None,
             worktree_path: None,
             worktree_deleted: None,
@@ -1087,6 +1111,7 @@ mod tests {
             initial_prompt: None,
             hidden_prompt: None,
             claude_session_id: None,
+            session_cli_tool: None,
         };
 
         let result = update(&test_db.pool, "non-existent-id", update_request).await;
@@ -1456,6 +1481,7 @@ mod tests {
         let update_request = UpdateChatRequest {
             title: None,
             executor_profile_id: None,
+            base_branch: None,
             branch: None,
             worktree_path: None,
             worktree_deleted: None,
@@ -1463,6 +1489,7 @@ mod tests {
             initial_prompt: None,
             hidden_prompt: None,
             claude_session_id: None,
+            session_cli_tool: None,
         };
         let completed = update(&test_db.pool, &chat.id, update_request)
             .await
@@ -0,0 +1,340 @@
+//! Codex CLI JSONL transcript recording.
+//!
+//! Codex is launched with `codex exec --json`, which prints one JSON event
+//! per line. This module translates those events into the `StreamJsonEvent`s
+//! of `stream_json`, so a `TranscriptRecorder` writes the transcript:
+//!
+//! - `thread.started` carries the thread id, which `codex exec resume`
+//!   takes, and is stored as the chat's session
+//! - completed `agent_message` items become assistant messages
+//! - completed `command_execution`, `file_change`, `mcp_tool_call` and
+//!   `web_search` items are recorded as a tool call plus its result on the
+//!   current assistant message
+//! - `turn.completed` and `turn.failed` mark the end of the run
+//!
+//! Reasoning and todo list items, and items that are still in progress, do
+//! not contribute to the transcript.
+//!
+//! # Usage
+//!
+//! `CodexTranscriptParser` implements `TranscriptParser` and is supplied by
+//! the Codex executor adapter.
+
+use async_trait::async_trait;
+use log::debug;
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+
+use openflow_contracts::CliToolType;
+
+use super::executor_adapter::TranscriptParser;
+use super::stream_json::{StreamJsonEvent, TranscriptRecorder};
+use super::ServiceResult;
+use crate::events::EventBroadcaster;
+
+/// Parse a single line of Codex JSONL output into stream events.
+///
+/// Returns no events for blank lines, lines that are not JSON objects and
+/// events that do not contribute to the transcript. A completed tool item
+/// yields its call followed by its result.
+pub fn parse_line(line: &str) -> Vec<StreamJsonEvent> {
+    let line = line.trim();
+    if !line.starts_with('{') {
+        return Vec::new();
+    }
+
+    let value: Value = match serde_json::from_str(line) {
+        Ok(value) => value,
+        Err(e) => {
+            debug!("parse_line: ignoring malformed Codex JSON line: {}", e);
+            return Vec::new();
+        }
+    };
+
+    match value.get("type").and_then(Value::as_str) {
+        Some("thread.started") => string_field(&value, "thread_id")
+            .map(|session_id| StreamJsonEvent::Init {
+                session_id,
+                model: None,
+            })
+            .into_iter()
+            .collect(),
+        Some("item.completed") => value.get("item").map(parse_item).unwrap_or_default(),
+        Some("turn.completed") => vec![StreamJsonEvent::Result {
+            session_id: None,
+            is_error: false,
+        }],
+        Some("turn.failed") => vec![StreamJsonEvent::Result {
+            session_id: None,
+            is_error: true,
+        }],
+        _ => Vec::new(),
+    }
+}
+
+/// Translate a completed item into stream events.
+fn parse_item(item: &Value) -> Vec<StreamJsonEvent> {
+    let id = string_field(item, "id");
+    let (name, input, output, is_error) = match item.get("type").and_then(Value::as_str) {
+        Some("agent_message") => {
+            return vec![StreamJsonEvent::Assistant {
+                message_id: id,
+                model: None,
+                text: string_field(item, "text").into_iter().collect(),
+                tool_calls: Vec::new(),
+                tokens_used: None,
+            }];
+        }
+        Some("command_execution") => (
+            "command_execution",
+            json!({ "command": item.get("command") }),
+            item.get("aggregated_output").cloned(),
+            item.get("exit_code").and_then(Value::as_i64) != Some(0),
+        ),
+        Some("file_change") => (
+            "file_change",
+            json!({ "changes": item.get("changes") }),
+            item.get("status").cloned(),
+            string_field(item, "status").as_deref() == Some("failed"),
+        ),
+        Some("mcp_tool_call") => (
+            "mcp_tool_call",
+            json!({ "server": item.get("server"), "tool": item.get("tool") }),
+            item.get("status").cloned(),
+            string_field(item, "status").as_deref() == Some("failed"),
+        ),
+        Some("web_search") => (
+            "web_search",
+            json!({ "query": item.get("query") }),
+            None,
+            false,
+        ),
+        _ => return Vec::new(),
+    };
+
+    vec![
+        StreamJsonEvent::Assistant {
+            message_id: None,
+            model: None,
+            text: Vec::new(),
+            tool_calls: vec![json!({
+                "type": "tool_use",
+                "id": id,
+                "name": name,
+                "input": input,
+            })],
+            tokens_used: None,
+        },
+        StreamJsonEvent::ToolResults {
+            results: vec![json!({
+                "type": "tool_result",
+                "tool_use_id": id,
+                "content": output.unwrap_or(Value::Null),
+                "is_error": is_error,
+            })],
+        },
+    ]
+}
+
+fn string_field(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(Value::as_str).map(str::to_string)
+}
+
+/// Records a Codex JSONL run as assistant messages of a chat.
+///
+/// Output may arrive in arbitrary chunks, so incomplete lines are buffered
+/// until their newline arrives.
+#[derive(Debug)]
+pub struct CodexTranscriptParser {
+    buffer: String,
+    recorder: TranscriptRecorder,
+}
+
+impl CodexTranscriptParser {
+    /// Create a parser that writes messages to the given chat.
+    pub fn new(chat_id: impl Into<String>) -> Self {
+        Self {
+            buffer: String::new(),
+            recorder: TranscriptRecorder::new(chat_id, CliToolType::CodexCli),
+        }
+    }
+
+    async fn handle_line(
+        &mut self,
+        pool: &SqlitePool,
+        broadcaster: &dyn EventBroadcaster,
+        line: &str,
+    ) -> ServiceResult<()> {
+        for event in parse_line(line) {
+            self.recorder.handle_event(pool, broadcaster, event).await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TranscriptParser for CodexTranscriptParser {
+    async fn handle_output(
+        &mut self,
+        pool: &SqlitePool,
+        broadcaster: &dyn EventBroadcaster,
+        chunk: &str,
+    ) -> ServiceResult<()> {
+        self.buffer.push_str(chunk);
+
+        while let Some(newline) = self.buffer.find('\n') {
+            let line: String = self.buffer.drain(..=newline).collect();
+            self.handle_line(pool, broadcaster, &line).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn finish(
+        &mut self,
+        pool: &SqlitePool,
+        broadcaster: &dyn EventBroadcaster,
+    ) -> ServiceResult<()> {
+        let remaining = std::mem::take(&mut self.buffer);
+        self.handle_line(pool, broadcaster, &remaining).await?;
+        self.recorder.finish(pool, broadcaster).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::NullBroadcaster;
+    use crate::services::{chat, message, project};
+    use openflow_contracts::{CreateChatRequest, CreateProjectRequest, MessageRole};
+    use openflow_db::create_test_db;
+
+    /// Helper to create a test project and chat.
+    async fn setup_chat(pool: &SqlitePool) -> String {
+        let project = project::create(
+            pool,
+            CreateProjectRequest {
+                name: "Test Project".to_string(),
+                git_repo_path: "/tmp/test-repo".to_string(),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Failed to create project");
+
+        let chat = chat::create(
+            pool,
+            CreateChatRequest {
+                project_id: project.id,
+                title: Some("Agent".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Failed to create chat");
+        chat.id
+    }
+
+    const THREAD: &str = r#"{"type":"thread.started","thread_id":"thread-1"}"#;
+    const TURN: &str = r#"{"type":"turn.started"}"#;
+    const REASONING: &str = r#"{"type":"item.completed","item":{"id":"item_0","type":"reasoning","text":"**Listing files**"}}"#;
+    const COMMAND_STARTED: &str = r#"{"type":"item.started","item":{"id":"item_1","type":"command_execution","command":"bash -lc ls","aggregated_output":"","exit_code":null,"status":"in_progress"}}"#;
+    const COMMAND: &str = r#"{"type":"item.completed","item":{"id":"item_1","type":"command_execution","command":"bash -lc ls","aggregated_output":"README.md\n","exit_code":0,"status":"completed"}}"#;
+    const MESSAGE: &str = r#"{"type":"item.completed","item":{"id":"item_2","type":"agent_message","text":"The repo has a README."}}"#;
+    const COMPLETED: &str = r#"{"type":"turn.completed","usage":{"input_tokens":100,"cached_input_tokens":50,"output_tokens":10}}"#;
+
+    #[test]
+    fn test_parse_line_events() {
+        assert_eq!(
+            parse_line(THREAD),
+            vec![StreamJsonEvent::Init {
+                session_id: "thread-1".to_string(),
+                model: None,
+            }]
+        );
+
+        let events = parse_line(COMMAND);
+        assert_eq!(events.len(), 2);
+        match &events[0] {
+            StreamJsonEvent::Assistant {
+                message_id,
+                tool_calls,
+                ..
+            } => {
+                assert!(message_id.is_none());
+                assert_eq!(tool_calls[0]["id"], "item_1");
+                assert_eq!(tool_calls[0]["input"]["command"], "bash -lc ls");
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+        match &events[1] {
+            StreamJsonEvent::ToolResults { results } => {
+                assert_eq!(results[0]["tool_use_id"], "item_1");
+                assert_eq!(results[0]["content"], "README.md\n");
+                assert_eq!(results[0]["is_error"], false);
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+
+        assert_eq!(
+            parse_line(r#"{"type":"turn.failed","error":{"message":"quota"}}"#),
+            vec![StreamJsonEvent::Result {
+                session_id: None,
+                is_error: true,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_line_ignores_noise() {
+        assert!(parse_line("").is_empty());
+        assert!(parse_line("plain text output").is_empty());
+        assert!(parse_line("{not json").is_empty());
+        assert!(parse_line(TURN).is_empty());
+        assert!(parse_line(REASONING).is_empty());
+        assert!(parse_line(COMMAND_STARTED).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_parser_builds_transcript() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let chat_id = setup_chat(&pool).await;
+
+        let mut parser = CodexTranscriptParser::new(&chat_id);
+        let output = [
+            THREAD,
+            TURN,
+            REASONING,
+            COMMAND_STARTED,
+            COMMAND,
+            MESSAGE,
+            COMPLETED,
+        ]
+        .join("\r\n");
+        // Feed in uneven chunks and leave the last line without a newline
+        for chunk in output.as_bytes().chunks(41) {
+            parser
+                .handle_output(&pool, &NullBroadcaster, std::str::from_utf8(chunk).unwrap())
+                .await
+                .expect("Failed to handle output");
+        }
+        parser
+            .finish(&pool, &NullBroadcaster)
+            .await
+            .expect("Failed to finish");
+
+        let chat = chat::get_chat(&pool, &chat_id).await.unwrap();
+        assert_eq!(chat.session_for(CliToolType::CodexCli), Some("thread-1"));
+        assert_eq!(chat.session_for(CliToolType::ClaudeCode), None);
+
+        let messages = message::list(&pool, &chat_id).await.unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].role, MessageRole::Assistant);
+        assert!(messages[0].content.is_empty());
+        let tool_results: Vec<Value> =
+            serde_json::from_str(messages[0].tool_results.as_deref().unwrap()).unwrap();
+        assert_eq!(tool_results[0]["content"], "README.md\n");
+        assert_eq!(messages[1].content, "The repo has a README.");
+        assert!(messages.iter().all(|m| !m.is_streaming));
+    }
+}
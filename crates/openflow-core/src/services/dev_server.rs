@@ -0,0 +1,470 @@
+//! Dev server service.
+//!
+//! Runs the project's `dev_script` in a chat's worktree as a
+//! `Devserver` execution process. Each chat worktree has at most one dev
+//! server at a time.
+//!
+//! A free port is picked for every dev server and passed to the script in the
+//! `PORT` environment variable. While the server starts, its output is
+//! scanned for a listening URL (e.g. `http://localhost:5173/`); if none is
+//! printed, the assigned port is probed instead. The detected URL is stored in
+//! the process's `dev_server_url` and broadcast as a process update.
+//!
+//! Dev servers are stopped with `stop`, and along with every other process of
+//! the chat when its worktree is cleaned up (see `worktree::cleanup`).
+//!
+//! # Logging
+//!
+//! This service uses the `log` crate for structured logging:
+//! - `debug!`: Detailed operation tracing (ports, detection)
+//! - `info!`: Dev server started, URL detected, stopped
+//! - `warn!`: URL detection gave up
+//! - `error!`: Operation failures (logged before returning error)
+//!
+//! # Error Handling
+//!
+//! All functions return `ServiceResult<T>`. `ServiceError::Validation` is
+//! returned if the chat has no worktree or the project has no dev script.
+
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, SocketAddr, TcpListener};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::Duration;
+
+use log::{debug, error, info, warn};
+use regex::Regex;
+use sqlx::SqlitePool;
+
+use openflow_contracts::{CreateProcessRequest, ExecutionProcess, RunReason, StartProcessRequest};
+
+use super::process::{self, ProcessService};
+use super::{chat, project, worktree, ServiceError, ServiceResult};
+use crate::events::{EntityType, Event, EventBroadcaster};
+
+/// Environment variable the assigned port is passed in.
+pub const PORT_ENV: &str = "PORT";
+
+/// How long to wait for a dev server to become reachable.
+const DETECT_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Interval between URL detection attempts.
+const DETECT_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Number of trailing output bytes kept between detection attempts, so a URL
+/// split across output chunks is still found.
+const DETECT_CARRY_LEN: usize = 256;
+
+/// Per-chat locks held by `start` across the running check and the spawn,
+/// so concurrent starts for one chat don't launch two dev servers.
+static START_LOCKS: LazyLock<Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>> =
+    LazyLock::new(Mutex::default);
+
+static ANSI_ESCAPE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\x1b\[[0-9;?]*[ -/]*[@-~]").expect("valid ANSI regex"));
+
+static LISTEN_URL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"https?://(?:localhost|\d{1,3}(?:\.\d{1,3}){3}|\[[0-9a-fA-F:]+\]|[A-Za-z0-9.-]+):\d{2,5}[^\s'"<>]*"#)
+        .expect("valid URL regex")
+});
+
+/// Find a listening URL in dev server output.
+///
+/// Only URLs with an explicit port are considered. ANSI escapes are stripped
+/// first and wildcard hosts (`0.0.0.0`, `[::]`) are rewritten to `localhost`.
+pub fn detect_url(output: &str) -> Option<String> {
+    let clean = ANSI_ESCAPE.replace_all(output, "");
+    LISTEN_URL.find(&clean).map(|m| {
+        m.as_str()
+            .trim_end_matches(['.', ',', ')'])
+            .replacen("0.0.0.0", "localhost", 1)
+            .replacen("[::]", "localhost", 1)
+    })
+}
+
+/// Find a free local TCP port.
+pub fn find_free_port() -> ServiceResult<u16> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+    Ok(listener.local_addr()?.port())
+}
+
+/// Get the start lock of a chat, dropping locks no other start holds.
+fn start_lock(chat_id: &str) -> Arc<tokio::sync::Mutex<()>> {
+    let mut locks = START_LOCKS.lock().unwrap_or_else(|e| e.into_inner());
+    locks.retain(|_, lock| Arc::strong_count(lock) > 1);
+    Arc::clone(locks.entry(chat_id.to_string()).or_default())
+}
+
+/// Get the running dev server of a chat, if any.
+pub async fn get_running(
+    pool: &SqlitePool,
+    chat_id: &str,
+) -> ServiceResult<Option<ExecutionProcess>> {
+    Ok(process::list_running_by_chat(pool, chat_id)
+        .await?
+        .into_iter()
+        .find(|p| p.run_reason == RunReason::Devserver))
+}
+
+/// Start the dev server for a chat's worktree.
+///
+/// If the chat already has a running dev server it is returned instead of
+/// starting another. The returned process has no URL yet; it is filled in
+/// and broadcast once detected.
+///
+/// # Errors
+///
+/// Returns `ServiceError::Validation` if the chat has no worktree or the
+/// project has no dev script, and `ServiceError::Process` if the script
+/// cannot be started.
+pub async fn start(
+    pool: &SqlitePool,
+    process_service: &ProcessService,
+    chat_id: &str,
+) -> ServiceResult<ExecutionProcess> {
+    let lock = start_lock(chat_id);
+    let _guard = lock.lock().await;
+
+    if let Some(running) = get_running(pool, chat_id).await? {
+        debug!(
+            "start: chat_id={} already has dev server process_id={}",
+            chat_id, running.id
+        );
+        return Ok(running);
+    }
+
+    let chat = chat::get_chat(pool, chat_id).await?;
+    let project = project::get(pool, &chat.project_id).await?;
+
+    let worktree_path = match chat.worktree_path.as_deref() {
+        Some(path) if worktree::has_worktree(&chat) => path,
+        _ => {
+            return Err(ServiceError::Validation(format!(
+                "Chat {} has no worktree to run a dev server in",
+                chat_id
+            )))
+        }
+    };
+    let script = project.dev_script.trim();
+    if script.is_empty() {
+        return Err(ServiceError::Validation(format!(
+            "Project {} has no dev script",
+            project.id
+        )));
+    }
+
+    let port = find_free_port().map_err(|e| {
+        error!(
+            "start: failed to find a free port for chat_id={}: {}",
+            chat_id, e
+        );
+        e
+    })?;
+    debug!("start: assigned port={} chat_id={}", port, chat_id);
+
+    let process = process_service
+        .start(
+            pool,
+            CreateProcessRequest::dev_server(chat_id, script),
+            StartProcessRequest {
+                resource_limits: project.get_script_limits().dev,
+                ..StartProcessRequest::script(script)
+                    .with_cwd(worktree_path)
+                    .with_env_var(PORT_ENV, port.to_string())
+            },
+        )
+        .await?;
+    process_service.broadcaster().broadcast(Event::created(
+        EntityType::Process,
+        process.id.clone(),
+        &process,
+    ));
+
+    tokio::spawn(watch_for_url(
+        pool.clone(),
+        Arc::clone(process_service.broadcaster()),
+        process.id.clone(),
+        port,
+    ));
+
+    info!(
+        "start: dev server started chat_id={} process_id={} port={}",
+        chat_id, process.id, port
+    );
+    Ok(process)
+}
+
+/// Stop the running dev server of a chat.
+///
+/// Returns the killed process, or `None` if no dev server was running.
+pub async fn stop(
+    pool: &SqlitePool,
+    process_service: &ProcessService,
+    chat_id: &str,
+) -> ServiceResult<Option<ExecutionProcess>> {
+    let Some(running) = get_running(pool, chat_id).await? else {
+        debug!("stop: no dev server running for chat_id={}", chat_id);
+        return Ok(None);
+    };
+
+    let killed = process_service.kill(pool, &running.id).await?;
+    process_service.broadcaster().broadcast(Event::updated(
+        EntityType::Process,
+        killed.id.clone(),
+        &killed,
+    ));
+
+    info!(
+        "stop: stopped dev server chat_id={} process_id={}",
+        chat_id, killed.id
+    );
+    Ok(Some(killed))
+}
+
+/// Watch a dev server until its URL is known, it exits, or detection times out.
+///
+/// Each attempt scans the output persisted since the last attempt, then
+/// probes the assigned port.
+async fn watch_for_url(
+    pool: SqlitePool,
+    broadcaster: Arc<dyn EventBroadcaster>,
+    process_id: String,
+    port: u16,
+) {
+    let deadline = tokio::time::Instant::now() + DETECT_TIMEOUT;
+    let mut cursor = None;
+    let mut carry = String::new();
+
+    while tokio::time::Instant::now() < deadline {
+        let running = match process::get(&pool, &process_id).await {
+            Ok(process) => process.is_running(),
+            Err(e) => {
+                error!(
+                    "watch_for_url: failed to load process_id={}: {}",
+                    process_id, e
+                );
+                return;
+            }
+        };
+        if !running {
+            debug!(
+                "watch_for_url: process_id={} exited before detection",
+                process_id
+            );
+            return;
+        }
+
+        let chunks = process::list_output(&pool, &process_id, cursor, None)
+            .await
+            .unwrap_or_default();
+        if let Some(last) = chunks.last() {
+            cursor = Some(last.id);
+        }
+        for chunk in &chunks {
+            carry.push_str(&chunk.content);
+        }
+
+        let url = match detect_url(&carry) {
+            Some(url) => Some(url),
+            None if port_is_open(port).await => Some(format!("http://localhost:{}", port)),
+            None => None,
+        };
+        if let Some(url) = url {
+            match process::update_dev_server_url(&pool, &process_id, &url).await {
+                Ok(updated) => broadcaster.broadcast(Event::updated(
+                    EntityType::Process,
+                    process_id.clone(),
+                    &updated,
+                )),
+                Err(e) => error!(
+                    "watch_for_url: failed to record url for process_id={}: {}",
+                    process_id, e
+                ),
+            }
+            return;
+        }
+
+        if carry.len() > DETECT_CARRY_LEN {
+            let mut start = carry.len() - DETECT_CARRY_LEN;
+            while !carry.is_char_boundary(start) {
+                start += 1;
+            }
+            carry.drain(..start);
+        }
+        tokio::time::sleep(DETECT_POLL_INTERVAL).await;
+    }
+
+    warn!(
+        "watch_for_url: no url detected for process_id={} within {:?}",
+        process_id, DETECT_TIMEOUT
+    );
+}
+
+/// Check whether something accepts connections on a local port.
+async fn port_is_open(port: u16) -> bool {
+    let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+    tokio::time::timeout(
+        Duration::from_millis(200),
+        tokio::net::TcpStream::connect(addr),
+    )
+    .await
+    .is_ok_and(|result| result.is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openflow_contracts::{
+        CreateChatRequest, CreateProjectRequest, ProcessStatus, UpdateChatRequest,
+    };
+    use openflow_db::create_test_db;
+    use tempfile::TempDir;
+
+    /// Helper to create a chat whose worktree is a plain temporary directory.
+    async fn setup_chat(pool: &SqlitePool, dir: &TempDir, dev_script: &str) -> String {
+        let project = project::create(
+            pool,
+            CreateProjectRequest {
+                name: "Dev".to_string(),
+                git_repo_path: dir.path().to_string_lossy().into_owned(),
+                dev_script: Some(dev_script.to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Failed to create project");
+        let chat = chat::create(
+            pool,
+            CreateChatRequest {
+                project_id: project.id,
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Failed to create chat");
+        chat::update(
+            pool,
+            &chat.id,
+            UpdateChatRequest::with_worktree(dir.path().to_string_lossy(), "openflow/dev/main"),
+        )
+        .await
+        .expect("Failed to set worktree");
+        chat.id
+    }
+
+    /// Poll until the dev server URL is recorded.
+    async fn wait_for_url(pool: &SqlitePool, id: &str) -> ExecutionProcess {
+        for _ in 0..100 {
+            let process = process::get(pool, id).await.expect("Failed to get process");
+            if process.dev_server_url.is_some() {
+                return process;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        panic!("dev server {} url was not detected in time", id);
+    }
+
+    #[test]
+    fn test_detect_url() {
+        assert_eq!(
+            detect_url("  VITE v5.0.0  ready\n  ➜  Local:   http://localhost:5173/\n"),
+            Some("http://localhost:5173/".to_string())
+        );
+        assert_eq!(
+            detect_url("\x1b[36mhttp://localhost:\x1b[1m3000\x1b[22m/\x1b[39m"),
+            Some("http://localhost:3000/".to_string())
+        );
+        assert_eq!(
+            detect_url("Listening on http://0.0.0.0:8080."),
+            Some("http://localhost:8080".to_string())
+        );
+        assert_eq!(
+            detect_url("- Local: http://127.0.0.1:4000/app"),
+            Some("http://127.0.0.1:4000/app".to_string())
+        );
+        assert_eq!(detect_url("see https://vitejs.dev/guide for docs"), None);
+        assert_eq!(detect_url("compiling..."), None);
+    }
+
+    #[test]
+    fn test_find_free_port() {
+        let port = find_free_port().unwrap();
+        assert!(port > 0);
+        assert!(TcpListener::bind((Ipv4Addr::LOCALHOST, port)).is_ok());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_start_detects_url_from_output_and_stop() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let dir = TempDir::new().unwrap();
+        let chat_id = setup_chat(
+            &pool,
+            &dir,
+            "echo \"ready on http://localhost:$PORT/\"; sleep 30",
+        )
+        .await;
+        let service = ProcessService::new();
+
+        let process = start(&pool, &service, &chat_id)
+            .await
+            .expect("Failed to start dev server");
+        assert_eq!(process.run_reason, RunReason::Devserver);
+
+        // Only one dev server per chat
+        let again = start(&pool, &service, &chat_id).await.unwrap();
+        assert_eq!(again.id, process.id);
+
+        let detected = wait_for_url(&pool, &process.id).await;
+        let url = detected.dev_server_url.unwrap();
+        assert!(url.starts_with("http://localhost:"));
+        assert!(url.ends_with('/'));
+
+        let stopped = stop(&pool, &service, &chat_id).await.unwrap().unwrap();
+        assert_eq!(stopped.status, ProcessStatus::Killed);
+        assert!(get_running(&pool, &chat_id).await.unwrap().is_none());
+        assert!(stop(&pool, &service, &chat_id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_port_is_open() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        assert!(port_is_open(port).await);
+
+        drop(listener);
+        assert!(!port_is_open(port).await);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_concurrent_starts_launch_one_server() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let dir = TempDir::new().unwrap();
+        let chat_id = setup_chat(&pool, &dir, "sleep 30").await;
+        let service = ProcessService::new();
+
+        let (first, second) = tokio::join!(
+            start(&pool, &service, &chat_id),
+            start(&pool, &service, &chat_id)
+        );
+
+        assert_eq!(first.unwrap().id, second.unwrap().id);
+        let running = process::list_running_by_chat(&pool, &chat_id)
+            .await
+            .unwrap();
+        assert_eq!(running.len(), 1);
+
+        stop(&pool, &service, &chat_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_start_requires_dev_script() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let dir = TempDir::new().unwrap();
+        let chat_id = setup_chat(&pool, &dir, "  ").await;
+
+        let result = start(&pool, &ProcessService::new(), &chat_id).await;
+
+        assert!(matches!(result, Err(ServiceError::Validation(_))));
+    }
+}
@@ -5,8 +5,10 @@
 //!
 //! - Profile resolution (specified or default)
 //! - Chat and project lookup
-//! - Command argument building
-//! - Environment variable setup
+//! - Worktree provisioning for the chat
+//! - Command argument building (via the profile's executor adapter)
+//! - Environment variable setup (profile env and model)
+//! - Sandbox setup for profiles that opt in
 //! - Process request creation
 //!
 //! The actual process spawning is delegated to the process service.
@@ -25,16 +27,21 @@
 //! Errors are logged at the appropriate level before being returned.
 
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
 
 use log::{debug, error, info, warn};
+use regex::Regex;
 use sqlx::SqlitePool;
 
 use openflow_contracts::{
-    Chat, CreateProcessRequest, ExecutorProfile, Project, RunReason, StartProcessRequest,
+    Chat, CreateProcessRequest, ExecutorProfile, ProcessSandbox, Project, RunReason,
+    StartProcessRequest,
 };
 
-use super::{chat, executor_profile, project, ServiceError, ServiceResult};
+use super::{
+    chat, executor_adapter, executor_profile, project, worktree, ServiceError, ServiceResult,
+};
 
 /// Prepared executor context after validation and lookup.
 ///
@@ -113,12 +120,15 @@ pub async fn resolve_profile(
 
 /// Build command arguments for the executor CLI.
 ///
-/// Includes session resumption if the chat has an existing Claude session ID.
+/// The arguments are built by the executor adapter the profile selects (see
+/// `executor_adapter::for_profile`), which includes session resumption if the
+/// chat has an existing session ID and the CLI supports it. Profile args are
+/// appended after the adapter args.
 ///
 /// # Arguments
 ///
 /// * `prompt` - The prompt/instructions to send to the AI agent
-/// * `session_id` - Optional Claude session ID for resuming conversations
+/// * `session_id` - Optional session ID for resuming conversations
 /// * `profile` - The executor profile with optional additional args
 ///
 /// # Returns
@@ -136,31 +146,24 @@ pub fn build_command_args(
         profile.name
     );
 
-    let mut cmd_args: Vec<String> = if let Some(sid) = session_id {
-        debug!("Including --resume flag for session_id={}", sid);
-        // Resume existing Claude Code session
-        vec![
-            "--output-format".to_string(),
-            "stream-json".to_string(),
-            "--verbose".to_string(),
-            "--dangerously-skip-permissions".to_string(),
-            "--resume".to_string(),
-            sid.to_string(),
-            "-p".to_string(),
-            prompt.to_string(),
-        ]
-    } else {
-        debug!("Starting new session (no --resume flag)");
-        // Start new session
-        vec![
-            "--output-format".to_string(),
-            "stream-json".to_string(),
-            "--verbose".to_string(),
-            "--dangerously-skip-permissions".to_string(),
-            "-p".to_string(),
-            prompt.to_string(),
-        ]
-    };
+    let adapter = executor_adapter::for_profile(profile);
+    if session_id.is_some() && !adapter.supports_resume() {
+        debug!(
+            "Adapter {:?} cannot resume sessions, starting a new session",
+            adapter.tool_type()
+        );
+    }
+    let mut cmd_args = adapter.build_args(
+        prompt,
+        session_id.filter(|_| adapter.supports_resume()),
+        profile_model(profile),
+    );
+    debug!(
+        "Adapter {:?} built {} args with output format {}",
+        adapter.tool_type(),
+        cmd_args.len(),
+        adapter.output_format()
+    );
 
     // Add any args from the profile
     if let Some(profile_args) = &profile.args {
@@ -187,6 +190,56 @@ pub fn build_command_args(
     cmd_args
 }
 
+/// Matches `${env:NAME}` references in profile env values.
+static ENV_REFERENCE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\$\{env:([A-Za-z_][A-Za-z0-9_]*)\}").unwrap());
+
+/// Get the profile's model, ignoring blank values.
+fn profile_model(profile: &ExecutorProfile) -> Option<&str> {
+    profile
+        .model
+        .as_deref()
+        .map(str::trim)
+        .filter(|model| !model.is_empty())
+}
+
+/// Resolve `${env:NAME}` references in a profile env value.
+///
+/// References are looked up in OpenFlow's own environment, so API keys can be
+/// kept out of the database and chosen per profile.
+///
+/// # Errors
+///
+/// Returns `ServiceError::Config` if a referenced variable is not set.
+pub fn resolve_env_value(value: &str) -> ServiceResult<String> {
+    resolve_env_value_with(value, |name| std::env::var(name).ok())
+}
+
+/// Resolve `${env:NAME}` references in a profile env value, looking each
+/// variable up with `lookup`.
+///
+/// See [`resolve_env_value`].
+pub fn resolve_env_value_with(
+    value: &str,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> ServiceResult<String> {
+    let mut missing = None;
+    let resolved = ENV_REFERENCE.replace_all(value, |caps: &regex::Captures| {
+        lookup(&caps[1]).unwrap_or_else(|| {
+            missing.get_or_insert_with(|| caps[1].to_string());
+            String::new()
+        })
+    });
+
+    match missing {
+        Some(name) => Err(ServiceError::config(format!(
+            "Environment variable {} referenced by executor profile is not set",
+            name
+        ))),
+        None => Ok(resolved.into_owned()),
+    }
+}
+
 /// Build environment variables for the executor process.
 ///
 /// Disables ANSI colors for clean JSON output parsing.
@@ -196,23 +249,70 @@ pub fn build_command_args(
 /// - `FORCE_COLOR=0` - Disable forced color output
 /// - `TERM=dumb` - Simple terminal for clean output
 ///
-/// # Returns
+/// The profile's `env` JSON is merged on top, with `${env:NAME}` references
+/// resolved (see [`resolve_env_value`]). `${secret:NAME}` references are left
+/// for the process service to substitute at spawn time. For adapters without
+/// a model flag the profile's model is passed in the adapter's model variable.
+///
+/// # Errors
 ///
-/// A HashMap of environment variables to set when running the process.
-pub fn build_environment() -> HashMap<String, String> {
-    debug!("Building environment variables for executor process");
+/// Returns `ServiceError::Config` if the profile env is not a JSON object of
+/// strings or references an unset variable.
+pub fn build_environment(profile: &ExecutorProfile) -> ServiceResult<HashMap<String, String>> {
+    build_environment_with(profile, |name| std::env::var(name).ok())
+}
+
+/// Build environment variables for the executor process, resolving
+/// `${env:NAME}` references with `lookup` instead of OpenFlow's environment.
+///
+/// See [`build_environment`].
+pub fn build_environment_with(
+    profile: &ExecutorProfile,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> ServiceResult<HashMap<String, String>> {
+    debug!(
+        "Building environment variables for executor process profile={}",
+        profile.name
+    );
 
     let mut env = HashMap::new();
     env.insert("NO_COLOR".to_string(), "1".to_string());
     env.insert("FORCE_COLOR".to_string(), "0".to_string());
     env.insert("TERM".to_string(), "dumb".to_string());
 
+    if let Some(model_var) = executor_adapter::for_profile(profile).model_env_var() {
+        if let Some(model) = profile_model(profile) {
+            env.insert(model_var.to_string(), model.to_string());
+        }
+    }
+
+    if let Some(profile_env) = profile.env.as_deref().filter(|e| !e.trim().is_empty()) {
+        let parsed: HashMap<String, String> = serde_json::from_str(profile_env).map_err(|e| {
+            error!("Invalid env for profile={}: {}", profile.name, e);
+            ServiceError::config(format!(
+                "Executor profile {} has invalid env JSON: {}",
+                profile.name, e
+            ))
+        })?;
+        debug!("Merging {} profile env vars", parsed.len());
+        for (key, value) in parsed {
+            let value = resolve_env_value_with(&value, &lookup).map_err(|e| {
+                error!(
+                    "Failed to resolve env {} for profile={}: {}",
+                    key, profile.name, e
+                );
+                e
+            })?;
+            env.insert(key, value);
+        }
+    }
+
     debug!(
         "Built {} environment variables: {:?}",
         env.len(),
         env.keys().collect::<Vec<_>>()
     );
-    env
+    Ok(env)
 }
 
 /// Create the process record request.
@@ -259,6 +359,9 @@ pub fn create_process_request(chat_id: &str, profile: &ExecutorProfile) -> Creat
 ///
 /// Uses a 120x40 pseudo-terminal for proper CLI tool output.
 ///
+/// The profile's max runtime and idle timeout are passed on for
+/// `ProcessService` to enforce.
+///
 /// # Returns
 ///
 /// A `StartProcessRequest` ready to be passed to the process service.
@@ -284,6 +387,10 @@ pub fn create_start_request(
         use_pty: true,
         pty_cols: Some(120),
         pty_rows: Some(40),
+        max_runtime_secs: profile.max_runtime_secs,
+        idle_timeout_secs: profile.idle_timeout_secs,
+        resource_limits: Some(profile.parse_resource_limits()).filter(|l| !l.is_empty()),
+        sandbox: None,
     };
 
     debug!(
@@ -293,13 +400,99 @@ pub fn create_start_request(
     request
 }
 
+/// Build the sandbox for a run of a profile, if the profile opts in.
+///
+/// The run may write to its worktree (`cwd`), the worktree's admin
+/// directory (`.git/worktrees/<name>`, holding its index and HEAD) and the
+/// repository's shared `objects`, `refs` and `logs`. Everything that makes
+/// git run code or find its repository - hooks, the repository config and
+/// the worktree's `.git` link files - stays read-only, so a sandboxed run
+/// cannot plant code that runs outside the sandbox.
+///
+/// # Returns
+///
+/// The `ProcessSandbox` to start the process in, or `None` if the profile
+/// is not sandboxed.
+///
+/// # Errors
+///
+/// `Config` if the profile's stored sandbox settings do not parse: the run
+/// must not silently start unsandboxed.
+pub fn build_sandbox(
+    profile: &ExecutorProfile,
+    cwd: &Path,
+    repo_path: &str,
+) -> ServiceResult<Option<ProcessSandbox>> {
+    let settings = profile.parse_sandbox().map_err(|e| {
+        error!(
+            "build_sandbox: invalid sandbox settings profile={}: {}",
+            profile.name, e
+        );
+        ServiceError::config(format!(
+            "Executor profile '{}' has invalid sandbox settings: {}",
+            profile.name, e
+        ))
+    })?;
+    if !settings.enabled {
+        return Ok(None);
+    }
+
+    let path_string = |path: &Path| path.to_string_lossy().into_owned();
+    let mut writable_paths = vec![path_string(cwd)];
+    let mut protected_paths = Vec::new();
+
+    let git_dir = Path::new(repo_path).join(".git");
+    if git_dir.is_dir() {
+        if !git_dir.starts_with(cwd) {
+            let dot_git = cwd.join(".git");
+            if let Some(admin_dir) = worktree_admin_dir(&dot_git) {
+                writable_paths.push(path_string(&admin_dir));
+                for file in ["gitdir", "commondir", "config.worktree"] {
+                    protected_paths.push(path_string(&admin_dir.join(file)));
+                }
+            }
+            protected_paths.push(path_string(&dot_git));
+            for shared in ["objects", "refs", "logs"] {
+                let shared = git_dir.join(shared);
+                if shared.is_dir() {
+                    writable_paths.push(path_string(&shared));
+                }
+            }
+        }
+        protected_paths.push(path_string(&git_dir.join("hooks")));
+        protected_paths.push(path_string(&git_dir.join("config")));
+    }
+    debug!(
+        "Sandboxing profile={}: writable={:?}, protected={:?}, network={}",
+        profile.name, writable_paths, protected_paths, settings.allow_network
+    );
+
+    Ok(Some(ProcessSandbox {
+        writable_paths,
+        read_only_paths: settings.read_only_paths,
+        protected_paths,
+        allow_network: settings.allow_network,
+    }))
+}
+
+/// Resolve the admin directory of a linked worktree from its `.git` file
+/// (`gitdir: <repo>/.git/worktrees/<name>`).
+fn worktree_admin_dir(dot_git: &Path) -> Option<PathBuf> {
+    let content = std::fs::read_to_string(dot_git).ok()?;
+    let gitdir = content.strip_prefix("gitdir:")?.trim();
+    let admin_dir = dot_git.parent()?.join(gitdir);
+    admin_dir.is_dir().then_some(admin_dir)
+}
+
 /// Prepare the executor context by validating and looking up all required data.
 ///
 /// This performs all the business logic needed before starting the process:
 /// 1. Resolves the executor profile
 /// 2. Fetches the chat and project
-/// 3. Builds command arguments and environment
-/// 4. Creates the process and start requests
+/// 3. Provisions the chat's git worktree if it has none (see `worktree::ensure_for_chat`)
+/// 4. Builds command arguments and environment, resuming the chat's session
+///    only if it was created by the profile's CLI tool
+/// 5. Creates the process and start requests, running in the chat's worktree
 ///
 /// # Arguments
 ///
@@ -312,11 +505,26 @@ pub fn create_start_request(
 ///
 /// Returns `ServiceError::NotFound` if chat or project doesn't exist.
 /// Returns `ServiceError::Validation` if no default profile is configured.
+/// Returns `ServiceError::Git` if the chat's worktree cannot be created.
 pub async fn prepare(
     pool: &SqlitePool,
     chat_id: &str,
     prompt: &str,
     executor_profile_id: Option<String>,
+) -> ServiceResult<ExecutorContext> {
+    prepare_with_model(pool, chat_id, prompt, executor_profile_id, None).await
+}
+
+/// Prepare the executor context like `prepare`, running `model` instead of
+/// the profile's model if given.
+///
+/// Used by workflow steps that pick their own model.
+pub async fn prepare_with_model(
+    pool: &SqlitePool,
+    chat_id: &str,
+    prompt: &str,
+    executor_profile_id: Option<String>,
+    model: Option<String>,
 ) -> ServiceResult<ExecutorContext> {
     info!(
         "Preparing executor context: chat_id={}, prompt_len={}, profile_id={:?}",
@@ -327,7 +535,14 @@ pub async fn prepare(
 
     // 1. Get the executor profile (specified or default)
     debug!("Step 1: Resolving executor profile");
-    let profile = resolve_profile(pool, executor_profile_id).await?;
+    let mut profile = resolve_profile(pool, executor_profile_id).await?;
+    if let Some(model) = model {
+        debug!(
+            "Overriding model of profile={} with {}",
+            profile.name, model
+        );
+        profile.model = Some(model);
+    }
 
     // 2. Get the chat to find the project
     debug!("Step 2: Fetching chat_id={}", chat_id);
@@ -357,26 +572,50 @@ pub async fn prepare(
         project_record.id, project_record.name, project_record.git_repo_path
     );
 
-    // 4. Build command arguments
-    debug!("Step 4: Building command arguments");
-    let cmd_args = build_command_args(prompt, chat_record.claude_session_id.as_deref(), &profile);
+    // 4. Make sure the chat has its own worktree to run in
+    debug!("Step 4: Ensuring worktree for chat_id={}", chat_record.id);
+    let chat_record = worktree::ensure_for_chat(pool, &chat_record, &project_record)
+        .await
+        .map_err(|e| {
+            error!(
+                "Failed to provision worktree for chat_id={}: {}",
+                chat_record.id, e
+            );
+            e
+        })?;
+    let cwd = PathBuf::from(
+        chat_record
+            .worktree_path
+            .as_deref()
+            .unwrap_or(&project_record.git_repo_path),
+    );
+    debug!("Running executor in cwd={:?}", cwd);
+
+    // 5. Build command arguments
+    debug!("Step 5: Building command arguments");
+    let tool = profile.tool_type();
+    let session_id = chat_record.session_for(tool);
+    if session_id.is_none() && chat_record.has_session() {
+        debug!(
+            "Session of chat_id={} was created by {:?}, not {:?}, starting a new session",
+            chat_record.id, chat_record.session_cli_tool, tool
+        );
+    }
+    let cmd_args = build_command_args(prompt, session_id, &profile);
 
-    // 5. Build environment variables
-    debug!("Step 5: Building environment variables");
-    let env = build_environment();
+    // 6. Build environment variables
+    debug!("Step 6: Building environment variables");
+    let env = build_environment(&profile)?;
 
-    // 6. Create process request
-    debug!("Step 6: Creating process request");
+    // 7. Create process request
+    debug!("Step 7: Creating process request");
     let create_request = create_process_request(chat_id, &profile);
 
-    // 7. Create start request
-    debug!("Step 7: Creating start request");
-    let start_request = create_start_request(
-        &profile,
-        cmd_args.clone(),
-        PathBuf::from(&project_record.git_repo_path),
-        env.clone(),
-    );
+    // 8. Create start request
+    debug!("Step 8: Creating start request");
+    let sandbox = build_sandbox(&profile, &cwd, &project_record.git_repo_path)?;
+    let mut start_request = create_start_request(&profile, cmd_args.clone(), cwd, env.clone());
+    start_request.sandbox = sandbox;
 
     info!(
         "Executor context prepared: chat_id={}, project={}, profile={}, args_count={}",
@@ -400,6 +639,7 @@ pub async fn prepare(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use openflow_contracts::CliToolType;
 
     fn sample_profile() -> ExecutorProfile {
         ExecutorProfile {
@@ -410,6 +650,11 @@ mod tests {
             args: None,
             env: None,
             model: None,
+            cli_tool: None,
+            max_runtime_secs: None,
+            idle_timeout_secs: None,
+            resource_limits: None,
+            sandbox: None,
             is_default: true,
             created_at: "2024-01-01T00:00:00Z".to_string(),
             updated_at: "2024-01-01T00:00:00Z".to_string(),
@@ -468,9 +713,36 @@ mod tests {
         assert!(!args.contains(&"invalid-json".to_string()));
     }
 
+    #[test]
+    fn test_build_command_args_uses_profile_adapter() {
+        let mut profile = sample_profile();
+        profile.name = "Gemini".to_string();
+        profile.command = "gemini".to_string();
+        profile.args = Some(r#"["--model", "gemini-2.5-pro"]"#.to_string());
+
+        let args = build_command_args("Hello", Some("session-123"), &profile);
+
+        assert!(!args.contains(&"--dangerously-skip-permissions".to_string()));
+        assert!(!args.contains(&"--resume".to_string()));
+        assert!(args.contains(&"Hello".to_string()));
+        assert_eq!(&args[args.len() - 2..], &["--model", "gemini-2.5-pro"]);
+    }
+
+    #[test]
+    fn test_build_command_args_explicit_cli_tool() {
+        let mut profile = sample_profile();
+        profile.command = "/opt/agents/run-agent".to_string();
+        profile.cli_tool = Some(CliToolType::CodexCli);
+
+        let args = build_command_args("Hello", None, &profile);
+
+        assert_eq!(args[0], "exec");
+        assert!(args.contains(&"--json".to_string()));
+    }
+
     #[test]
     fn test_build_environment() {
-        let env = build_environment();
+        let env = build_environment(&sample_profile()).unwrap();
 
         assert_eq!(env.get("NO_COLOR"), Some(&"1".to_string()));
         assert_eq!(env.get("FORCE_COLOR"), Some(&"0".to_string()));
@@ -478,6 +750,62 @@ mod tests {
         assert_eq!(env.len(), 3);
     }
 
+    #[test]
+    fn test_build_environment_merges_profile_env() {
+        let mut profile = sample_profile();
+        profile.env = Some(
+            r#"{"ANTHROPIC_API_KEY": "${env:OPENFLOW_TEST_PROFILE_KEY}", "TERM": "xterm"}"#
+                .to_string(),
+        );
+
+        let env = build_environment_with(&profile, |name| {
+            (name == "OPENFLOW_TEST_PROFILE_KEY").then(|| "sk-test".to_string())
+        })
+        .unwrap();
+
+        assert_eq!(env.get("ANTHROPIC_API_KEY"), Some(&"sk-test".to_string()));
+        assert_eq!(env.get("TERM"), Some(&"xterm".to_string()));
+        assert_eq!(env.get("NO_COLOR"), Some(&"1".to_string()));
+    }
+
+    #[test]
+    fn test_build_environment_rejects_bad_profile_env() {
+        let mut profile = sample_profile();
+        profile.env = Some(r#"{"KEY": "${env:OPENFLOW_TEST_UNSET_VARIABLE}"}"#.to_string());
+        assert!(matches!(
+            build_environment_with(&profile, |_| None),
+            Err(ServiceError::Config(_))
+        ));
+
+        profile.env = Some("not json".to_string());
+        assert!(matches!(
+            build_environment(&profile),
+            Err(ServiceError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn test_model_passed_to_cli() {
+        let mut profile = sample_profile();
+        profile.model = Some("opus".to_string());
+        let args = build_command_args("Hello", None, &profile);
+        assert!(args.windows(2).any(|pair| pair == ["--model", "opus"]));
+        assert!(!build_environment(&profile)
+            .unwrap()
+            .contains_key(executor_adapter::MODEL_ENV_VAR));
+
+        profile.command = "my-agent".to_string();
+        profile.cli_tool = Some(CliToolType::Custom);
+        let args = build_command_args("Hello", None, &profile);
+        assert!(!args.contains(&"--model".to_string()));
+        assert_eq!(
+            build_environment(&profile)
+                .unwrap()
+                .get(executor_adapter::MODEL_ENV_VAR),
+            Some(&"opus".to_string())
+        );
+    }
+
     #[test]
     fn test_create_process_request() {
         let profile = sample_profile();
@@ -499,7 +827,7 @@ mod tests {
         let profile = sample_profile();
         let cmd_args = vec!["-p".to_string(), "test prompt".to_string()];
         let cwd = PathBuf::from("/home/user/project");
-        let env = build_environment();
+        let env = build_environment(&profile).unwrap();
 
         let request = create_start_request(&profile, cmd_args.clone(), cwd.clone(), env.clone());
 
@@ -510,6 +838,139 @@ mod tests {
         assert!(request.use_pty);
         assert_eq!(request.pty_cols, Some(120));
         assert_eq!(request.pty_rows, Some(40));
+        assert!(request.max_runtime_secs.is_none());
+    }
+
+    #[test]
+    fn test_create_start_request_carries_timeouts() {
+        let mut profile = sample_profile();
+        profile.max_runtime_secs = Some(1800);
+        profile.idle_timeout_secs = Some(90);
+
+        let request = create_start_request(&profile, vec![], PathBuf::from("/tmp"), HashMap::new());
+
+        assert_eq!(request.max_runtime_secs, Some(1800));
+        assert_eq!(request.idle_timeout_secs, Some(90));
+    }
+
+    /// Create a repository with one commit and a linked worktree of it.
+    fn repo_with_worktree() -> (tempfile::TempDir, PathBuf) {
+        let repo = tempfile::TempDir::new().expect("Failed to create temp dir");
+        let worktree = repo.path().join("worktrees").join("chat");
+        for args in [
+            &["init", "-b", "main"][..],
+            &[
+                "-c",
+                "user.name=Test",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "--allow-empty",
+                "-m",
+                "init",
+            ][..],
+            &["worktree", "add", "-b", "chat", worktree.to_str().unwrap()][..],
+        ] {
+            let output = std::process::Command::new("git")
+                .args(args)
+                .current_dir(repo.path())
+                .output()
+                .expect("Failed to run git");
+            assert!(output.status.success(), "git {:?} failed", args);
+        }
+        (repo, worktree)
+    }
+
+    #[test]
+    fn test_build_sandbox() {
+        let (repo, worktree) = repo_with_worktree();
+        let repo_path = repo.path().to_string_lossy().into_owned();
+        let git_dir = repo.path().join(".git");
+        let git_path = |path: &str| git_dir.join(path).to_string_lossy().into_owned();
+
+        let mut profile = sample_profile();
+        assert!(build_sandbox(&profile, &worktree, &repo_path)
+            .unwrap()
+            .is_none());
+
+        profile.sandbox = Some(r#"{"enabled": true}"#.to_string());
+        let sandbox = build_sandbox(&profile, &worktree, &repo_path)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            sandbox.writable_paths,
+            vec![
+                worktree.to_string_lossy().into_owned(),
+                git_path("worktrees/chat"),
+                git_path("objects"),
+                git_path("refs"),
+                git_path("logs"),
+            ]
+        );
+        // Nothing that makes git run code is writable
+        assert!(!sandbox.writable_paths.contains(&git_path("")));
+        for protected in [
+            worktree.join(".git").to_string_lossy().into_owned(),
+            git_path("worktrees/chat/commondir"),
+            git_path("hooks"),
+            git_path("config"),
+        ] {
+            assert!(
+                sandbox.protected_paths.contains(&protected),
+                "{}",
+                protected
+            );
+        }
+        assert!(!sandbox.allow_network);
+
+        // Running in the repository itself keeps its hooks and config read-only
+        let sandbox = build_sandbox(&profile, repo.path(), &repo_path)
+            .unwrap()
+            .unwrap();
+        assert_eq!(sandbox.writable_paths, vec![repo_path.clone()]);
+        assert_eq!(
+            sandbox.protected_paths,
+            vec![git_path("hooks"), git_path("config")]
+        );
+    }
+
+    #[test]
+    fn test_build_sandbox_rejects_invalid_settings() {
+        let mut profile = sample_profile();
+        profile.sandbox = Some(r#"{"enabled": tru"#.to_string());
+
+        let result = build_sandbox(&profile, Path::new("/worktrees/chat"), "/repo");
+        assert!(matches!(result, Err(ServiceError::Config(_))));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    #[ignore = "requires bubblewrap (bwrap)"]
+    fn test_sandboxed_process_cannot_write_git_hooks() {
+        use openflow_process::{PipeSpawnConfig, ProcessSpawner, SandboxConfig};
+
+        let (repo, worktree) = repo_with_worktree();
+        let mut profile = sample_profile();
+        profile.sandbox = Some(r#"{"enabled": true}"#.to_string());
+        let sandbox = build_sandbox(&profile, &worktree, &repo.path().to_string_lossy())
+            .unwrap()
+            .unwrap();
+
+        let hooks = repo.path().join(".git").join("hooks");
+        let script = format!(
+            "touch {hooks}/pre-commit; echo $?; git config core.hooksPath /tmp; echo $?; touch inside",
+            hooks = hooks.display()
+        );
+        let config = PipeSpawnConfig::new("sh", &["-c", &script])
+            .with_cwd(&worktree)
+            .with_sandbox(SandboxConfig::from(&sandbox));
+
+        let (stdout, _, _) = ProcessSpawner::run_capture(config).unwrap();
+        let codes: Vec<&str> = stdout.lines().collect();
+        assert_eq!(codes.len(), 2);
+        assert!(codes.iter().all(|code| *code != "0"), "{:?}", codes);
+        assert!(!hooks.join("pre-commit").exists());
+        assert!(worktree.join("inside").exists());
     }
 
     #[test]
@@ -527,6 +988,106 @@ mod tests {
         assert_eq!(request.pty_rows, Some(40));
     }
 
+    #[tokio::test]
+    async fn test_prepare_runs_in_chat_worktree() {
+        use openflow_contracts::{
+            CliToolType, CreateChatRequest, CreateExecutorProfileRequest, CreateProjectRequest,
+            UpdateChatRequest,
+        };
+        use openflow_db::create_test_db;
+        use std::process::Command;
+
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let repo = tempfile::TempDir::new().unwrap();
+        let worktrees = tempfile::TempDir::new().unwrap();
+        for args in [
+            &["init", "-b", "main"][..],
+            &[
+                "-c",
+                "user.name=Test",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "--allow-empty",
+                "-m",
+                "init",
+            ][..],
+        ] {
+            Command::new("git")
+                .args(args)
+                .current_dir(repo.path())
+                .output()
+                .expect("Failed to run git");
+        }
+        super::super::settings::set(
+            &pool,
+            worktree::BASE_PATH_SETTING,
+            worktrees.path().to_str().unwrap(),
+        )
+        .await
+        .unwrap();
+
+        executor_profile::create(
+            &pool,
+            CreateExecutorProfileRequest::new("Claude Code", "claude").with_is_default(true),
+        )
+        .await
+        .unwrap();
+        let project = project::create(
+            &pool,
+            CreateProjectRequest {
+                name: "Repo".to_string(),
+                git_repo_path: repo.path().to_string_lossy().into_owned(),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        let chat = chat::create(
+            &pool,
+            CreateChatRequest {
+                project_id: project.id.clone(),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let context = prepare(&pool, &chat.id, "Hello", None)
+            .await
+            .expect("Failed to prepare executor");
+
+        let worktree_path = context.chat.worktree_path.clone().expect("No worktree");
+        assert!(worktree_path.starts_with(worktrees.path().to_str().unwrap()));
+        assert_eq!(
+            context.start_request.cwd,
+            Some(PathBuf::from(&worktree_path))
+        );
+        let stored = chat::get_chat(&pool, &chat.id).await.unwrap();
+        assert_eq!(stored.worktree_path, Some(worktree_path));
+        assert!(stored.branch.is_some());
+
+        // A Claude session is only resumed by Claude profiles
+        chat::update(
+            &pool,
+            &chat.id,
+            UpdateChatRequest::with_session_id("sess-1", CliToolType::ClaudeCode),
+        )
+        .await
+        .unwrap();
+        let context = prepare(&pool, &chat.id, "Next", None).await.unwrap();
+        assert!(context.cmd_args.contains(&"sess-1".to_string()));
+        let codex =
+            executor_profile::create(&pool, CreateExecutorProfileRequest::new("Codex", "codex"))
+                .await
+                .unwrap();
+        let context = prepare(&pool, &chat.id, "Next", Some(codex.id))
+            .await
+            .unwrap();
+        assert!(!context.cmd_args.contains(&"resume".to_string()));
+        assert!(!context.cmd_args.contains(&"sess-1".to_string()));
+    }
+
     #[test]
     fn test_executor_context_fields() {
         // Test that ExecutorContext can be constructed with all fields
@@ -549,6 +1110,7 @@ mod tests {
             main_chat_id: None,
             workflow_step_index: None,
             claude_session_id: None,
+            session_cli_tool: None,
             archived_at: None,
             created_at: "2024-01-01T00:00:00Z".to_string(),
             updated_at: "2024-01-01T00:00:00Z".to_string(),
@@ -567,6 +1129,7 @@ mod tests {
             always_included_rules: None,
             workflows_folder: ".openflow/workflows".to_string(),
             verification_config: None,
+            script_limits: None,
             archived_at: None,
             created_at: "2024-01-01T00:00:00Z".to_string(),
             updated_at: "2024-01-01T00:00:00Z".to_string(),
@@ -577,13 +1140,13 @@ mod tests {
             chat: chat.clone(),
             project: project.clone(),
             cmd_args: vec!["-p".to_string(), "test".to_string()],
-            env: build_environment(),
+            env: build_environment(&profile).unwrap(),
             create_request: create_process_request(&chat.id, &profile),
             start_request: create_start_request(
                 &profile,
                 vec!["-p".to_string(), "test".to_string()],
                 PathBuf::from(&project.git_repo_path),
-                build_environment(),
+                build_environment(&profile).unwrap(),
             ),
         };
 
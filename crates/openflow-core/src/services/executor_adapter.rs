@@ -0,0 +1,613 @@
+//! Per-CLI executor adapters.
+//!
+//! Each supported AI coding CLI (see `CliToolType`) is driven by an
+//! `ExecutorAdapter` that knows how to:
+//!
+//! - Build the command arguments for a prompt
+//! - Resume a previous session, if the CLI supports it
+//! - Name the output format it asks the CLI for
+//! - Supply a parser that turns the CLI output into chat messages
+//!
+//! Every tool has a parser: stream-json CLIs (Claude Code, Cursor) use
+//! `stream_json`, Codex uses `codex_json`, and the plain text CLIs (Gemini,
+//! Amazon Q, Copilot, custom commands) use `text_transcript`, which records
+//! the whole output as one message. Only parsers of structured output store
+//! a session ID, and a chat's session is only resumed by the tool that
+//! created it (see `Chat::session_for`).
+//!
+//! Profiles select an adapter through `ExecutorProfile::tool_type()`, which
+//! uses the profile's `cli_tool` or infers the tool from its command.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let adapter = executor_adapter::for_profile(&profile);
+//! let session_id = chat.session_for(adapter.tool_type());
+//! let args = adapter.build_args("Fix the tests", session_id, None);
+//! let parser = adapter.output_parser(&chat.id);
+//! ```
+
+use async_trait::async_trait;
+use log::debug;
+use sqlx::SqlitePool;
+
+use openflow_contracts::{CliToolType, ExecutorProfile};
+
+use super::codex_json::CodexTranscriptParser;
+use super::stream_json::TranscriptRecorder;
+use super::text_transcript::TextTranscriptParser;
+use super::ServiceResult;
+use crate::events::EventBroadcaster;
+
+/// Output format an adapter requests from its CLI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Newline-delimited Claude Code style JSON events
+    StreamJson,
+    /// Newline-delimited JSON events in a CLI specific schema
+    Jsonl,
+    /// Plain text
+    Text,
+}
+
+impl OutputFormat {
+    /// Get the format name as a string
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::StreamJson => "stream-json",
+            Self::Jsonl => "jsonl",
+            Self::Text => "text",
+        }
+    }
+}
+
+impl std::fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Turns the stdout of an executor run into chat messages.
+///
+/// Parsers receive output in arbitrary chunks and must buffer partial lines
+/// themselves. `finish` is called once after the process has exited.
+#[async_trait]
+pub trait TranscriptParser: Send {
+    /// Feed a chunk of process output to the parser.
+    async fn handle_output(
+        &mut self,
+        pool: &SqlitePool,
+        broadcaster: &dyn EventBroadcaster,
+        chunk: &str,
+    ) -> ServiceResult<()>;
+
+    /// Flush buffered output and finalize any open message.
+    async fn finish(
+        &mut self,
+        pool: &SqlitePool,
+        broadcaster: &dyn EventBroadcaster,
+    ) -> ServiceResult<()>;
+}
+
+/// Adapter driving a specific AI coding CLI.
+pub trait ExecutorAdapter: Send + Sync {
+    /// The CLI tool this adapter drives.
+    fn tool_type(&self) -> CliToolType;
+
+    /// Output format the adapter asks the CLI for.
+    fn output_format(&self) -> OutputFormat;
+
+    /// Whether the CLI can resume a previous session by ID.
+    fn supports_resume(&self) -> bool;
+
+    /// Build the CLI arguments for a prompt.
+    ///
+    /// `session_id` is only used if the adapter supports resume. `model` is
+    /// passed with the CLI's own flag, if it has one. Profile args are
+    /// appended by the caller.
+    fn build_args(
+        &self,
+        prompt: &str,
+        session_id: Option<&str>,
+        model: Option<&str>,
+    ) -> Vec<String>;
+
+    /// Environment variable the model is passed in, for CLIs without a
+    /// model flag.
+    fn model_env_var(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Create a parser that records the CLI output as messages of a chat.
+    fn output_parser(&self, chat_id: &str) -> Box<dyn TranscriptParser>;
+}
+
+/// Get the adapter for a CLI tool type.
+pub fn for_tool(tool: CliToolType) -> &'static dyn ExecutorAdapter {
+    match tool {
+        CliToolType::ClaudeCode => &ClaudeCodeAdapter,
+        CliToolType::GeminiCli => &GeminiCliAdapter,
+        CliToolType::CodexCli => &CodexCliAdapter,
+        CliToolType::CursorCli => &CursorCliAdapter,
+        CliToolType::AmazonQCli => &AmazonQCliAdapter,
+        CliToolType::CopilotCli => &CopilotCliAdapter,
+        CliToolType::Custom => &CustomAdapter,
+    }
+}
+
+/// Get the adapter selected by an executor profile.
+pub fn for_profile(profile: &ExecutorProfile) -> &'static dyn ExecutorAdapter {
+    let tool = profile.tool_type();
+    debug!(
+        "for_profile: profile={} command={} uses adapter {:?}",
+        profile.name, profile.command, tool
+    );
+    for_tool(tool)
+}
+
+/// Environment variable custom commands receive the profile's model in.
+pub const MODEL_ENV_VAR: &str = "OPENFLOW_MODEL";
+
+fn to_args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|a| a.to_string()).collect()
+}
+
+/// Append `--model <model>` if a model is set.
+fn push_model(args: &mut Vec<String>, model: Option<&str>) {
+    if let Some(model) = model {
+        args.extend(to_args(&["--model", model]));
+    }
+}
+
+// ============================================================================
+// Adapters
+// ============================================================================
+
+/// Anthropic's Claude Code (`claude -p`).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ClaudeCodeAdapter;
+
+impl ExecutorAdapter for ClaudeCodeAdapter {
+    fn tool_type(&self) -> CliToolType {
+        CliToolType::ClaudeCode
+    }
+
+    fn output_format(&self) -> OutputFormat {
+        OutputFormat::StreamJson
+    }
+
+    fn supports_resume(&self) -> bool {
+        true
+    }
+
+    fn build_args(
+        &self,
+        prompt: &str,
+        session_id: Option<&str>,
+        model: Option<&str>,
+    ) -> Vec<String> {
+        let mut args = to_args(&[
+            "--output-format",
+            "stream-json",
+            "--verbose",
+            "--dangerously-skip-permissions",
+        ]);
+        push_model(&mut args, model);
+        if let Some(sid) = session_id {
+            args.extend(to_args(&["--resume", sid]));
+        }
+        args.extend(to_args(&["-p", prompt]));
+        args
+    }
+
+    fn output_parser(&self, chat_id: &str) -> Box<dyn TranscriptParser> {
+        Box::new(TranscriptRecorder::new(chat_id, self.tool_type()))
+    }
+}
+
+/// Google's Gemini CLI (`gemini -p`).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GeminiCliAdapter;
+
+impl ExecutorAdapter for GeminiCliAdapter {
+    fn tool_type(&self) -> CliToolType {
+        CliToolType::GeminiCli
+    }
+
+    fn output_format(&self) -> OutputFormat {
+        OutputFormat::Text
+    }
+
+    fn supports_resume(&self) -> bool {
+        false
+    }
+
+    fn build_args(
+        &self,
+        prompt: &str,
+        _session_id: Option<&str>,
+        model: Option<&str>,
+    ) -> Vec<String> {
+        let mut args = to_args(&["--yolo"]);
+        push_model(&mut args, model);
+        args.extend(to_args(&["-p", prompt]));
+        args
+    }
+
+    fn output_parser(&self, chat_id: &str) -> Box<dyn TranscriptParser> {
+        Box::new(TextTranscriptParser::new(chat_id, self.tool_type()))
+    }
+}
+
+/// OpenAI's Codex CLI (`codex exec`).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CodexCliAdapter;
+
+impl ExecutorAdapter for CodexCliAdapter {
+    fn tool_type(&self) -> CliToolType {
+        CliToolType::CodexCli
+    }
+
+    fn output_format(&self) -> OutputFormat {
+        OutputFormat::Jsonl
+    }
+
+    fn supports_resume(&self) -> bool {
+        true
+    }
+
+    fn build_args(
+        &self,
+        prompt: &str,
+        session_id: Option<&str>,
+        model: Option<&str>,
+    ) -> Vec<String> {
+        let mut args = to_args(&[
+            "exec",
+            "--json",
+            "--dangerously-bypass-approvals-and-sandbox",
+        ]);
+        // Options of `exec` must come before the `resume` subcommand
+        push_model(&mut args, model);
+        if let Some(sid) = session_id {
+            args.extend(to_args(&["resume", sid]));
+        }
+        args.push(prompt.to_string());
+        args
+    }
+
+    fn output_parser(&self, chat_id: &str) -> Box<dyn TranscriptParser> {
+        Box::new(CodexTranscriptParser::new(chat_id))
+    }
+}
+
+/// Cursor's agent CLI (`cursor-agent -p`), which emits Claude compatible
+/// stream-json.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CursorCliAdapter;
+
+impl ExecutorAdapter for CursorCliAdapter {
+    fn tool_type(&self) -> CliToolType {
+        CliToolType::CursorCli
+    }
+
+    fn output_format(&self) -> OutputFormat {
+        OutputFormat::StreamJson
+    }
+
+    fn supports_resume(&self) -> bool {
+        true
+    }
+
+    fn build_args(
+        &self,
+        prompt: &str,
+        session_id: Option<&str>,
+        model: Option<&str>,
+    ) -> Vec<String> {
+        let mut args = to_args(&["--output-format", "stream-json", "--force"]);
+        push_model(&mut args, model);
+        if let Some(sid) = session_id {
+            args.extend(to_args(&["--resume", sid]));
+        }
+        args.extend(to_args(&["-p", prompt]));
+        args
+    }
+
+    fn output_parser(&self, chat_id: &str) -> Box<dyn TranscriptParser> {
+        Box::new(TranscriptRecorder::new(chat_id, self.tool_type()))
+    }
+}
+
+/// Amazon Q Developer CLI (`q chat`).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AmazonQCliAdapter;
+
+impl ExecutorAdapter for AmazonQCliAdapter {
+    fn tool_type(&self) -> CliToolType {
+        CliToolType::AmazonQCli
+    }
+
+    fn output_format(&self) -> OutputFormat {
+        OutputFormat::Text
+    }
+
+    fn supports_resume(&self) -> bool {
+        false
+    }
+
+    fn build_args(
+        &self,
+        prompt: &str,
+        _session_id: Option<&str>,
+        model: Option<&str>,
+    ) -> Vec<String> {
+        let mut args = to_args(&["chat", "--no-interactive", "--trust-all-tools"]);
+        push_model(&mut args, model);
+        args.push(prompt.to_string());
+        args
+    }
+
+    fn output_parser(&self, chat_id: &str) -> Box<dyn TranscriptParser> {
+        Box::new(TextTranscriptParser::new(chat_id, self.tool_type()))
+    }
+}
+
+/// GitHub Copilot CLI (`copilot -p`).
+///
+/// Copilot can resume a session by ID, but its plain text output never
+/// reports one, so runs always start a new session.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CopilotCliAdapter;
+
+impl ExecutorAdapter for CopilotCliAdapter {
+    fn tool_type(&self) -> CliToolType {
+        CliToolType::CopilotCli
+    }
+
+    fn output_format(&self) -> OutputFormat {
+        OutputFormat::Text
+    }
+
+    fn supports_resume(&self) -> bool {
+        true
+    }
+
+    fn build_args(
+        &self,
+        prompt: &str,
+        session_id: Option<&str>,
+        model: Option<&str>,
+    ) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(sid) = session_id {
+            args.extend(to_args(&["--resume", sid]));
+        }
+        push_model(&mut args, model);
+        args.extend(to_args(&["--allow-all-tools", "-p", prompt]));
+        args
+    }
+
+    fn output_parser(&self, chat_id: &str) -> Box<dyn TranscriptParser> {
+        Box::new(TextTranscriptParser::new(chat_id, self.tool_type()))
+    }
+}
+
+/// Any other command: the prompt is passed as the last argument and the
+/// model in `OPENFLOW_MODEL`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CustomAdapter;
+
+impl ExecutorAdapter for CustomAdapter {
+    fn tool_type(&self) -> CliToolType {
+        CliToolType::Custom
+    }
+
+    fn output_format(&self) -> OutputFormat {
+        OutputFormat::Text
+    }
+
+    fn supports_resume(&self) -> bool {
+        false
+    }
+
+    fn build_args(
+        &self,
+        prompt: &str,
+        _session_id: Option<&str>,
+        _model: Option<&str>,
+    ) -> Vec<String> {
+        vec![prompt.to_string()]
+    }
+
+    fn model_env_var(&self) -> Option<&'static str> {
+        Some(MODEL_ENV_VAR)
+    }
+
+    fn output_parser(&self, chat_id: &str) -> Box<dyn TranscriptParser> {
+        Box::new(TextTranscriptParser::new(chat_id, self.tool_type()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::NullBroadcaster;
+    use crate::services::{chat, project};
+    use openflow_contracts::{CreateChatRequest, CreateProjectRequest};
+    use openflow_db::create_test_db;
+
+    const ALL_TOOLS: [CliToolType; 7] = [
+        CliToolType::ClaudeCode,
+        CliToolType::GeminiCli,
+        CliToolType::CodexCli,
+        CliToolType::CursorCli,
+        CliToolType::AmazonQCli,
+        CliToolType::CopilotCli,
+        CliToolType::Custom,
+    ];
+
+    #[test]
+    fn test_for_tool_matches_tool_type() {
+        for tool in ALL_TOOLS {
+            assert_eq!(for_tool(tool).tool_type(), tool);
+        }
+    }
+
+    #[test]
+    fn test_all_adapters_pass_prompt() {
+        for tool in ALL_TOOLS {
+            let args = for_tool(tool).build_args("Fix the build", None, None);
+            assert!(
+                args.contains(&"Fix the build".to_string()),
+                "{:?} dropped the prompt: {:?}",
+                tool,
+                args
+            );
+        }
+    }
+
+    #[test]
+    fn test_resume_only_when_supported() {
+        for tool in ALL_TOOLS {
+            let adapter = for_tool(tool);
+            let args = adapter.build_args("Continue", Some("session-123"), None);
+            assert_eq!(
+                args.contains(&"session-123".to_string()),
+                adapter.supports_resume(),
+                "{:?}: {:?}",
+                tool,
+                args
+            );
+        }
+    }
+
+    #[test]
+    fn test_claude_code_args() {
+        let args = ClaudeCodeAdapter.build_args("Hello", Some("sid"), None);
+        assert_eq!(
+            args,
+            to_args(&[
+                "--output-format",
+                "stream-json",
+                "--verbose",
+                "--dangerously-skip-permissions",
+                "--resume",
+                "sid",
+                "-p",
+                "Hello",
+            ])
+        );
+        assert_eq!(ClaudeCodeAdapter.output_format(), OutputFormat::StreamJson);
+    }
+
+    #[test]
+    fn test_non_claude_args_have_no_claude_flags() {
+        for tool in [
+            CliToolType::GeminiCli,
+            CliToolType::CodexCli,
+            CliToolType::Custom,
+        ] {
+            let args = for_tool(tool).build_args("Hello", None, None);
+            assert!(!args.contains(&"--dangerously-skip-permissions".to_string()));
+            assert!(!args.contains(&"--verbose".to_string()));
+        }
+    }
+
+    #[test]
+    fn test_codex_resume_args() {
+        let args = CodexCliAdapter.build_args("Next", Some("thread-1"), Some("gpt-5"));
+        assert_eq!(args[0], "exec");
+        assert_eq!(
+            &args[args.len() - 5..],
+            &to_args(&["--model", "gpt-5", "resume", "thread-1", "Next"])
+        );
+    }
+
+    #[test]
+    fn test_model_passed_per_cli() {
+        for tool in ALL_TOOLS {
+            let adapter = for_tool(tool);
+            let args = adapter.build_args("Hello", None, Some("fast-model"));
+            let has_flag = args
+                .windows(2)
+                .any(|pair| pair[0] == "--model" && pair[1] == "fast-model");
+            assert_eq!(
+                has_flag,
+                adapter.model_env_var().is_none(),
+                "{:?}: {:?}",
+                tool,
+                args
+            );
+            assert!(!adapter
+                .build_args("Hello", None, None)
+                .contains(&"--model".to_string()));
+        }
+        assert_eq!(CustomAdapter.model_env_var(), Some(MODEL_ENV_VAR));
+    }
+
+    #[tokio::test]
+    async fn test_parsers_store_sessions_of_their_tool() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let project = project::create(
+            &pool,
+            CreateProjectRequest {
+                name: "Test Project".to_string(),
+                git_repo_path: "/tmp/test-repo".to_string(),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        let broadcaster = NullBroadcaster;
+        let output = [
+            (
+                CliToolType::ClaudeCode,
+                r#"{"type":"system","subtype":"init","session_id":"sess-1"}"#,
+            ),
+            (
+                CliToolType::CursorCli,
+                r#"{"type":"system","subtype":"init","session_id":"sess-1"}"#,
+            ),
+            (
+                CliToolType::CodexCli,
+                r#"{"type":"thread.started","thread_id":"sess-1"}"#,
+            ),
+        ];
+
+        for tool in ALL_TOOLS {
+            let chat = chat::create(&pool, CreateChatRequest::new(&project.id))
+                .await
+                .unwrap();
+            let line = output
+                .iter()
+                .find(|(t, _)| *t == tool)
+                .map_or("Session sess-1", |(_, line)| line);
+            let mut parser = for_tool(tool).output_parser(&chat.id);
+            parser
+                .handle_output(&pool, &broadcaster, &format!("{}\n", line))
+                .await
+                .unwrap();
+            parser.finish(&pool, &broadcaster).await.unwrap();
+
+            let chat = chat::get_chat(&pool, &chat.id).await.unwrap();
+            for other in ALL_TOOLS {
+                let resumable = other == tool && output.iter().any(|(t, _)| *t == tool);
+                assert_eq!(
+                    chat.session_for(other).is_some(),
+                    resumable,
+                    "{:?} output resumed by {:?}",
+                    tool,
+                    other
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_output_format_as_str() {
+        assert_eq!(OutputFormat::StreamJson.as_str(), "stream-json");
+        assert_eq!(OutputFormat::Jsonl.to_string(), "jsonl");
+        assert_eq!(OutputFormat::Text.as_str(), "text");
+    }
+}
@@ -21,7 +21,8 @@ use sqlx::SqlitePool;
 use uuid::Uuid;
 
 use openflow_contracts::{
-    CreateExecutorProfileRequest, ExecutorProfile, UpdateExecutorProfileRequest,
+    CliToolType, CreateExecutorProfileRequest, ExecutorProfile, SandboxSettings,
+    UpdateExecutorProfileRequest,
 };
 
 use super::{ServiceError, ServiceResult};
@@ -45,6 +46,11 @@ pub async fn list(pool: &SqlitePool) -> ServiceResult<Vec<ExecutorProfile>> {
             args,
             env,
             model,
+            cli_tool,
+            max_runtime_secs,
+            idle_timeout_secs,
+            resource_limits,
+            sandbox,
             is_default,
             created_at,
             updated_at
@@ -83,6 +89,11 @@ pub async fn get(pool: &SqlitePool, id: &str) -> ServiceResult<ExecutorProfile>
             args,
             env,
             model,
+            cli_tool,
+            max_runtime_secs,
+            idle_timeout_secs,
+            resource_limits,
+            sandbox,
             is_default,
             created_at,
             updated_at
@@ -109,6 +120,74 @@ pub async fn get(pool: &SqlitePool, id: &str) -> ServiceResult<ExecutorProfile>
     Ok(profile)
 }
 
+/// Find an executor profile by ID or name.
+///
+/// Names are matched case-insensitively, so templates can refer to profiles
+/// by name (e.g. `executor_profile: Claude Code`).
+///
+/// # Arguments
+/// * `pool` - Database connection pool
+/// * `reference` - Profile ID or name
+///
+/// Returns the profile if one matches, or `None`.
+pub async fn find(pool: &SqlitePool, reference: &str) -> ServiceResult<Option<ExecutorProfile>> {
+    debug!("Finding executor profile by id or name={}", reference);
+
+    let profile = sqlx::query_as::<_, ExecutorProfile>(
+        r#"
+        SELECT
+            id,
+            name,
+            description,
+            command,
+            args,
+            env,
+            model,
+            cli_tool,
+            max_runtime_secs,
+            idle_timeout_secs,
+            resource_limits,
+            sandbox,
+            is_default,
+            created_at,
+            updated_at
+        FROM executor_profiles
+        WHERE id = ? OR name = ? COLLATE NOCASE
+        ORDER BY id = ? DESC, name ASC
+        LIMIT 1
+        "#,
+    )
+    .bind(reference)
+    .bind(reference)
+    .bind(reference)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        error!(
+            "Database error while finding executor profile {}: {}",
+            reference, e
+        );
+        ServiceError::Database(e)
+    })?;
+
+    match &profile {
+        Some(p) => debug!("Found executor profile id={}, name={}", p.id, p.name),
+        None => debug!("No executor profile matches {}", reference),
+    }
+
+    Ok(profile)
+}
+
+/// Reject sandbox settings that do not parse.
+///
+/// An invalid value would otherwise be stored and the profile's runs would
+/// refuse to start (see `executor::build_sandbox`).
+fn validate_sandbox(sandbox: Option<&str>) -> ServiceResult<()> {
+    SandboxSettings::from_json(sandbox)
+        .map(|_| ())
+        .map_err(|e| ServiceError::Validation(format!("Invalid sandbox settings: {}", e)))
+}
+
 /// Create a new executor profile.
 ///
 /// If is_default is true, clears default from all other profiles first
@@ -123,6 +202,8 @@ pub async fn create(
     pool: &SqlitePool,
     request: CreateExecutorProfileRequest,
 ) -> ServiceResult<ExecutorProfile> {
+    validate_sandbox(request.sandbox.as_deref())?;
+
     let id = Uuid::new_v4().to_string();
     let is_default = request.is_default.unwrap_or(false);
 
@@ -140,9 +221,10 @@ pub async fn create(
     sqlx::query(
         r#"
         INSERT INTO executor_profiles (
-            id, name, description, command, args, env, model, is_default
+            id, name, description, command, args, env, model, cli_tool,
+            max_runtime_secs, idle_timeout_secs, resource_limits, sandbox, is_default
         )
-        VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
         "#,
     )
     .bind(&id)
@@ -152,6 +234,11 @@ pub async fn create(
     .bind(&request.args)
     .bind(&request.env)
     .bind(&request.model)
+    .bind(request.cli_tool)
+    .bind(request.max_runtime_secs)
+    .bind(request.idle_timeout_secs)
+    .bind(&request.resource_limits)
+    .bind(&request.sandbox)
     .bind(is_default)
     .execute(pool)
     .await
@@ -191,6 +278,8 @@ pub async fn update(
 ) -> ServiceResult<ExecutorProfile> {
     debug!("Updating executor profile id={}", id);
 
+    validate_sandbox(request.sandbox.as_deref())?;
+
     // Verify the profile exists first
     let existing = get(pool, id).await?;
 
@@ -201,6 +290,11 @@ pub async fn update(
     let args = request.args.or(existing.args);
     let env = request.env.or(existing.env);
     let model = request.model.or(existing.model);
+    let cli_tool = request.cli_tool.or(existing.cli_tool);
+    let max_runtime_secs = request.max_runtime_secs.or(existing.max_runtime_secs);
+    let idle_timeout_secs = request.idle_timeout_secs.or(existing.idle_timeout_secs);
+    let resource_limits = request.resource_limits.or(existing.resource_limits);
+    let sandbox = request.sandbox.or(existing.sandbox);
     let is_default = request.is_default.unwrap_or(existing.is_default);
 
     // If setting this profile as default, clear default from all others
@@ -222,6 +316,11 @@ pub async fn update(
             args = ?,
             env = ?,
             model = ?,
+            cli_tool = ?,
+            max_runtime_secs = ?,
+            idle_timeout_secs = ?,
+            resource_limits = ?,
+            sandbox = ?,
             is_default = ?,
             updated_at = datetime('now', 'subsec')
         WHERE id = ?
@@ -233,6 +332,11 @@ pub async fn update(
     .bind(&args)
     .bind(&env)
     .bind(&model)
+    .bind(cli_tool)
+    .bind(max_runtime_secs)
+    .bind(idle_timeout_secs)
+    .bind(&resource_limits)
+    .bind(&sandbox)
     .bind(is_default)
     .bind(id)
     .execute(pool)
@@ -299,6 +403,11 @@ pub async fn get_default(pool: &SqlitePool) -> ServiceResult<Option<ExecutorProf
             args,
             env,
             model,
+            cli_tool,
+            max_runtime_secs,
+            idle_timeout_secs,
+            resource_limits,
+            sandbox,
             is_default,
             created_at,
             updated_at
@@ -384,6 +493,11 @@ pub async fn seed_default_profile(pool: &SqlitePool) -> ServiceResult<()> {
             args: None,
             env: None,
             model: None,
+            cli_tool: Some(CliToolType::ClaudeCode),
+            max_runtime_secs: None,
+            idle_timeout_secs: None,
+            resource_limits: None,
+            sandbox: None,
             is_default: Some(true),
         };
 
@@ -399,6 +513,7 @@ pub async fn seed_default_profile(pool: &SqlitePool) -> ServiceResult<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use openflow_contracts::ResourceLimits;
     use openflow_db::create_test_db;
 
     /// Helper to create a test executor profile request.
@@ -410,6 +525,11 @@ mod tests {
             args: None,
             env: None,
             model: None,
+            cli_tool: None,
+            max_runtime_secs: None,
+            idle_timeout_secs: None,
+            resource_limits: None,
+            sandbox: None,
             is_default: None,
         }
     }
@@ -443,6 +563,11 @@ mod tests {
             args: Some(r#"["--verbose", "--no-confirm"]"#.to_string()),
             env: Some(r#"{"ANTHROPIC_API_KEY": "test"}"#.to_string()),
             model: Some("claude-3-opus".to_string()),
+            cli_tool: None,
+            max_runtime_secs: None,
+            idle_timeout_secs: None,
+            resource_limits: None,
+            sandbox: None,
             is_default: Some(true),
         };
 
@@ -494,6 +619,20 @@ mod tests {
         assert!(result.unwrap_err().is_not_found());
     }
 
+    #[tokio::test]
+    async fn test_find_executor_profile_by_id_or_name() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let created = create(&pool, test_create_request("Review Agent", "codex"))
+            .await
+            .expect("Failed to create executor profile");
+
+        let by_id = find(&pool, &created.id).await.unwrap().unwrap();
+        assert_eq!(by_id.id, created.id);
+        let by_name = find(&pool, "review agent").await.unwrap().unwrap();
+        assert_eq!(by_name.id, created.id);
+        assert!(find(&pool, "Unknown").await.unwrap().is_none());
+    }
+
     #[tokio::test]
     async fn test_list_executor_profiles() {
         let pool = create_test_db().await.expect("Failed to create test db");
@@ -541,6 +680,11 @@ mod tests {
             args: Some(r#"["--flag"]"#.to_string()),
             env: None,
             model: Some("new-model".to_string()),
+            cli_tool: None,
+            max_runtime_secs: None,
+            idle_timeout_secs: None,
+            resource_limits: None,
+            sandbox: None,
             is_default: None,
         };
 
@@ -556,6 +700,114 @@ mod tests {
         assert_ne!(updated.updated_at, created.updated_at);
     }
 
+    #[tokio::test]
+    async fn test_cli_tool_persisted() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+
+        let created = create(&pool, test_create_request("Wrapper", "my-agent"))
+            .await
+            .expect("Failed to create executor profile");
+        assert!(created.cli_tool.is_none());
+        assert_eq!(created.tool_type(), CliToolType::Custom);
+
+        let update_request =
+            UpdateExecutorProfileRequest::new().with_cli_tool(CliToolType::GeminiCli);
+        let updated = update(&pool, &created.id, update_request)
+            .await
+            .expect("Failed to update executor profile");
+
+        assert_eq!(updated.cli_tool, Some(CliToolType::GeminiCli));
+        assert_eq!(updated.tool_type(), CliToolType::GeminiCli);
+    }
+
+    #[tokio::test]
+    async fn test_timeouts_persisted() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+
+        let request = test_create_request("Claude", "claude").with_max_runtime_secs(3600);
+        let created = create(&pool, request)
+            .await
+            .expect("Failed to create executor profile");
+        assert_eq!(created.max_runtime_secs, Some(3600));
+        assert!(created.idle_timeout_secs.is_none());
+
+        let update_request = UpdateExecutorProfileRequest::new()
+            .with_max_runtime_secs(0)
+            .with_idle_timeout_secs(120);
+        let updated = update(&pool, &created.id, update_request)
+            .await
+            .expect("Failed to update executor profile");
+
+        assert!(updated.max_runtime().is_none());
+        assert_eq!(updated.idle_timeout_secs, Some(120));
+    }
+
+    #[tokio::test]
+    async fn test_resource_limits_persisted() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+
+        let limits = ResourceLimits {
+            address_space_mb: Some(4096),
+            open_files: Some(1024),
+            ..Default::default()
+        };
+        let request = test_create_request("Claude", "claude").with_resource_limits(&limits);
+        let created = create(&pool, request)
+            .await
+            .expect("Failed to create executor profile");
+        assert_eq!(created.parse_resource_limits(), limits);
+
+        let update_request =
+            UpdateExecutorProfileRequest::new().with_resource_limits(&ResourceLimits::default());
+        let updated = update(&pool, &created.id, update_request)
+            .await
+            .expect("Failed to update executor profile");
+        assert!(updated.parse_resource_limits().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_sandbox_persisted() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+
+        let sandbox = SandboxSettings {
+            enabled: true,
+            allow_network: true,
+            ..Default::default()
+        };
+        let request = test_create_request("Claude", "claude").with_sandbox(&sandbox);
+        let created = create(&pool, request)
+            .await
+            .expect("Failed to create executor profile");
+        assert_eq!(created.parse_sandbox().unwrap(), sandbox);
+
+        let update_request = UpdateExecutorProfileRequest::new().with_model("claude-opus-4");
+        let updated = update(&pool, &created.id, update_request)
+            .await
+            .expect("Failed to update executor profile");
+        assert!(updated.parse_sandbox().unwrap().enabled);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_sandbox_rejected() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+
+        let mut request = test_create_request("Claude", "claude");
+        request.sandbox = Some(r#"{"enabled": tru}"#.to_string());
+        let result = create(&pool, request).await;
+        assert!(matches!(result, Err(ServiceError::Validation(_))));
+
+        let created = create(&pool, test_create_request("Claude", "claude"))
+            .await
+            .expect("Failed to create executor profile");
+        let mut update_request = UpdateExecutorProfileRequest::new();
+        update_request.sandbox = Some(r#"{"enabeld": true}"#.to_string());
+        let result = update(&pool, &created.id, update_request).await;
+        assert!(matches!(result, Err(ServiceError::Validation(_))));
+
+        let unchanged = get(&pool, &created.id).await.unwrap();
+        assert!(unchanged.sandbox.is_none());
+    }
+
     #[tokio::test]
     async fn test_update_executor_profile_not_found() {
         let pool = create_test_db().await.expect("Failed to create test db");
@@ -567,6 +819,11 @@ mod tests {
             args: None,
             env: None,
             model: None,
+            cli_tool: None,
+            max_runtime_secs: None,
+            idle_timeout_secs: None,
+            resource_limits: None,
+            sandbox: None,
             is_default: None,
         };
 
@@ -634,6 +891,11 @@ mod tests {
             args: None,
             env: None,
             model: None,
+            cli_tool: None,
+            max_runtime_secs: None,
+            idle_timeout_secs: None,
+            resource_limits: None,
+            sandbox: None,
             is_default: Some(true),
         };
         create(&pool, request)
@@ -659,6 +921,11 @@ mod tests {
             args: None,
             env: None,
             model: None,
+            cli_tool: None,
+            max_runtime_secs: None,
+            idle_timeout_secs: None,
+            resource_limits: None,
+            sandbox: None,
             is_default: Some(true),
         };
         let first = create(&pool, request1)
@@ -674,6 +941,11 @@ mod tests {
             args: None,
             env: None,
             model: None,
+            cli_tool: None,
+            max_runtime_secs: None,
+            idle_timeout_secs: None,
+            resource_limits: None,
+            sandbox: None,
             is_default: Some(true),
         };
         let second = create(&pool, request2)
@@ -704,6 +976,11 @@ mod tests {
             args: None,
             env: None,
             model: None,
+            cli_tool: None,
+            max_runtime_secs: None,
+            idle_timeout_secs: None,
+            resource_limits: None,
+            sandbox: None,
             is_default: Some(true),
         };
         let first = create(&pool, request1)
@@ -725,6 +1002,11 @@ mod tests {
             args: None,
             env: None,
             model: None,
+            cli_tool: None,
+            max_runtime_secs: None,
+            idle_timeout_secs: None,
+            resource_limits: None,
+            sandbox: None,
             is_default: Some(true),
         };
         let second_updated = update(&pool, &second.id, update_request)
@@ -119,6 +119,79 @@ pub async fn create_worktree(
     Ok(worktree_path.to_string())
 }
 
+/// Create a new git worktree for an existing branch.
+///
+/// Used to restore the worktree of a chat whose branch was kept when its
+/// previous worktree was deleted.
+///
+/// # Arguments
+/// * `repo_path` - Path to the main repository
+/// * `branch_name` - Existing branch to check out in the worktree
+/// * `worktree_path` - Path where the worktree will be created
+///
+/// # Returns
+/// The path to the created worktree.
+///
+/// # Errors
+/// Returns an error if the git command fails (e.g. the branch is checked out
+/// in another worktree).
+pub async fn create_worktree_for_branch(
+    repo_path: &str,
+    branch_name: &str,
+    worktree_path: &str,
+) -> ServiceResult<String> {
+    debug!(
+        "Creating worktree for existing branch: repo_path={}, branch_name={}, worktree_path={}",
+        repo_path, branch_name, worktree_path
+    );
+
+    let worktree_parent = Path::new(worktree_path).parent().ok_or_else(|| {
+        error!("Invalid worktree path: {}", worktree_path);
+        ServiceError::git("Invalid worktree path")
+    })?;
+
+    if !worktree_parent.exists() {
+        debug!("Creating parent directory: {:?}", worktree_parent);
+        std::fs::create_dir_all(worktree_parent)?;
+    }
+
+    // git worktree add <worktree_path> <branch_name>
+    debug!(
+        "Running: git -C {} worktree add {} {}",
+        repo_path, worktree_path, branch_name
+    );
+    let output = Command::new("git")
+        .args([
+            "-C",
+            repo_path,
+            "worktree",
+            "add",
+            worktree_path,
+            branch_name,
+        ])
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        error!(
+            "Failed to create worktree for branch: repo_path={}, branch_name={}, stderr={}",
+            repo_path,
+            branch_name,
+            stderr.trim()
+        );
+        return Err(ServiceError::git(format!(
+            "Failed to create worktree: {}",
+            stderr
+        )));
+    }
+
+    info!(
+        "Created worktree for existing branch: branch_name={}, worktree_path={}",
+        branch_name, worktree_path
+    );
+    Ok(worktree_path.to_string())
+}
+
 /// Delete a git worktree.
 ///
 /// # Arguments
@@ -794,6 +867,108 @@ pub async fn get_head_commit(worktree_path: &str) -> ServiceResult<Option<String
     Ok(Some(hash))
 }
 
+/// Check if a local branch exists.
+pub async fn branch_exists(repo_path: &str, branch_name: &str) -> ServiceResult<bool> {
+    debug!(
+        "Checking branch exists: repo_path={}, branch_name={}",
+        repo_path, branch_name
+    );
+
+    let output = Command::new("git")
+        .args([
+            "-C",
+            repo_path,
+            "show-ref",
+            "--verify",
+            "--quiet",
+            &format!("refs/heads/{}", branch_name),
+        ])
+        .output()?;
+
+    let exists = output.status.success();
+    debug!(
+        "Branch exists: repo_path={}, branch_name={}, exists={}",
+        repo_path, branch_name, exists
+    );
+    Ok(exists)
+}
+
+/// Delete a local branch, even if it is not merged.
+pub async fn delete_branch(repo_path: &str, branch_name: &str) -> ServiceResult<()> {
+    debug!(
+        "Deleting branch: repo_path={}, branch_name={}",
+        repo_path, branch_name
+    );
+
+    let output = Command::new("git")
+        .args(["-C", repo_path, "branch", "-D", branch_name])
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        error!(
+            "Failed to delete branch: branch_name={}, stderr={}",
+            branch_name,
+            stderr.trim()
+        );
+        return Err(ServiceError::git(format!(
+            "Failed to delete branch: {}",
+            stderr
+        )));
+    }
+
+    info!("Deleted branch: branch_name={}", branch_name);
+    Ok(())
+}
+
+/// Merge a branch into the branch checked out in a worktree.
+///
+/// A merge that fails (e.g. on conflicts) is aborted, leaving the worktree
+/// as it was.
+///
+/// # Errors
+/// Returns an error if the merge fails.
+pub async fn merge_branch(worktree_path: &str, branch_name: &str) -> ServiceResult<()> {
+    debug!(
+        "Running: git -C {} merge --no-edit {}",
+        worktree_path, branch_name
+    );
+
+    let output = Command::new("git")
+        .args(["-C", worktree_path, "merge", "--no-edit", branch_name])
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        error!(
+            "Failed to merge branch: worktree_path={}, branch_name={}, stderr={}",
+            worktree_path,
+            branch_name,
+            stderr.trim()
+        );
+        let abort = Command::new("git")
+            .args(["-C", worktree_path, "merge", "--abort"])
+            .output();
+        if let Err(e) = abort {
+            warn!(
+                "Failed to abort merge: worktree_path={}, error={}",
+                worktree_path, e
+            );
+        }
+        return Err(ServiceError::git(format!(
+            "Failed to merge branch {}: {}{}",
+            branch_name, stdout, stderr
+        )));
+    }
+
+    info!(
+        "Merged branch: branch_name={}, worktree_path={}",
+        branch_name, worktree_path
+    );
+    Ok(())
+}
+
 /// Check if a repository has uncommitted changes.
 pub async fn has_uncommitted_changes(worktree_path: &str) -> ServiceResult<bool> {
     debug!(
@@ -1099,6 +1274,52 @@ mod tests {
         assert_eq!(path, "/base/proj/task-review");
     }
 
+    #[tokio::test]
+    async fn test_branch_exists_and_worktree_for_branch() {
+        let repo = setup_test_repo();
+        let repo_path = repo.path().to_str().unwrap();
+        let base = get_current_branch(repo_path).await.unwrap();
+
+        assert!(branch_exists(repo_path, &base).await.unwrap());
+        assert!(!branch_exists(repo_path, "openflow/t1/main").await.unwrap());
+
+        let worktrees = TempDir::new().unwrap();
+        let first = worktrees.path().join("first");
+        create_worktree(
+            repo_path,
+            "openflow/t1/main",
+            &base,
+            first.to_str().unwrap(),
+        )
+        .await
+        .expect("Failed to create worktree");
+        assert!(branch_exists(repo_path, "openflow/t1/main").await.unwrap());
+
+        // Delete the worktree but keep the branch, then restore it elsewhere
+        delete_worktree(repo_path, first.to_str().unwrap())
+            .await
+            .expect("Failed to delete worktree");
+        let second = worktrees.path().join("second");
+        create_worktree_for_branch(repo_path, "openflow/t1/main", second.to_str().unwrap())
+            .await
+            .expect("Failed to restore worktree");
+
+        assert_eq!(
+            get_current_branch(second.to_str().unwrap()).await.unwrap(),
+            "openflow/t1/main"
+        );
+
+        // Once no worktree uses it, the branch can be deleted
+        delete_worktree(repo_path, second.to_str().unwrap())
+            .await
+            .expect("Failed to delete worktree");
+        delete_branch(repo_path, "openflow/t1/main")
+            .await
+            .expect("Failed to delete branch");
+        assert!(!branch_exists(repo_path, "openflow/t1/main").await.unwrap());
+        assert!(delete_branch(repo_path, "openflow/t1/main").await.is_err());
+    }
+
     #[test]
     fn test_parse_hunk_header() {
         let (old_start, old_lines, new_start, new_lines) =
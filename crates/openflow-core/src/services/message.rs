@@ -538,6 +538,7 @@ mod tests {
             always_included_rules: None,
             workflows_folder: None,
             verification_config: None,
+            script_limits: None,
         };
         project::create(pool, request)
             .await
@@ -552,6 +553,7 @@ mod tests {
             title: title.to_string(),
             description: None,
             workflow_template: None,
+            workflow_variables: None,
             parent_task_id: None,
             base_branch: None,
         };
@@ -17,33 +17,55 @@
 //! - **project**: Project CRUD operations
 //! - **task**: Task CRUD operations
 //! - **chat**: Chat session management
+//! - **codex_json**: Codex CLI JSONL transcript recording
 //! - **message**: Message CRUD operations
+//! - **dev_server**: Per-worktree dev servers with URL detection
 //! - **executor**: AI agent execution
+//! - **executor_adapter**: Per-CLI argument building and output parsing
 //! - **executor_profile**: Executor profile management
 //! - **process**: Process lifecycle management
+//! - **recording**: asciicast recordings of PTY processes
 //! - **git**: Git operations (worktree, diff, commits)
 //! - **github**: GitHub integration (PR creation)
 //! - **terminal**: Terminal session management
+//! - **verification**: Verification checks (tests, lint) in chat worktrees
+//! - **scheduler**: Concurrency limits and run queue for coding agents
 //! - **search**: Full-text search operations
+//! - **secret**: Encrypted secrets referenced as `${secret:NAME}`
 //! - **settings**: Application settings
+//! - **stream_json**: Claude Code stream-json transcript recording
+//! - **text_transcript**: Plain text transcript recording
 //! - **workflow**: Workflow template parsing and management
+//! - **workflow_runner**: Runs a task's workflow step by step
+//! - **worktree**: Chat worktree provisioning
 
 mod error;
 
 // Service modules
 pub mod artifact;
 pub mod chat;
+pub mod codex_json;
+pub mod dev_server;
 pub mod executor;
+pub mod executor_adapter;
 pub mod executor_profile;
 pub mod git;
 pub mod github;
 pub mod message;
 pub mod process;
 pub mod project;
+pub mod recording;
+pub mod scheduler;
 pub mod search;
+pub mod secret;
 pub mod settings;
+pub mod stream_json;
 pub mod task;
 pub mod terminal;
+pub mod text_transcript;
+pub mod verification;
 pub mod workflow;
+pub mod workflow_runner;
+pub mod worktree;
 
 pub use error::{ServiceError, ServiceResult};
@@ -3,15 +3,22 @@
 //! Handles execution process lifecycle and management, including:
 //! - Creating and tracking process records in the database
 //! - Starting CLI tool processes (Claude Code, Gemini CLI, etc.)
-//! - Killing running processes
+//! - Killing running processes and everything they started (SIGINT, then
+//!   SIGTERM, then SIGKILL to the process group)
 //! - Sending input to processes
 //! - Real-time output streaming via events
+//! - Persisting output chunks for replay
+//! - Recording exit status, exit code and git HEAD when a process exits
+//! - Killing runs that exceed their max runtime or idle timeout
+//! - Applying resource limits and recording runs that exceed them as failed
+//! - Queuing coding agent runs beyond the concurrency limits (see `scheduler`)
 //!
 //! # Event Broadcasting
 //!
 //! The ProcessService emits events via the EventBroadcaster trait:
 //! - `ProcessOutput` events when stdout/stderr output is received
-//! - `ProcessStatus` events when process status changes (started, completed, killed, failed)
+//! - `ProcessStatus` events when process status changes (queued, started, completed, killed,
+//!   failed, timed out)
 //!
 //! These events are automatically broadcasted to:
 //! - Tauri IPC (via TauriBroadcaster)
@@ -33,23 +40,36 @@
 //! - `ServiceError::Process` for PTY/spawn failures
 //! - `ServiceError::Database` for SQLx errors
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use log::{debug, error, info, warn};
 use sqlx::SqlitePool;
 use std::collections::HashMap;
 use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, mpsc, Mutex, Notify};
 use uuid::Uuid;
 
 use openflow_contracts::{
-    CreateProcessRequest, ExecutionProcess, ProcessStatus, ProcessStatusEvent, StartProcessRequest,
+    CliToolType, CreateProcessRequest, ExecutionProcess, GetProcessOutputRequest,
+    OutputType as ContractOutputType, ProcessOutputChunk, ProcessSnapshot, ProcessStatus,
+    ProcessStatusEvent, QueuedProcess, RunReason, StartProcessRequest,
+};
+use openflow_process::{
+    AsciicastEvent, OutputChunk, OutputStreamer, OutputType as StreamOutputType, ProcessError,
+    PtyConfig, PtyManager, PtySize, ResourceLimits, SandboxConfig, TerminationSignal,
+    DEFAULT_SCROLLBACK_BYTES,
 };
-use openflow_process::{PtyConfig, PtyManager, PtySize};
 
-use super::{ServiceError, ServiceResult};
+use super::executor_adapter::{self, TranscriptParser};
+use super::scheduler::{self, QueuedRun, RunLimits, RunQueue, RunningAgents};
+use super::secret::{self, SecretCipher, SecretRedactor};
+use super::{chat, executor_profile, git, recording, settings, ServiceError, ServiceResult};
 use crate::events::{
-    Event, EventBroadcaster, NullBroadcaster, OutputType, ProcessStatus as EventProcessStatus,
+    EntityType, Event, EventBroadcaster, NullBroadcaster, OutputType,
+    ProcessStatus as EventProcessStatus,
 };
 
 // =============================================================================
@@ -66,8 +86,57 @@ struct RunningProcess {
     use_pty: bool,
     /// The OS process ID (for non-PTY processes).
     os_pid: Option<u32>,
+    /// When the process last produced output (or started).
+    last_output: Instant,
+    /// Resource limits the process was started with.
+    limits: ResourceLimits,
+    /// Masks secret values in output served from the PTY scrollback.
+    redactor: Arc<SecretRedactor>,
+}
+
+/// Messages sent from an output reader to the task that records a run.
+#[derive(Debug)]
+enum StreamMessage {
+    /// A chunk of output to persist.
+    Output(ContractOutputType, String),
+    /// An event for the process's terminal recording.
+    Recording(AsciicastEvent),
+    /// The process exited.
+    Exited(ProcessExit),
+}
+
+/// How a process exited, as reported by its output reader.
+#[derive(Debug)]
+struct ProcessExit {
+    /// Status to record.
+    status: EventProcessStatus,
+    /// Exit code, if the process exited normally.
+    exit_code: Option<i32>,
+    /// Description of the exit status, e.g. the signal that ended it.
+    description: String,
+}
+
+impl ProcessExit {
+    fn new(status: EventProcessStatus, exit_code: Option<i32>) -> Self {
+        Self {
+            status,
+            exit_code,
+            description: String::new(),
+        }
+    }
+
+    fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
 }
 
+/// How much of a run's most recent output is kept to explain its exit.
+const OUTPUT_TAIL_BYTES: usize = 4096;
+
+/// Status reason prefix for runs that exceeded a resource limit.
+pub const LIMIT_EXCEEDED_REASON_PREFIX: &str = "Exceeded";
+
 // =============================================================================
 // Database Operations (Stateless Functions)
 // =============================================================================
@@ -84,11 +153,14 @@ pub async fn get(pool: &SqlitePool, id: &str) -> ServiceResult<ExecutionProcess>
             executor_profile_id,
             status,
             exit_code,
+            status_reason,
+            termination_signal,
             executor_action,
             run_reason,
             before_head_commit,
             after_head_commit,
             pid,
+            dev_server_url,
             started_at,
             completed_at,
             created_at,
@@ -131,11 +203,14 @@ pub async fn list(pool: &SqlitePool) -> ServiceResult<Vec<ExecutionProcess>> {
             executor_profile_id,
             status,
             exit_code,
+            status_reason,
+            termination_signal,
             executor_action,
             run_reason,
             before_head_commit,
             after_head_commit,
             pid,
+            dev_server_url,
             started_at,
             completed_at,
             created_at,
@@ -170,11 +245,14 @@ pub async fn list_by_chat(
             executor_profile_id,
             status,
             exit_code,
+            status_reason,
+            termination_signal,
             executor_action,
             run_reason,
             before_head_commit,
             after_head_commit,
             pid,
+            dev_server_url,
             started_at,
             completed_at,
             created_at,
@@ -215,11 +293,14 @@ pub async fn list_running(pool: &SqlitePool) -> ServiceResult<Vec<ExecutionProce
             executor_profile_id,
             status,
             exit_code,
+            status_reason,
+            termination_signal,
             executor_action,
             run_reason,
             before_head_commit,
             after_head_commit,
             pid,
+            dev_server_url,
             started_at,
             completed_at,
             created_at,
@@ -240,6 +321,46 @@ pub async fn list_running(pool: &SqlitePool) -> ServiceResult<Vec<ExecutionProce
     Ok(processes)
 }
 
+/// List processes waiting in the run queue, oldest first.
+pub async fn list_queued(pool: &SqlitePool) -> ServiceResult<Vec<ExecutionProcess>> {
+    debug!("list_queued: fetching queued processes");
+
+    let processes = sqlx::query_as::<_, ExecutionProcess>(
+        r#"
+        SELECT
+            id,
+            chat_id,
+            executor_profile_id,
+            status,
+            exit_code,
+            status_reason,
+            termination_signal,
+            executor_action,
+            run_reason,
+            before_head_commit,
+            after_head_commit,
+            pid,
+            dev_server_url,
+            started_at,
+            completed_at,
+            created_at,
+            updated_at
+        FROM execution_processes
+        WHERE status = 'queued'
+        ORDER BY created_at ASC, id ASC
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        error!("list_queued: database error: {}", e);
+        e
+    })?;
+
+    debug!("list_queued: found {} queued processes", processes.len());
+    Ok(processes)
+}
+
 /// List running processes for a chat.
 pub async fn list_running_by_chat(
     pool: &SqlitePool,
@@ -258,11 +379,14 @@ pub async fn list_running_by_chat(
             executor_profile_id,
             status,
             exit_code,
+            status_reason,
+            termination_signal,
             executor_action,
             run_reason,
             before_head_commit,
             after_head_commit,
             pid,
+            dev_server_url,
             started_at,
             completed_at,
             created_at,
@@ -295,13 +419,30 @@ pub async fn list_running_by_chat(
 pub async fn create(
     pool: &SqlitePool,
     request: CreateProcessRequest,
+) -> ServiceResult<ExecutionProcess> {
+    insert(pool, request, ProcessStatus::Running).await
+}
+
+/// Create a process record for a run waiting in the run queue.
+pub async fn create_queued(
+    pool: &SqlitePool,
+    request: CreateProcessRequest,
+) -> ServiceResult<ExecutionProcess> {
+    insert(pool, request, ProcessStatus::Queued).await
+}
+
+/// Insert a process record with the given initial status.
+async fn insert(
+    pool: &SqlitePool,
+    request: CreateProcessRequest,
+    status: ProcessStatus,
 ) -> ServiceResult<ExecutionProcess> {
     let id = Uuid::new_v4().to_string();
     let now = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f").to_string();
 
     debug!(
-        "create: creating process id={} chat_id={} action={} reason={:?}",
-        id, request.chat_id, request.executor_action, request.run_reason
+        "insert: creating process id={} chat_id={} action={} reason={:?} status={:?}",
+        id, request.chat_id, request.executor_action, request.run_reason, status
     );
 
     sqlx::query(
@@ -310,12 +451,13 @@ pub async fn create(
             id, chat_id, executor_profile_id, status, executor_action,
             run_reason, before_head_commit, started_at, created_at, updated_at
         )
-        VALUES (?, ?, ?, 'running', ?, ?, ?, ?, ?, ?)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
         "#,
     )
     .bind(&id)
     .bind(&request.chat_id)
     .bind(&request.executor_profile_id)
+    .bind(status.to_string())
     .bind(&request.executor_action)
     .bind(request.run_reason.to_string())
     .bind(&request.before_head_commit)
@@ -326,15 +468,15 @@ pub async fn create(
     .await
     .map_err(|e| {
         error!(
-            "create: database error creating process id={} chat_id={}: {}",
+            "insert: database error creating process id={} chat_id={}: {}",
             id, request.chat_id, e
         );
         e
     })?;
 
     info!(
-        "create: created process id={} chat_id={} action={} reason={:?}",
-        id, request.chat_id, request.executor_action, request.run_reason
+        "insert: created process id={} chat_id={} action={} reason={:?} status={:?}",
+        id, request.chat_id, request.executor_action, request.run_reason, status
     );
     get(pool, &id).await
 }
@@ -356,7 +498,7 @@ pub async fn update_status(
 
     let now = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f").to_string();
     let completed_at = match status {
-        ProcessStatus::Running => None,
+        ProcessStatus::Running | ProcessStatus::Queued => None,
         _ => Some(now.clone()),
     };
 
@@ -393,7 +535,65 @@ pub async fn update_status(
     get(pool, id).await
 }
 
+/// Mark a queued process as running.
+///
+/// Resets `started_at` so time spent in the queue doesn't count as run time,
+/// and records `before_head_commit` unless one was already set.
+///
+/// Returns `ServiceError::Validation` if the process is not queued.
+pub async fn mark_started(
+    pool: &SqlitePool,
+    id: &str,
+    before_head_commit: Option<&str>,
+) -> ServiceResult<ExecutionProcess> {
+    debug!("mark_started: starting queued process id={}", id);
+
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f").to_string();
+    let result = sqlx::query(
+        r#"
+        UPDATE execution_processes
+        SET
+            status = 'running',
+            before_head_commit = COALESCE(before_head_commit, ?),
+            started_at = ?,
+            updated_at = ?
+        WHERE id = ? AND status = 'queued'
+        "#,
+    )
+    .bind(before_head_commit)
+    .bind(&now)
+    .bind(&now)
+    .bind(id)
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        error!(
+            "mark_started: database error updating process id={}: {}",
+            id, e
+        );
+        e
+    })?;
+
+    if result.rows_affected() == 0 {
+        let current = get(pool, id).await?;
+        warn!(
+            "mark_started: process id={} is not queued (status={:?})",
+            id, current.status
+        );
+        return Err(ServiceError::Validation(format!(
+            "Process {} is not queued",
+            id
+        )));
+    }
+
+    info!("mark_started: queued process id={} is now running", id);
+    get(pool, id).await
+}
+
 /// Update the PID of a process.
+///
+/// The start time of the OS process is recorded alongside the PID, so a later
+/// session can tell whether the PID still belongs to this process.
 pub async fn update_pid(pool: &SqlitePool, id: &str, pid: i32) -> ServiceResult<ExecutionProcess> {
     debug!("update_pid: updating process id={} to pid={}", id, pid);
 
@@ -401,15 +601,23 @@ pub async fn update_pid(pool: &SqlitePool, id: &str, pid: i32) -> ServiceResult<
     get(pool, id).await?;
 
     let now = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f").to_string();
+    let start_time = pid_start_time(pid);
+    if start_time.is_none() {
+        warn!(
+            "update_pid: could not read start time of pid={} for process id={}",
+            pid, id
+        );
+    }
 
     sqlx::query(
         r#"
         UPDATE execution_processes
-        SET pid = ?, updated_at = ?
+        SET pid = ?, pid_start_time = ?, updated_at = ?
         WHERE id = ?
         "#,
     )
     .bind(pid)
+    .bind(&start_time)
     .bind(&now)
     .bind(id)
     .execute(pool)
@@ -426,6 +634,17 @@ pub async fn update_pid(pool: &SqlitePool, id: &str, pid: i32) -> ServiceResult<
     get(pool, id).await
 }
 
+/// Get the start time recorded with a process's PID by `update_pid`.
+async fn get_pid_start_time(pool: &SqlitePool, id: &str) -> ServiceResult<Option<String>> {
+    let start_time: Option<String> =
+        sqlx::query_scalar("SELECT pid_start_time FROM execution_processes WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await?
+            .flatten();
+    Ok(start_time)
+}
+
 /// Update the after_head_commit field.
 pub async fn update_after_commit(
     pool: &SqlitePool,
@@ -469,6 +688,86 @@ pub async fn update_after_commit(
     get(pool, id).await
 }
 
+/// Record why a process reached its current status.
+pub async fn update_status_reason(
+    pool: &SqlitePool,
+    id: &str,
+    reason: &str,
+) -> ServiceResult<ExecutionProcess> {
+    debug!(
+        "update_status_reason: updating process id={} reason={}",
+        id, reason
+    );
+
+    // Verify process exists
+    get(pool, id).await?;
+
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f").to_string();
+
+    sqlx::query(
+        r#"
+        UPDATE execution_processes
+        SET status_reason = ?, updated_at = ?
+        WHERE id = ?
+        "#,
+    )
+    .bind(reason)
+    .bind(&now)
+    .bind(id)
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        error!(
+            "update_status_reason: database error updating process id={}: {}",
+            id, e
+        );
+        e
+    })?;
+
+    debug!("update_status_reason: updated process id={}", id);
+    get(pool, id).await
+}
+
+/// Record the last signal sent to a process group when it was stopped.
+pub async fn update_termination_signal(
+    pool: &SqlitePool,
+    id: &str,
+    signal: TerminationSignal,
+) -> ServiceResult<ExecutionProcess> {
+    debug!(
+        "update_termination_signal: updating process id={} signal={}",
+        id, signal
+    );
+
+    // Verify process exists
+    get(pool, id).await?;
+
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f").to_string();
+
+    sqlx::query(
+        r#"
+        UPDATE execution_processes
+        SET termination_signal = ?, updated_at = ?
+        WHERE id = ?
+        "#,
+    )
+    .bind(signal.as_str())
+    .bind(&now)
+    .bind(id)
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        error!(
+            "update_termination_signal: database error updating process id={}: {}",
+            id, e
+        );
+        e
+    })?;
+
+    debug!("update_termination_signal: updated process id={}", id);
+    get(pool, id).await
+}
+
 /// Mark a process as completed.
 pub async fn complete(
     pool: &SqlitePool,
@@ -499,6 +798,69 @@ pub async fn mark_killed(pool: &SqlitePool, id: &str) -> ServiceResult<Execution
     update_status(pool, id, ProcessStatus::Killed, None).await
 }
 
+/// Record the URL a dev server process is serving on.
+pub async fn update_dev_server_url(
+    pool: &SqlitePool,
+    id: &str,
+    url: &str,
+) -> ServiceResult<ExecutionProcess> {
+    debug!(
+        "update_dev_server_url: updating process id={} url={}",
+        id, url
+    );
+
+    // Verify process exists
+    get(pool, id).await?;
+
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f").to_string();
+
+    sqlx::query(
+        r#"
+        UPDATE execution_processes
+        SET dev_server_url = ?, updated_at = ?
+        WHERE id = ?
+        "#,
+    )
+    .bind(url)
+    .bind(&now)
+    .bind(id)
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        error!(
+            "update_dev_server_url: database error updating process id={}: {}",
+            id, e
+        );
+        e
+    })?;
+
+    info!("update_dev_server_url: process id={} url={}", id, url);
+    get(pool, id).await
+}
+
+/// Interval between status checks in `wait_until_finished`.
+const WAIT_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// Wait for a process to finish.
+///
+/// Polls the process record until it is neither queued nor running and
+/// returns the final record. Used to wait for setup scripts and other runs
+/// whose outcome gates further work.
+pub async fn wait_until_finished(pool: &SqlitePool, id: &str) -> ServiceResult<ExecutionProcess> {
+    debug!("wait_until_finished: waiting for process id={}", id);
+    loop {
+        let process = get(pool, id).await?;
+        if process.status.is_finished() {
+            debug!(
+                "wait_until_finished: process id={} finished with status={:?}",
+                id, process.status
+            );
+            return Ok(process);
+        }
+        tokio::time::sleep(WAIT_POLL_INTERVAL).await;
+    }
+}
+
 /// Delete a process.
 pub async fn delete(pool: &SqlitePool, id: &str) -> ServiceResult<()> {
     debug!("delete: deleting process id={}", id);
@@ -519,6 +881,108 @@ pub async fn delete(pool: &SqlitePool, id: &str) -> ServiceResult<()> {
     Ok(())
 }
 
+/// Default number of output chunks returned by `list_output`.
+pub const DEFAULT_OUTPUT_LIMIT: i32 = 1000;
+
+/// Persist a chunk of process output.
+///
+/// Chunks are assigned a monotonically increasing id which clients use as
+/// the `since` cursor when replaying output.
+pub async fn append_output(
+    pool: &SqlitePool,
+    process_id: &str,
+    output_type: ContractOutputType,
+    content: &str,
+) -> ServiceResult<ProcessOutputChunk> {
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f").to_string();
+
+    let chunk = sqlx::query_as::<_, ProcessOutputChunk>(
+        r#"
+        INSERT INTO process_outputs (process_id, output_type, content, created_at)
+        VALUES (?, ?, ?, ?)
+        RETURNING id, process_id, output_type, content, created_at
+        "#,
+    )
+    .bind(process_id)
+    .bind(&output_type)
+    .bind(content)
+    .bind(&now)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| {
+        error!(
+            "append_output: database error storing output for process id={}: {}",
+            process_id, e
+        );
+        e
+    })?;
+
+    Ok(chunk)
+}
+
+/// List persisted output chunks for a process in arrival order.
+///
+/// Only chunks with an id greater than `since` are returned, up to `limit`
+/// chunks (defaults to `DEFAULT_OUTPUT_LIMIT`).
+///
+/// Returns `ServiceError::Validation` if `limit` is outside
+/// `1..=GetProcessOutputRequest::MAX_LIMIT`.
+pub async fn list_output(
+    pool: &SqlitePool,
+    process_id: &str,
+    since: Option<i64>,
+    limit: Option<i32>,
+) -> ServiceResult<Vec<ProcessOutputChunk>> {
+    debug!(
+        "list_output: fetching output for process id={} since={:?} limit={:?}",
+        process_id, since, limit
+    );
+
+    let limit = limit.unwrap_or(DEFAULT_OUTPUT_LIMIT);
+    if !(1..=GetProcessOutputRequest::MAX_LIMIT).contains(&limit) {
+        warn!(
+            "list_output: invalid limit={} for process id={}",
+            limit, process_id
+        );
+        return Err(ServiceError::Validation(format!(
+            "limit must be between 1 and {}",
+            GetProcessOutputRequest::MAX_LIMIT
+        )));
+    }
+
+    // Verify process exists
+    get(pool, process_id).await?;
+
+    let chunks = sqlx::query_as::<_, ProcessOutputChunk>(
+        r#"
+        SELECT id, process_id, output_type, content, created_at
+        FROM process_outputs
+        WHERE process_id = ? AND id > ?
+        ORDER BY id ASC
+        LIMIT ?
+        "#,
+    )
+    .bind(process_id)
+    .bind(since.unwrap_or(0))
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        error!(
+            "list_output: database error fetching output for process id={}: {}",
+            process_id, e
+        );
+        e
+    })?;
+
+    debug!(
+        "list_output: found {} chunks for process id={}",
+        chunks.len(),
+        process_id
+    );
+    Ok(chunks)
+}
+
 /// Create a process status event for the given process.
 pub fn create_status_event(process: &ExecutionProcess) -> ProcessStatusEvent {
     ProcessStatusEvent {
@@ -528,16 +992,581 @@ pub fn create_status_event(process: &ExecutionProcess) -> ProcessStatusEvent {
     }
 }
 
-// =============================================================================
-// Process Service (Stateful)
-// =============================================================================
-
-/// Service for managing execution processes.
+/// Record the output and final state of a run as reported by a streamer.
 ///
-/// This service provides:
-/// - Database CRUD operations for process records
-/// - Process spawning (both PTY and non-PTY modes)
-/// - Process lifecycle management (start, kill, send input)
+/// Messages are handled sequentially, so output chunk ids reflect arrival
+/// order and all output is persisted before the exit is recorded. For coding
+/// agent runs, stdout is also fed to the transcript parser supplied by the
+/// run's executor adapter, which records it as messages of the chat. The
+/// tail of the output is kept to tell whether the run hit a resource limit.
+/// Once the exit is recorded, `queue_notify` wakes the run queue and the
+/// process ID is sent to `exits` subscribers.
+#[allow(clippy::too_many_arguments)]
+async fn record_stream(
+    pool: SqlitePool,
+    running_processes: Arc<Mutex<HashMap<String, RunningProcess>>>,
+    broadcaster: Arc<dyn EventBroadcaster>,
+    queue_notify: Arc<Notify>,
+    exits: broadcast::Sender<String>,
+    process_id: String,
+    cwd: Option<PathBuf>,
+    mut stream_rx: mpsc::UnboundedReceiver<StreamMessage>,
+) {
+    let mut transcript = match get(&pool, &process_id).await {
+        Ok(process) if process.run_reason == RunReason::Codingagent => {
+            transcript_parser(&pool, &process).await
+        }
+        Ok(_) => None,
+        Err(e) => {
+            warn!(
+                "record_stream: cannot load process id={}: {}",
+                process_id, e
+            );
+            None
+        }
+    };
+
+    let mut output_tail = String::new();
+    while let Some(message) = stream_rx.recv().await {
+        match message {
+            StreamMessage::Output(output_type, content) => {
+                if let Some(running) = running_processes.lock().await.get_mut(&process_id) {
+                    running.last_output = Instant::now();
+                }
+                push_output_tail(&mut output_tail, &content);
+                let is_stdout = output_type == ContractOutputType::Stdout;
+                if let Err(e) = append_output(&pool, &process_id, output_type, &content).await {
+                    warn!(
+                        "record_stream: failed to persist output for process id={}: {}",
+                        process_id, e
+                    );
+                }
+                if is_stdout {
+                    if let Some(recorder) = transcript.as_mut() {
+                        if let Err(e) = recorder
+                            .handle_output(&pool, broadcaster.as_ref(), &content)
+                            .await
+                        {
+                            warn!(
+                                "record_stream: failed to record transcript for process id={}: {}",
+                                process_id, e
+                            );
+                        }
+                    }
+                }
+            }
+            StreamMessage::Recording(event) => {
+                if let Err(e) = recording::append_event(&pool, &process_id, &event).await {
+                    warn!(
+                        "record_stream: failed to record event for process id={}: {}",
+                        process_id, e
+                    );
+                }
+            }
+            StreamMessage::Exited(exit) => {
+                if let Some(recorder) = transcript.as_mut() {
+                    if let Err(e) = recorder.finish(&pool, broadcaster.as_ref()).await {
+                        warn!(
+                            "record_stream: failed to finish transcript for process id={}: {}",
+                            process_id, e
+                        );
+                    }
+                }
+                record_exit(
+                    &pool,
+                    &running_processes,
+                    broadcaster.as_ref(),
+                    &process_id,
+                    cwd.as_deref(),
+                    exit,
+                    &output_tail,
+                )
+                .await;
+                // A slot may have freed up for a queued run
+                queue_notify.notify_one();
+                // Sending only fails when nobody is subscribed
+                let _ = exits.send(process_id.clone());
+            }
+        }
+    }
+    debug!("record_stream: stream closed for process id={}", process_id);
+}
+
+/// Append output to a tail buffer, keeping at most `OUTPUT_TAIL_BYTES`.
+fn push_output_tail(tail: &mut String, content: &str) {
+    tail.push_str(content);
+    if tail.len() > OUTPUT_TAIL_BYTES {
+        let mut start = tail.len() - OUTPUT_TAIL_BYTES;
+        while !tail.is_char_boundary(start) {
+            start += 1;
+        }
+        tail.drain(..start);
+    }
+}
+
+/// Get the transcript parser for a coding agent run.
+///
+/// The parser is supplied by the executor adapter of the run's profile. Runs
+/// without a profile are assumed to be Claude Code. Returns `None` if the
+/// profile cannot be loaded.
+async fn transcript_parser(
+    pool: &SqlitePool,
+    process: &ExecutionProcess,
+) -> Option<Box<dyn TranscriptParser>> {
+    let adapter = match &process.executor_profile_id {
+        Some(profile_id) => match executor_profile::get(pool, profile_id).await {
+            Ok(profile) => executor_adapter::for_profile(&profile),
+            Err(e) => {
+                warn!(
+                    "transcript_parser: cannot load profile id={} for process id={}: {}",
+                    profile_id, process.id, e
+                );
+                return None;
+            }
+        },
+        None => executor_adapter::for_tool(CliToolType::ClaudeCode),
+    };
+    Some(adapter.output_parser(&process.chat_id))
+}
+
+/// Record the final state of a process after it has exited.
+///
+/// Stops tracking the process, stores the worktree HEAD as the after-commit,
+/// and writes the final status and exit code. If the record is no longer
+/// running (e.g. it was killed via `ProcessService::kill`), the status
+/// written there is kept and no further status event is broadcast.
+///
+/// A run that ended because it hit one of its resource limits (judged from
+/// the signal in the exit description) is recorded as failed, with a status
+/// reason naming the limit. `output_tail` is only used to log a hint.
+async fn record_exit(
+    pool: &SqlitePool,
+    running_processes: &Mutex<HashMap<String, RunningProcess>>,
+    broadcaster: &dyn EventBroadcaster,
+    process_id: &str,
+    cwd: Option<&Path>,
+    exit: ProcessExit,
+    output_tail: &str,
+) {
+    let ProcessExit {
+        status,
+        exit_code,
+        description,
+    } = exit;
+    debug!(
+        "record_exit: process id={} exited status={:?} exit_code={:?} description={}",
+        process_id, status, exit_code, description
+    );
+
+    let limits = running_processes
+        .lock()
+        .await
+        .remove(process_id)
+        .map(|running| running.limits)
+        .unwrap_or_default();
+
+    let process = match get(pool, process_id).await {
+        Ok(process) => process,
+        Err(e) => {
+            warn!(
+                "record_exit: cannot record exit for process id={}: {}",
+                process_id, e
+            );
+            return;
+        }
+    };
+
+    if let Some(cwd) = cwd {
+        match git::get_head_commit(&cwd.to_string_lossy()).await {
+            Ok(Some(commit)) => {
+                if let Err(e) = update_after_commit(pool, process_id, &commit).await {
+                    warn!(
+                        "record_exit: failed to store after commit for process id={}: {}",
+                        process_id, e
+                    );
+                }
+            }
+            Ok(None) => debug!(
+                "record_exit: no HEAD commit in {:?} for process id={}",
+                cwd, process_id
+            ),
+            Err(e) => warn!(
+                "record_exit: failed to read HEAD commit in {:?} for process id={}: {}",
+                cwd, process_id, e
+            ),
+        }
+    }
+
+    if process.status != ProcessStatus::Running {
+        debug!(
+            "record_exit: process id={} already finalized with status={:?}",
+            process_id, process.status
+        );
+        return;
+    }
+
+    let exceeded = match status {
+        EventProcessStatus::Completed => None,
+        _ => limits.exceeded(&description),
+    };
+    if let (None, EventProcessStatus::Failed) = (exceeded, status) {
+        if let Some(limit) = limits.suspected(output_tail) {
+            info!(
+                "record_exit: process id={} failed with output suggesting the {} was hit",
+                process_id,
+                limits.describe(limit)
+            );
+        }
+    }
+    if let Some(limit) = exceeded {
+        let reason = format!(
+            "{} {}",
+            LIMIT_EXCEEDED_REASON_PREFIX,
+            limits.describe(limit)
+        );
+        warn!("record_exit: process id={} failed: {}", process_id, reason);
+        // Store the reason first so the run never looks finished without it
+        let result = async {
+            update_status_reason(pool, process_id, &reason).await?;
+            update_status(pool, process_id, ProcessStatus::Failed, exit_code).await
+        }
+        .await;
+        match result {
+            Ok(failed) => {
+                broadcaster.broadcast(Event::updated(EntityType::Process, process_id, &failed))
+            }
+            Err(e) => error!(
+                "record_exit: failed to record limit failure for process id={}: {}",
+                process_id, e
+            ),
+        }
+        broadcaster.broadcast(Event::process_status(
+            process_id,
+            EventProcessStatus::Failed,
+            exit_code,
+        ));
+        return;
+    }
+
+    let result = match (status, exit_code) {
+        (EventProcessStatus::Killed, _) => mark_killed(pool, process_id).await,
+        (_, Some(code)) => complete(pool, process_id, code).await,
+        _ => update_status(pool, process_id, ProcessStatus::Failed, None).await,
+    };
+    if let Err(e) = result {
+        error!(
+            "record_exit: failed to record final status for process id={}: {}",
+            process_id, e
+        );
+    }
+
+    broadcaster.broadcast(Event::process_status(process_id, status, exit_code));
+}
+
+/// Read the HEAD commit of `cwd`, logging failures.
+async fn read_head_commit(cwd: &Path) -> Option<String> {
+    git::get_head_commit(&cwd.to_string_lossy())
+        .await
+        .unwrap_or_else(|e| {
+            warn!(
+                "read_head_commit: failed to read HEAD commit in {:?}: {}",
+                cwd, e
+            );
+            None
+        })
+}
+
+/// How long to keep reading a pipe process's output after it exits.
+const PIPE_DRAIN_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Spawn a task that streams one pipe of a standard process line by line.
+fn spawn_pipe_reader<R>(
+    process_id: &str,
+    reader: R,
+    output_type: StreamOutputType,
+    chunk_tx: mpsc::UnboundedSender<OutputChunk>,
+) -> tokio::task::JoinHandle<()>
+where
+    R: tokio::io::AsyncRead + Unpin + Send + 'static,
+{
+    let streamer = OutputStreamer::for_process(process_id);
+    tokio::spawn(async move {
+        if let Err(e) = streamer
+            .stream_to_channel(reader, output_type, chunk_tx)
+            .await
+        {
+            debug!(
+                "spawn_pipe_reader: {:?} stream ended for process id={}: {}",
+                output_type,
+                streamer.process_id(),
+                e
+            );
+        }
+    })
+}
+
+// =============================================================================
+// Process Termination
+// =============================================================================
+
+/// Setting key for how long a process group gets to exit after SIGINT, and
+/// again after SIGTERM, before it is sent SIGKILL (in milliseconds).
+pub const KILL_GRACE_PERIOD_SETTING: &str = "processes.kill_grace_period_ms";
+
+/// Load the kill grace period from settings, falling back to
+/// `openflow_process::DEFAULT_GRACE_PERIOD` when the setting is missing or
+/// invalid.
+pub async fn kill_grace_period(pool: &SqlitePool) -> ServiceResult<Duration> {
+    let Some(value) = settings::get_value(pool, KILL_GRACE_PERIOD_SETTING).await? else {
+        return Ok(openflow_process::DEFAULT_GRACE_PERIOD);
+    };
+    match value.trim().parse::<u64>() {
+        Ok(millis) => Ok(Duration::from_millis(millis)),
+        Err(e) => {
+            warn!(
+                "kill_grace_period: invalid value {:?} for {}: {}, using default",
+                value, KILL_GRACE_PERIOD_SETTING, e
+            );
+            Ok(openflow_process::DEFAULT_GRACE_PERIOD)
+        }
+    }
+}
+
+/// Stop an OS process and its process group, escalating SIGINT → SIGTERM →
+/// SIGKILL with `grace` between signals.
+///
+/// The PID is read from the database, so nothing is signalled unless it
+/// still belongs to the process started with it (see `is_same_process`);
+/// otherwise a reused PID would take an unrelated process group down.
+///
+/// Returns the last signal sent, or `None` if the process was already gone.
+async fn terminate_os_process(
+    pid: i32,
+    start_time: Option<String>,
+    grace: Duration,
+) -> Option<TerminationSignal> {
+    let result = tokio::task::spawn_blocking(move || {
+        if !is_same_process(pid, start_time.as_deref()) {
+            debug!(
+                "terminate_os_process: pid={} no longer belongs to the recorded process",
+                pid
+            );
+            return None;
+        }
+        let pid = u32::try_from(pid).ok()?;
+        openflow_process::terminate_groups(&[pid], grace, || {})
+    })
+    .await;
+    match result {
+        Ok(signal) => {
+            debug!("terminate_os_process: pid={} stopped by {:?}", pid, signal);
+            signal
+        }
+        Err(e) => {
+            warn!(
+                "terminate_os_process: termination task failed for pid={}: {}",
+                pid, e
+            );
+            None
+        }
+    }
+}
+
+// =============================================================================
+// Orphan Reconciliation
+// =============================================================================
+
+/// Setting key that selects the `OrphanPolicy` applied at startup.
+pub const ORPHAN_POLICY_SETTING: &str = "processes.orphan_policy";
+
+/// Reason recorded for orphaned processes found dead at startup.
+pub const ORPHAN_DEAD_REASON: &str =
+    "Process was no longer running at startup (orphaned by a previous session)";
+
+/// Reason recorded for orphaned processes killed at startup.
+pub const ORPHAN_KILLED_REASON: &str =
+    "Process was killed at startup (orphaned by a previous session)";
+
+/// Reason recorded when an adopted process exits.
+pub const ADOPTED_EXIT_REASON: &str =
+    "Process exited after being adopted at startup; exit code unavailable";
+
+/// How often adopted processes are checked for liveness.
+const ADOPTED_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// What to do with processes left running by a previous session that are
+/// still alive at startup.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OrphanPolicy {
+    /// Keep the process running and track it until it exits.
+    /// Its output cannot be recovered.
+    #[default]
+    Adopt,
+    /// Kill the process and mark it as killed.
+    Kill,
+}
+
+impl std::fmt::Display for OrphanPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OrphanPolicy::Adopt => write!(f, "adopt"),
+            OrphanPolicy::Kill => write!(f, "kill"),
+        }
+    }
+}
+
+impl std::str::FromStr for OrphanPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "adopt" => Ok(OrphanPolicy::Adopt),
+            "kill" => Ok(OrphanPolicy::Kill),
+            _ => Err(format!("Invalid orphan policy: {}", s)),
+        }
+    }
+}
+
+impl OrphanPolicy {
+    /// Load the policy from settings, falling back to the default when the
+    /// setting is missing or invalid.
+    pub async fn from_settings(pool: &SqlitePool) -> ServiceResult<Self> {
+        let value = settings::get_value(pool, ORPHAN_POLICY_SETTING).await?;
+        Ok(match value {
+            Some(value) => value.parse().unwrap_or_else(|e| {
+                warn!("OrphanPolicy::from_settings: {}, using default", e);
+                OrphanPolicy::default()
+            }),
+            None => OrphanPolicy::default(),
+        })
+    }
+}
+
+/// Outcome of `ProcessService::reconcile_orphans`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReconcileReport {
+    /// Processes that were no longer alive and were marked failed.
+    pub failed: Vec<String>,
+    /// Processes that were still alive and are now tracked by this session.
+    pub adopted: Vec<String>,
+    /// Processes that were still alive and were killed.
+    pub killed: Vec<String>,
+    /// Runs left in the run queue, which were cancelled.
+    pub cancelled: Vec<String>,
+}
+
+impl ReconcileReport {
+    /// Total number of orphaned processes found.
+    pub fn total(&self) -> usize {
+        self.failed.len() + self.adopted.len() + self.killed.len() + self.cancelled.len()
+    }
+}
+
+/// Read the start time of an OS process, identifying it across PID reuse.
+///
+/// On Linux this is the boot id followed by the `starttime` field of
+/// `/proc/<pid>/stat`; on other Unix systems it is the start time reported by
+/// `ps`, and on Windows the one reported by PowerShell. Returns `None` if the
+/// process does not exist or its start time cannot be read.
+fn pid_start_time(pid: i32) -> Option<String> {
+    if pid <= 0 {
+        return None;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+        // The command name (field 2) may contain spaces and parentheses, so
+        // count fields from the last ')'. starttime is field 22 overall.
+        let after_comm = &stat[stat.rfind(')')? + 1..];
+        let start_ticks = after_comm.split_whitespace().nth(19)?;
+        let boot_id = std::fs::read_to_string("/proc/sys/kernel/random/boot_id").ok()?;
+        Some(format!("{}:{}", boot_id.trim(), start_ticks))
+    }
+    #[cfg(all(unix, not(target_os = "linux")))]
+    {
+        use std::process::{Command, Stdio};
+
+        let output = Command::new("ps")
+            .args(["-o", "lstart=", "-p", &pid.to_string()])
+            .stderr(Stdio::null())
+            .output()
+            .ok()?;
+        let start = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (output.status.success() && !start.is_empty()).then_some(start)
+    }
+    #[cfg(windows)]
+    {
+        use std::process::{Command, Stdio};
+
+        let output = Command::new("powershell")
+            .args([
+                "-NoProfile",
+                "-Command",
+                &format!("(Get-Process -Id {}).StartTime.ToFileTimeUtc()", pid),
+            ])
+            .stderr(Stdio::null())
+            .output()
+            .ok()?;
+        let start = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (output.status.success() && !start.is_empty()).then_some(start)
+    }
+}
+
+/// Check whether a PID read from the database still belongs to the process
+/// that was started with it.
+///
+/// After a reboot or PID wraparound the PID may name an unrelated process, so
+/// it only counts as the same process if it is alive and its start time
+/// matches the one recorded by `update_pid`. Without a recorded start time the
+/// process is treated as gone.
+fn is_same_process(pid: i32, start_time: Option<&str>) -> bool {
+    match start_time {
+        Some(expected) => is_pid_alive(pid) && pid_start_time(pid).as_deref() == Some(expected),
+        None => false,
+    }
+}
+
+/// Check whether an OS process with the given PID is alive.
+fn is_pid_alive(pid: i32) -> bool {
+    use std::process::{Command, Stdio};
+
+    if pid <= 0 {
+        return false;
+    }
+
+    #[cfg(unix)]
+    {
+        Command::new("kill")
+            .arg("-0")
+            .arg(pid.to_string())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map(|status| status.success())
+            .unwrap_or(false)
+    }
+    #[cfg(windows)]
+    {
+        Command::new("tasklist")
+            .args(["/FI", &format!("PID eq {}", pid), "/NH"])
+            .stderr(Stdio::null())
+            .output()
+            .map(|output| String::from_utf8_lossy(&output.stdout).contains(&pid.to_string()))
+            .unwrap_or(false)
+    }
+}
+
+// =============================================================================
+// Process Service (Stateful)
+// =============================================================================
+
+/// Service for managing execution processes.
+///
+/// This service provides:
+/// - Database CRUD operations for process records
+/// - Process spawning (both PTY and non-PTY modes)
+/// - Process lifecycle management (start, kill, send input)
 /// - Tracking of running processes
 /// - Real-time event broadcasting for output and status changes
 ///
@@ -553,6 +1582,22 @@ pub fn create_status_event(process: &ExecutionProcess) -> ProcessStatusEvent {
 /// These events can be received by:
 /// - Tauri IPC listeners (desktop app)
 /// - WebSocket clients (browser/web app)
+///
+/// # Secrets
+///
+/// With a cipher configured (`with_secret_cipher()`), `${secret:NAME}`
+/// references in a start request's env and in project scripts are resolved
+/// at spawn time and the values are masked in the persisted and broadcast
+/// output.
+///
+/// # Run Queue
+///
+/// Coding agent runs beyond the limits configured in settings (see
+/// `scheduler`) are recorded as `queued` and started in order as running
+/// agents exit. Queued runs can be cancelled with `kill()`.
+///
+/// Clones share all state, including running processes and the run queue.
+#[derive(Clone)]
 pub struct ProcessService {
     /// PTY manager for interactive processes.
     pty_manager: Arc<PtyManager>,
@@ -560,8 +1605,31 @@ pub struct ProcessService {
     running_processes: Arc<Mutex<HashMap<String, RunningProcess>>>,
     /// Event broadcaster for real-time updates.
     broadcaster: Arc<dyn EventBroadcaster>,
+    /// Key for decrypting `${secret:NAME}` references, if configured.
+    secret_cipher: Option<Arc<SecretCipher>>,
+    /// Coding agent runs waiting for a free slot.
+    run_queue: Arc<Mutex<RunQueue>>,
+    /// Serializes admission decisions so limits can't be overshot.
+    admission: Arc<Mutex<()>>,
+    /// Woken when a process exits so queued runs can start.
+    queue_notify: Arc<Notify>,
+    /// Whether the task dispatching queued runs is active.
+    queue_pump: Arc<AtomicBool>,
+    /// Notified with a process's ID once its exit has been recorded, or
+    /// once a queued run is cancelled or fails to start.
+    exits: broadcast::Sender<String>,
 }
 
+/// How often the run queue is checked even without exits, so raised
+/// limits are picked up.
+const QUEUE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How often running processes are checked against their timeouts.
+const TIMEOUT_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Number of exit notifications buffered for slow subscribers.
+const EXIT_CHANNEL_CAPACITY: usize = 64;
+
 impl ProcessService {
     /// Create a new ProcessService instance with no event broadcasting.
     ///
@@ -572,6 +1640,12 @@ impl ProcessService {
             pty_manager: Arc::new(PtyManager::new()),
             running_processes: Arc::new(Mutex::new(HashMap::new())),
             broadcaster: NullBroadcaster::arc(),
+            secret_cipher: None,
+            run_queue: Arc::new(Mutex::new(RunQueue::new())),
+            admission: Arc::new(Mutex::new(())),
+            queue_notify: Arc::new(Notify::new()),
+            queue_pump: Arc::new(AtomicBool::new(false)),
+            exits: broadcast::channel(EXIT_CHANNEL_CAPACITY).0,
         }
     }
 
@@ -596,9 +1670,35 @@ impl ProcessService {
             pty_manager: Arc::new(PtyManager::new()),
             running_processes: Arc::new(Mutex::new(HashMap::new())),
             broadcaster,
+            secret_cipher: None,
+            run_queue: Arc::new(Mutex::new(RunQueue::new())),
+            admission: Arc::new(Mutex::new(())),
+            queue_notify: Arc::new(Notify::new()),
+            queue_pump: Arc::new(AtomicBool::new(false)),
+            exits: broadcast::channel(EXIT_CHANNEL_CAPACITY).0,
         }
     }
 
+    /// Set the cipher used to resolve `${secret:NAME}` references.
+    ///
+    /// Builder method; without a cipher, starting a process that references
+    /// a secret fails.
+    pub fn with_secret_cipher(mut self, cipher: SecretCipher) -> Self {
+        self.secret_cipher = Some(Arc::new(cipher));
+        self
+    }
+
+    /// Get the secret cipher.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ServiceError::Config` if no cipher is configured.
+    pub fn secret_cipher(&self) -> ServiceResult<&SecretCipher> {
+        self.secret_cipher
+            .as_deref()
+            .ok_or_else(|| ServiceError::config("No secrets key is configured"))
+    }
+
     /// Get the PTY manager for direct access.
     pub fn pty_manager(&self) -> Arc<PtyManager> {
         Arc::clone(&self.pty_manager)
@@ -609,6 +1709,16 @@ impl ProcessService {
         &self.broadcaster
     }
 
+    /// Subscribe to process exits.
+    ///
+    /// Receives the ID of every process whose exit has been recorded, after
+    /// its final status is stored, so the record can be read back. Queued
+    /// runs that are cancelled or fail to start are received too, as they
+    /// never exit. Exits recorded before subscribing are not received.
+    pub fn subscribe_exits(&self) -> broadcast::Receiver<String> {
+        self.exits.subscribe()
+    }
+
     /// Broadcast a ProcessStatus event.
     fn broadcast_status(
         &self,
@@ -631,13 +1741,25 @@ impl ProcessService {
         self.broadcaster.broadcast(event);
     }
 
-    /// Kill a running process.
+    /// Kill a running process or cancel a queued one.
     ///
-    /// This method attempts to kill the process using either PTY or standard
-    /// process kill mechanisms, then updates the database record.
+    /// The process is marked killed, then its process group is sent SIGINT,
+    /// SIGTERM and finally SIGKILL, waiting the grace period from
+    /// `KILL_GRACE_PERIOD_SETTING` after each signal. The last signal sent is
+    /// stored as the record's `termination_signal`. Queued runs are removed
+    /// from the run queue and marked killed with
+    /// `scheduler::QUEUED_CANCELLED_REASON`.
     pub async fn kill(&self, pool: &SqlitePool, id: &str) -> ServiceResult<ExecutionProcess> {
         info!("kill: attempting to kill process id={}", id);
-        let process = get(pool, id).await?;
+        let mut process = get(pool, id).await?;
+
+        if process.status == ProcessStatus::Queued {
+            if let Some(cancelled) = self.cancel_queued(pool, id).await? {
+                return Ok(cancelled);
+            }
+            // The run left the queue in the meantime; kill what it became
+            process = get(pool, id).await?;
+        }
 
         // Only kill running processes
         if process.status != ProcessStatus::Running {
@@ -651,142 +1773,551 @@ impl ProcessService {
             )));
         }
 
-        // Try to kill via PTY first
-        let killed_via_pty = self.pty_manager.kill(id).is_ok();
-        debug!(
-            "kill: PTY kill attempt for id={} success={}",
-            id, killed_via_pty
+        let grace = kill_grace_period(pool).await?;
+
+        // Record the status first so the exit recorded by the output
+        // streamer doesn't overwrite it
+        let mut killed_process = mark_killed(pool, id).await?;
+        let signal = self.terminate(pool, &process, grace).await;
+        if let Some(signal) = signal {
+            killed_process = update_termination_signal(pool, id, signal).await?;
+        }
+        info!(
+            "kill: successfully killed process id={} signal={:?}",
+            id, signal
         );
 
-        // If not a PTY process, try to kill via OS PID
-        if !killed_via_pty {
-            if let Some(pid) = process.pid {
-                debug!("kill: falling back to OS kill for id={} pid={}", id, pid);
-                #[cfg(unix)]
-                {
-                    use std::process::Command;
-                    let result = Command::new("kill").arg("-9").arg(pid.to_string()).output();
-                    match result {
-                        Ok(_) => debug!("kill: OS kill signal sent to pid={}", pid),
-                        Err(e) => warn!("kill: failed to send OS kill to pid={}: {}", pid, e),
-                    }
+        // Broadcast status event
+        self.broadcast_status(id, EventProcessStatus::Killed, None);
+        self.queue_notify.notify_one();
+
+        Ok(killed_process)
+    }
+
+    /// Stop a running process and everything it started, and stop tracking
+    /// it.
+    ///
+    /// Signals the PTY's process groups if there is a PTY, otherwise the
+    /// process group led by the OS PID. The caller records the final status.
+    ///
+    /// Returns the last signal sent, or `None` if the process was already
+    /// gone.
+    async fn terminate(
+        &self,
+        pool: &SqlitePool,
+        process: &ExecutionProcess,
+        grace: Duration,
+    ) -> Option<TerminationSignal> {
+        let id = process.id.as_str();
+
+        // Try to stop via PTY first
+        let pty_manager = Arc::clone(&self.pty_manager);
+        let pty_id = id.to_string();
+        let via_pty =
+            tokio::task::spawn_blocking(move || pty_manager.terminate(&pty_id, grace)).await;
+
+        let signal = match via_pty {
+            Ok(Ok(signal)) => {
+                debug!("terminate: PTY process id={} stopped by {:?}", id, signal);
+                signal
+            }
+            // If not a PTY process, stop via OS PID
+            _ => match process.pid {
+                Some(pid) => {
+                    debug!(
+                        "terminate: falling back to OS process group for id={} pid={}",
+                        id, pid
+                    );
+                    let start_time = get_pid_start_time(pool, id).await.unwrap_or_else(|e| {
+                        warn!(
+                            "terminate: cannot read pid start time for process id={}: {}",
+                            id, e
+                        );
+                        None
+                    });
+                    terminate_os_process(pid, start_time, grace).await
                 }
-                #[cfg(windows)]
-                {
-                    use std::process::Command;
-                    let result = Command::new("taskkill")
-                        .args(&["/F", "/PID", &pid.to_string()])
-                        .output();
-                    match result {
-                        Ok(_) => debug!("kill: taskkill sent to pid={}", pid),
-                        Err(e) => warn!("kill: failed to taskkill pid={}: {}", pid, e),
-                    }
+                None => {
+                    debug!("terminate: no OS PID available for process id={}", id);
+                    None
                 }
-            } else {
-                debug!("kill: no OS PID available for process id={}", id);
-            }
-        }
+            },
+        };
 
         // Remove from running processes tracking
         {
             let mut running = self.running_processes.lock().await;
             running.remove(id);
-            debug!("kill: removed process id={} from running tracking", id);
+            debug!("terminate: removed process id={} from running tracking", id);
         }
 
         // Close PTY if exists
         let _ = self.pty_manager.close(id);
-        debug!("kill: closed PTY for process id={}", id);
-
-        // Update database
-        info!("kill: successfully killed process id={}", id);
-        let killed_process = mark_killed(pool, id).await?;
+        debug!("terminate: closed PTY for process id={}", id);
 
-        // Broadcast status event
-        self.broadcast_status(id, EventProcessStatus::Killed, None);
-
-        Ok(killed_process)
+        signal
     }
 
-    /// Start a process with the given request parameters.
-    ///
-    /// This creates a process record, spawns the process (either PTY or standard),
-    /// and begins tracking it.
-    pub async fn start(
+    /// Kill a process that exceeded a timeout and record it as `TimedOut`,
+    /// with the timeout error as the status reason.
+    async fn time_out(
         &self,
         pool: &SqlitePool,
-        create_request: CreateProcessRequest,
-        start_request: StartProcessRequest,
+        id: &str,
+        error: &ProcessError,
     ) -> ServiceResult<ExecutionProcess> {
-        info!(
-            "start: starting process for chat_id={} command={} use_pty={}",
-            create_request.chat_id, start_request.command, start_request.use_pty
-        );
-
-        // Create database record
-        let process = create(pool, create_request).await?;
-        debug!("start: created database record id={}", process.id);
-
-        // Spawn the process
-        if start_request.use_pty {
-            // Use PTY for interactive processes
+        let process = get(pool, id).await?;
+        if process.status != ProcessStatus::Running {
             debug!(
-                "start: spawning PTY process id={} cols={} rows={}",
-                process.id,
-                start_request.pty_cols.unwrap_or(80),
-                start_request.pty_rows.unwrap_or(24)
+                "time_out: process id={} already finished with status={:?}",
+                id, process.status
             );
+            return Ok(process);
+        }
 
-            let config = PtyConfig {
-                command: start_request.command.clone(),
-                args: start_request.args.clone(),
-                cwd: start_request.cwd.clone(),
-                env: start_request.env.clone(),
+        // Record the status first so the exit recorded by the output
+        // streamer doesn't overwrite it
+        let grace = kill_grace_period(pool).await?;
+        update_status(pool, id, ProcessStatus::TimedOut, None).await?;
+        let mut timed_out = update_status_reason(pool, id, &error.to_string()).await?;
+        if let Some(signal) = self.terminate(pool, &process, grace).await {
+            timed_out = update_termination_signal(pool, id, signal).await?;
+        }
+        info!("time_out: process id={} timed out: {}", id, error);
+
+        self.broadcast_status(id, EventProcessStatus::TimedOut, None);
+        self.broadcaster
+            .broadcast(Event::updated(EntityType::Process, id, &timed_out));
+        self.queue_notify.notify_one();
+
+        Ok(timed_out)
+    }
+
+    /// Spawn a background task that enforces a run's max runtime and idle
+    /// timeout.
+    ///
+    /// Output activity is tracked by `record_stream`. The task stops once
+    /// the process is no longer tracked, e.g. because it exited or was
+    /// killed.
+    fn spawn_timeout_watcher(
+        &self,
+        pool: &SqlitePool,
+        process_id: &str,
+        max_runtime: Option<Duration>,
+        idle_timeout: Option<Duration>,
+    ) {
+        let service = self.clone();
+        let pool = pool.clone();
+        let process_id = process_id.to_string();
+        let started = Instant::now();
+
+        tokio::spawn(async move {
+            let error = loop {
+                tokio::time::sleep(TIMEOUT_POLL_INTERVAL).await;
+                let last_output = match service.running_processes.lock().await.get(&process_id) {
+                    Some(running) => running.last_output,
+                    None => {
+                        debug!(
+                            "spawn_timeout_watcher: process id={} no longer tracked",
+                            process_id
+                        );
+                        return;
+                    }
+                };
+                if let Some(limit) = max_runtime.filter(|limit| started.elapsed() >= *limit) {
+                    break ProcessError::timeout(format!(
+                        "exceeded max runtime of {}s",
+                        limit.as_secs()
+                    ));
+                }
+                if let Some(limit) = idle_timeout.filter(|limit| last_output.elapsed() >= *limit) {
+                    break ProcessError::timeout(format!("no output for {}s", limit.as_secs()));
+                }
+            };
+
+            warn!(
+                "spawn_timeout_watcher: killing process id={}: {}",
+                process_id, error
+            );
+            if let Err(e) = service.time_out(&pool, &process_id, &error).await {
+                error!(
+                    "spawn_timeout_watcher: failed to record timeout for process id={}: {}",
+                    process_id, e
+                );
+            }
+        });
+    }
+
+    /// Cancel a queued run before it starts.
+    ///
+    /// Returns `None` if the run was dequeued and started in the meantime.
+    async fn cancel_queued(
+        &self,
+        pool: &SqlitePool,
+        id: &str,
+    ) -> ServiceResult<Option<ExecutionProcess>> {
+        {
+            let _admission = self.admission.lock().await;
+            self.run_queue.lock().await.remove(id);
+            if get(pool, id).await?.status != ProcessStatus::Queued {
+                debug!("cancel_queued: process id={} already left the queue", id);
+                return Ok(None);
+            }
+            mark_killed(pool, id).await?;
+        }
+
+        let cancelled = update_status_reason(pool, id, scheduler::QUEUED_CANCELLED_REASON).await?;
+        info!("cancel_queued: cancelled queued process id={}", id);
+        self.broadcast_status(id, EventProcessStatus::Killed, None);
+        let _ = self.exits.send(id.to_string());
+
+        Ok(Some(cancelled))
+    }
+
+    /// Start a process with the given request parameters.
+    ///
+    /// This creates a process record, spawns the process (either PTY or standard),
+    /// and begins tracking it. `${secret:NAME}` references in the env and in
+    /// project scripts are resolved first (see `secret::resolve_start_request`).
+    ///
+    /// When concurrency limits are configured, coding agent runs go through
+    /// the run queue: the returned record is `queued` if no slot is free, and
+    /// the run is started once one frees up.
+    pub async fn start(
+        &self,
+        pool: &SqlitePool,
+        create_request: CreateProcessRequest,
+        start_request: StartProcessRequest,
+    ) -> ServiceResult<ExecutionProcess> {
+        info!(
+            "start: starting process for chat_id={} command={} use_pty={}",
+            create_request.chat_id, start_request.command, start_request.use_pty
+        );
+
+        let mut start_request = start_request;
+        let redactor = Arc::new(
+            secret::resolve_start_request(
+                pool,
+                self.secret_cipher.as_deref(),
+                &create_request.run_reason,
+                &mut start_request,
+            )
+            .await?,
+        );
+
+        if create_request.run_reason == RunReason::Codingagent
+            && RunLimits::from_settings(pool).await?.is_limited()
+        {
+            return self
+                .enqueue(pool, create_request, start_request, redactor)
+                .await;
+        }
+
+        // Capture the worktree HEAD so the run's changes can be diffed later
+        let mut create_request = create_request;
+        if create_request.before_head_commit.is_none() {
+            if let Some(cwd) = &start_request.cwd {
+                create_request.before_head_commit = read_head_commit(cwd).await;
+            }
+        }
+
+        // Create database record
+        let process = create(pool, create_request).await?;
+        debug!("start: created database record id={}", process.id);
+
+        self.spawn(pool, &process.id, start_request, redactor).await
+    }
+
+    /// Add a coding agent run to the run queue and start whatever the
+    /// limits allow, which may include this run.
+    async fn enqueue(
+        &self,
+        pool: &SqlitePool,
+        create_request: CreateProcessRequest,
+        start_request: StartProcessRequest,
+        redactor: Arc<SecretRedactor>,
+    ) -> ServiceResult<ExecutionProcess> {
+        let chat = chat::get_chat(pool, &create_request.chat_id).await?;
+
+        let process = {
+            let _admission = self.admission.lock().await;
+            let process = create_queued(pool, create_request).await?;
+            self.run_queue.lock().await.push(QueuedRun {
+                process_id: process.id.clone(),
+                chat_id: chat.id.clone(),
+                project_id: chat.project_id.clone(),
+                start_request,
+                redactor,
+            });
+            process
+        };
+        self.broadcast_status(&process.id, EventProcessStatus::Queued, None);
+
+        self.dispatch_queued(pool).await?;
+        self.ensure_queue_pump(pool).await;
+
+        get(pool, &process.id).await
+    }
+
+    /// Start queued runs, in order, while the limits allow.
+    ///
+    /// Each run is marked started while admission is held, so concurrent
+    /// callers see it as running. A run that fails to spawn is recorded as
+    /// failed and does not hold up the rest. Returns the number of runs
+    /// started.
+    async fn dispatch_queued(&self, pool: &SqlitePool) -> ServiceResult<usize> {
+        let mut started = 0;
+        loop {
+            let run = {
+                let _admission = self.admission.lock().await;
+                let limits = RunLimits::from_settings(pool).await?;
+                let running = RunningAgents::count(pool).await?;
+                let Some(run) = self.run_queue.lock().await.take_next(&limits, &running) else {
+                    break;
+                };
+                let before_head_commit = match &run.start_request.cwd {
+                    Some(cwd) => read_head_commit(cwd).await,
+                    None => None,
+                };
+                if let Err(e) =
+                    mark_started(pool, &run.process_id, before_head_commit.as_deref()).await
+                {
+                    warn!(
+                        "dispatch_queued: cannot start queued process id={}: {}",
+                        run.process_id, e
+                    );
+                    self.fail_dequeued(pool, &run.process_id, &e).await;
+                    continue;
+                }
+                run
+            };
+
+            debug!(
+                "dispatch_queued: starting queued process id={} project_id={}",
+                run.process_id, run.project_id
+            );
+            if let Err(e) = self
+                .spawn(pool, &run.process_id, run.start_request, run.redactor)
+                .await
+            {
+                warn!(
+                    "dispatch_queued: failed to spawn queued process id={}: {}",
+                    run.process_id, e
+                );
+                self.broadcast_status(&run.process_id, EventProcessStatus::Failed, None);
+                let _ = self.exits.send(run.process_id.clone());
+            }
+            started += 1;
+        }
+        Ok(started)
+    }
+
+    /// Record a run that was taken from the run queue but could not be marked
+    /// started as failed, so it is not left `queued` with no way to dispatch
+    /// or cancel it.
+    async fn fail_dequeued(&self, pool: &SqlitePool, process_id: &str, error: &ServiceError) {
+        let reason = format!("{}: {}", scheduler::QUEUED_START_FAILED_REASON, error);
+        let result = async {
+            if get(pool, process_id).await?.status != ProcessStatus::Queued {
+                return Ok(None);
+            }
+            update_status(pool, process_id, ProcessStatus::Failed, None).await?;
+            update_status_reason(pool, process_id, &reason)
+                .await
+                .map(Some)
+        }
+        .await;
+        match result {
+            Ok(Some(updated)) => {
+                self.broadcast_status(process_id, EventProcessStatus::Failed, None);
+                self.broadcaster.broadcast(Event::updated(
+                    EntityType::Process,
+                    process_id,
+                    &updated,
+                ));
+                let _ = self.exits.send(process_id.to_string());
+            }
+            Ok(None) => {}
+            Err(e) => error!(
+                "fail_dequeued: failed to record failure for process id={}: {}",
+                process_id, e
+            ),
+        }
+    }
+
+    /// Start the background task that dispatches queued runs, unless it is
+    /// already active or the queue is empty.
+    ///
+    /// The task wakes whenever a process exits, and at least every
+    /// `QUEUE_POLL_INTERVAL` so changed limits are picked up. It stops once
+    /// the queue is empty.
+    async fn ensure_queue_pump(&self, pool: &SqlitePool) {
+        // The queue lock is held while the flag changes so a run pushed
+        // just as the task stops is never left without a pump
+        {
+            let queue = self.run_queue.lock().await;
+            if queue.is_empty() || self.queue_pump.swap(true, Ordering::SeqCst) {
+                return;
+            }
+        }
+
+        let service = self.clone();
+        let pool = pool.clone();
+        tokio::spawn(async move {
+            debug!("ensure_queue_pump: run queue dispatcher started");
+            loop {
+                tokio::select! {
+                    _ = service.queue_notify.notified() => {}
+                    _ = tokio::time::sleep(QUEUE_POLL_INTERVAL) => {}
+                }
+                if let Err(e) = service.dispatch_queued(&pool).await {
+                    warn!("ensure_queue_pump: failed to dispatch queued runs: {}", e);
+                }
+                let queue = service.run_queue.lock().await;
+                if queue.is_empty() {
+                    service.queue_pump.store(false, Ordering::SeqCst);
+                    break;
+                }
+            }
+            debug!("ensure_queue_pump: run queue empty, dispatcher stopped");
+        });
+    }
+
+    /// List the runs waiting in the run queue, in order.
+    pub async fn queue(&self) -> Vec<QueuedProcess> {
+        self.run_queue.lock().await.entries()
+    }
+
+    /// Position of a run in the run queue, starting at 1.
+    ///
+    /// Returns `None` if the run is not queued.
+    pub async fn queue_position(&self, process_id: &str) -> Option<usize> {
+        self.run_queue.lock().await.position(process_id)
+    }
+
+    /// Spawn the process for an existing record and begin tracking it.
+    async fn spawn(
+        &self,
+        pool: &SqlitePool,
+        process_id: &str,
+        start_request: StartProcessRequest,
+        redactor: Arc<SecretRedactor>,
+    ) -> ServiceResult<ExecutionProcess> {
+        let limits = start_request
+            .resource_limits
+            .as_ref()
+            .map(ResourceLimits::from)
+            .unwrap_or_default();
+        if !limits.is_empty() {
+            debug!(
+                "spawn: applying resource limits to process id={}: {:?}",
+                process_id, limits
+            );
+        }
+        let sandbox = start_request.sandbox.as_ref().map(SandboxConfig::from);
+        if let Some(sandbox) = &sandbox {
+            debug!(
+                "spawn: sandboxing process id={} writable={:?} network={}",
+                process_id, sandbox.writable_paths, sandbox.allow_network
+            );
+        }
+
+        if start_request.use_pty {
+            // Use PTY for interactive processes
+            debug!(
+                "spawn: spawning PTY process id={} cols={} rows={}",
+                process_id,
+                start_request.pty_cols.unwrap_or(80),
+                start_request.pty_rows.unwrap_or(24)
+            );
+
+            let config = PtyConfig {
+                command: start_request.command.clone(),
+                args: start_request.args.clone(),
+                cwd: start_request.cwd.clone(),
+                env: start_request.env.clone(),
                 cols: start_request.pty_cols.unwrap_or(80),
                 rows: start_request.pty_rows.unwrap_or(24),
+                limits,
+                sandbox: sandbox.clone(),
+                scrollback_bytes: DEFAULT_SCROLLBACK_BYTES,
             };
 
-            self.pty_manager.create(&process.id, config).map_err(|e| {
+            if let Err(e) = self.pty_manager.create(process_id, config) {
                 error!(
-                    "start: failed to create PTY for process id={}: {}",
-                    process.id, e
+                    "spawn: failed to create PTY for process id={}: {}",
+                    process_id, e
                 );
-                ServiceError::Process(e.to_string())
-            })?;
+                update_status(pool, process_id, ProcessStatus::Failed, None).await?;
+                return Err(ServiceError::Process(e.to_string()));
+            }
+
+            // Record the PID so the process can be reconciled after a restart
+            let os_pid = self.pty_manager.process_id(process_id).ok().flatten();
+            if let Some(pid) = os_pid {
+                update_pid(pool, process_id, pid as i32).await?;
+            }
+
+            let recording_started_at = self
+                .start_recording(
+                    pool,
+                    process_id,
+                    PtySize {
+                        cols: start_request.pty_cols.unwrap_or(80),
+                        rows: start_request.pty_rows.unwrap_or(24),
+                    },
+                )
+                .await;
 
             // Track the running process
             {
                 let mut running = self.running_processes.lock().await;
                 running.insert(
-                    process.id.clone(),
+                    process_id.to_string(),
                     RunningProcess {
-                        process_id: process.id.clone(),
+                        process_id: process_id.to_string(),
                         use_pty: true,
-                        os_pid: None,
+                        os_pid,
+                        last_output: Instant::now(),
+                        limits,
+                        redactor: redactor.clone(),
                     },
                 );
                 debug!(
-                    "start: added PTY process id={} to running tracking (total={})",
-                    process.id,
+                    "spawn: added PTY process id={} to running tracking (total={})",
+                    process_id,
                     running.len()
                 );
             }
 
             // Broadcast running status
-            self.broadcast_status(&process.id, EventProcessStatus::Running, None);
+            self.broadcast_status(process_id, EventProcessStatus::Running, None);
 
             // Start output streaming task
-            self.spawn_pty_output_streamer(&process.id);
+            self.spawn_pty_output_streamer(
+                pool,
+                process_id,
+                start_request.cwd.clone(),
+                redactor.clone(),
+                recording_started_at,
+            );
 
             info!(
-                "start: PTY process started id={} command={}",
-                process.id, start_request.command
+                "spawn: PTY process started id={} command={}",
+                process_id,
+                redactor.redact(&start_request.command)
             );
         } else {
             // Use standard process spawning via ProcessSpawner
             debug!(
-                "start: spawning standard process id={} command={} args={:?}",
-                process.id, start_request.command, start_request.args
+                "spawn: spawning standard process id={} command={} args={:?}",
+                process_id,
+                redactor.redact(&start_request.command),
+                start_request
+                    .args
+                    .iter()
+                    .map(|arg| redactor.redact(arg))
+                    .collect::<Vec<_>>()
             );
 
             let config = openflow_process::PipeSpawnConfig {
@@ -795,77 +2326,209 @@ impl ProcessService {
                 cwd: start_request.cwd.clone(),
                 env: start_request.env.clone(),
                 inherit_env: true,
+                limits,
+                sandbox: sandbox.clone(),
             };
 
-            let child = openflow_process::ProcessSpawner::spawn(config).map_err(|e| {
-                error!("start: failed to spawn process id={}: {}", process.id, e);
-                ServiceError::Process(e.to_string())
-            })?;
+            let child = match openflow_process::ProcessSpawner::spawn(config) {
+                Ok(child) => child,
+                Err(e) => {
+                    error!("spawn: failed to spawn process id={}: {}", process_id, e);
+                    update_status(pool, process_id, ProcessStatus::Failed, None).await?;
+                    return Err(ServiceError::Process(e.to_string()));
+                }
+            };
 
             let os_pid = child.id();
             debug!(
-                "start: spawned standard process id={} os_pid={}",
-                process.id, os_pid
+                "spawn: spawned standard process id={} os_pid={}",
+                process_id, os_pid
             );
 
             // Update PID in database
-            update_pid(pool, &process.id, os_pid as i32).await?;
+            update_pid(pool, process_id, os_pid as i32).await?;
 
             // Track the running process
             {
                 let mut running = self.running_processes.lock().await;
                 running.insert(
-                    process.id.clone(),
+                    process_id.to_string(),
                     RunningProcess {
-                        process_id: process.id.clone(),
+                        process_id: process_id.to_string(),
                         use_pty: false,
                         os_pid: Some(os_pid),
+                        last_output: Instant::now(),
+                        limits,
+                        redactor: redactor.clone(),
                     },
                 );
                 debug!(
-                    "start: added standard process id={} pid={} to running tracking (total={})",
-                    process.id,
+                    "spawn: added standard process id={} pid={} to running tracking (total={})",
+                    process_id,
                     os_pid,
                     running.len()
                 );
             }
 
             // Broadcast running status
-            self.broadcast_status(&process.id, EventProcessStatus::Running, None);
+            self.broadcast_status(process_id, EventProcessStatus::Running, None);
+
+            // Stream stdout/stderr and record the outcome in the background
+            self.spawn_pipe_output_streamer(
+                pool,
+                process_id,
+                start_request.cwd.clone(),
+                child,
+                redactor.clone(),
+            );
 
             info!(
-                "start: standard process started id={} pid={} command={}",
-                process.id, os_pid, start_request.command
+                "spawn: standard process started id={} pid={} command={}",
+                process_id,
+                os_pid,
+                redactor.redact(&start_request.command)
             );
         }
 
-        get(pool, &process.id).await
+        let max_runtime = start_request
+            .max_runtime_secs
+            .filter(|secs| *secs > 0)
+            .map(|secs| Duration::from_secs(secs.into()));
+        let idle_timeout = start_request
+            .idle_timeout_secs
+            .filter(|secs| *secs > 0)
+            .map(|secs| Duration::from_secs(secs.into()));
+        if max_runtime.is_some() || idle_timeout.is_some() {
+            debug!(
+                "spawn: enforcing timeouts for process id={} max_runtime={:?} idle_timeout={:?}",
+                process_id, max_runtime, idle_timeout
+            );
+            self.spawn_timeout_watcher(pool, process_id, max_runtime, idle_timeout);
+        }
+
+        get(pool, process_id).await
+    }
+
+    /// Start recording a PTY process if `recording::RECORD_SESSIONS_SETTING`
+    /// is enabled.
+    ///
+    /// Resizes are recorded by a background task subscribed to the PTY;
+    /// output is recorded by the PTY output streamer. Returns the start time
+    /// of the recording, or `None` if the process is not recorded. Failures
+    /// are logged and leave the process unrecorded.
+    async fn start_recording(
+        &self,
+        pool: &SqlitePool,
+        process_id: &str,
+        size: PtySize,
+    ) -> Option<DateTime<Utc>> {
+        match recording::is_enabled(pool).await {
+            Ok(true) => {}
+            Ok(false) => return None,
+            Err(e) => {
+                warn!(
+                    "start_recording: cannot read recording setting for process id={}: {}",
+                    process_id, e
+                );
+                return None;
+            }
+        }
+
+        let mut resizes = match self.pty_manager.subscribe_resizes(process_id) {
+            Ok(resizes) => resizes,
+            Err(e) => {
+                warn!(
+                    "start_recording: cannot subscribe to resizes of process id={}: {}",
+                    process_id, e
+                );
+                return None;
+            }
+        };
+        let started_at = match recording::start(pool, process_id, size).await {
+            Ok(started_at) => started_at,
+            Err(e) => {
+                warn!(
+                    "start_recording: cannot start recording of process id={}: {}",
+                    process_id, e
+                );
+                return None;
+            }
+        };
+
+        info!("start_recording: recording process id={}", process_id);
+        let pool = pool.clone();
+        let process_id = process_id.to_string();
+        tokio::spawn(async move {
+            // Ends when the PTY is closed
+            while let Some(resize) = resizes.recv().await {
+                let event = AsciicastEvent::resize(started_at, &resize);
+                if let Err(e) = recording::append_event(&pool, &process_id, &event).await {
+                    warn!(
+                        "start_recording: failed to record resize of process id={}: {}",
+                        process_id, e
+                    );
+                }
+            }
+        });
+
+        Some(started_at)
     }
 
     /// Spawn a background task to stream PTY output and broadcast events.
     ///
     /// This task reads from the PTY and broadcasts `ProcessOutput` events
-    /// for each chunk of output. When the process exits, it broadcasts a
-    /// `ProcessStatus` event with the exit status.
+    /// for each chunk of output. Chunks are also handed to an async task that
+    /// persists them (see `append_output`) so the output can be replayed later,
+    /// along with recording events if the process is recorded
+    /// (`recording_started_at` is set).
+    /// When the process exits, the same task records the final status, exit
+    /// code and the HEAD commit of `cwd`, then broadcasts a `ProcessStatus`
+    /// event.
     ///
     /// Note: We read raw bytes in chunks rather than lines because:
     /// - Shell prompts don't end with newlines
     /// - ANSI escape sequences need to be preserved
     /// - xterm.js expects raw terminal output
-    fn spawn_pty_output_streamer(&self, process_id: &str) {
+    ///
+    /// Secret values are masked with `redactor` before output is persisted or
+    /// broadcast.
+    fn spawn_pty_output_streamer(
+        &self,
+        pool: &SqlitePool,
+        process_id: &str,
+        cwd: Option<PathBuf>,
+        redactor: Arc<SecretRedactor>,
+        recording_started_at: Option<DateTime<Utc>>,
+    ) {
         let pty_manager = Arc::clone(&self.pty_manager);
         let broadcaster = Arc::clone(&self.broadcaster);
         let process_id = process_id.to_string();
 
+        let (stream_tx, stream_rx) = mpsc::unbounded_channel();
+        tokio::spawn(record_stream(
+            pool.clone(),
+            Arc::clone(&self.running_processes),
+            Arc::clone(&self.broadcaster),
+            Arc::clone(&self.queue_notify),
+            self.exits.clone(),
+            process_id.clone(),
+            cwd,
+            stream_rx,
+        ));
+
         std::thread::spawn(move || {
             // Get reader from PTY
-            let mut reader = match pty_manager.try_clone_reader(&process_id) {
+            let mut reader = match pty_manager.output_reader(&process_id) {
                 Ok(r) => r,
                 Err(e) => {
                     error!(
                         "spawn_pty_output_streamer: failed to get PTY reader for {}: {}",
                         process_id, e
                     );
+                    let _ = stream_tx.send(StreamMessage::Exited(ProcessExit::new(
+                        EventProcessStatus::Failed,
+                        None,
+                    )));
                     return;
                 }
             };
@@ -875,8 +2538,35 @@ impl ProcessService {
                 process_id
             );
 
+            // `offset` is the terminal output offset at the end of
+            // `content`, matching `PtySnapshot::offset` so clients can
+            // de-duplicate
+            let emit = |content: String, offset: u64| {
+                if content.is_empty() {
+                    return;
+                }
+                // Hand off for persistence; the receiver only goes away if
+                // the runtime is shutting down
+                let _ = stream_tx.send(StreamMessage::Output(
+                    ContractOutputType::Stdout,
+                    content.clone(),
+                ));
+                if let Some(started_at) = recording_started_at {
+                    let chunk = OutputChunk::stdout(&process_id, content.clone());
+                    let _ = stream_tx.send(StreamMessage::Recording(AsciicastEvent::output(
+                        started_at, &chunk,
+                    )));
+                }
+
+                // Broadcast the output (treating PTY output as stdout)
+                let event = Event::terminal_output(&process_id, content, offset);
+                broadcaster.broadcast(event);
+            };
+
             // Read chunks from PTY and broadcast
-            // Use a buffer size that balances latency vs overhead
+            // Use a buffer size that balances latency vs overhead. Secrets
+            // split across reads are masked by carrying the tail over.
+            let mut redacting = redactor.stream();
             let mut buffer = [0u8; 4096];
             loop {
                 match reader.read(&mut buffer) {
@@ -889,14 +2579,11 @@ impl ProcessService {
                         break;
                     }
                     Ok(n) => {
-                        // Convert bytes to string, handling invalid UTF-8 gracefully
-                        let content = String::from_utf8_lossy(&buffer[..n]).into_owned();
-                        if !content.is_empty() {
-                            // Broadcast the output (treating PTY output as stdout)
-                            let event =
-                                Event::process_output(&process_id, OutputType::Stdout, content);
-                            broadcaster.broadcast(event);
-                        }
+                        // Convert bytes to string, handling invalid UTF-8 gracefully.
+                        // The offset comes from the scrollback, which counts
+                        // the output of every reader of the PTY.
+                        let content = redacting.push(&String::from_utf8_lossy(&buffer[..n]));
+                        emit(content, reader.offset() - redacting.held_back() as u64);
                     }
                     Err(e) => {
                         // IO error typically means process exited or PTY closed
@@ -908,6 +2595,7 @@ impl ProcessService {
                     }
                 }
             }
+            emit(redacting.finish(), reader.offset());
 
             // Check exit status with retry loop
             // The PTY EOF may arrive before the process fully exits,
@@ -934,7 +2622,8 @@ impl ProcessService {
                                 "spawn_pty_output_streamer: process {} exited with status {:?} (after {} attempts)",
                                 process_id, event_status, attempts
                             );
-                            break (event_status, code);
+                            break ProcessExit::new(event_status, code)
+                                .with_description(status.to_string());
                         }
                         Ok(None) => {
                             attempts += 1;
@@ -944,7 +2633,7 @@ impl ProcessService {
                                     "spawn_pty_output_streamer: stream closed but process {} still running after {} attempts, treating as killed",
                                     process_id, attempts
                                 );
-                                break (EventProcessStatus::Killed, None);
+                                break ProcessExit::new(EventProcessStatus::Killed, None);
                             }
                             // Wait briefly and retry
                             debug!(
@@ -958,20 +2647,164 @@ impl ProcessService {
                                 "spawn_pty_output_streamer: failed to get exit status for {}: {}",
                                 process_id, e
                             );
-                            break (EventProcessStatus::Failed, None);
+                            break ProcessExit::new(EventProcessStatus::Failed, None);
                         }
                     }
                 }
             };
 
-            // Broadcast completion status
-            let event = Event::process_status(&process_id, exit_status.0, exit_status.1);
-            broadcaster.broadcast(event);
-
             info!(
                 "spawn_pty_output_streamer: finished streaming for process_id={} status={:?}",
-                process_id, exit_status.0
+                process_id, exit_status.status
+            );
+            // Record and broadcast completion status once all output is persisted
+            let _ = stream_tx.send(StreamMessage::Exited(exit_status));
+        });
+    }
+
+    /// Spawn background tasks that stream a standard (non-PTY) process's
+    /// output and record its exit.
+    ///
+    /// stdout and stderr are read separately with `OutputStreamer`, so each
+    /// line is broadcast as a `ProcessOutput` event with the matching
+    /// `OutputType` and persisted for replay. Once the process exits and its
+    /// pipes are drained, the final status, exit code and after-commit are
+    /// recorded.
+    ///
+    /// stdin is closed immediately, so processes that read input see EOF
+    /// instead of blocking. A process terminated by a signal has no exit code
+    /// and is recorded as killed. Secret values are masked with `redactor`.
+    fn spawn_pipe_output_streamer(
+        &self,
+        pool: &SqlitePool,
+        process_id: &str,
+        cwd: Option<PathBuf>,
+        mut child: std::process::Child,
+        redactor: Arc<SecretRedactor>,
+    ) {
+        let broadcaster = Arc::clone(&self.broadcaster);
+        let process_id = process_id.to_string();
+
+        let (stream_tx, stream_rx) = mpsc::unbounded_channel();
+        tokio::spawn(record_stream(
+            pool.clone(),
+            Arc::clone(&self.running_processes),
+            Arc::clone(&self.broadcaster),
+            Arc::clone(&self.queue_notify),
+            self.exits.clone(),
+            process_id.clone(),
+            cwd,
+            stream_rx,
+        ));
+
+        drop(child.stdin.take());
+
+        // Read stdout and stderr concurrently into a shared chunk channel
+        let (chunk_tx, mut chunk_rx) = mpsc::unbounded_channel::<OutputChunk>();
+        let mut readers = Vec::new();
+        if let Some(stdout) = child.stdout.take() {
+            match tokio::process::ChildStdout::from_std(stdout) {
+                Ok(stdout) => readers.push(spawn_pipe_reader(
+                    &process_id,
+                    stdout,
+                    StreamOutputType::Stdout,
+                    chunk_tx.clone(),
+                )),
+                Err(e) => warn!(
+                    "spawn_pipe_output_streamer: cannot read stdout for process id={}: {}",
+                    process_id, e
+                ),
+            }
+        }
+        if let Some(stderr) = child.stderr.take() {
+            match tokio::process::ChildStderr::from_std(stderr) {
+                Ok(stderr) => readers.push(spawn_pipe_reader(
+                    &process_id,
+                    stderr,
+                    StreamOutputType::Stderr,
+                    chunk_tx.clone(),
+                )),
+                Err(e) => warn!(
+                    "spawn_pipe_output_streamer: cannot read stderr for process id={}: {}",
+                    process_id, e
+                ),
+            }
+        }
+        drop(chunk_tx);
+
+        // Broadcast each chunk and hand it off for persistence
+        let forwarder = {
+            let stream_tx = stream_tx.clone();
+            let process_id = process_id.clone();
+            tokio::spawn(async move {
+                while let Some(mut chunk) = chunk_rx.recv().await {
+                    chunk.content = redactor.redact(&chunk.content);
+                    let (event_type, contract_type) = match chunk.output_type {
+                        StreamOutputType::Stdout => {
+                            (OutputType::Stdout, ContractOutputType::Stdout)
+                        }
+                        StreamOutputType::Stderr => {
+                            (OutputType::Stderr, ContractOutputType::Stderr)
+                        }
+                    };
+                    let _ =
+                        stream_tx.send(StreamMessage::Output(contract_type, chunk.content.clone()));
+                    broadcaster.broadcast(Event::process_output(
+                        &process_id,
+                        event_type,
+                        chunk.content,
+                    ));
+                }
+            })
+        };
+
+        tokio::spawn(async move {
+            let exit = match tokio::task::spawn_blocking(move || child.wait()).await {
+                Ok(Ok(exit)) => match exit.code() {
+                    Some(0) => ProcessExit::new(EventProcessStatus::Completed, Some(0)),
+                    Some(code) => ProcessExit::new(EventProcessStatus::Failed, Some(code)),
+                    None => ProcessExit::new(EventProcessStatus::Killed, None),
+                }
+                .with_description(exit.to_string()),
+                Ok(Err(e)) => {
+                    warn!(
+                        "spawn_pipe_output_streamer: failed to wait on process id={}: {}",
+                        process_id, e
+                    );
+                    ProcessExit::new(EventProcessStatus::Failed, None)
+                }
+                Err(e) => {
+                    warn!(
+                        "spawn_pipe_output_streamer: wait task failed for process id={}: {}",
+                        process_id, e
+                    );
+                    ProcessExit::new(EventProcessStatus::Failed, None)
+                }
+            };
+
+            // Drain output still buffered in the pipes. Background children
+            // that inherited the pipes can hold them open indefinitely, so
+            // stop reading after a grace period.
+            let deadline = tokio::time::Instant::now() + PIPE_DRAIN_TIMEOUT;
+            for mut reader in readers {
+                if tokio::time::timeout_at(deadline, &mut reader)
+                    .await
+                    .is_err()
+                {
+                    debug!(
+                        "spawn_pipe_output_streamer: pipes still open for process id={}, stop reading",
+                        process_id
+                    );
+                    reader.abort();
+                }
+            }
+            let _ = forwarder.await;
+
+            info!(
+                "spawn_pipe_output_streamer: finished streaming for process_id={} status={:?}",
+                process_id, exit.status
             );
+            let _ = stream_tx.send(StreamMessage::Exited(exit));
         });
     }
 
@@ -1022,17 +2855,79 @@ impl ProcessService {
         Ok(())
     }
 
-    /// Check if a process is currently running.
-    pub async fn is_running(&self, process_id: &str) -> bool {
-        let running = self.running_processes.lock().await;
-        let is_running = running.contains_key(process_id);
-        debug!(
-            "is_running: process id={} is_running={}",
-            process_id, is_running
-        );
-        is_running
-    }
-
+    /// Get the recent output and current size of a running PTY process.
+    ///
+    /// Used by clients that attach to a terminal or agent mid-run. Secret
+    /// values are masked like in streamed output.
+    ///
+    /// # Errors
+    ///
+    /// `NotFound` if the process is not running, `Validation` if it does not
+    /// use a PTY.
+    pub async fn snapshot(&self, process_id: &str) -> ServiceResult<ProcessSnapshot> {
+        debug!("snapshot: taking snapshot of process id={}", process_id);
+
+        let redactor = {
+            let running = self.running_processes.lock().await;
+            match running.get(process_id) {
+                Some(process) if process.use_pty => process.redactor.clone(),
+                Some(_) => {
+                    return Err(ServiceError::Validation(format!(
+                        "Process {} does not use a terminal",
+                        process_id
+                    )));
+                }
+                None => {
+                    return Err(ServiceError::NotFound {
+                        entity: "Running process",
+                        id: process_id.to_string(),
+                    });
+                }
+            }
+        };
+
+        let snapshot = self.pty_manager.snapshot(process_id).map_err(|e| {
+            error!(
+                "snapshot: failed to take snapshot of process id={}: {}",
+                process_id, e
+            );
+            ServiceError::Process(e.to_string())
+        })?;
+
+        // Hold back a possible partial secret at the end, like the output
+        // streamer does, so the offset lines up with live output events
+        let mut redacting = redactor.stream();
+        let content = redacting.push(&String::from_utf8_lossy(&snapshot.data));
+        let offset = snapshot.offset - redacting.held_back() as u64;
+
+        debug!(
+            "snapshot: took snapshot of process id={} bytes={} offset={} truncated={}",
+            process_id,
+            snapshot.data.len(),
+            offset,
+            snapshot.truncated
+        );
+        Ok(ProcessSnapshot {
+            process_id: process_id.to_string(),
+            content,
+            cols: snapshot.size.cols,
+            rows: snapshot.size.rows,
+            truncated: snapshot.truncated,
+            offset: offset as i64,
+        })
+    }
+
+    /// Check if a process is currently running.
+    pub async fn is_running(&self, process_id: &str) -> bool {
+        let running = self.running_processes.lock().await;
+        let is_running = running.contains_key(process_id);
+        debug!(
+            "is_running: process id={} is_running={}",
+            process_id, is_running
+        );
+        is_running
+    }
+
     /// Get the count of running processes.
     pub async fn running_count(&self) -> usize {
         let running = self.running_processes.lock().await;
@@ -1041,19 +2936,232 @@ impl ProcessService {
         count
     }
 
+    /// Reconcile processes left `running` by a previous session.
+    ///
+    /// Call once at startup, before any processes are started. Each running
+    /// record that this service does not track (and whose PTY is unknown to
+    /// the `PtyManager`) is checked by PID and the start time recorded with it:
+    /// - Dead processes, and PIDs now held by an unrelated process, are marked
+    ///   failed with `ORPHAN_DEAD_REASON`
+    /// - Live processes are adopted or killed according to `policy`
+    ///
+    /// Queued records that are not in this session's run queue are marked
+    /// killed with `scheduler::QUEUED_ORPHAN_REASON`.
+    ///
+    /// A `DataChanged` event is broadcast for every record that changes.
+    pub async fn reconcile_orphans(
+        &self,
+        pool: &SqlitePool,
+        policy: OrphanPolicy,
+    ) -> ServiceResult<ReconcileReport> {
+        let candidates = list_running(pool).await?;
+        info!(
+            "reconcile_orphans: checking {} running process records policy={}",
+            candidates.len(),
+            policy
+        );
+
+        let mut report = ReconcileReport::default();
+        for queued in list_queued(pool).await? {
+            if self.queue_position(&queued.id).await.is_some() {
+                continue;
+            }
+            info!(
+                "reconcile_orphans: cancelling stale queued process id={}",
+                queued.id
+            );
+            mark_killed(pool, &queued.id).await?;
+            let updated =
+                update_status_reason(pool, &queued.id, scheduler::QUEUED_ORPHAN_REASON).await?;
+            self.broadcast_status(&queued.id, EventProcessStatus::Killed, None);
+            self.broadcaster
+                .broadcast(Event::updated(EntityType::Process, &queued.id, &updated));
+            report.cancelled.push(queued.id);
+        }
+
+        for process in candidates {
+            let id = process.id.clone();
+
+            if self.is_running(&id).await || self.pty_manager.exists(&id).unwrap_or(false) {
+                debug!(
+                    "reconcile_orphans: process id={} is owned by this session",
+                    id
+                );
+                continue;
+            }
+
+            let start_time = get_pid_start_time(pool, &id).await?;
+            let alive_pid = process
+                .pid
+                .filter(|pid| is_same_process(*pid, start_time.as_deref()));
+
+            let updated = match (alive_pid, policy) {
+                (None, _) => {
+                    info!(
+                        "reconcile_orphans: process id={} pid={:?} is dead, marking failed",
+                        id, process.pid
+                    );
+                    update_status(pool, &id, ProcessStatus::Failed, None).await?;
+                    let updated = update_status_reason(pool, &id, ORPHAN_DEAD_REASON).await?;
+                    self.broadcast_status(&id, EventProcessStatus::Failed, None);
+                    report.failed.push(id.clone());
+                    updated
+                }
+                (Some(pid), OrphanPolicy::Kill) => {
+                    info!(
+                        "reconcile_orphans: killing orphaned process id={} pid={}",
+                        id, pid
+                    );
+                    let signal =
+                        terminate_os_process(pid, start_time, kill_grace_period(pool).await?).await;
+                    mark_killed(pool, &id).await?;
+                    let mut updated = update_status_reason(pool, &id, ORPHAN_KILLED_REASON).await?;
+                    if let Some(signal) = signal {
+                        updated = update_termination_signal(pool, &id, signal).await?;
+                    }
+                    self.broadcast_status(&id, EventProcessStatus::Killed, None);
+                    report.killed.push(id.clone());
+                    updated
+                }
+                (Some(pid), OrphanPolicy::Adopt) => {
+                    info!(
+                        "reconcile_orphans: adopting orphaned process id={} pid={}",
+                        id, pid
+                    );
+                    self.running_processes.lock().await.insert(
+                        id.clone(),
+                        RunningProcess {
+                            process_id: id.clone(),
+                            use_pty: false,
+                            os_pid: Some(pid as u32),
+                            last_output: Instant::now(),
+                            limits: ResourceLimits::default(),
+                            redactor: Arc::default(),
+                        },
+                    );
+                    self.spawn_adopted_watcher(pool, &id, pid, start_time);
+                    report.adopted.push(id.clone());
+                    continue;
+                }
+            };
+
+            self.broadcaster
+                .broadcast(Event::updated(EntityType::Process, &id, &updated));
+        }
+
+        info!(
+            "reconcile_orphans: completed - failed={} killed={} adopted={} cancelled={}",
+            report.failed.len(),
+            report.killed.len(),
+            report.adopted.len(),
+            report.cancelled.len()
+        );
+        Ok(report)
+    }
+
+    /// Spawn a background task that polls an adopted process until it exits.
+    ///
+    /// The exit code of an adopted process cannot be observed, so it is
+    /// recorded as completed without an exit code and with
+    /// `ADOPTED_EXIT_REASON`. The task stops early if the process stops being
+    /// tracked (e.g. it was killed via `kill`). A PID whose start time no
+    /// longer matches `start_time` has been reused and counts as exited.
+    fn spawn_adopted_watcher(
+        &self,
+        pool: &SqlitePool,
+        process_id: &str,
+        pid: i32,
+        start_time: Option<String>,
+    ) {
+        let pool = pool.clone();
+        let running_processes = Arc::clone(&self.running_processes);
+        let broadcaster = Arc::clone(&self.broadcaster);
+        let queue_notify = Arc::clone(&self.queue_notify);
+        let process_id = process_id.to_string();
+
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(ADOPTED_POLL_INTERVAL).await;
+                if !running_processes.lock().await.contains_key(&process_id) {
+                    debug!(
+                        "spawn_adopted_watcher: process id={} no longer tracked",
+                        process_id
+                    );
+                    return;
+                }
+                if !is_same_process(pid, start_time.as_deref()) {
+                    break;
+                }
+            }
+
+            running_processes.lock().await.remove(&process_id);
+            info!(
+                "spawn_adopted_watcher: adopted process id={} pid={} exited",
+                process_id, pid
+            );
+
+            let result = async {
+                update_status(&pool, &process_id, ProcessStatus::Completed, None).await?;
+                update_status_reason(&pool, &process_id, ADOPTED_EXIT_REASON).await
+            }
+            .await;
+            match result {
+                Ok(updated) => {
+                    broadcaster.broadcast(Event::process_status(
+                        &process_id,
+                        EventProcessStatus::Completed,
+                        None,
+                    ));
+                    broadcaster.broadcast(Event::updated(
+                        EntityType::Process,
+                        &process_id,
+                        &updated,
+                    ));
+                }
+                Err(e) => error!(
+                    "spawn_adopted_watcher: failed to record exit for process id={}: {}",
+                    process_id, e
+                ),
+            }
+            queue_notify.notify_one();
+        });
+    }
+
     /// Kill all running processes (cleanup on shutdown).
+    ///
+    /// Queued runs are cancelled first, so killing running agents does not
+    /// start them. Running processes are stopped concurrently through `kill`,
+    /// so shutdown waits for at most one signal escalation.
     pub async fn kill_all(&self, pool: &SqlitePool) -> ServiceResult<()> {
-        let process_ids: Vec<String> = {
+        let queued_ids: Vec<String> = self
+            .queue()
+            .await
+            .into_iter()
+            .map(|queued| queued.process_id)
+            .collect();
+        let running_ids: Vec<String> = {
             let running = self.running_processes.lock().await;
             running.keys().cloned().collect()
         };
 
-        info!("kill_all: killing {} running processes", process_ids.len());
+        info!(
+            "kill_all: killing {} running and {} queued processes",
+            running_ids.len(),
+            queued_ids.len()
+        );
+
+        let mut results = Vec::with_capacity(queued_ids.len() + running_ids.len());
+        for id in &queued_ids {
+            results.push(self.kill(pool, id).await);
+        }
+        results.extend(
+            futures::future::join_all(running_ids.iter().map(|id| self.kill(pool, id))).await,
+        );
 
         let mut killed_count = 0;
         let mut failed_count = 0;
-        for id in &process_ids {
-            match self.kill(pool, id).await {
+        for (id, result) in queued_ids.iter().chain(&running_ids).zip(results) {
+            match result {
                 Ok(_) => {
                     killed_count += 1;
                     debug!("kill_all: killed process id={}", id);
@@ -1124,166 +3232,1319 @@ mod tests {
         };
         let chat = chat::create(pool, chat_request)
             .await
-            .expect("Failed to create chat");
+            .expect("Failed to create chat");
+
+        chat.id
+    }
+
+    /// Helper to create a test process request.
+    fn test_create_request(chat_id: &str) -> CreateProcessRequest {
+        CreateProcessRequest::terminal(chat_id, "test action")
+    }
+
+    #[tokio::test]
+    async fn test_create_process() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let chat_id = create_test_chat(&pool).await;
+
+        let request = test_create_request(&chat_id);
+        let process = create(&pool, request)
+            .await
+            .expect("Failed to create process");
+
+        assert_eq!(process.chat_id, chat_id);
+        assert_eq!(process.status, ProcessStatus::Running);
+        assert_eq!(process.executor_action, "test action");
+        assert_eq!(process.run_reason, RunReason::Terminal);
+        assert!(process.exit_code.is_none());
+        assert!(!process.id.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_process() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let chat_id = create_test_chat(&pool).await;
+
+        let request = test_create_request(&chat_id);
+        let created = create(&pool, request)
+            .await
+            .expect("Failed to create process");
+
+        let fetched = get(&pool, &created.id)
+            .await
+            .expect("Failed to get process");
+
+        assert_eq!(fetched.id, created.id);
+        assert_eq!(fetched.chat_id, chat_id);
+    }
+
+    #[tokio::test]
+    async fn test_get_process_not_found() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+
+        let result = get(&pool, "non-existent-id").await;
+
+        assert!(result.is_err());
+        match result.unwrap_err() {
+            ServiceError::NotFound { entity, id } => {
+                assert_eq!(entity, "ExecutionProcess");
+                assert_eq!(id, "non-existent-id");
+            }
+            other => panic!("Expected NotFound error, got: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_processes() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let chat_id = create_test_chat(&pool).await;
+
+        // Create multiple processes
+        for i in 0..3 {
+            let request = CreateProcessRequest::terminal(&chat_id, format!("action {}", i));
+            create(&pool, request)
+                .await
+                .expect("Failed to create process");
+        }
+
+        let processes = list(&pool).await.expect("Failed to list processes");
+        assert!(processes.len() >= 3);
+    }
+
+    #[tokio::test]
+    async fn test_list_by_chat() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let chat_id = create_test_chat(&pool).await;
+
+        // Create multiple processes
+        for i in 0..3 {
+            let request = CreateProcessRequest::terminal(&chat_id, format!("action {}", i));
+            create(&pool, request)
+                .await
+                .expect("Failed to create process");
+        }
+
+        let processes = list_by_chat(&pool, &chat_id)
+            .await
+            .expect("Failed to list processes");
+
+        assert_eq!(processes.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_list_running() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let chat_id = create_test_chat(&pool).await;
+
+        // Create processes
+        let request1 = test_create_request(&chat_id);
+        let process1 = create(&pool, request1)
+            .await
+            .expect("Failed to create process 1");
+
+        let request2 = test_create_request(&chat_id);
+        let _process2 = create(&pool, request2)
+            .await
+            .expect("Failed to create process 2");
+
+        // Complete one process
+        complete(&pool, &process1.id, 0)
+            .await
+            .expect("Failed to complete process");
+
+        // List running
+        let running = list_running(&pool)
+            .await
+            .expect("Failed to list running processes");
+
+        assert_eq!(running.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_list_running_by_chat() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let chat_id = create_test_chat(&pool).await;
+
+        // Create processes
+        let request1 = test_create_request(&chat_id);
+        let process1 = create(&pool, request1)
+            .await
+            .expect("Failed to create process 1");
+
+        let request2 = test_create_request(&chat_id);
+        let process2 = create(&pool, request2)
+            .await
+            .expect("Failed to create process 2");
+
+        // Complete one process
+        complete(&pool, &process1.id, 0)
+            .await
+            .expect("Failed to complete process");
+
+        // List running
+        let running = list_running_by_chat(&pool, &chat_id)
+            .await
+            .expect("Failed to list running processes");
+
+        assert_eq!(running.len(), 1);
+        assert_eq!(running[0].id, process2.id);
+    }
+
+    #[tokio::test]
+    async fn test_update_status() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let chat_id = create_test_chat(&pool).await;
+
+        let request = test_create_request(&chat_id);
+        let process = create(&pool, request)
+            .await
+            .expect("Failed to create process");
+
+        assert_eq!(process.status, ProcessStatus::Running);
+
+        let updated = update_status(&pool, &process.id, ProcessStatus::Completed, Some(0))
+            .await
+            .expect("Failed to update status");
+
+        assert_eq!(updated.status, ProcessStatus::Completed);
+        assert_eq!(updated.exit_code, Some(0));
+        assert!(updated.completed_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_update_pid() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let chat_id = create_test_chat(&pool).await;
+
+        let request = test_create_request(&chat_id);
+        let process = create(&pool, request)
+            .await
+            .expect("Failed to create process");
+
+        assert!(process.pid.is_none());
+
+        let updated = update_pid(&pool, &process.id, 12345)
+            .await
+            .expect("Failed to update PID");
+
+        assert_eq!(updated.pid, Some(12345));
+    }
+
+    #[tokio::test]
+    async fn test_update_after_commit() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let chat_id = create_test_chat(&pool).await;
+
+        let request = CreateProcessRequest::terminal(&chat_id, "test").with_before_commit("abc123");
+
+        let process = create(&pool, request)
+            .await
+            .expect("Failed to create process");
+
+        assert!(process.after_head_commit.is_none());
+        assert_eq!(process.before_head_commit, Some("abc123".to_string()));
+
+        let updated = update_after_commit(&pool, &process.id, "def456")
+            .await
+            .expect("Failed to update after commit");
+
+        assert_eq!(updated.after_head_commit, Some("def456".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_complete_success() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let chat_id = create_test_chat(&pool).await;
+
+        let request = test_create_request(&chat_id);
+        let process = create(&pool, request)
+            .await
+            .expect("Failed to create process");
+
+        let completed = complete(&pool, &process.id, 0)
+            .await
+            .expect("Failed to complete process");
+
+        assert_eq!(completed.status, ProcessStatus::Completed);
+        assert_eq!(completed.exit_code, Some(0));
+    }
+
+    #[tokio::test]
+    async fn test_complete_failure() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let chat_id = create_test_chat(&pool).await;
+
+        let request = test_create_request(&chat_id);
+        let process = create(&pool, request)
+            .await
+            .expect("Failed to create process");
+
+        let completed = complete(&pool, &process.id, 1)
+            .await
+            .expect("Failed to complete process");
+
+        assert_eq!(completed.status, ProcessStatus::Failed);
+        assert_eq!(completed.exit_code, Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_mark_killed() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let chat_id = create_test_chat(&pool).await;
+
+        let request = test_create_request(&chat_id);
+        let process = create(&pool, request)
+            .await
+            .expect("Failed to create process");
+
+        let killed = mark_killed(&pool, &process.id)
+            .await
+            .expect("Failed to mark killed");
+
+        assert_eq!(killed.status, ProcessStatus::Killed);
+        assert!(killed.completed_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_delete_process() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let chat_id = create_test_chat(&pool).await;
+
+        let request = test_create_request(&chat_id);
+        let process = create(&pool, request)
+            .await
+            .expect("Failed to create process");
+
+        delete(&pool, &process.id)
+            .await
+            .expect("Failed to delete process");
+
+        let result = get(&pool, &process.id).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_create_status_event() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let chat_id = create_test_chat(&pool).await;
+
+        let request = test_create_request(&chat_id);
+        let process = create(&pool, request)
+            .await
+            .expect("Failed to create process");
+
+        let event = create_status_event(&process);
+
+        assert_eq!(event.process_id, process.id);
+        assert_eq!(event.status, ProcessStatus::Running);
+        assert!(event.exit_code.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_append_and_list_output() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let chat_id = create_test_chat(&pool).await;
+
+        let request = test_create_request(&chat_id);
+        let process = create(&pool, request)
+            .await
+            .expect("Failed to create process");
+
+        let first = append_output(&pool, &process.id, ContractOutputType::Stdout, "hello ")
+            .await
+            .expect("Failed to append output");
+        append_output(&pool, &process.id, ContractOutputType::Stderr, "oops")
+            .await
+            .expect("Failed to append output");
+        append_output(&pool, &process.id, ContractOutputType::Stdout, "world")
+            .await
+            .expect("Failed to append output");
+
+        let all = list_output(&pool, &process.id, None, None)
+            .await
+            .expect("Failed to list output");
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0].content, "hello ");
+        assert!(all[1].is_stderr());
+        assert!(all.windows(2).all(|w| w[0].id < w[1].id));
+
+        let after_first = list_output(&pool, &process.id, Some(first.id), Some(1))
+            .await
+            .expect("Failed to list output");
+        assert_eq!(after_first.len(), 1);
+        assert_eq!(after_first[0].content, "oops");
+    }
+
+    #[tokio::test]
+    async fn test_list_output_invalid_limit() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let chat_id = create_test_chat(&pool).await;
+
+        let request = test_create_request(&chat_id);
+        let process = create(&pool, request)
+            .await
+            .expect("Failed to create process");
+
+        let result = list_output(&pool, &process.id, None, Some(0)).await;
+
+        assert!(matches!(result, Err(ServiceError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_list_output_not_found() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+
+        let result = list_output(&pool, "non-existent-id", None, None).await;
+
+        assert!(matches!(result, Err(ServiceError::NotFound { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_output_deleted_with_process() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let chat_id = create_test_chat(&pool).await;
+
+        let request = test_create_request(&chat_id);
+        let process = create(&pool, request)
+            .await
+            .expect("Failed to create process");
+        append_output(&pool, &process.id, ContractOutputType::Stdout, "data")
+            .await
+            .expect("Failed to append output");
+
+        delete(&pool, &process.id)
+            .await
+            .expect("Failed to delete process");
+
+        let count: (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM process_outputs WHERE process_id = ?")
+                .bind(&process.id)
+                .fetch_one(&pool)
+                .await
+                .expect("Failed to count output");
+        assert_eq!(count.0, 0);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_pty_output_is_persisted() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let chat_id = create_test_chat(&pool).await;
+
+        let service = ProcessService::new();
+        let process = service
+            .start(
+                &pool,
+                test_create_request(&chat_id),
+                StartProcessRequest::pty("echo", 80, 24).with_args(["persisted-output"]),
+            )
+            .await
+            .expect("Failed to start process");
+
+        // Output is persisted asynchronously; poll until it shows up
+        let mut content = String::new();
+        for _ in 0..50 {
+            let chunks = list_output(&pool, &process.id, None, None)
+                .await
+                .expect("Failed to list output");
+            content = chunks.iter().map(|c| c.content.as_str()).collect();
+            if content.contains("persisted-output") {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        }
+
+        assert!(content.contains("persisted-output"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_snapshot_of_running_pty_process() {
+        use openflow_contracts::SetSecretRequest;
+
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let chat_id = create_test_chat(&pool).await;
+        let cipher = SecretCipher::generate();
+        secret::set(&pool, &cipher, SetSecretRequest::new("TOKEN", "tok-2342"))
+            .await
+            .expect("Failed to set secret");
+
+        let service = ProcessService::new().with_secret_cipher(cipher);
+        let process = service
+            .start(
+                &pool,
+                test_create_request(&chat_id),
+                StartProcessRequest::pty("sh", 100, 30)
+                    .with_args(["-c", "echo \"snap $TOKEN\"; sleep 30"])
+                    .with_env_var("TOKEN", "${secret:TOKEN}"),
+            )
+            .await
+            .expect("Failed to start process");
+
+        let mut snapshot = service.snapshot(&process.id).await.unwrap();
+        for _ in 0..50 {
+            if snapshot.content.contains("snap") {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+            snapshot = service.snapshot(&process.id).await.unwrap();
+        }
+        assert!(snapshot.content.contains("snap ${secret:TOKEN}"));
+        assert!(!snapshot.content.contains("tok-2342"));
+        assert_eq!((snapshot.cols, snapshot.rows), (100, 30));
+
+        service.kill(&pool, &process.id).await.unwrap();
+        assert!(matches!(
+            service.snapshot(&process.id).await,
+            Err(ServiceError::NotFound { .. })
+        ));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_snapshot_offset_deduplicates_live_output() {
+        use crate::events::CollectingBroadcaster;
+
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let chat_id = create_test_chat(&pool).await;
+
+        let broadcaster = Arc::new(CollectingBroadcaster::new());
+        let service = ProcessService::with_broadcaster(broadcaster.clone());
+        let process = service
+            .start(
+                &pool,
+                test_create_request(&chat_id),
+                StartProcessRequest::pty("sh", 80, 24)
+                    .with_args(["-c", "echo before; sleep 0.5; echo after"]),
+            )
+            .await
+            .expect("Failed to start process");
+
+        // Attach mid-run: the snapshot has the first line, events have both
+        let mut snapshot = service.snapshot(&process.id).await.unwrap();
+        for _ in 0..50 {
+            if snapshot.content.contains("before") {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            snapshot = service.snapshot(&process.id).await.unwrap();
+        }
+        assert!(!snapshot.content.contains("after"));
+        wait_for_exit(&pool, &process.id).await;
+
+        let mut offsets = Vec::new();
+        let mut terminal = snapshot.content.clone();
+        for event in broadcaster.events() {
+            if let Event::ProcessOutput {
+                content, offset, ..
+            } = event
+            {
+                let offset = offset.expect("PTY output has an offset");
+                offsets.push(offset);
+                if offset > snapshot.offset {
+                    terminal.push_str(&content);
+                }
+            }
+        }
+        assert!(offsets.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(offsets.contains(&snapshot.offset));
+        assert_eq!(terminal.matches("before").count(), 1);
+        assert_eq!(terminal.matches("after").count(), 1);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_snapshot_offset_lines_up_with_output_of_another_reader() {
+        use crate::events::CollectingBroadcaster;
+        use std::io::Read;
+
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let chat_id = create_test_chat(&pool).await;
+
+        let broadcaster = Arc::new(CollectingBroadcaster::new());
+        let service = ProcessService::with_broadcaster(broadcaster.clone());
+        let process = service
+            .start(
+                &pool,
+                test_create_request(&chat_id),
+                StartProcessRequest::pty("sh", 80, 24).with_args([
+                    "-c",
+                    "for i in $(seq 1 40); do echo line-$i; sleep 0.02; done",
+                ]),
+            )
+            .await
+            .expect("Failed to start process");
+
+        // A second reader takes part of the output away from the streamer
+        let mut other = service.pty_manager().try_clone_reader(&process.id).unwrap();
+        let other_reader = std::thread::spawn(move || {
+            let mut buffer = [0u8; 64];
+            while matches!(other.read(&mut buffer), Ok(n) if n > 0) {}
+        });
+
+        let mut snapshot = service.snapshot(&process.id).await.unwrap();
+        for _ in 0..50 {
+            if snapshot.content.contains("line-10") {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            snapshot = service.snapshot(&process.id).await.unwrap();
+        }
+        wait_for_exit(&pool, &process.id).await;
+        other_reader.join().unwrap();
+
+        // Every event is exactly the output between its start and end offset,
+        // so a client attaching with the snapshot neither drops nor repeats it
+        let snapshot_start = snapshot.offset - snapshot.content.len() as i64;
+        let mut last_offset = 0;
+        for event in broadcaster.events() {
+            if let Event::ProcessOutput {
+                content, offset, ..
+            } = event
+            {
+                let offset = offset.expect("PTY output has an offset");
+                let start = offset - content.len() as i64;
+                assert!(start >= last_offset);
+                last_offset = offset;
+                if offset <= snapshot.offset {
+                    let range =
+                        (start - snapshot_start) as usize..(offset - snapshot_start) as usize;
+                    assert_eq!(&snapshot.content[range], content);
+                } else {
+                    assert!(start >= snapshot.offset);
+                }
+            }
+        }
+        assert!(last_offset > snapshot.offset);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_pty_session_is_recorded() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let chat_id = create_test_chat(&pool).await;
+        settings::set(&pool, recording::RECORD_SESSIONS_SETTING, "true")
+            .await
+            .expect("Failed to enable recording");
+
+        let service = ProcessService::new();
+        let process = service
+            .start(
+                &pool,
+                test_create_request(&chat_id),
+                StartProcessRequest::pty("sh", 80, 24)
+                    .with_args(["-c", "sleep 0.5; echo recorded-output"]),
+            )
+            .await
+            .expect("Failed to start process");
+        service.resize(&process.id, 100, 30).unwrap();
+
+        let finished = wait_for_exit(&pool, &process.id).await;
+        assert_eq!(finished.status, ProcessStatus::Completed);
+
+        let cast = recording::export(&pool, &process.id)
+            .await
+            .expect("Failed to export recording");
+        let mut lines = cast.lines();
+        assert!(lines
+            .next()
+            .unwrap()
+            .starts_with(r#"{"version":2,"width":80,"height":24,"#));
+        let events: Vec<&str> = lines.collect();
+        let resize = events
+            .iter()
+            .position(|line| line.ends_with(r#","r","100x30"]"#))
+            .expect("resize not recorded");
+        let output = events
+            .iter()
+            .position(|line| line.contains("recorded-output"))
+            .expect("output not recorded");
+        assert!(resize < output);
+    }
+
+    /// Poll until the process record leaves the running state.
+    async fn wait_for_exit(pool: &SqlitePool, id: &str) -> ExecutionProcess {
+        for _ in 0..100 {
+            let process = get(pool, id).await.expect("Failed to get process");
+            if process.is_finished() {
+                return process;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        }
+        panic!("process {} did not exit in time", id);
+    }
+
+    /// Wait until `exits` reports the process with ID `id`.
+    async fn wait_for_exit_notice(exits: &mut broadcast::Receiver<String>, id: &str) {
+        tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            while exits.recv().await.expect("Exit channel closed") != id {}
+        })
+        .await
+        .unwrap_or_else(|_| panic!("no exit notice for process {}", id));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_pty_exit_is_recorded() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let chat_id = create_test_chat(&pool).await;
+
+        let service = ProcessService::new();
+        let process = service
+            .start(
+                &pool,
+                test_create_request(&chat_id),
+                StartProcessRequest::pty("sh", 80, 24).with_args(["-c", "exit 3"]),
+            )
+            .await
+            .expect("Failed to start process");
+
+        let finished = wait_for_exit(&pool, &process.id).await;
+
+        assert_eq!(finished.status, ProcessStatus::Failed);
+        assert_eq!(finished.exit_code, Some(3));
+        assert!(finished.completed_at.is_some());
+        assert!(!service.is_running(&process.id).await);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_pipe_exit_is_recorded() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let chat_id = create_test_chat(&pool).await;
+
+        let service = ProcessService::new();
+        let process = service
+            .start(
+                &pool,
+                test_create_request(&chat_id),
+                StartProcessRequest::new("true"),
+            )
+            .await
+            .expect("Failed to start process");
+
+        let finished = wait_for_exit(&pool, &process.id).await;
+
+        assert_eq!(finished.status, ProcessStatus::Completed);
+        assert_eq!(finished.exit_code, Some(0));
+        assert!(finished.completed_at.is_some());
+        assert_eq!(service.running_count().await, 0);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_secrets_are_substituted_and_redacted() {
+        use crate::events::CollectingBroadcaster;
+        use openflow_contracts::SetSecretRequest;
+
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let chat_id = create_test_chat(&pool).await;
+        let cipher = SecretCipher::generate();
+        secret::set(&pool, &cipher, SetSecretRequest::new("TOKEN", "tok-4815"))
+            .await
+            .expect("Failed to set secret");
+
+        let broadcaster = Arc::new(CollectingBroadcaster::new());
+        let service =
+            ProcessService::with_broadcaster(broadcaster.clone()).with_secret_cipher(cipher);
+        let process = service
+            .start(
+                &pool,
+                test_create_request(&chat_id),
+                StartProcessRequest::script("test \"$TOKEN\" = tok-4815 && echo \"got $TOKEN\"")
+                    .with_env_var("TOKEN", "${secret:TOKEN}"),
+            )
+            .await
+            .expect("Failed to start process");
+
+        let finished = wait_for_exit(&pool, &process.id).await;
+        assert_eq!(finished.status, ProcessStatus::Completed);
+
+        let output: String = list_output(&pool, &process.id, None, None)
+            .await
+            .expect("Failed to list output")
+            .iter()
+            .map(|c| c.content.as_str())
+            .collect();
+        assert_eq!(output, "got ${secret:TOKEN}\n");
+        assert!(!broadcaster
+            .events()
+            .iter()
+            .any(|event| format!("{:?}", event).contains("tok-4815")));
+
+        // Project scripts get values with shell metacharacters intact through
+        // the environment
+        let password = "it's $(exit 1) \"quoted\"";
+        let cipher = service.secret_cipher().unwrap().clone();
+        secret::set(&pool, &cipher, SetSecretRequest::new("PASSWORD", password))
+            .await
+            .expect("Failed to set secret");
+        let script = "printf '%s\\n' \"${secret:PASSWORD}\"";
+        let process = service
+            .start(
+                &pool,
+                CreateProcessRequest::verification(&chat_id, script),
+                StartProcessRequest::script(script),
+            )
+            .await
+            .expect("Failed to start script");
+
+        let finished = wait_for_exit(&pool, &process.id).await;
+        assert_eq!(finished.status, ProcessStatus::Completed);
+        let output: String = list_output(&pool, &process.id, None, None)
+            .await
+            .expect("Failed to list output")
+            .iter()
+            .map(|c| c.content.as_str())
+            .collect();
+        assert_eq!(output, "${secret:PASSWORD}\n");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_queued_run_that_cannot_start_is_failed() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let chat_id = create_test_chat(&pool).await;
+        settings::set(&pool, scheduler::MAX_CONCURRENT_AGENTS_SETTING, "1")
+            .await
+            .expect("Failed to set limit");
+        let agent_request = || CreateProcessRequest {
+            run_reason: RunReason::Codingagent,
+            ..test_create_request(&chat_id)
+        };
+
+        let service = ProcessService::new();
+        let first = service
+            .start(
+                &pool,
+                agent_request(),
+                StartProcessRequest::script("sleep 30"),
+            )
+            .await
+            .expect("Failed to start first run");
+        let queued = service
+            .start(
+                &pool,
+                agent_request(),
+                StartProcessRequest::script("exit 0"),
+            )
+            .await
+            .expect("Failed to queue second run");
+        assert_eq!(queued.status, ProcessStatus::Queued);
+
+        // Make marking the queued run started fail
+        sqlx::query(&format!(
+            "CREATE TRIGGER block_start BEFORE UPDATE OF status ON execution_processes \
+             WHEN NEW.id = '{}' AND NEW.status = 'running' \
+             BEGIN SELECT RAISE(ABORT, 'start blocked'); END",
+            queued.id
+        ))
+        .execute(&pool)
+        .await
+        .expect("Failed to create trigger");
+
+        let mut exits = service.subscribe_exits();
+        service
+            .kill(&pool, &first.id)
+            .await
+            .expect("Failed to kill first run");
+
+        // Watchers of the run hear about it although it never ran
+        wait_for_exit_notice(&mut exits, &queued.id).await;
+        let failed = wait_for_exit(&pool, &queued.id).await;
+        assert_eq!(failed.status, ProcessStatus::Failed);
+        assert!(failed
+            .status_reason
+            .unwrap()
+            .starts_with(scheduler::QUEUED_START_FAILED_REASON));
+        assert!(service.queue().await.is_empty());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_agent_runs_beyond_limit_are_queued() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let chat_id = create_test_chat(&pool).await;
+        settings::set(&pool, scheduler::MAX_CONCURRENT_AGENTS_SETTING, "1")
+            .await
+            .expect("Failed to set limit");
+        let agent_request = || CreateProcessRequest {
+            run_reason: RunReason::Codingagent,
+            ..test_create_request(&chat_id)
+        };
+
+        let service = ProcessService::new();
+        let first = service
+            .start(
+                &pool,
+                agent_request(),
+                StartProcessRequest::script("sleep 30"),
+            )
+            .await
+            .expect("Failed to start first run");
+        assert_eq!(first.status, ProcessStatus::Running);
+
+        let second = service
+            .start(
+                &pool,
+                agent_request(),
+                StartProcessRequest::script("exit 0"),
+            )
+            .await
+            .expect("Failed to queue second run");
+        let third = service
+            .start(
+                &pool,
+                agent_request(),
+                StartProcessRequest::script("exit 0"),
+            )
+            .await
+            .expect("Failed to queue third run");
+        assert_eq!(second.status, ProcessStatus::Queued);
+        assert!(second.completed_at.is_none());
+        assert_eq!(service.queue_position(&third.id).await, Some(2));
+
+        // Queued runs can be cancelled
+        let mut exits = service.subscribe_exits();
+        let cancelled = service
+            .kill(&pool, &third.id)
+            .await
+            .expect("Failed to cancel queued run");
+        assert_eq!(cancelled.status, ProcessStatus::Killed);
+        assert_eq!(
+            cancelled.status_reason.as_deref(),
+            Some(scheduler::QUEUED_CANCELLED_REASON)
+        );
+        wait_for_exit_notice(&mut exits, &third.id).await;
+        assert_eq!(service.queue().await.len(), 1);
+
+        // Terminals are not limited
+        let terminal = service
+            .start(
+                &pool,
+                test_create_request(&chat_id),
+                StartProcessRequest::script("exit 0"),
+            )
+            .await
+            .expect("Failed to start terminal");
+        assert_eq!(terminal.status, ProcessStatus::Running);
+
+        // A finished agent frees its slot for the next queued run
+        service
+            .kill(&pool, &first.id)
+            .await
+            .expect("Failed to kill first run");
+        let finished = wait_for_exit(&pool, &second.id).await;
+        assert_eq!(finished.status, ProcessStatus::Completed);
+        assert!(service.queue().await.is_empty());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_idle_timeout_kills_silent_process() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let chat_id = create_test_chat(&pool).await;
+
+        let service = ProcessService::new();
+        let process = service
+            .start(
+                &pool,
+                test_create_request(&chat_id),
+                StartProcessRequest::script("echo started; sleep 30").with_idle_timeout_secs(1),
+            )
+            .await
+            .expect("Failed to start process");
+
+        let finished = wait_for_exit(&pool, &process.id).await;
+        assert_eq!(finished.status, ProcessStatus::TimedOut);
+        assert!(finished.completed_at.is_some());
+        assert_eq!(
+            finished.status_reason,
+            Some(ProcessError::timeout("no output for 1s").to_string())
+        );
+        assert!(!service.is_running(&process.id).await);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_cpu_limit_recorded_as_failure() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let chat_id = create_test_chat(&pool).await;
+
+        let service = ProcessService::new();
+        let process = service
+            .start(
+                &pool,
+                test_create_request(&chat_id),
+                StartProcessRequest::script("while :; do :; done").with_resource_limits(
+                    openflow_contracts::ResourceLimits {
+                        cpu_secs: Some(1),
+                        ..Default::default()
+                    },
+                ),
+            )
+            .await
+            .expect("Failed to start process");
 
-        chat.id
+        let finished = wait_for_exit(&pool, &process.id).await;
+        assert_eq!(finished.status, ProcessStatus::Failed);
+        assert_eq!(
+            finished.status_reason.as_deref(),
+            Some("Exceeded CPU time limit of 1s")
+        );
+        assert!(!service.is_running(&process.id).await);
     }
 
-    /// Helper to create a test process request.
-    fn test_create_request(chat_id: &str) -> CreateProcessRequest {
-        CreateProcessRequest::terminal(chat_id, "test action")
+    #[cfg(target_os = "linux")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_cpu_limit_applies_to_pty_process() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let chat_id = create_test_chat(&pool).await;
+
+        let service = ProcessService::new();
+        let process = service
+            .start(
+                &pool,
+                test_create_request(&chat_id),
+                StartProcessRequest::script("while :; do :; done")
+                    .with_pty(80, 24)
+                    .with_resource_limits(openflow_contracts::ResourceLimits {
+                        cpu_secs: Some(1),
+                        ..Default::default()
+                    }),
+            )
+            .await
+            .expect("Failed to start process");
+
+        let finished = wait_for_exit(&pool, &process.id).await;
+        assert_eq!(finished.status, ProcessStatus::Failed);
+        assert_eq!(
+            finished.status_reason.as_deref(),
+            Some("Exceeded CPU time limit of 1s")
+        );
     }
 
-    #[tokio::test]
-    async fn test_create_process() {
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_max_runtime_kills_pty_process() {
         let pool = create_test_db().await.expect("Failed to create test db");
         let chat_id = create_test_chat(&pool).await;
 
-        let request = test_create_request(&chat_id);
-        let process = create(&pool, request)
+        let service = ProcessService::new();
+        let process = service
+            .start(
+                &pool,
+                test_create_request(&chat_id),
+                StartProcessRequest::script("while true; do echo tick; sleep 0.2; done")
+                    .with_pty(80, 24)
+                    .with_max_runtime_secs(1),
+            )
             .await
-            .expect("Failed to create process");
+            .expect("Failed to start process");
 
-        assert_eq!(process.chat_id, chat_id);
-        assert_eq!(process.status, ProcessStatus::Running);
-        assert_eq!(process.executor_action, "test action");
-        assert_eq!(process.run_reason, RunReason::Terminal);
-        assert!(process.exit_code.is_none());
-        assert!(!process.id.is_empty());
+        let finished = wait_for_exit(&pool, &process.id).await;
+        assert_eq!(finished.status, ProcessStatus::TimedOut);
+        assert_eq!(
+            finished.status_reason,
+            Some(ProcessError::timeout("exceeded max runtime of 1s").to_string())
+        );
     }
 
-    #[tokio::test]
-    async fn test_get_process() {
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_kill_escalates_and_stops_process_group() {
         let pool = create_test_db().await.expect("Failed to create test db");
         let chat_id = create_test_chat(&pool).await;
-
-        let request = test_create_request(&chat_id);
-        let created = create(&pool, request)
+        settings::set(&pool, KILL_GRACE_PERIOD_SETTING, "200")
             .await
-            .expect("Failed to create process");
+            .unwrap();
 
-        let fetched = get(&pool, &created.id)
+        // The shell and its background child ignore SIGINT
+        let service = ProcessService::new();
+        let process = service
+            .start(
+                &pool,
+                test_create_request(&chat_id),
+                StartProcessRequest::script("trap '' INT; sleep 30 & echo started; wait"),
+            )
             .await
-            .expect("Failed to get process");
+            .expect("Failed to start process");
+        let pid = process.pid.expect("pipe process has a pid") as u32;
+        tokio::time::sleep(Duration::from_millis(300)).await;
 
-        assert_eq!(fetched.id, created.id);
-        assert_eq!(fetched.chat_id, chat_id);
+        let killed = service.kill(&pool, &process.id).await.unwrap();
+        assert_eq!(killed.status, ProcessStatus::Killed);
+        assert_eq!(killed.termination_signal.as_deref(), Some("SIGTERM"));
+        assert!(!openflow_process::signal::is_group_alive(pid));
     }
 
-    #[tokio::test]
-    async fn test_get_process_not_found() {
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_kill_interrupts_pty_process() {
         let pool = create_test_db().await.expect("Failed to create test db");
+        let chat_id = create_test_chat(&pool).await;
 
-        let result = get(&pool, "non-existent-id").await;
+        let service = ProcessService::new();
+        let process = service
+            .start(
+                &pool,
+                test_create_request(&chat_id),
+                StartProcessRequest::script("sleep 30").with_pty(80, 24),
+            )
+            .await
+            .expect("Failed to start process");
+        tokio::time::sleep(Duration::from_millis(300)).await;
 
-        assert!(result.is_err());
-        match result.unwrap_err() {
-            ServiceError::NotFound { entity, id } => {
-                assert_eq!(entity, "ExecutionProcess");
-                assert_eq!(id, "non-existent-id");
-            }
-            other => panic!("Expected NotFound error, got: {:?}", other),
-        }
+        let killed = service.kill(&pool, &process.id).await.unwrap();
+        assert_eq!(killed.termination_signal.as_deref(), Some("SIGINT"));
+
+        let finished = wait_for_exit(&pool, &process.id).await;
+        assert_eq!(finished.status, ProcessStatus::Killed);
     }
 
     #[tokio::test]
-    async fn test_list_processes() {
+    async fn test_kill_grace_period_from_settings() {
         let pool = create_test_db().await.expect("Failed to create test db");
-        let chat_id = create_test_chat(&pool).await;
 
-        // Create multiple processes
-        for i in 0..3 {
-            let request = CreateProcessRequest::terminal(&chat_id, format!("action {}", i));
-            create(&pool, request)
-                .await
-                .expect("Failed to create process");
-        }
+        let grace = kill_grace_period(&pool).await.unwrap();
+        assert_eq!(grace, openflow_process::DEFAULT_GRACE_PERIOD);
 
-        let processes = list(&pool).await.expect("Failed to list processes");
-        assert!(processes.len() >= 3);
+        settings::set(&pool, KILL_GRACE_PERIOD_SETTING, "250")
+            .await
+            .unwrap();
+        let grace = kill_grace_period(&pool).await.unwrap();
+        assert_eq!(grace, Duration::from_millis(250));
+
+        settings::set(&pool, KILL_GRACE_PERIOD_SETTING, "soon")
+            .await
+            .unwrap();
+        let grace = kill_grace_period(&pool).await.unwrap();
+        assert_eq!(grace, openflow_process::DEFAULT_GRACE_PERIOD);
     }
 
-    #[tokio::test]
-    async fn test_list_by_chat() {
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_pipe_output_separates_stdout_and_stderr() {
+        use crate::events::CollectingBroadcaster;
+
         let pool = create_test_db().await.expect("Failed to create test db");
         let chat_id = create_test_chat(&pool).await;
 
-        // Create multiple processes
-        for i in 0..3 {
-            let request = CreateProcessRequest::terminal(&chat_id, format!("action {}", i));
-            create(&pool, request)
-                .await
-                .expect("Failed to create process");
-        }
-
-        let processes = list_by_chat(&pool, &chat_id)
+        let broadcaster = Arc::new(CollectingBroadcaster::new());
+        let service = ProcessService::with_broadcaster(broadcaster.clone());
+        let process = service
+            .start(
+                &pool,
+                test_create_request(&chat_id),
+                StartProcessRequest::new("sh").with_args(["-c", "echo out; echo err >&2; exit 2"]),
+            )
             .await
-            .expect("Failed to list processes");
+            .expect("Failed to start process");
 
-        assert_eq!(processes.len(), 3);
+        let finished = wait_for_exit(&pool, &process.id).await;
+        assert_eq!(finished.status, ProcessStatus::Failed);
+        assert_eq!(finished.exit_code, Some(2));
+
+        // All output is persisted before the exit is recorded
+        let chunks = list_output(&pool, &process.id, None, None)
+            .await
+            .expect("Failed to list output");
+        let stdout: String = chunks
+            .iter()
+            .filter(|c| c.is_stdout())
+            .map(|c| c.content.as_str())
+            .collect();
+        let stderr: String = chunks
+            .iter()
+            .filter(|c| c.is_stderr())
+            .map(|c| c.content.as_str())
+            .collect();
+        assert_eq!(stdout, "out\n");
+        assert_eq!(stderr, "err\n");
+
+        let events = broadcaster.events();
+        assert!(events.iter().any(|event| matches!(
+            event,
+            Event::ProcessOutput { output_type: OutputType::Stderr, content, .. } if content == "err\n"
+        )));
+        assert!(events.iter().any(|event| matches!(
+            event,
+            Event::ProcessStatus {
+                status: EventProcessStatus::Failed,
+                exit_code: Some(2),
+                ..
+            }
+        )));
     }
 
-    #[tokio::test]
-    async fn test_list_running() {
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_pipe_exit_recorded_when_pipes_held_open() {
         let pool = create_test_db().await.expect("Failed to create test db");
         let chat_id = create_test_chat(&pool).await;
 
-        // Create processes
-        let request1 = test_create_request(&chat_id);
-        let process1 = create(&pool, request1)
+        let service = ProcessService::new();
+        let process = service
+            .start(
+                &pool,
+                test_create_request(&chat_id),
+                // The background sleep inherits stdout/stderr and keeps them open
+                StartProcessRequest::new("sh").with_args(["-c", "sleep 10 & echo done"]),
+            )
             .await
-            .expect("Failed to create process 1");
+            .expect("Failed to start process");
 
-        let request2 = test_create_request(&chat_id);
-        let _process2 = create(&pool, request2)
-            .await
-            .expect("Failed to create process 2");
+        let finished = wait_for_exit(&pool, &process.id).await;
 
-        // Complete one process
-        complete(&pool, &process1.id, 0)
+        assert_eq!(finished.status, ProcessStatus::Completed);
+        let chunks = list_output(&pool, &process.id, None, None)
             .await
-            .expect("Failed to complete process");
+            .expect("Failed to list output");
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].content, "done\n");
+    }
 
-        // List running
-        let running = list_running(&pool)
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_coding_agent_output_recorded_as_messages() {
+        use crate::services::{chat, message};
+        use openflow_contracts::MessageRole;
+
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let chat_id = create_test_chat(&pool).await;
+
+        let stream = [
+            r#"{"type":"system","subtype":"init","session_id":"sess-42","model":"claude-sonnet-4"}"#,
+            r#"{"type":"assistant","message":{"id":"msg_1","content":[{"type":"text","text":"Hello"}],"usage":{"input_tokens":4,"output_tokens":2}}}"#,
+            r#"{"type":"result","subtype":"success","is_error":false,"session_id":"sess-42"}"#,
+        ]
+        .join("\n");
+
+        let service = ProcessService::new();
+        let process = service
+            .start(
+                &pool,
+                CreateProcessRequest {
+                    run_reason: RunReason::Codingagent,
+                    ..test_create_request(&chat_id)
+                },
+                StartProcessRequest::new("printf").with_args(["%s\n", &stream]),
+            )
             .await
-            .expect("Failed to list running processes");
+            .expect("Failed to start process");
 
-        assert_eq!(running.len(), 1);
+        let finished = wait_for_exit(&pool, &process.id).await;
+        assert_eq!(finished.status, ProcessStatus::Completed);
+
+        let chat = chat::get_chat(&pool, &chat_id).await.unwrap();
+        assert_eq!(chat.claude_session_id.as_deref(), Some("sess-42"));
+
+        let messages = message::list(&pool, &chat_id).await.unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].role, MessageRole::Assistant);
+        assert_eq!(messages[0].content, "Hello");
+        assert_eq!(messages[0].model.as_deref(), Some("claude-sonnet-4"));
+        assert_eq!(messages[0].tokens_used, Some(6));
+        assert!(!messages[0].is_streaming);
     }
 
     #[tokio::test]
-    async fn test_list_running_by_chat() {
+    async fn test_start_spawn_failure_marks_failed() {
         let pool = create_test_db().await.expect("Failed to create test db");
         let chat_id = create_test_chat(&pool).await;
 
-        // Create processes
-        let request1 = test_create_request(&chat_id);
-        let process1 = create(&pool, request1)
+        let service = ProcessService::new();
+        let result = service
+            .start(
+                &pool,
+                test_create_request(&chat_id),
+                StartProcessRequest::new("openflow-definitely-not-a-command"),
+            )
+            .await;
+
+        assert!(matches!(result, Err(ServiceError::Process(_))));
+        let processes = list_by_chat(&pool, &chat_id)
             .await
-            .expect("Failed to create process 1");
+            .expect("Failed to list processes");
+        assert_eq!(processes.len(), 1);
+        assert_eq!(processes[0].status, ProcessStatus::Failed);
+    }
 
-        let request2 = test_create_request(&chat_id);
-        let process2 = create(&pool, request2)
-            .await
-            .expect("Failed to create process 2");
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_exit_records_head_commits() {
+        use std::process::Command;
 
-        // Complete one process
-        complete(&pool, &process1.id, 0)
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let chat_id = create_test_chat(&pool).await;
+
+        let repo = tempfile::tempdir().expect("Failed to create temp dir");
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+                .args(args)
+                .current_dir(repo.path())
+                .status()
+                .expect("Failed to run git");
+            assert!(status.success());
+        };
+        git(&["init", "-q"]);
+        git(&["commit", "-q", "--allow-empty", "-m", "initial"]);
+
+        let service = ProcessService::new();
+        let process = service
+            .start(
+                &pool,
+                test_create_request(&chat_id),
+                StartProcessRequest::new("git")
+                    .with_args([
+                        "-c",
+                        "user.name=Test",
+                        "-c",
+                        "user.email=test@example.com",
+                        "commit",
+                        "-q",
+                        "--allow-empty",
+                        "-m",
+                        "agent change",
+                    ])
+                    .with_cwd(repo.path()),
+            )
             .await
-            .expect("Failed to complete process");
+            .expect("Failed to start process");
+        assert!(process.before_head_commit.is_some());
 
-        // List running
-        let running = list_running_by_chat(&pool, &chat_id)
+        let finished = wait_for_exit(&pool, &process.id).await;
+
+        assert_eq!(finished.status, ProcessStatus::Completed);
+        assert!(finished.after_head_commit.is_some());
+        assert!(finished.has_git_changes());
+    }
+
+    #[tokio::test]
+    async fn test_update_dev_server_url() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let chat_id = create_test_chat(&pool).await;
+        let process = create(
+            &pool,
+            CreateProcessRequest::dev_server(&chat_id, "npm run dev"),
+        )
+        .await
+        .unwrap();
+        assert!(process.dev_server_url.is_none());
+
+        let updated = update_dev_server_url(&pool, &process.id, "http://localhost:5173/")
             .await
-            .expect("Failed to list running processes");
+            .unwrap();
 
-        assert_eq!(running.len(), 1);
-        assert_eq!(running[0].id, process2.id);
+        assert_eq!(
+            updated.dev_server_url.as_deref(),
+            Some("http://localhost:5173/")
+        );
     }
 
     #[tokio::test]
-    async fn test_update_status() {
+    async fn test_update_status_reason() {
         let pool = create_test_db().await.expect("Failed to create test db");
         let chat_id = create_test_chat(&pool).await;
 
@@ -1291,145 +4552,300 @@ mod tests {
         let process = create(&pool, request)
             .await
             .expect("Failed to create process");
+        assert!(process.status_reason.is_none());
 
-        assert_eq!(process.status, ProcessStatus::Running);
-
-        let updated = update_status(&pool, &process.id, ProcessStatus::Completed, Some(0))
+        let updated = update_status_reason(&pool, &process.id, "because")
             .await
-            .expect("Failed to update status");
+            .expect("Failed to update status reason");
 
-        assert_eq!(updated.status, ProcessStatus::Completed);
-        assert_eq!(updated.exit_code, Some(0));
-        assert!(updated.completed_at.is_some());
+        assert_eq!(updated.status_reason, Some("because".to_string()));
+    }
+
+    #[test]
+    fn test_orphan_policy_parse() {
+        assert_eq!("adopt".parse::<OrphanPolicy>(), Ok(OrphanPolicy::Adopt));
+        assert_eq!("KILL".parse::<OrphanPolicy>(), Ok(OrphanPolicy::Kill));
+        assert!("ignore".parse::<OrphanPolicy>().is_err());
+        assert_eq!(OrphanPolicy::default(), OrphanPolicy::Adopt);
+        assert_eq!(OrphanPolicy::Kill.to_string(), "kill");
     }
 
     #[tokio::test]
-    async fn test_update_pid() {
+    async fn test_orphan_policy_from_settings() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+
+        let policy = OrphanPolicy::from_settings(&pool).await.unwrap();
+        assert_eq!(policy, OrphanPolicy::Adopt);
+
+        settings::set(&pool, ORPHAN_POLICY_SETTING, "kill")
+            .await
+            .unwrap();
+        let policy = OrphanPolicy::from_settings(&pool).await.unwrap();
+        assert_eq!(policy, OrphanPolicy::Kill);
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_marks_dead_orphans_failed() {
+        use crate::events::{CollectingBroadcaster, DataAction};
+
         let pool = create_test_db().await.expect("Failed to create test db");
         let chat_id = create_test_chat(&pool).await;
 
-        let request = test_create_request(&chat_id);
-        let process = create(&pool, request)
+        let orphan = create(&pool, test_create_request(&chat_id))
             .await
             .expect("Failed to create process");
 
-        assert!(process.pid.is_none());
-
-        let updated = update_pid(&pool, &process.id, 12345)
+        let broadcaster = Arc::new(CollectingBroadcaster::new());
+        let service = ProcessService::with_broadcaster(broadcaster.clone());
+        let report = service
+            .reconcile_orphans(&pool, OrphanPolicy::Adopt)
             .await
-            .expect("Failed to update PID");
+            .expect("Failed to reconcile");
 
-        assert_eq!(updated.pid, Some(12345));
+        assert_eq!(report.failed, vec![orphan.id.clone()]);
+        assert_eq!(report.total(), 1);
+
+        let reconciled = get(&pool, &orphan.id).await.unwrap();
+        assert_eq!(reconciled.status, ProcessStatus::Failed);
+        assert_eq!(
+            reconciled.status_reason,
+            Some(ORPHAN_DEAD_REASON.to_string())
+        );
+        assert!(reconciled.completed_at.is_some());
+
+        assert!(broadcaster.events().iter().any(|event| matches!(
+            event,
+            Event::DataChanged {
+                entity: EntityType::Process,
+                action: DataAction::Updated,
+                id,
+                ..
+            } if *id == orphan.id
+        )));
     }
 
     #[tokio::test]
-    async fn test_update_after_commit() {
+    async fn test_reconcile_cancels_stale_queued_runs() {
         let pool = create_test_db().await.expect("Failed to create test db");
         let chat_id = create_test_chat(&pool).await;
 
-        let request = CreateProcessRequest::terminal(&chat_id, "test").with_before_commit("abc123");
-
-        let process = create(&pool, request)
+        let stale = create_queued(&pool, test_create_request(&chat_id))
             .await
-            .expect("Failed to create process");
-
-        assert!(process.after_head_commit.is_none());
-        assert_eq!(process.before_head_commit, Some("abc123".to_string()));
+            .expect("Failed to create queued process");
+        assert_eq!(stale.status, ProcessStatus::Queued);
 
-        let updated = update_after_commit(&pool, &process.id, "def456")
+        let service = ProcessService::new();
+        let report = service
+            .reconcile_orphans(&pool, OrphanPolicy::Adopt)
             .await
-            .expect("Failed to update after commit");
-
-        assert_eq!(updated.after_head_commit, Some("def456".to_string()));
+            .expect("Failed to reconcile");
+        assert_eq!(report.cancelled, vec![stale.id.clone()]);
+
+        let reconciled = get(&pool, &stale.id).await.unwrap();
+        assert_eq!(reconciled.status, ProcessStatus::Killed);
+        assert_eq!(
+            reconciled.status_reason.as_deref(),
+            Some(scheduler::QUEUED_ORPHAN_REASON)
+        );
     }
 
+    #[cfg(unix)]
     #[tokio::test]
-    async fn test_complete_success() {
+    async fn test_reconcile_kills_live_orphans() {
         let pool = create_test_db().await.expect("Failed to create test db");
         let chat_id = create_test_chat(&pool).await;
 
-        let request = test_create_request(&chat_id);
-        let process = create(&pool, request)
+        let mut child = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .expect("Failed to spawn sleep");
+        let orphan = create(&pool, test_create_request(&chat_id))
             .await
             .expect("Failed to create process");
+        update_pid(&pool, &orphan.id, child.id() as i32)
+            .await
+            .expect("Failed to update pid");
 
-        let completed = complete(&pool, &process.id, 0)
+        let service = ProcessService::new();
+        let report = service
+            .reconcile_orphans(&pool, OrphanPolicy::Kill)
             .await
-            .expect("Failed to complete process");
+            .expect("Failed to reconcile");
 
-        assert_eq!(completed.status, ProcessStatus::Completed);
-        assert_eq!(completed.exit_code, Some(0));
+        assert_eq!(report.killed, vec![orphan.id.clone()]);
+        let exit = child.wait().expect("Failed to wait on child");
+        assert!(!exit.success());
+
+        let reconciled = get(&pool, &orphan.id).await.unwrap();
+        assert_eq!(reconciled.status, ProcessStatus::Killed);
+        assert_eq!(
+            reconciled.status_reason,
+            Some(ORPHAN_KILLED_REASON.to_string())
+        );
     }
 
+    #[cfg(unix)]
     #[tokio::test]
-    async fn test_complete_failure() {
+    async fn test_reconcile_ignores_reused_pids() {
         let pool = create_test_db().await.expect("Failed to create test db");
         let chat_id = create_test_chat(&pool).await;
 
-        let request = test_create_request(&chat_id);
-        let process = create(&pool, request)
+        let mut child = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .expect("Failed to spawn sleep");
+        let orphan = create(&pool, test_create_request(&chat_id))
             .await
             .expect("Failed to create process");
+        update_pid(&pool, &orphan.id, child.id() as i32)
+            .await
+            .expect("Failed to update pid");
+        // Simulate the PID having been reused by an unrelated process
+        sqlx::query("UPDATE execution_processes SET pid_start_time = 'stale' WHERE id = ?")
+            .bind(&orphan.id)
+            .execute(&pool)
+            .await
+            .expect("Failed to overwrite start time");
 
-        let completed = complete(&pool, &process.id, 1)
+        let service = ProcessService::new();
+        let report = service
+            .reconcile_orphans(&pool, OrphanPolicy::Kill)
             .await
-            .expect("Failed to complete process");
+            .expect("Failed to reconcile");
+
+        assert_eq!(report.failed, vec![orphan.id.clone()]);
+        assert!(report.killed.is_empty());
+        assert!(child.try_wait().expect("Failed to poll child").is_none());
+        assert_eq!(
+            get(&pool, &orphan.id).await.unwrap().status_reason,
+            Some(ORPHAN_DEAD_REASON.to_string())
+        );
 
-        assert_eq!(completed.status, ProcessStatus::Failed);
-        assert_eq!(completed.exit_code, Some(1));
+        child.kill().expect("Failed to kill child");
+        child.wait().expect("Failed to wait on child");
     }
 
+    #[cfg(unix)]
     #[tokio::test]
-    async fn test_mark_killed() {
+    async fn test_kill_does_not_signal_reused_pid() {
         let pool = create_test_db().await.expect("Failed to create test db");
         let chat_id = create_test_chat(&pool).await;
 
-        let request = test_create_request(&chat_id);
-        let process = create(&pool, request)
+        let mut child = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .expect("Failed to spawn sleep");
+        let process = create(&pool, test_create_request(&chat_id))
             .await
             .expect("Failed to create process");
+        update_pid(&pool, &process.id, child.id() as i32)
+            .await
+            .expect("Failed to update pid");
+        // Simulate the PID having been reused by an unrelated process
+        sqlx::query("UPDATE execution_processes SET pid_start_time = 'stale' WHERE id = ?")
+            .bind(&process.id)
+            .execute(&pool)
+            .await
+            .expect("Failed to overwrite start time");
 
-        let killed = mark_killed(&pool, &process.id)
+        let killed = ProcessService::new()
+            .kill(&pool, &process.id)
             .await
-            .expect("Failed to mark killed");
+            .expect("Failed to kill process");
 
         assert_eq!(killed.status, ProcessStatus::Killed);
-        assert!(killed.completed_at.is_some());
+        assert!(killed.termination_signal.is_none());
+        assert!(child.try_wait().expect("Failed to poll child").is_none());
+
+        child.kill().expect("Failed to kill child");
+        child.wait().expect("Failed to wait on child");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_pid_start_time_identifies_process() {
+        let pid = std::process::id() as i32;
+        let start_time = pid_start_time(pid).expect("Failed to read start time");
+
+        assert_eq!(pid_start_time(pid), Some(start_time.clone()));
+        assert!(is_same_process(pid, Some(&start_time)));
+        assert!(!is_same_process(pid, Some("stale")));
+        assert!(!is_same_process(pid, None));
     }
 
+    #[cfg(unix)]
     #[tokio::test]
-    async fn test_delete_process() {
+    async fn test_reconcile_adopts_live_orphans() {
         let pool = create_test_db().await.expect("Failed to create test db");
         let chat_id = create_test_chat(&pool).await;
 
-        let request = test_create_request(&chat_id);
-        let process = create(&pool, request)
+        let mut child = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .expect("Failed to spawn sleep");
+        let orphan = create(&pool, test_create_request(&chat_id))
             .await
             .expect("Failed to create process");
+        update_pid(&pool, &orphan.id, child.id() as i32)
+            .await
+            .expect("Failed to update pid");
 
-        delete(&pool, &process.id)
+        let service = ProcessService::new();
+        let report = service
+            .reconcile_orphans(&pool, OrphanPolicy::Adopt)
             .await
-            .expect("Failed to delete process");
+            .expect("Failed to reconcile");
 
-        let result = get(&pool, &process.id).await;
-        assert!(result.is_err());
+        assert_eq!(report.adopted, vec![orphan.id.clone()]);
+        assert!(service.is_running(&orphan.id).await);
+        assert_eq!(
+            get(&pool, &orphan.id).await.unwrap().status,
+            ProcessStatus::Running
+        );
+
+        // Once the adopted process exits, it is recorded without an exit code
+        child.kill().expect("Failed to kill child");
+        child.wait().expect("Failed to wait on child");
+
+        let finished = wait_for_exit(&pool, &orphan.id).await;
+        assert_eq!(finished.status, ProcessStatus::Completed);
+        assert!(finished.exit_code.is_none());
+        assert_eq!(
+            finished.status_reason,
+            Some(ADOPTED_EXIT_REASON.to_string())
+        );
+        assert!(!service.is_running(&orphan.id).await);
     }
 
-    #[tokio::test]
-    async fn test_create_status_event() {
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_reconcile_skips_owned_processes() {
         let pool = create_test_db().await.expect("Failed to create test db");
         let chat_id = create_test_chat(&pool).await;
 
-        let request = test_create_request(&chat_id);
-        let process = create(&pool, request)
+        let service = ProcessService::new();
+        let process = service
+            .start(
+                &pool,
+                test_create_request(&chat_id),
+                StartProcessRequest::pty("sleep", 80, 24).with_args(["30"]),
+            )
             .await
-            .expect("Failed to create process");
+            .expect("Failed to start process");
+        assert!(process.pid.is_some());
 
-        let event = create_status_event(&process);
+        let report = service
+            .reconcile_orphans(&pool, OrphanPolicy::Kill)
+            .await
+            .expect("Failed to reconcile");
 
-        assert_eq!(event.process_id, process.id);
-        assert_eq!(event.status, ProcessStatus::Running);
-        assert!(event.exit_code.is_none());
+        assert_eq!(report.total(), 0);
+        assert_eq!(
+            get(&pool, &process.id).await.unwrap().status,
+            ProcessStatus::Running
+        );
+
+        service.kill(&pool, &process.id).await.unwrap();
     }
 
     #[tokio::test]
@@ -44,6 +44,7 @@ pub async fn list(pool: &SqlitePool) -> ServiceResult<Vec<Project>> {
             always_included_rules,
             workflows_folder,
             verification_config,
+            script_limits,
             archived_at,
             created_at,
             updated_at
@@ -89,6 +90,7 @@ pub async fn list_archived(pool: &SqlitePool) -> ServiceResult<Vec<Project>> {
             always_included_rules,
             workflows_folder,
             verification_config,
+            script_limits,
             archived_at,
             created_at,
             updated_at
@@ -134,6 +136,7 @@ pub async fn get(pool: &SqlitePool, id: &str) -> ServiceResult<Project> {
             always_included_rules,
             workflows_folder,
             verification_config,
+            script_limits,
             archived_at,
             created_at,
             updated_at
@@ -190,9 +193,9 @@ pub async fn create(pool: &SqlitePool, request: CreateProjectRequest) -> Service
         INSERT INTO projects (
             id, name, git_repo_path, base_branch, setup_script, dev_script,
             cleanup_script, copy_files, icon, rule_folders, always_included_rules,
-            workflows_folder, verification_config
+            workflows_folder, verification_config, script_limits
         )
-        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
         "#,
     )
     .bind(&id)
@@ -208,6 +211,7 @@ pub async fn create(pool: &SqlitePool, request: CreateProjectRequest) -> Service
     .bind(&request.always_included_rules)
     .bind(&workflows_folder)
     .bind(&request.verification_config)
+    .bind(&request.script_limits)
     .execute(pool)
     .await
     .map_err(|e| {
@@ -275,6 +279,9 @@ pub async fn update(
     if request.verification_config.is_some() {
         updated_fields.push("verification_config");
     }
+    if request.script_limits.is_some() {
+        updated_fields.push("script_limits");
+    }
 
     debug!(
         "Updating {} fields for project {}: {:?}",
@@ -300,6 +307,7 @@ pub async fn update(
         .workflows_folder
         .unwrap_or(existing.workflows_folder);
     let verification_config = request.verification_config.or(existing.verification_config);
+    let script_limits = request.script_limits.or(existing.script_limits);
 
     sqlx::query(
         r#"
@@ -317,6 +325,7 @@ pub async fn update(
             always_included_rules = ?,
             workflows_folder = ?,
             verification_config = ?,
+            script_limits = ?,
             updated_at = datetime('now', 'subsec')
         WHERE id = ?
         "#,
@@ -333,6 +342,7 @@ pub async fn update(
     .bind(&always_included_rules)
     .bind(&workflows_folder)
     .bind(&verification_config)
+    .bind(&script_limits)
     .bind(id)
     .execute(pool)
     .await
@@ -505,6 +515,7 @@ mod tests {
             always_included_rules: None,
             workflows_folder: None,
             verification_config: None,
+            script_limits: None,
         }
     }
 
@@ -542,6 +553,7 @@ mod tests {
             always_included_rules: Some(r#"["rule1.md"]"#.to_string()),
             workflows_folder: Some("workflows".to_string()),
             verification_config: Some(r#"{"test": "npm test"}"#.to_string()),
+            script_limits: Some(r#"{"setup": {"cpuSecs": 600}}"#.to_string()),
         };
 
         let project = create(&test_db.pool, request)
@@ -555,6 +567,10 @@ mod tests {
         assert_eq!(project.cleanup_script, Some("npm run clean".to_string()));
         assert_eq!(project.icon, "rocket");
         assert_eq!(project.workflows_folder, "workflows");
+        assert_eq!(
+            project.get_script_limits().setup.unwrap().cpu_secs,
+            Some(600)
+        );
     }
 
     #[tokio::test]
@@ -646,6 +662,7 @@ mod tests {
             always_included_rules: None,
             workflows_folder: None,
             verification_config: None,
+            script_limits: None,
         };
 
         let updated = update(&test_db.pool, &created.id, update_request)
@@ -677,6 +694,7 @@ mod tests {
             always_included_rules: None,
             workflows_folder: None,
             verification_config: None,
+            script_limits: None,
         };
 
         let result = update(&test_db.pool, "non-existent-id", update_request).await;
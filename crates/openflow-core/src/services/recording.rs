@@ -0,0 +1,269 @@
+//! PTY session recordings.
+//!
+//! When the `processes.record_pty_sessions` setting is enabled, every PTY
+//! process is recorded in asciicast v2 format alongside its process record:
+//!
+//! - `process_recordings` holds the header (initial size, start time)
+//! - `process_recording_events` holds `o` events for (redacted) output and
+//!   `r` events for terminal resizes, timed relative to the start
+//!
+//! `ProcessService` starts the recording when it spawns the PTY and feeds it
+//! from the output stream and `PtyManager::subscribe_resizes`. `export`
+//! renders a recording as a `.cast` file that standard players can replay.
+
+use chrono::{DateTime, Utc};
+use log::{debug, error, warn};
+use sqlx::SqlitePool;
+
+use openflow_process::{write_cast, AsciicastEvent, AsciicastEventType, AsciicastHeader, PtySize};
+
+use super::{process, settings, ServiceError, ServiceResult};
+
+/// Setting key that enables recording of PTY processes (`true`/`false`).
+pub const RECORD_SESSIONS_SETTING: &str = "processes.record_pty_sessions";
+
+/// Whether PTY processes should be recorded. Defaults to `false`.
+pub async fn is_enabled(pool: &SqlitePool) -> ServiceResult<bool> {
+    let Some(value) = settings::get_value(pool, RECORD_SESSIONS_SETTING).await? else {
+        return Ok(false);
+    };
+    match value.trim() {
+        "true" | "1" => Ok(true),
+        "false" | "0" | "" => Ok(false),
+        other => {
+            warn!(
+                "is_enabled: invalid value {:?} for {}, not recording",
+                other, RECORD_SESSIONS_SETTING
+            );
+            Ok(false)
+        }
+    }
+}
+
+/// Start a recording of a process's terminal with its initial `size`.
+///
+/// Returns the start time, which event times are relative to.
+pub async fn start(
+    pool: &SqlitePool,
+    process_id: &str,
+    size: PtySize,
+) -> ServiceResult<DateTime<Utc>> {
+    let started_at = Utc::now();
+
+    sqlx::query(
+        r#"
+        INSERT INTO process_recordings (process_id, width, height, started_at)
+        VALUES (?, ?, ?, ?)
+        "#,
+    )
+    .bind(process_id)
+    .bind(i64::from(size.cols))
+    .bind(i64::from(size.rows))
+    .bind(started_at.to_rfc3339())
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        error!(
+            "start: database error starting recording for process id={}: {}",
+            process_id, e
+        );
+        e
+    })?;
+
+    debug!(
+        "start: recording process id={} size={}x{}",
+        process_id, size.cols, size.rows
+    );
+    Ok(started_at)
+}
+
+/// Append an event to a process's recording.
+pub async fn append_event(
+    pool: &SqlitePool,
+    process_id: &str,
+    event: &AsciicastEvent,
+) -> ServiceResult<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO process_recording_events (process_id, time, event_type, data)
+        VALUES (?, ?, ?, ?)
+        "#,
+    )
+    .bind(process_id)
+    .bind(event.time)
+    .bind(event.event_type.as_str())
+    .bind(&event.data)
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        error!(
+            "append_event: database error recording event for process id={}: {}",
+            process_id, e
+        );
+        e
+    })?;
+
+    Ok(())
+}
+
+/// Render a process's recording as an asciicast v2 file.
+///
+/// The process's executor action is used as the title.
+///
+/// # Errors
+///
+/// Returns `ServiceError::NotFound` if the process was not recorded.
+pub async fn export(pool: &SqlitePool, process_id: &str) -> ServiceResult<String> {
+    debug!("export: exporting recording of process id={}", process_id);
+
+    let (width, height, started_at) = sqlx::query_as::<_, (i64, i64, String)>(
+        r#"
+        SELECT width, height, started_at
+        FROM process_recordings
+        WHERE process_id = ?
+        "#,
+    )
+    .bind(process_id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| ServiceError::NotFound {
+        entity: "ProcessRecording",
+        id: process_id.to_string(),
+    })?;
+
+    let process = process::get(pool, process_id).await?;
+    let size = PtySize {
+        cols: u16::try_from(width).unwrap_or(80),
+        rows: u16::try_from(height).unwrap_or(24),
+    };
+    let started_at = DateTime::parse_from_rfc3339(&started_at)
+        .map(|at| at.with_timezone(&Utc))
+        .unwrap_or_else(|_| Utc::now());
+    let header = AsciicastHeader::new(size, started_at).with_title(process.executor_action);
+
+    let rows = sqlx::query_as::<_, (f64, String, String)>(
+        r#"
+        SELECT time, event_type, data
+        FROM process_recording_events
+        WHERE process_id = ?
+        ORDER BY time ASC, id ASC
+        "#,
+    )
+    .bind(process_id)
+    .fetch_all(pool)
+    .await?;
+
+    let events: Vec<AsciicastEvent> = rows
+        .into_iter()
+        .filter_map(|(time, event_type, data)| {
+            Some(AsciicastEvent {
+                time,
+                event_type: AsciicastEventType::parse(&event_type)?,
+                data,
+            })
+        })
+        .collect();
+
+    debug!(
+        "export: exported recording of process id={} events={}",
+        process_id,
+        events.len()
+    );
+    Ok(write_cast(&header, &events))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openflow_contracts::{CreateChatRequest, CreateProcessRequest, CreateProjectRequest};
+    use openflow_db::create_test_db;
+    use openflow_process::{OutputChunk, PtyResize};
+
+    use crate::services::{chat, project};
+
+    async fn create_test_process(pool: &SqlitePool) -> String {
+        let project = project::create(
+            pool,
+            CreateProjectRequest {
+                name: "Recording Project".to_string(),
+                git_repo_path: format!("/tmp/test-repo-{}", uuid::Uuid::new_v4()),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Failed to create project");
+        let chat = chat::create(pool, CreateChatRequest::new(&project.id))
+            .await
+            .expect("Failed to create chat");
+        process::create(
+            pool,
+            CreateProcessRequest::terminal(&chat.id, "Recorded terminal"),
+        )
+        .await
+        .expect("Failed to create process")
+        .id
+    }
+
+    #[tokio::test]
+    async fn test_is_enabled_defaults_to_false() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        assert!(!is_enabled(&pool).await.unwrap());
+
+        settings::set(&pool, RECORD_SESSIONS_SETTING, "true")
+            .await
+            .unwrap();
+        assert!(is_enabled(&pool).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_export_orders_events_by_time() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let process_id = create_test_process(&pool).await;
+
+        let started_at = start(&pool, &process_id, PtySize { cols: 80, rows: 24 })
+            .await
+            .unwrap();
+        let mut chunk = OutputChunk::stdout(&process_id, "$ ls\r\n");
+        chunk.timestamp = (started_at + chrono::Duration::seconds(2)).to_rfc3339();
+        append_event(
+            &pool,
+            &process_id,
+            &AsciicastEvent::output(started_at, &chunk),
+        )
+        .await
+        .unwrap();
+        let resize = PtyResize {
+            size: PtySize {
+                cols: 120,
+                rows: 40,
+            },
+            timestamp: (started_at + chrono::Duration::seconds(1)).to_rfc3339(),
+        };
+        append_event(
+            &pool,
+            &process_id,
+            &AsciicastEvent::resize(started_at, &resize),
+        )
+        .await
+        .unwrap();
+
+        let cast = export(&pool, &process_id).await.unwrap();
+        let lines: Vec<&str> = cast.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with(r#"{"version":2,"width":80,"height":24,"#));
+        assert!(lines[0].contains(r#""title":"Recorded terminal""#));
+        assert_eq!(lines[1], r#"[1.0,"r","120x40"]"#);
+        assert_eq!(lines[2], r#"[2.0,"o","$ ls\r\n"]"#);
+    }
+
+    #[tokio::test]
+    async fn test_export_without_recording() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let process_id = create_test_process(&pool).await;
+
+        assert!(matches!(
+            export(&pool, &process_id).await,
+            Err(ServiceError::NotFound { .. })
+        ));
+    }
+}
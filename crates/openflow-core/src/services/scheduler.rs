@@ -0,0 +1,357 @@
+//! Run Queue Scheduler
+//!
+//! Limits how many coding agent runs execute at once. Starting a run that
+//! would exceed the global or per-project limit parks it in a FIFO
+//! `RunQueue` with status `queued`; `ProcessService` starts queued runs as
+//! running agents finish.
+//!
+//! Limits are read from settings on every admission decision, so changes
+//! take effect without a restart:
+//! - `processes.max_concurrent_agents`: running agents across all projects
+//! - `processes.max_concurrent_agents_per_project`: running agents per project
+//!
+//! A missing, empty or zero value means unlimited. Only coding agent runs
+//! are limited; scripts, dev servers and terminals always start immediately.
+//!
+//! # Logging
+//!
+//! - `debug`: Admission decisions and queue changes
+//! - `warn`: Invalid limit settings
+//! - `error`: Database failures
+//!
+//! # Error Handling
+//!
+//! All public functions return `ServiceResult<T>`:
+//! - `ServiceError::Database` for SQLx errors
+
+use log::{debug, error, warn};
+use sqlx::SqlitePool;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+
+use openflow_contracts::{QueuedProcess, StartProcessRequest};
+
+use super::secret::SecretRedactor;
+use super::{settings, ServiceResult};
+
+/// Setting key for the maximum number of running agents across all projects.
+pub const MAX_CONCURRENT_AGENTS_SETTING: &str = "processes.max_concurrent_agents";
+
+/// Setting key for the maximum number of running agents per project.
+pub const MAX_CONCURRENT_AGENTS_PER_PROJECT_SETTING: &str =
+    "processes.max_concurrent_agents_per_project";
+
+/// Status reason recorded for runs cancelled before they started.
+pub const QUEUED_CANCELLED_REASON: &str = "Cancelled while queued";
+
+/// Status reason prefix recorded for runs taken from the queue that could
+/// not be marked started.
+pub const QUEUED_START_FAILED_REASON: &str = "Run could not be started from the queue";
+
+/// Status reason recorded for runs still queued when the app stopped.
+pub const QUEUED_ORPHAN_REASON: &str = "Run was still queued when the app stopped";
+
+// =============================================================================
+// Limits
+// =============================================================================
+
+/// Concurrency limits for coding agent runs. `None` means unlimited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RunLimits {
+    /// Maximum running agents across all projects.
+    pub global: Option<usize>,
+    /// Maximum running agents within one project.
+    pub per_project: Option<usize>,
+}
+
+impl RunLimits {
+    /// Load the limits from settings.
+    ///
+    /// Missing, empty and zero values are treated as unlimited; values that
+    /// are not a number are logged and ignored.
+    pub async fn from_settings(pool: &SqlitePool) -> ServiceResult<Self> {
+        Ok(Self {
+            global: limit_setting(pool, MAX_CONCURRENT_AGENTS_SETTING).await?,
+            per_project: limit_setting(pool, MAX_CONCURRENT_AGENTS_PER_PROJECT_SETTING).await?,
+        })
+    }
+
+    /// Whether any limit is configured.
+    pub fn is_limited(&self) -> bool {
+        self.global.is_some() || self.per_project.is_some()
+    }
+
+    /// Whether another agent may start in `project_id` given what is running.
+    pub fn allows(&self, running: &RunningAgents, project_id: &str) -> bool {
+        let global_ok = self.global.is_none_or(|max| running.total < max);
+        let project_ok = self
+            .per_project
+            .is_none_or(|max| running.in_project(project_id) < max);
+        global_ok && project_ok
+    }
+}
+
+/// Read a single limit setting.
+async fn limit_setting(pool: &SqlitePool, key: &str) -> ServiceResult<Option<usize>> {
+    let Some(value) = settings::get_value(pool, key).await? else {
+        return Ok(None);
+    };
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    match value.parse::<usize>() {
+        Ok(0) => Ok(None),
+        Ok(max) => Ok(Some(max)),
+        Err(e) => {
+            warn!(
+                "limit_setting: invalid value {:?} for {}: {}, treating as unlimited",
+                value, key, e
+            );
+            Ok(None)
+        }
+    }
+}
+
+// =============================================================================
+// Running Agents
+// =============================================================================
+
+/// Number of coding agent runs currently running, in total and per project.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RunningAgents {
+    /// Running agents across all projects.
+    pub total: usize,
+    /// Running agents keyed by project ID.
+    pub by_project: HashMap<String, usize>,
+}
+
+impl RunningAgents {
+    /// Running agents in the given project.
+    pub fn in_project(&self, project_id: &str) -> usize {
+        self.by_project.get(project_id).copied().unwrap_or(0)
+    }
+
+    /// Count running coding agent processes from the database.
+    pub async fn count(pool: &SqlitePool) -> ServiceResult<Self> {
+        let rows: Vec<(String, i64)> = sqlx::query_as(
+            r#"
+            SELECT c.project_id, COUNT(*)
+            FROM execution_processes p
+            JOIN chats c ON c.id = p.chat_id
+            WHERE p.status = 'running' AND p.run_reason = 'codingagent'
+            GROUP BY c.project_id
+            "#,
+        )
+        .fetch_all(pool)
+        .await
+        .map_err(|e| {
+            error!("RunningAgents::count: database error: {}", e);
+            e
+        })?;
+
+        let by_project: HashMap<String, usize> = rows
+            .into_iter()
+            .map(|(project_id, count)| (project_id, count as usize))
+            .collect();
+        Ok(Self {
+            total: by_project.values().sum(),
+            by_project,
+        })
+    }
+}
+
+// =============================================================================
+// Run Queue
+// =============================================================================
+
+/// A run waiting for a free slot, with everything needed to spawn it.
+#[derive(Clone)]
+pub struct QueuedRun {
+    /// Process record of the run (status `queued`).
+    pub process_id: String,
+    /// Chat the run belongs to.
+    pub chat_id: String,
+    /// Project the run counts against.
+    pub project_id: String,
+    /// Spawn parameters, with secrets already resolved.
+    pub start_request: StartProcessRequest,
+    /// Masks resolved secret values in the run's output.
+    pub redactor: Arc<SecretRedactor>,
+}
+
+// The start request holds resolved secret values, so it is left out.
+impl std::fmt::Debug for QueuedRun {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QueuedRun")
+            .field("process_id", &self.process_id)
+            .field("chat_id", &self.chat_id)
+            .field("project_id", &self.project_id)
+            .finish_non_exhaustive()
+    }
+}
+
+/// FIFO queue of runs waiting for a free slot.
+#[derive(Debug, Default)]
+pub struct RunQueue {
+    runs: VecDeque<QueuedRun>,
+}
+
+impl RunQueue {
+    /// Create an empty queue.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of queued runs.
+    pub fn len(&self) -> usize {
+        self.runs.len()
+    }
+
+    /// Whether the queue is empty.
+    pub fn is_empty(&self) -> bool {
+        self.runs.is_empty()
+    }
+
+    /// Add a run to the back of the queue.
+    pub fn push(&mut self, run: QueuedRun) {
+        debug!(
+            "RunQueue::push: queued process id={} project_id={} (queued={})",
+            run.process_id,
+            run.project_id,
+            self.runs.len() + 1
+        );
+        self.runs.push_back(run);
+    }
+
+    /// Remove a run from the queue, returning it if it was queued.
+    pub fn remove(&mut self, process_id: &str) -> Option<QueuedRun> {
+        let index = self
+            .runs
+            .iter()
+            .position(|run| run.process_id == process_id)?;
+        self.runs.remove(index)
+    }
+
+    /// Position of a run in the queue, starting at 1.
+    pub fn position(&self, process_id: &str) -> Option<usize> {
+        self.runs
+            .iter()
+            .position(|run| run.process_id == process_id)
+            .map(|index| index + 1)
+    }
+
+    /// The queued runs in order, with their positions.
+    pub fn entries(&self) -> Vec<QueuedProcess> {
+        self.runs
+            .iter()
+            .enumerate()
+            .map(|(index, run)| QueuedProcess {
+                process_id: run.process_id.clone(),
+                chat_id: run.chat_id.clone(),
+                project_id: run.project_id.clone(),
+                position: index as u32 + 1,
+            })
+            .collect()
+    }
+
+    /// Take the first run that `limits` allow to start.
+    ///
+    /// Runs whose project is at its limit are skipped, so a busy project
+    /// does not hold up the others.
+    pub fn take_next(&mut self, limits: &RunLimits, running: &RunningAgents) -> Option<QueuedRun> {
+        let index = self
+            .runs
+            .iter()
+            .position(|run| limits.allows(running, &run.project_id))?;
+        self.runs.remove(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openflow_db::create_test_db;
+    use std::collections::BTreeMap;
+
+    fn queued(process_id: &str, project_id: &str) -> QueuedRun {
+        QueuedRun {
+            process_id: process_id.to_string(),
+            chat_id: format!("chat-{}", process_id),
+            project_id: project_id.to_string(),
+            start_request: StartProcessRequest::default(),
+            redactor: Arc::new(SecretRedactor::new(BTreeMap::new())),
+        }
+    }
+
+    fn running(counts: &[(&str, usize)]) -> RunningAgents {
+        let by_project: HashMap<String, usize> = counts
+            .iter()
+            .map(|(project, count)| (project.to_string(), *count))
+            .collect();
+        RunningAgents {
+            total: by_project.values().sum(),
+            by_project,
+        }
+    }
+
+    #[test]
+    fn test_take_next_respects_limits() {
+        let mut queue = RunQueue::new();
+        queue.push(queued("a1", "a"));
+        queue.push(queued("a2", "a"));
+        queue.push(queued("b1", "b"));
+        assert_eq!(queue.position("b1"), Some(3));
+
+        let limits = RunLimits {
+            global: Some(3),
+            per_project: Some(1),
+        };
+
+        // Project a is full, so b1 jumps ahead
+        let next = queue.take_next(&limits, &running(&[("a", 1)])).unwrap();
+        assert_eq!(next.process_id, "b1");
+
+        // Global limit reached
+        assert!(queue
+            .take_next(&limits, &running(&[("a", 1), ("b", 1), ("c", 1)]))
+            .is_none());
+
+        let next = queue.take_next(&limits, &running(&[])).unwrap();
+        assert_eq!(next.process_id, "a1");
+        assert_eq!(queue.position("a2"), Some(1));
+    }
+
+    #[test]
+    fn test_remove_and_entries() {
+        let mut queue = RunQueue::new();
+        queue.push(queued("a1", "a"));
+        queue.push(queued("b1", "b"));
+
+        assert!(queue.remove("a1").is_some());
+        assert!(queue.remove("a1").is_none());
+
+        let entries = queue.entries();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].process_id, "b1");
+        assert_eq!(entries[0].position, 1);
+    }
+
+    #[tokio::test]
+    async fn test_limits_from_settings() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+
+        let limits = RunLimits::from_settings(&pool).await.unwrap();
+        assert!(!limits.is_limited());
+
+        settings::set(&pool, MAX_CONCURRENT_AGENTS_SETTING, "4")
+            .await
+            .unwrap();
+        settings::set(&pool, MAX_CONCURRENT_AGENTS_PER_PROJECT_SETTING, "0")
+            .await
+            .unwrap();
+        let limits = RunLimits::from_settings(&pool).await.unwrap();
+        assert_eq!(limits.global, Some(4));
+        assert_eq!(limits.per_project, None);
+    }
+}
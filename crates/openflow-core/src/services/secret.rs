@@ -0,0 +1,708 @@
+//! Secrets service.
+//!
+//! Stores API keys, tokens and other sensitive values encrypted in the
+//! `secrets` table. Values are encrypted with ChaCha20-Poly1305 under a key
+//! kept in a file in the app data directory (see [`SecretCipher`]), so a copy
+//! of the database alone does not reveal them.
+//!
+//! Secrets are write-only: they can be listed by name, set and deleted, but
+//! never read back through the API. Instead, executor profile env, project
+//! scripts and verification commands reference them as `${secret:NAME}` and
+//! the process service resolves them when the process is spawned (see
+//! [`resolve_start_request`]). Agent prompts are never resolved. The returned [`SecretRedactor`] masks the
+//! values again in the process's persisted and broadcast output.
+//!
+//! # Logging
+//!
+//! This service uses the `log` crate for structured logging:
+//! - `debug!`: Detailed operation tracing (lookups, resolved names)
+//! - `info!`: Successful mutations (set, delete) and key file creation
+//! - `error!`: Operation failures (logged before returning error)
+//!
+//! **Security**: Secret values are never logged, only names.
+//!
+//! # Error Handling
+//!
+//! All functions return `ServiceResult<T>`. `ServiceError::Validation` is
+//! returned for invalid names or empty values, `ServiceError::NotFound` for
+//! references to unknown secrets and `ServiceError::Config` if the key file is
+//! missing, unreadable or doesn't match the stored values.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::Path;
+use std::sync::LazyLock;
+
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use chrono::Utc;
+use log::{debug, error, info};
+use regex::Regex;
+use sqlx::SqlitePool;
+
+use openflow_contracts::{RunReason, Secret, SetSecretRequest, StartProcessRequest};
+
+use super::{ServiceError, ServiceResult};
+
+/// Name of the key file in the app data directory.
+pub const KEY_FILE_NAME: &str = "secrets.key";
+
+/// Length of the encryption key in bytes.
+const KEY_LENGTH: usize = 32;
+
+/// Matches `${secret:NAME}` references.
+static SECRET_REFERENCE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\$\{secret:([A-Za-z_][A-Za-z0-9_]*)\}").unwrap());
+
+// =============================================================================
+// Encryption
+// =============================================================================
+
+/// Encryption key for secret values.
+///
+/// The key is stored hex-encoded in [`KEY_FILE_NAME`] and created on first
+/// use. Losing the file makes stored secrets unreadable; they have to be set
+/// again.
+#[derive(Clone)]
+pub struct SecretCipher {
+    key: Key,
+}
+
+impl SecretCipher {
+    /// Create a cipher with a new random key (not persisted).
+    pub fn generate() -> Self {
+        Self {
+            key: ChaCha20Poly1305::generate_key(&mut OsRng),
+        }
+    }
+
+    /// Load the key from `KEY_FILE_NAME` in `dir`, creating it if missing.
+    pub fn from_directory(dir: &Path) -> ServiceResult<Self> {
+        Self::load_or_create(&dir.join(KEY_FILE_NAME))
+    }
+
+    /// Load the key file at `path`, creating it with a new key if missing.
+    ///
+    /// New key files are created only readable by the current user on Unix.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ServiceError::Config` if the file doesn't contain a valid key
+    /// and `ServiceError::Io` if it can't be read or written.
+    pub fn load_or_create(path: &Path) -> ServiceResult<Self> {
+        if path.exists() {
+            let contents = std::fs::read_to_string(path)?;
+            let key = decode_hex(contents.trim())
+                .filter(|bytes| bytes.len() == KEY_LENGTH)
+                .ok_or_else(|| {
+                    error!("load_or_create: invalid key file {:?}", path);
+                    ServiceError::config(format!("Invalid secrets key file {:?}", path))
+                })?;
+            debug!("load_or_create: loaded secrets key from {:?}", path);
+            return Ok(Self {
+                key: *Key::from_slice(&key),
+            });
+        }
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let cipher = Self::generate();
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        options
+            .open(path)?
+            .write_all(encode_hex(&cipher.key).as_bytes())?;
+        info!("load_or_create: created secrets key file {:?}", path);
+        Ok(cipher)
+    }
+
+    /// Encrypt a value, returning `(nonce, ciphertext)`.
+    ///
+    /// The secret name is authenticated with the value, so ciphertexts can't
+    /// be swapped between names.
+    fn encrypt(&self, name: &str, value: &str) -> ServiceResult<(Vec<u8>, Vec<u8>)> {
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = ChaCha20Poly1305::new(&self.key)
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: value.as_bytes(),
+                    aad: name.as_bytes(),
+                },
+            )
+            .map_err(|_| ServiceError::Internal(format!("Failed to encrypt secret {}", name)))?;
+        Ok((nonce.to_vec(), ciphertext))
+    }
+
+    /// Decrypt a value stored for `name`.
+    fn decrypt(&self, name: &str, nonce: &[u8], ciphertext: &[u8]) -> ServiceResult<String> {
+        if nonce.len() != 12 {
+            return Err(ServiceError::config(format!(
+                "Secret {} has a malformed nonce",
+                name
+            )));
+        }
+        let plaintext = ChaCha20Poly1305::new(&self.key)
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: name.as_bytes(),
+                },
+            )
+            .map_err(|_| {
+                ServiceError::config(format!(
+                    "Secret {} cannot be decrypted with the current key file",
+                    name
+                ))
+            })?;
+        String::from_utf8(plaintext)
+            .map_err(|_| ServiceError::Internal(format!("Secret {} is not valid UTF-8", name)))
+    }
+}
+
+// The key is left out so the cipher can be logged safely
+impl std::fmt::Debug for SecretCipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SecretCipher").finish_non_exhaustive()
+    }
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn decode_hex(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) || !s.is_ascii() {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).ok())
+        .collect()
+}
+
+// =============================================================================
+// Storage
+// =============================================================================
+
+/// List all secrets by name (values are not included).
+pub async fn list(pool: &SqlitePool) -> ServiceResult<Vec<Secret>> {
+    debug!("list: fetching secrets");
+
+    let secrets = sqlx::query_as::<_, (String, String, String)>(
+        "SELECT name, created_at, updated_at FROM secrets ORDER BY name",
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        error!("list: failed to fetch secrets: {}", e);
+        e
+    })?
+    .into_iter()
+    .map(|(name, created_at, updated_at)| Secret {
+        name,
+        created_at,
+        updated_at,
+    })
+    .collect::<Vec<_>>();
+
+    debug!("list: found {} secrets", secrets.len());
+    Ok(secrets)
+}
+
+/// Create or replace a secret.
+///
+/// # Errors
+///
+/// Returns `ServiceError::Validation` if the name is invalid or the value is
+/// empty.
+pub async fn set(
+    pool: &SqlitePool,
+    cipher: &SecretCipher,
+    request: SetSecretRequest,
+) -> ServiceResult<Secret> {
+    if !Secret::is_valid_name(&request.name) {
+        return Err(ServiceError::validation(format!(
+            "Invalid secret name '{}': use letters, digits and underscores",
+            request.name
+        )));
+    }
+    if request.value.is_empty() {
+        return Err(ServiceError::validation("Secret value cannot be empty"));
+    }
+
+    let (nonce, ciphertext) = cipher.encrypt(&request.name, &request.value)?;
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f").to_string();
+
+    sqlx::query(
+        r#"
+        INSERT INTO secrets (name, nonce, ciphertext, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?)
+        ON CONFLICT(name) DO UPDATE SET
+            nonce = excluded.nonce,
+            ciphertext = excluded.ciphertext,
+            updated_at = excluded.updated_at
+        "#,
+    )
+    .bind(&request.name)
+    .bind(nonce)
+    .bind(ciphertext)
+    .bind(&now)
+    .bind(&now)
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        error!("set: failed to store secret name={}: {}", request.name, e);
+        e
+    })?;
+
+    info!("set: stored secret name={}", request.name);
+    get(pool, &request.name).await
+}
+
+/// Get a secret by name (without its value).
+pub async fn get(pool: &SqlitePool, name: &str) -> ServiceResult<Secret> {
+    sqlx::query_as::<_, (String, String, String)>(
+        "SELECT name, created_at, updated_at FROM secrets WHERE name = ?",
+    )
+    .bind(name)
+    .fetch_optional(pool)
+    .await?
+    .map(|(name, created_at, updated_at)| Secret {
+        name,
+        created_at,
+        updated_at,
+    })
+    .ok_or_else(|| ServiceError::not_found("Secret", name))
+}
+
+/// Delete a secret.
+///
+/// # Errors
+///
+/// Returns `ServiceError::NotFound` if no secret has this name.
+pub async fn delete(pool: &SqlitePool, name: &str) -> ServiceResult<()> {
+    let result = sqlx::query("DELETE FROM secrets WHERE name = ?")
+        .bind(name)
+        .execute(pool)
+        .await
+        .map_err(|e| {
+            error!("delete: failed to delete secret name={}: {}", name, e);
+            e
+        })?;
+
+    if result.rows_affected() == 0 {
+        return Err(ServiceError::not_found("Secret", name));
+    }
+    info!("delete: deleted secret name={}", name);
+    Ok(())
+}
+
+/// Decrypt a secret's value. Only used to substitute references at spawn time.
+async fn get_value(pool: &SqlitePool, cipher: &SecretCipher, name: &str) -> ServiceResult<String> {
+    let (nonce, ciphertext) = sqlx::query_as::<_, (Vec<u8>, Vec<u8>)>(
+        "SELECT nonce, ciphertext FROM secrets WHERE name = ?",
+    )
+    .bind(name)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| ServiceError::not_found("Secret", name))?;
+
+    cipher.decrypt(name, &nonce, &ciphertext)
+}
+
+// =============================================================================
+// Resolution and redaction
+// =============================================================================
+
+/// Get the names of the secrets referenced in `text`.
+pub fn references(text: &str) -> Vec<String> {
+    SECRET_REFERENCE
+        .captures_iter(text)
+        .map(|caps| caps[1].to_string())
+        .collect()
+}
+
+/// Masks secret values in process output.
+///
+/// Each value is replaced with the `${secret:NAME}` placeholder it was
+/// substituted for. `redact` matches values within a single piece of text;
+/// use [`SecretRedactor::stream`] for output read in chunks.
+#[derive(Clone, Default)]
+pub struct SecretRedactor {
+    /// Values to mask, longest first so overlapping values mask fully.
+    values: Vec<(String, String)>,
+}
+
+impl SecretRedactor {
+    /// Create a redactor for the given name/value pairs.
+    pub fn new(secrets: BTreeMap<String, String>) -> Self {
+        let mut values: Vec<_> = secrets
+            .into_iter()
+            .filter(|(_, value)| !value.is_empty())
+            .map(|(name, value)| (value, format!("${{secret:{}}}", name)))
+            .collect();
+        values.sort_by_key(|(value, _)| std::cmp::Reverse(value.len()));
+        Self { values }
+    }
+
+    /// Check if there is nothing to redact.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Mask all secret values in `text`.
+    pub fn redact(&self, text: &str) -> String {
+        self.values
+            .iter()
+            .fold(text.to_string(), |text, (value, placeholder)| {
+                text.replace(value.as_str(), placeholder)
+            })
+    }
+
+    /// Start masking a stream of output chunks, so values split across
+    /// chunks are masked too.
+    pub fn stream(&self) -> RedactingStream<'_> {
+        RedactingStream {
+            redactor: self,
+            pending: String::new(),
+        }
+    }
+
+    /// Length of the longest end of `text` that could be the start of a
+    /// secret value, i.e. a proper prefix of one.
+    fn partial_suffix_len(&self, text: &str) -> usize {
+        self.values
+            .iter()
+            .filter_map(|(value, _)| {
+                (1..value.len().min(text.len() + 1))
+                    .rev()
+                    .filter(|&len| value.is_char_boundary(len))
+                    .find(|&len| text.ends_with(&value[..len]))
+            })
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Masks secret values in output that arrives in chunks.
+///
+/// Created with [`SecretRedactor::stream`]. When a chunk ends with what could
+/// be the start of a secret value, that tail is held back and masked together
+/// with the next chunk. Call [`RedactingStream::finish`] at the end of the
+/// stream to get the held-back tail.
+pub struct RedactingStream<'a> {
+    redactor: &'a SecretRedactor,
+    /// Masked output held back because it may continue into a secret value.
+    pending: String,
+}
+
+impl RedactingStream<'_> {
+    /// Mask a chunk of output, returning the text that is safe to emit.
+    pub fn push(&mut self, chunk: &str) -> String {
+        if self.redactor.is_empty() {
+            return chunk.to_string();
+        }
+        let mut text = std::mem::take(&mut self.pending);
+        text.push_str(chunk);
+        let mut redacted = self.redactor.redact(&text);
+        let split = redacted.len() - self.redactor.partial_suffix_len(&redacted);
+        self.pending = redacted.split_off(split);
+        redacted
+    }
+
+    /// Length of the output currently held back.
+    pub fn held_back(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// End the stream, returning any held-back output.
+    pub fn finish(&mut self) -> String {
+        std::mem::take(&mut self.pending)
+    }
+}
+
+// Values are left out so the redactor can be logged safely
+impl std::fmt::Debug for SecretRedactor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SecretRedactor")
+            .field("count", &self.values.len())
+            .finish()
+    }
+}
+
+/// Check if processes started for `run_reason` run a project-configured
+/// shell script, whose `${secret:NAME}` references are resolved.
+fn runs_project_script(run_reason: &RunReason) -> bool {
+    matches!(
+        run_reason,
+        RunReason::Setupscript
+            | RunReason::Cleanupscript
+            | RunReason::Devserver
+            | RunReason::Verification
+    )
+}
+
+/// Shell reference to the environment variable `name`.
+fn env_reference(name: &str) -> String {
+    if cfg!(windows) {
+        format!("%{}%", name)
+    } else {
+        format!("${{{}}}", name)
+    }
+}
+
+/// Resolve `${secret:NAME}` references for a process about to be spawned.
+///
+/// References in env values (e.g. from the executor profile) are replaced
+/// with the secret values. For project scripts (setup, cleanup, dev server
+/// and verification commands) each reference in the script is replaced with
+/// a reference to an environment variable `NAME` holding the value, so the
+/// value is never part of the command line and needs no shell quoting. Refer
+/// to secrets unquoted or in double quotes in scripts; single quotes stop the
+/// shell from expanding them.
+///
+/// The command and args of any other process, in particular coding agent
+/// prompts, are never resolved, so a message can't send a secret to the
+/// model provider.
+///
+/// Returns a redactor for the resolved values. Requests without references
+/// are left unchanged and don't need a cipher.
+///
+/// # Errors
+///
+/// Returns `ServiceError::NotFound` if a referenced secret doesn't exist and
+/// `ServiceError::Config` if secrets are referenced but no cipher is
+/// configured.
+pub async fn resolve_start_request(
+    pool: &SqlitePool,
+    cipher: Option<&SecretCipher>,
+    run_reason: &RunReason,
+    request: &mut StartProcessRequest,
+) -> ServiceResult<SecretRedactor> {
+    let script = runs_project_script(run_reason);
+    let mut names: Vec<String> = request.env.values().flat_map(|v| references(v)).collect();
+    if script {
+        names.extend(request.args.iter().flat_map(|arg| references(arg)));
+    }
+    if names.is_empty() {
+        return Ok(SecretRedactor::default());
+    }
+    names.sort();
+    names.dedup();
+
+    let cipher = cipher.ok_or_else(|| {
+        error!("resolve_start_request: secrets referenced but no key is configured");
+        ServiceError::config("Secrets are referenced but no secrets key is configured")
+    })?;
+
+    let mut values = BTreeMap::new();
+    for name in names {
+        let value = get_value(pool, cipher, &name).await.map_err(|e| {
+            error!(
+                "resolve_start_request: cannot resolve secret name={}: {}",
+                name, e
+            );
+            e
+        })?;
+        values.insert(name, value);
+    }
+    debug!(
+        "resolve_start_request: resolved secrets {:?}",
+        values.keys().collect::<Vec<_>>()
+    );
+
+    for value in request.env.values_mut() {
+        *value = SECRET_REFERENCE
+            .replace_all(value, |caps: &regex::Captures| values[&caps[1]].clone())
+            .into_owned();
+    }
+    if script {
+        for arg in request.args.iter_mut() {
+            let mut exported = Vec::new();
+            *arg = SECRET_REFERENCE
+                .replace_all(arg, |caps: &regex::Captures| {
+                    exported.push(caps[1].to_string());
+                    env_reference(&caps[1])
+                })
+                .into_owned();
+            for name in exported {
+                request.env.insert(name.clone(), values[&name].clone());
+            }
+        }
+    }
+
+    Ok(SecretRedactor::new(values))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openflow_db::create_test_db;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_key_file_is_created_and_reused() {
+        let dir = TempDir::new().unwrap();
+
+        let first = SecretCipher::from_directory(dir.path()).unwrap();
+        let second = SecretCipher::from_directory(dir.path()).unwrap();
+
+        assert_eq!(first.key, second.key);
+        let (nonce, ciphertext) = first.encrypt("NAME", "value").unwrap();
+        assert_eq!(
+            second.decrypt("NAME", &nonce, &ciphertext).unwrap(),
+            "value"
+        );
+        assert!(second.decrypt("OTHER", &nonce, &ciphertext).is_err());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let metadata = std::fs::metadata(dir.path().join(KEY_FILE_NAME)).unwrap();
+            assert_eq!(metadata.permissions().mode() & 0o777, 0o600);
+        }
+
+        std::fs::write(dir.path().join(KEY_FILE_NAME), "not a key").unwrap();
+        assert!(matches!(
+            SecretCipher::from_directory(dir.path()),
+            Err(ServiceError::Config(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_set_list_delete() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let cipher = SecretCipher::generate();
+
+        set(&pool, &cipher, SetSecretRequest::new("API_KEY", "one"))
+            .await
+            .unwrap();
+        set(&pool, &cipher, SetSecretRequest::new("API_KEY", "two"))
+            .await
+            .unwrap();
+
+        let secrets = list(&pool).await.unwrap();
+        assert_eq!(secrets.len(), 1);
+        assert_eq!(secrets[0].name, "API_KEY");
+        assert_eq!(get_value(&pool, &cipher, "API_KEY").await.unwrap(), "two");
+
+        // Only ciphertext is stored
+        let stored: Vec<u8> = sqlx::query_scalar("SELECT ciphertext FROM secrets")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert!(!String::from_utf8_lossy(&stored).contains("two"));
+
+        assert!(matches!(
+            set(&pool, &cipher, SetSecretRequest::new("BAD-NAME", "x")).await,
+            Err(ServiceError::Validation(_))
+        ));
+
+        delete(&pool, "API_KEY").await.unwrap();
+        assert!(list(&pool).await.unwrap().is_empty());
+        assert!(matches!(
+            delete(&pool, "API_KEY").await,
+            Err(ServiceError::NotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_redact_value_split_across_chunks() {
+        let redactor = SecretRedactor::new(BTreeMap::from([(
+            "TOKEN".to_string(),
+            "tok-123".to_string(),
+        )]));
+        let mut stream = redactor.stream();
+
+        let mut output = stream.push("token is to");
+        assert_eq!(output, "token is ");
+        output.push_str(&stream.push("k-123 and t"));
+        output.push_str(&stream.push("ea\n"));
+        output.push_str(&stream.push("last to"));
+        output.push_str(&stream.finish());
+
+        assert_eq!(output, "token is ${secret:TOKEN} and tea\nlast to");
+    }
+
+    #[tokio::test]
+    async fn test_resolve_start_request_and_redact() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let cipher = SecretCipher::generate();
+        set(&pool, &cipher, SetSecretRequest::new("TOKEN", "tok-123"))
+            .await
+            .unwrap();
+
+        let mut request = StartProcessRequest::script("curl -H \"Auth: ${secret:TOKEN}\" x");
+        request
+            .env
+            .insert("API_TOKEN".to_string(), "${secret:TOKEN}".to_string());
+
+        let redactor =
+            resolve_start_request(&pool, Some(&cipher), &RunReason::Setupscript, &mut request)
+                .await
+                .unwrap();
+
+        // The script refers to an exported variable instead of the value
+        let script = request.args.last().unwrap();
+        assert!(!script.contains("tok-123"));
+        assert!(script.contains(&format!("Auth: {}", env_reference("TOKEN"))));
+        assert_eq!(request.env["TOKEN"], "tok-123");
+        assert_eq!(request.env["API_TOKEN"], "tok-123");
+        assert_eq!(
+            redactor.redact("token is tok-123\n"),
+            "token is ${secret:TOKEN}\n"
+        );
+
+        let mut missing = StartProcessRequest::script("echo ${secret:MISSING}");
+        assert!(matches!(
+            resolve_start_request(&pool, Some(&cipher), &RunReason::Verification, &mut missing)
+                .await,
+            Err(ServiceError::NotFound { .. })
+        ));
+        assert!(matches!(
+            resolve_start_request(&pool, None, &RunReason::Setupscript, &mut request.clone()).await,
+            Ok(redactor) if redactor.is_empty()
+        ));
+        let mut unconfigured = StartProcessRequest::script("echo ${secret:TOKEN}");
+        assert!(matches!(
+            resolve_start_request(&pool, None, &RunReason::Devserver, &mut unconfigured).await,
+            Err(ServiceError::Config(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_agent_prompts_are_not_resolved() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let cipher = SecretCipher::generate();
+        set(&pool, &cipher, SetSecretRequest::new("TOKEN", "tok-123"))
+            .await
+            .unwrap();
+
+        let mut request = StartProcessRequest::new("claude")
+            .with_args(["-p", "print ${secret:TOKEN}"])
+            .with_env_var("API_TOKEN", "${secret:TOKEN}");
+        let redactor =
+            resolve_start_request(&pool, Some(&cipher), &RunReason::Codingagent, &mut request)
+                .await
+                .unwrap();
+
+        assert_eq!(request.args, vec!["-p", "print ${secret:TOKEN}"]);
+        assert_eq!(request.env["API_TOKEN"], "tok-123");
+        assert!(!request.env.contains_key("TOKEN"));
+        assert!(!redactor.is_empty());
+
+        let mut terminal = StartProcessRequest::script("echo ${secret:TOKEN}");
+        resolve_start_request(&pool, Some(&cipher), &RunReason::Terminal, &mut terminal)
+            .await
+            .unwrap();
+        assert_eq!(terminal.args.last().unwrap(), "echo ${secret:TOKEN}");
+    }
+}
@@ -0,0 +1,649 @@
+//! Claude Code stream-json transcript recording.
+//!
+//! Coding agents are launched with `--output-format stream-json`, which makes
+//! Claude Code print one JSON object per line describing the session. This
+//! module turns that stream into chat transcripts:
+//!
+//! - `system`/`init` events carry the session id, which is stored on the chat
+//!   as `claude_session_id`, with the CLI tool as `session_cli_tool`, so
+//!   follow-up runs of the same tool can `--resume` it
+//! - `assistant` events create or extend an assistant `Message`: text blocks
+//!   are appended with `message::append_content`, `tool_use` blocks are
+//!   collected into `tool_calls`, and usage is stored as `tokens_used`
+//! - `user` events carrying `tool_result` blocks are collected into the
+//!   `tool_results` of the assistant message that issued the calls
+//! - `result` events mark the end of the run
+//!
+//! Each API message (identified by `message.id`) becomes one assistant
+//! `Message` row. The row is kept streaming until the next API message
+//! starts or the run finishes.
+//!
+//! # Usage
+//!
+//! `TranscriptRecorder` implements `TranscriptParser` and is supplied by the
+//! executor adapters whose CLIs emit stream-json. Parsers of other output
+//! formats translate their output into `StreamJsonEvent`s and apply them with
+//! `TranscriptRecorder::handle_event`.
+//!
+//! ```rust,ignore
+//! let mut recorder = TranscriptRecorder::new(&chat_id, CliToolType::ClaudeCode);
+//! recorder.handle_output(&pool, broadcaster, &chunk).await?;
+//! // ... once the process has exited
+//! recorder.finish(&pool, broadcaster).await?;
+//! ```
+
+use async_trait::async_trait;
+use log::{debug, info, warn};
+use serde_json::Value;
+use sqlx::SqlitePool;
+
+use openflow_contracts::{
+    CliToolType, CreateMessageRequest, MessageRole, UpdateChatRequest, UpdateMessageRequest,
+};
+
+use super::executor_adapter::TranscriptParser;
+use super::{chat, message, ServiceResult};
+use crate::events::{EntityType, Event, EventBroadcaster};
+
+// ============================================================================
+// Stream Events
+// ============================================================================
+
+/// A parsed line of Claude Code stream-json output.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamJsonEvent {
+    /// Session initialization (`{"type":"system","subtype":"init",...}`).
+    Init {
+        /// Claude session ID
+        session_id: String,
+        /// Model used for the session
+        model: Option<String>,
+    },
+    /// An assistant message or a part of one.
+    Assistant {
+        /// API message ID; parts of the same message share it
+        message_id: Option<String>,
+        /// Model that produced the message
+        model: Option<String>,
+        /// Text blocks of the message
+        text: Vec<String>,
+        /// `tool_use` blocks of the message
+        tool_calls: Vec<Value>,
+        /// Input plus output tokens reported for the message
+        tokens_used: Option<i32>,
+    },
+    /// Tool results sent back to the model (`user` messages).
+    ToolResults {
+        /// `tool_result` blocks
+        results: Vec<Value>,
+    },
+    /// Final result of the run.
+    Result {
+        /// Claude session ID
+        session_id: Option<String>,
+        /// Whether the run ended with an error
+        is_error: bool,
+    },
+}
+
+/// Parse a single line of stream-json output.
+///
+/// Returns `None` for blank lines, lines that are not JSON objects and event
+/// types that do not contribute to the transcript.
+pub fn parse_line(line: &str) -> Option<StreamJsonEvent> {
+    let line = line.trim();
+    if !line.starts_with('{') {
+        return None;
+    }
+
+    let value: Value = match serde_json::from_str(line) {
+        Ok(value) => value,
+        Err(e) => {
+            debug!("parse_line: ignoring malformed stream-json line: {}", e);
+            return None;
+        }
+    };
+
+    match value.get("type").and_then(Value::as_str)? {
+        "system" => {
+            if value.get("subtype").and_then(Value::as_str) != Some("init") {
+                return None;
+            }
+            Some(StreamJsonEvent::Init {
+                session_id: string_field(&value, "session_id")?,
+                model: string_field(&value, "model"),
+            })
+        }
+        "assistant" => {
+            let message = value.get("message")?;
+            let mut text = Vec::new();
+            let mut tool_calls = Vec::new();
+            for block in content_blocks(message) {
+                match block.get("type").and_then(Value::as_str) {
+                    Some("text") => {
+                        if let Some(t) = block.get("text").and_then(Value::as_str) {
+                            text.push(t.to_string());
+                        }
+                    }
+                    Some("tool_use") => tool_calls.push(block.clone()),
+                    _ => {}
+                }
+            }
+            Some(StreamJsonEvent::Assistant {
+                message_id: string_field(message, "id"),
+                model: string_field(message, "model"),
+                text,
+                tool_calls,
+                tokens_used: message.get("usage").and_then(total_tokens),
+            })
+        }
+        "user" => {
+            let results: Vec<Value> = value
+                .get("message")
+                .map(content_blocks)
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|block| block.get("type").and_then(Value::as_str) == Some("tool_result"))
+                .cloned()
+                .collect();
+            if results.is_empty() {
+                return None;
+            }
+            Some(StreamJsonEvent::ToolResults { results })
+        }
+        "result" => Some(StreamJsonEvent::Result {
+            session_id: string_field(&value, "session_id"),
+            is_error: value
+                .get("is_error")
+                .and_then(Value::as_bool)
+                .unwrap_or(false),
+        }),
+        _ => None,
+    }
+}
+
+fn string_field(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(Value::as_str).map(str::to_string)
+}
+
+fn content_blocks(message: &Value) -> Vec<&Value> {
+    message
+        .get("content")
+        .and_then(Value::as_array)
+        .map(|blocks| blocks.iter().collect())
+        .unwrap_or_default()
+}
+
+fn total_tokens(usage: &Value) -> Option<i32> {
+    let input = usage.get("input_tokens").and_then(Value::as_i64);
+    let output = usage.get("output_tokens").and_then(Value::as_i64);
+    if input.is_none() && output.is_none() {
+        return None;
+    }
+    let total = input.unwrap_or(0) + output.unwrap_or(0);
+    Some(total.clamp(0, i32::MAX as i64) as i32)
+}
+
+// ============================================================================
+// Transcript Recorder
+// ============================================================================
+
+/// The assistant message currently being written.
+#[derive(Debug)]
+struct CurrentMessage {
+    /// Database ID of the `Message` row
+    id: String,
+    /// API message ID the row was created for
+    api_id: Option<String>,
+    has_content: bool,
+    tool_calls: Vec<Value>,
+    tool_results: Vec<Value>,
+}
+
+/// Records a stream-json run as assistant messages of a chat.
+///
+/// Output may arrive in arbitrary chunks (PTY reads are not line aligned),
+/// so incomplete lines are buffered until their newline arrives.
+#[derive(Debug)]
+pub struct TranscriptRecorder {
+    chat_id: String,
+    tool: CliToolType,
+    buffer: String,
+    session_id: Option<String>,
+    model: Option<String>,
+    current: Option<CurrentMessage>,
+}
+
+impl TranscriptRecorder {
+    /// Create a recorder that writes messages to the given chat.
+    ///
+    /// `tool` is the CLI producing the output; session IDs are stored as
+    /// sessions of that tool.
+    pub fn new(chat_id: impl Into<String>, tool: CliToolType) -> Self {
+        Self {
+            chat_id: chat_id.into(),
+            tool,
+            buffer: String::new(),
+            session_id: None,
+            model: None,
+            current: None,
+        }
+    }
+
+    /// The chat messages are written to.
+    pub fn chat_id(&self) -> &str {
+        &self.chat_id
+    }
+
+    /// The session ID reported by the stream, if any.
+    pub fn session_id(&self) -> Option<&str> {
+        self.session_id.as_deref()
+    }
+
+    /// Apply a parsed stream event to the transcript.
+    pub async fn handle_event(
+        &mut self,
+        pool: &SqlitePool,
+        broadcaster: &dyn EventBroadcaster,
+        event: StreamJsonEvent,
+    ) -> ServiceResult<()> {
+        match event {
+            StreamJsonEvent::Init { session_id, model } => {
+                if model.is_some() {
+                    self.model = model;
+                }
+                self.store_session_id(pool, broadcaster, session_id).await
+            }
+            StreamJsonEvent::Assistant {
+                message_id,
+                model,
+                text,
+                tool_calls,
+                tokens_used,
+            } => {
+                self.record_assistant(
+                    pool,
+                    broadcaster,
+                    message_id,
+                    model,
+                    text,
+                    tool_calls,
+                    tokens_used,
+                )
+                .await
+            }
+            StreamJsonEvent::ToolResults { results } => {
+                self.record_tool_results(pool, broadcaster, results).await
+            }
+            StreamJsonEvent::Result {
+                session_id,
+                is_error,
+            } => {
+                debug!(
+                    "handle_event: run finished for chat_id={} is_error={}",
+                    self.chat_id, is_error
+                );
+                if let Some(session_id) = session_id {
+                    self.store_session_id(pool, broadcaster, session_id).await?;
+                }
+                self.finish_current(pool, broadcaster).await
+            }
+        }
+    }
+
+    async fn store_session_id(
+        &mut self,
+        pool: &SqlitePool,
+        broadcaster: &dyn EventBroadcaster,
+        session_id: String,
+    ) -> ServiceResult<()> {
+        if self.session_id.as_deref() == Some(session_id.as_str()) {
+            return Ok(());
+        }
+
+        let request = UpdateChatRequest::with_session_id(session_id.clone(), self.tool);
+        let chat = chat::update(pool, &self.chat_id, request).await?;
+        broadcaster.broadcast(Event::updated(EntityType::Chat, &chat.id, &chat));
+
+        info!(
+            "store_session_id: chat_id={} session_id={} tool={:?}",
+            self.chat_id, session_id, self.tool
+        );
+        self.session_id = Some(session_id);
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn record_assistant(
+        &mut self,
+        pool: &SqlitePool,
+        broadcaster: &dyn EventBroadcaster,
+        message_id: Option<String>,
+        model: Option<String>,
+        text: Vec<String>,
+        tool_calls: Vec<Value>,
+        tokens_used: Option<i32>,
+    ) -> ServiceResult<()> {
+        let continues_current = matches!(
+            &self.current,
+            Some(current) if message_id.is_none() || current.api_id == message_id
+        );
+        if !continues_current {
+            self.finish_current(pool, broadcaster).await?;
+
+            let created = message::create(
+                pool,
+                CreateMessageRequest {
+                    chat_id: self.chat_id.clone(),
+                    role: MessageRole::Assistant,
+                    content: String::new(),
+                    tool_calls: None,
+                    tool_results: None,
+                    model: model.clone().or_else(|| self.model.clone()),
+                    is_streaming: Some(true),
+                },
+            )
+            .await?;
+            broadcaster.broadcast(Event::created(EntityType::Message, &created.id, &created));
+
+            debug!(
+                "record_assistant: created message id={} for chat_id={} api_id={:?}",
+                created.id, self.chat_id, message_id
+            );
+            self.current = Some(CurrentMessage {
+                id: created.id,
+                api_id: message_id,
+                has_content: false,
+                tool_calls: Vec::new(),
+                tool_results: Vec::new(),
+            });
+        }
+
+        let Some(current) = self.current.as_mut() else {
+            return Ok(());
+        };
+
+        let mut updated = None;
+        for block in text.iter().filter(|t| !t.is_empty()) {
+            let content = if current.has_content {
+                format!("\n\n{}", block)
+            } else {
+                block.clone()
+            };
+            current.has_content = true;
+            updated = Some(message::append_content(pool, &current.id, &content).await?);
+        }
+
+        if !tool_calls.is_empty() || tokens_used.is_some() || model.is_some() {
+            let tool_calls_json = if tool_calls.is_empty() {
+                None
+            } else {
+                current.tool_calls.extend(tool_calls);
+                Some(serde_json::to_string(&current.tool_calls).unwrap_or_default())
+            };
+            let request = UpdateMessageRequest {
+                tool_calls: tool_calls_json,
+                tokens_used,
+                model,
+                ..Default::default()
+            };
+            updated = Some(message::update(pool, &current.id, request).await?);
+        }
+
+        if let Some(updated) = updated {
+            broadcaster.broadcast(Event::updated(EntityType::Message, &updated.id, &updated));
+        }
+        Ok(())
+    }
+
+    async fn record_tool_results(
+        &mut self,
+        pool: &SqlitePool,
+        broadcaster: &dyn EventBroadcaster,
+        results: Vec<Value>,
+    ) -> ServiceResult<()> {
+        let Some(current) = self.current.as_mut() else {
+            warn!(
+                "record_tool_results: {} tool results without an assistant message in chat_id={}",
+                results.len(),
+                self.chat_id
+            );
+            return Ok(());
+        };
+
+        current.tool_results.extend(results);
+        let request = UpdateMessageRequest {
+            tool_results: Some(serde_json::to_string(&current.tool_results).unwrap_or_default()),
+            ..Default::default()
+        };
+        let updated = message::update(pool, &current.id, request).await?;
+        broadcaster.broadcast(Event::updated(EntityType::Message, &updated.id, &updated));
+        Ok(())
+    }
+
+    async fn finish_current(
+        &mut self,
+        pool: &SqlitePool,
+        broadcaster: &dyn EventBroadcaster,
+    ) -> ServiceResult<()> {
+        let Some(current) = self.current.take() else {
+            return Ok(());
+        };
+
+        let updated = message::set_streaming(pool, &current.id, false).await?;
+        broadcaster.broadcast(Event::updated(EntityType::Message, &updated.id, &updated));
+        debug!(
+            "finish_current: finished message id={} in chat_id={}",
+            current.id, self.chat_id
+        );
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TranscriptParser for TranscriptRecorder {
+    /// Feed a chunk of process output to the recorder.
+    ///
+    /// Every complete line is parsed and applied; the trailing partial line
+    /// is kept until more output arrives.
+    async fn handle_output(
+        &mut self,
+        pool: &SqlitePool,
+        broadcaster: &dyn EventBroadcaster,
+        chunk: &str,
+    ) -> ServiceResult<()> {
+        self.buffer.push_str(chunk);
+
+        while let Some(newline) = self.buffer.find('\n') {
+            let line: String = self.buffer.drain(..=newline).collect();
+            if let Some(event) = parse_line(&line) {
+                self.handle_event(pool, broadcaster, event).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Flush any buffered output and mark the last message as complete.
+    ///
+    /// Call this once the process has exited.
+    async fn finish(
+        &mut self,
+        pool: &SqlitePool,
+        broadcaster: &dyn EventBroadcaster,
+    ) -> ServiceResult<()> {
+        let remaining = std::mem::take(&mut self.buffer);
+        if let Some(event) = parse_line(&remaining) {
+            self.handle_event(pool, broadcaster, event).await?;
+        }
+        self.finish_current(pool, broadcaster).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{CollectingBroadcaster, NullBroadcaster};
+    use crate::services::project;
+    use openflow_contracts::{CreateChatRequest, CreateProjectRequest};
+    use openflow_db::create_test_db;
+
+    /// Helper to create a test project and chat.
+    async fn setup_chat(pool: &SqlitePool) -> String {
+        let project = project::create(
+            pool,
+            CreateProjectRequest {
+                name: "Test Project".to_string(),
+                git_repo_path: "/tmp/test-repo".to_string(),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Failed to create project");
+
+        let chat = chat::create(
+            pool,
+            CreateChatRequest {
+                project_id: project.id,
+                title: Some("Agent".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Failed to create chat");
+        chat.id
+    }
+
+    const INIT: &str = r#"{"type":"system","subtype":"init","session_id":"sess-1","model":"claude-sonnet-4","tools":["Bash"]}"#;
+    const TEXT: &str = r#"{"type":"assistant","message":{"id":"msg_1","model":"claude-sonnet-4","role":"assistant","content":[{"type":"text","text":"Listing files."}],"usage":{"input_tokens":10,"output_tokens":5}},"session_id":"sess-1"}"#;
+    const TOOL_USE: &str = r#"{"type":"assistant","message":{"id":"msg_1","model":"claude-sonnet-4","role":"assistant","content":[{"type":"tool_use","id":"toolu_1","name":"Bash","input":{"command":"ls"}}],"usage":{"input_tokens":10,"output_tokens":12}},"session_id":"sess-1"}"#;
+    const TOOL_RESULT: &str = r#"{"type":"user","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"toolu_1","content":"README.md","is_error":false}]},"session_id":"sess-1"}"#;
+    const FINAL: &str = r#"{"type":"assistant","message":{"id":"msg_2","model":"claude-sonnet-4","role":"assistant","content":[{"type":"text","text":"Done."}],"usage":{"input_tokens":30,"output_tokens":3}},"session_id":"sess-1"}"#;
+    const RESULT: &str = r#"{"type":"result","subtype":"success","is_error":false,"result":"Done.","session_id":"sess-1"}"#;
+
+    #[test]
+    fn test_parse_line_events() {
+        assert_eq!(
+            parse_line(INIT),
+            Some(StreamJsonEvent::Init {
+                session_id: "sess-1".to_string(),
+                model: Some("claude-sonnet-4".to_string()),
+            })
+        );
+
+        match parse_line(TOOL_USE) {
+            Some(StreamJsonEvent::Assistant {
+                message_id,
+                text,
+                tool_calls,
+                tokens_used,
+                ..
+            }) => {
+                assert_eq!(message_id.as_deref(), Some("msg_1"));
+                assert!(text.is_empty());
+                assert_eq!(tool_calls.len(), 1);
+                assert_eq!(tool_calls[0]["name"], "Bash");
+                assert_eq!(tokens_used, Some(22));
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+
+        match parse_line(TOOL_RESULT) {
+            Some(StreamJsonEvent::ToolResults { results }) => {
+                assert_eq!(results[0]["tool_use_id"], "toolu_1");
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+
+        assert_eq!(
+            parse_line(RESULT),
+            Some(StreamJsonEvent::Result {
+                session_id: Some("sess-1".to_string()),
+                is_error: false,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_line_ignores_noise() {
+        assert_eq!(parse_line(""), None);
+        assert_eq!(parse_line("plain text output"), None);
+        assert_eq!(parse_line("{not json"), None);
+        assert_eq!(parse_line(r#"{"type":"system","subtype":"other"}"#), None);
+        assert_eq!(
+            parse_line(r#"{"type":"user","message":{"content":"hi"}}"#),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_recorder_builds_transcript() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let chat_id = setup_chat(&pool).await;
+        let broadcaster = CollectingBroadcaster::new();
+
+        let mut recorder = TranscriptRecorder::new(&chat_id, CliToolType::CursorCli);
+        let output = [INIT, TEXT, TOOL_USE, TOOL_RESULT, FINAL, RESULT].join("\r\n") + "\r\n";
+        // Feed in uneven chunks, as PTY reads would arrive
+        for chunk in output.as_bytes().chunks(37) {
+            recorder
+                .handle_output(&pool, &broadcaster, std::str::from_utf8(chunk).unwrap())
+                .await
+                .expect("Failed to handle output");
+        }
+        recorder
+            .finish(&pool, &broadcaster)
+            .await
+            .expect("Failed to finish");
+
+        let chat = chat::get_chat(&pool, &chat_id).await.unwrap();
+        assert_eq!(chat.claude_session_id.as_deref(), Some("sess-1"));
+        assert_eq!(chat.session_cli_tool, Some(CliToolType::CursorCli));
+
+        let messages = message::list(&pool, &chat_id).await.unwrap();
+        assert_eq!(messages.len(), 2);
+
+        let first = &messages[0];
+        assert_eq!(first.role, MessageRole::Assistant);
+        assert_eq!(first.content, "Listing files.");
+        assert_eq!(first.model.as_deref(), Some("claude-sonnet-4"));
+        assert_eq!(first.tokens_used, Some(22));
+        assert!(!first.is_streaming);
+        let tool_calls: Vec<Value> =
+            serde_json::from_str(first.tool_calls.as_deref().unwrap()).unwrap();
+        assert_eq!(tool_calls[0]["id"], "toolu_1");
+        let tool_results: Vec<Value> =
+            serde_json::from_str(first.tool_results.as_deref().unwrap()).unwrap();
+        assert_eq!(tool_results[0]["content"], "README.md");
+
+        let second = &messages[1];
+        assert_eq!(second.content, "Done.");
+        assert_eq!(second.tokens_used, Some(33));
+        assert!(second.tool_calls.is_none());
+        assert!(!second.is_streaming);
+
+        assert!(broadcaster.events().iter().any(
+            |e| matches!(e, Event::DataChanged { entity, .. } if *entity == EntityType::Chat)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_recorder_finish_flushes_partial_line() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let chat_id = setup_chat(&pool).await;
+
+        let mut recorder = TranscriptRecorder::new(&chat_id, CliToolType::ClaudeCode);
+        recorder
+            .handle_output(&pool, &NullBroadcaster, TEXT)
+            .await
+            .unwrap();
+        assert!(message::list(&pool, &chat_id).await.unwrap().is_empty());
+
+        recorder.finish(&pool, &NullBroadcaster).await.unwrap();
+
+        let messages = message::list(&pool, &chat_id).await.unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].content, "Listing files.");
+        assert!(!messages[0].is_streaming);
+    }
+}
@@ -49,7 +49,7 @@ pub async fn list(
             r#"
                 SELECT
                     id, project_id, title, description, status,
-                    workflow_template, actions_required_count, parent_task_id,
+                    workflow_template, workflow_variables, actions_required_count, parent_task_id,
                     auto_start_next_step, default_executor_profile_id, base_branch,
                     archived_at, created_at, updated_at
                 FROM tasks
@@ -69,7 +69,7 @@ pub async fn list(
             r#"
                 SELECT
                     id, project_id, title, description, status,
-                    workflow_template, actions_required_count, parent_task_id,
+                    workflow_template, workflow_variables, actions_required_count, parent_task_id,
                     auto_start_next_step, default_executor_profile_id, base_branch,
                     archived_at, created_at, updated_at
                 FROM tasks
@@ -89,7 +89,7 @@ pub async fn list(
             r#"
                 SELECT
                     id, project_id, title, description, status,
-                    workflow_template, actions_required_count, parent_task_id,
+                    workflow_template, workflow_variables, actions_required_count, parent_task_id,
                     auto_start_next_step, default_executor_profile_id, base_branch,
                     archived_at, created_at, updated_at
                 FROM tasks
@@ -108,7 +108,7 @@ pub async fn list(
             r#"
                 SELECT
                     id, project_id, title, description, status,
-                    workflow_template, actions_required_count, parent_task_id,
+                    workflow_template, workflow_variables, actions_required_count, parent_task_id,
                     auto_start_next_step, default_executor_profile_id, base_branch,
                     archived_at, created_at, updated_at
                 FROM tasks
@@ -152,7 +152,7 @@ pub async fn get(pool: &SqlitePool, id: &str) -> ServiceResult<TaskWithChats> {
         r#"
         SELECT
             id, project_id, title, description, status,
-            workflow_template, actions_required_count, parent_task_id,
+            workflow_template, workflow_variables, actions_required_count, parent_task_id,
             auto_start_next_step, default_executor_profile_id, base_branch,
             archived_at, created_at, updated_at
         FROM tasks
@@ -181,7 +181,7 @@ pub async fn get(pool: &SqlitePool, id: &str) -> ServiceResult<TaskWithChats> {
             base_branch, branch, worktree_path, worktree_deleted,
             setup_completed_at, initial_prompt, hidden_prompt,
             is_plan_container, main_chat_id, workflow_step_index,
-            claude_session_id, archived_at, created_at, updated_at
+            claude_session_id, session_cli_tool, archived_at, created_at, updated_at
         FROM chats
         WHERE task_id = ? AND archived_at IS NULL
         ORDER BY workflow_step_index ASC, created_at ASC
@@ -214,7 +214,7 @@ pub async fn get_task(pool: &SqlitePool, id: &str) -> ServiceResult<Task> {
         r#"
         SELECT
             id, project_id, title, description, status,
-            workflow_template, actions_required_count, parent_task_id,
+            workflow_template, workflow_variables, actions_required_count, parent_task_id,
             auto_start_next_step, default_executor_profile_id, base_branch,
             archived_at, created_at, updated_at
         FROM tasks
@@ -245,6 +245,9 @@ pub async fn get_task(pool: &SqlitePool, id: &str) -> ServiceResult<Task> {
 }
 
 /// Create a new task.
+///
+/// The values given for the workflow template's variables are stored as is;
+/// callers check them first (see `workflow_runner::check_new_task`).
 pub async fn create(pool: &SqlitePool, request: CreateTaskRequest) -> ServiceResult<Task> {
     let id = Uuid::new_v4().to_string();
 
@@ -253,14 +256,23 @@ pub async fn create(pool: &SqlitePool, request: CreateTaskRequest) -> ServiceRes
         id, request.project_id, request.title, request.workflow_template, request.parent_task_id
     );
 
+    let workflow_variables = request
+        .workflow_variables
+        .as_ref()
+        .map(serde_json::to_string)
+        .transpose()
+        .map_err(|e| {
+            ServiceError::Internal(format!("Failed to encode workflow variables: {}", e))
+        })?;
+
     sqlx::query(
         r#"
         INSERT INTO tasks (
             id, project_id, title, description, status,
-            workflow_template, actions_required_count, parent_task_id,
+            workflow_template, workflow_variables, actions_required_count, parent_task_id,
             auto_start_next_step, base_branch
         )
-        VALUES (?, ?, ?, ?, 'todo', ?, 0, ?, FALSE, ?)
+        VALUES (?, ?, ?, ?, 'todo', ?, ?, 0, ?, FALSE, ?)
         "#,
     )
     .bind(&id)
@@ -268,6 +280,7 @@ pub async fn create(pool: &SqlitePool, request: CreateTaskRequest) -> ServiceRes
     .bind(&request.title)
     .bind(&request.description)
     .bind(&request.workflow_template)
+    .bind(&workflow_variables)
     .bind(&request.parent_task_id)
     .bind(&request.base_branch)
     .execute(pool)
@@ -511,10 +524,10 @@ pub async fn duplicate(pool: &SqlitePool, id: &str) -> ServiceResult<Task> {
         r#"
         INSERT INTO tasks (
             id, project_id, title, description, status,
-            workflow_template, actions_required_count, parent_task_id,
+            workflow_template, workflow_variables, actions_required_count, parent_task_id,
             auto_start_next_step, default_executor_profile_id, base_branch
         )
-        VALUES (?, ?, ?, ?, 'todo', ?, 0, ?, ?, ?, ?)
+        VALUES (?, ?, ?, ?, 'todo', ?, ?, 0, ?, ?, ?, ?)
         "#,
     )
     .bind(&new_id)
@@ -522,6 +535,7 @@ pub async fn duplicate(pool: &SqlitePool, id: &str) -> ServiceResult<Task> {
     .bind(&new_title)
     .bind(&original.description)
     .bind(&original.workflow_template)
+    .bind(&original.workflow_variables)
     .bind(&original.parent_task_id)
     .bind(original.auto_start_next_step)
     .bind(&original.default_executor_profile_id)
@@ -584,6 +598,7 @@ mod tests {
             always_included_rules: None,
             workflows_folder: None,
             verification_config: None,
+            script_limits: None,
         };
         project::create(pool, request)
             .await
@@ -598,6 +613,7 @@ mod tests {
             title: title.to_string(),
             description: None,
             workflow_template: None,
+            workflow_variables: None,
             parent_task_id: None,
             base_branch: None,
         }
@@ -632,6 +648,7 @@ mod tests {
             title: "Full Task".to_string(),
             description: Some("A complete task description".to_string()),
             workflow_template: Some("feature".to_string()),
+            workflow_variables: None,
             parent_task_id: None,
             base_branch: Some("develop".to_string()),
         };
@@ -857,6 +874,7 @@ mod tests {
             title: "Original Title".to_string(),
             description: Some("Original Description".to_string()),
             workflow_template: None,
+            workflow_variables: None,
             parent_task_id: None,
             base_branch: None,
         };
@@ -1061,6 +1079,7 @@ mod tests {
             title: "Child Task".to_string(),
             description: None,
             workflow_template: None,
+            workflow_variables: None,
             parent_task_id: Some(parent.id.clone()),
             base_branch: None,
         };
@@ -1136,6 +1155,7 @@ mod tests {
             title: "Full Task".to_string(),
             description: Some("A complete task description".to_string()),
             workflow_template: Some("feature".to_string()),
+            workflow_variables: None,
             parent_task_id: None,
             base_branch: Some("develop".to_string()),
         };
@@ -1194,6 +1214,7 @@ mod tests {
             title: "Child Task".to_string(),
             description: None,
             workflow_template: None,
+            workflow_variables: None,
             parent_task_id: Some(parent.id.clone()),
             base_branch: None,
         };
@@ -206,6 +206,10 @@ pub fn create_start_request(
         use_pty: true,
         pty_cols: Some(final_cols),
         pty_rows: Some(final_rows),
+        max_runtime_secs: None,
+        idle_timeout_secs: None,
+        resource_limits: None,
+        sandbox: None,
     }
 }
 
@@ -0,0 +1,215 @@
+//! Plain text transcript recording.
+//!
+//! CLIs that only print plain text (Gemini, Amazon Q, Copilot and custom
+//! commands) don't report messages, tool calls or a session ID. Their output
+//! is recorded as a single assistant message once the run has finished,
+//! with terminal escape sequences and carriage returns removed.
+//!
+//! Only the last `MAX_TRANSCRIPT_BYTES` of the output are kept; the full
+//! output is still persisted as process output.
+
+use async_trait::async_trait;
+use sqlx::SqlitePool;
+
+use openflow_contracts::CliToolType;
+
+use super::executor_adapter::TranscriptParser;
+use super::stream_json::{StreamJsonEvent, TranscriptRecorder};
+use super::ServiceResult;
+use crate::events::EventBroadcaster;
+
+/// Maximum size of the recorded message, matching the message content limit.
+pub const MAX_TRANSCRIPT_BYTES: usize = 1_000_000;
+
+/// Remove ANSI escape sequences and carriage returns from terminal output.
+pub fn strip_terminal_codes(output: &str) -> String {
+    let mut result = String::with_capacity(output.len());
+    let mut chars = output.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\x1b' => match chars.next() {
+                // CSI: parameters up to a final byte in @..~
+                Some('[') => {
+                    for c in chars.by_ref() {
+                        if ('@'..='~').contains(&c) {
+                            break;
+                        }
+                    }
+                }
+                // OSC: up to BEL or ST (ESC \)
+                Some(']') => {
+                    while let Some(c) = chars.next() {
+                        if c == '\x07' {
+                            break;
+                        }
+                        if c == '\x1b' && chars.peek() == Some(&'\\') {
+                            chars.next();
+                            break;
+                        }
+                    }
+                }
+                _ => {}
+            },
+            '\r' => {}
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+/// Records plain text output as an assistant message of a chat.
+#[derive(Debug)]
+pub struct TextTranscriptParser {
+    output: String,
+    recorder: TranscriptRecorder,
+}
+
+impl TextTranscriptParser {
+    /// Create a parser that writes the output of `tool` to the given chat.
+    pub fn new(chat_id: impl Into<String>, tool: CliToolType) -> Self {
+        Self {
+            output: String::new(),
+            recorder: TranscriptRecorder::new(chat_id, tool),
+        }
+    }
+}
+
+#[async_trait]
+impl TranscriptParser for TextTranscriptParser {
+    /// Buffer output until the run has finished, keeping only the tail.
+    async fn handle_output(
+        &mut self,
+        _pool: &SqlitePool,
+        _broadcaster: &dyn EventBroadcaster,
+        chunk: &str,
+    ) -> ServiceResult<()> {
+        self.output.push_str(chunk);
+        // Leave room for escape sequences that are stripped later
+        if self.output.len() > 2 * MAX_TRANSCRIPT_BYTES {
+            let mut start = self.output.len() - MAX_TRANSCRIPT_BYTES;
+            while !self.output.is_char_boundary(start) {
+                start += 1;
+            }
+            self.output.drain(..start);
+        }
+        Ok(())
+    }
+
+    /// Record the buffered output, if there is any, as a finished message.
+    async fn finish(
+        &mut self,
+        pool: &SqlitePool,
+        broadcaster: &dyn EventBroadcaster,
+    ) -> ServiceResult<()> {
+        let output = strip_terminal_codes(&std::mem::take(&mut self.output));
+        let mut text = output.trim();
+        if text.len() > MAX_TRANSCRIPT_BYTES {
+            let mut start = text.len() - MAX_TRANSCRIPT_BYTES;
+            while !text.is_char_boundary(start) {
+                start += 1;
+            }
+            text = &text[start..];
+        }
+        if !text.is_empty() {
+            let event = StreamJsonEvent::Assistant {
+                message_id: None,
+                model: None,
+                text: vec![text.to_string()],
+                tool_calls: Vec::new(),
+                tokens_used: None,
+            };
+            self.recorder.handle_event(pool, broadcaster, event).await?;
+        }
+        self.recorder.finish(pool, broadcaster).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::NullBroadcaster;
+    use crate::services::{chat, message, project};
+    use openflow_contracts::{CreateChatRequest, CreateProjectRequest, MessageRole};
+    use openflow_db::create_test_db;
+
+    /// Helper to create a test project and chat.
+    async fn setup_chat(pool: &SqlitePool) -> String {
+        let project = project::create(
+            pool,
+            CreateProjectRequest {
+                name: "Test Project".to_string(),
+                git_repo_path: "/tmp/test-repo".to_string(),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Failed to create project");
+
+        let chat = chat::create(
+            pool,
+            CreateChatRequest {
+                project_id: project.id,
+                title: Some("Agent".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Failed to create chat");
+        chat.id
+    }
+
+    #[test]
+    fn test_strip_terminal_codes() {
+        assert_eq!(
+            strip_terminal_codes("\x1b[1;32mDone\x1b[0m\r\n\x1b]0;title\x07next\x1b]8;;\x1b\\"),
+            "Done\nnext"
+        );
+        assert_eq!(strip_terminal_codes("plain"), "plain");
+    }
+
+    #[tokio::test]
+    async fn test_parser_records_output_as_one_message() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let chat_id = setup_chat(&pool).await;
+
+        let mut parser = TextTranscriptParser::new(&chat_id, CliToolType::GeminiCli);
+        for chunk in [
+            "Updated \x1b[1",
+            "msrc/main.rs\x1b[0m\r\n",
+            "All tests pass.\r\n",
+        ] {
+            parser
+                .handle_output(&pool, &NullBroadcaster, chunk)
+                .await
+                .unwrap();
+        }
+        assert!(message::list(&pool, &chat_id).await.unwrap().is_empty());
+        parser.finish(&pool, &NullBroadcaster).await.unwrap();
+
+        let messages = message::list(&pool, &chat_id).await.unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].role, MessageRole::Assistant);
+        assert_eq!(messages[0].content, "Updated src/main.rs\nAll tests pass.");
+        assert!(!messages[0].is_streaming);
+        assert!(chat::get_chat(&pool, &chat_id)
+            .await
+            .unwrap()
+            .claude_session_id
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_parser_skips_empty_output() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let chat_id = setup_chat(&pool).await;
+
+        let mut parser = TextTranscriptParser::new(&chat_id, CliToolType::Custom);
+        parser
+            .handle_output(&pool, &NullBroadcaster, "\x1b[2J\r\n")
+            .await
+            .unwrap();
+        parser.finish(&pool, &NullBroadcaster).await.unwrap();
+
+        assert!(message::list(&pool, &chat_id).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,360 @@
+//! Verification service.
+//!
+//! Runs a project's verification checks in a chat's worktree. Checks are
+//! configured in `projects.verification_config` as a JSON map of check name
+//! to shell command, e.g. `{"test": "pnpm test", "lint": "pnpm lint"}`.
+//!
+//! Each check runs as its own pipe-mode execution process with
+//! `RunReason::Verification`, so its output is streamed and persisted like
+//! any other run. Checks run one at a time, ordered by name. The collected
+//! `VerificationResult` is stored in `verification_results` and broadcast as
+//! a `Verification` event. A chat's latest result is returned with the chat
+//! (see `chat::get`), and all of its results by `list`.
+//!
+//! # Logging
+//!
+//! This service uses the `log` crate for structured logging:
+//! - `debug!`: Detailed operation tracing (individual checks)
+//! - `info!`: Verification start and outcome
+//! - `warn!`: Checks that could not be started
+//! - `error!`: Operation failures (logged before returning error)
+//!
+//! # Error Handling
+//!
+//! All functions return `ServiceResult<T>`. A failing check is not an error;
+//! it is reported in the result. `ServiceError::Validation` is returned if the
+//! chat has no worktree or the project has no checks configured.
+
+use chrono::Utc;
+use log::{debug, error, info, warn};
+use sqlx::SqlitePool;
+
+use openflow_contracts::{
+    CreateProcessRequest, ProcessStatus, VerificationCheck, VerificationCheckStatus,
+    VerificationResult,
+};
+
+use super::process::ProcessService;
+use super::{chat, project, worktree, ServiceError, ServiceResult};
+use crate::events::{EntityType, Event};
+
+/// Run all verification checks configured for a chat's project.
+///
+/// # Errors
+///
+/// Returns `ServiceError::NotFound` if the chat or project doesn't exist and
+/// `ServiceError::Validation` if the chat has no worktree or no checks are
+/// configured.
+pub async fn run(
+    pool: &SqlitePool,
+    process_service: &ProcessService,
+    chat_id: &str,
+) -> ServiceResult<VerificationResult> {
+    let chat = chat::get_chat(pool, chat_id).await?;
+    let project = project::get(pool, &chat.project_id).await?;
+
+    let worktree_path = match chat.worktree_path.as_deref() {
+        Some(path) if worktree::has_worktree(&chat) => path,
+        _ => {
+            return Err(ServiceError::Validation(format!(
+                "Chat {} has no worktree to verify",
+                chat_id
+            )))
+        }
+    };
+
+    let config = project.get_verification_config();
+    if config.is_empty() {
+        return Err(ServiceError::Validation(format!(
+            "Project {} has no verification checks configured",
+            project.id
+        )));
+    }
+
+    info!("run: verifying chat_id={} checks={}", chat_id, config.len());
+    let started_at = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f").to_string();
+
+    let mut checks = Vec::with_capacity(config.len());
+    for (name, command) in config {
+        debug!("run: running check name={} chat_id={}", name, chat_id);
+        let outcome = worktree::run_script(
+            pool,
+            process_service,
+            CreateProcessRequest::verification(chat_id, &command),
+            worktree_path,
+            &command,
+            None,
+        )
+        .await;
+
+        let check = match outcome {
+            Ok(process) => VerificationCheck {
+                status: if process.status == ProcessStatus::Completed {
+                    VerificationCheckStatus::Passed
+                } else {
+                    VerificationCheckStatus::Failed
+                },
+                exit_code: process.exit_code,
+                process_id: Some(process.id),
+                error: None,
+                name,
+                command,
+            },
+            Err(e) => {
+                warn!(
+                    "run: check name={} could not be run for chat_id={}: {}",
+                    name, chat_id, e
+                );
+                VerificationCheck {
+                    status: VerificationCheckStatus::Error,
+                    exit_code: None,
+                    process_id: None,
+                    error: Some(e.to_string()),
+                    name,
+                    command,
+                }
+            }
+        };
+        debug!(
+            "run: check name={} status={} chat_id={}",
+            check.name, check.status, chat_id
+        );
+        checks.push(check);
+    }
+
+    let completed_at = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f").to_string();
+    let result = VerificationResult::new(chat_id, checks, started_at, completed_at);
+    save(pool, &result).await?;
+
+    process_service.broadcaster().broadcast(Event::created(
+        EntityType::Verification,
+        chat_id,
+        &result,
+    ));
+
+    info!(
+        "run: verification finished chat_id={} passed={}",
+        chat_id, result.passed
+    );
+    Ok(result)
+}
+
+/// Store the result of a verification run.
+async fn save(pool: &SqlitePool, result: &VerificationResult) -> ServiceResult<()> {
+    let checks = serde_json::to_string(&result.checks)
+        .map_err(|e| ServiceError::Internal(format!("Failed to serialize checks: {}", e)))?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO verification_results (chat_id, passed, checks, started_at, completed_at)
+        VALUES (?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(&result.chat_id)
+    .bind(result.passed)
+    .bind(&checks)
+    .bind(&result.started_at)
+    .bind(&result.completed_at)
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        error!(
+            "save: failed to store verification result for chat_id={}: {}",
+            result.chat_id, e
+        );
+        ServiceError::Database(e)
+    })?;
+
+    debug!(
+        "save: stored verification result for chat_id={}",
+        result.chat_id
+    );
+    Ok(())
+}
+
+/// List the verification results of a chat, newest first.
+pub async fn list(pool: &SqlitePool, chat_id: &str) -> ServiceResult<Vec<VerificationResult>> {
+    query_results(pool, chat_id, None).await
+}
+
+/// Get the latest verification result of a chat, if it was ever verified.
+pub async fn get_latest(
+    pool: &SqlitePool,
+    chat_id: &str,
+) -> ServiceResult<Option<VerificationResult>> {
+    Ok(query_results(pool, chat_id, Some(1)).await?.pop())
+}
+
+/// Load the results of a chat, newest first, at most `limit` if given.
+async fn query_results(
+    pool: &SqlitePool,
+    chat_id: &str,
+    limit: Option<i64>,
+) -> ServiceResult<Vec<VerificationResult>> {
+    let rows = sqlx::query_as::<_, (bool, String, String, String)>(
+        r#"
+        SELECT passed, checks, started_at, completed_at
+        FROM verification_results
+        WHERE chat_id = ?
+        ORDER BY id DESC
+        LIMIT ?
+        "#,
+    )
+    .bind(chat_id)
+    .bind(limit.unwrap_or(-1))
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        error!(
+            "query_results: database error for chat_id={}: {}",
+            chat_id, e
+        );
+        ServiceError::Database(e)
+    })?;
+
+    rows.into_iter()
+        .map(|(passed, checks, started_at, completed_at)| {
+            let checks = serde_json::from_str(&checks).map_err(|e| {
+                ServiceError::Internal(format!(
+                    "Invalid verification checks of chat {}: {}",
+                    chat_id, e
+                ))
+            })?;
+            Ok(VerificationResult {
+                chat_id: chat_id.to_string(),
+                passed,
+                checks,
+                started_at,
+                completed_at,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openflow_contracts::{
+        CreateChatRequest, CreateProjectRequest, RunReason, UpdateChatRequest,
+    };
+    use openflow_db::create_test_db;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    use crate::events::CollectingBroadcaster;
+    use crate::services::process;
+
+    /// Helper to create a chat whose worktree is a plain temporary directory.
+    async fn setup_chat(
+        pool: &SqlitePool,
+        dir: &TempDir,
+        verification_config: Option<&str>,
+    ) -> String {
+        let project = project::create(
+            pool,
+            CreateProjectRequest {
+                name: "Verify".to_string(),
+                git_repo_path: dir.path().to_string_lossy().into_owned(),
+                verification_config: verification_config.map(str::to_string),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Failed to create project");
+        let chat = chat::create(
+            pool,
+            CreateChatRequest {
+                project_id: project.id,
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Failed to create chat");
+        chat::update(
+            pool,
+            &chat.id,
+            UpdateChatRequest::with_worktree(dir.path().to_string_lossy(), "openflow/verify/main"),
+        )
+        .await
+        .expect("Failed to set worktree");
+        chat.id
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_run_collects_check_results() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("ok.txt"), "ok").unwrap();
+        let chat_id = setup_chat(
+            &pool,
+            &dir,
+            Some(r#"{"test": "test -f ok.txt", "lint": "echo lint failed >&2; exit 2"}"#),
+        )
+        .await;
+        let broadcaster = Arc::new(CollectingBroadcaster::new());
+        let service = ProcessService::with_broadcaster(broadcaster.clone());
+
+        let result = run(&pool, &service, &chat_id)
+            .await
+            .expect("Failed to run verification");
+
+        assert!(!result.passed);
+        assert_eq!(result.checks.len(), 2);
+        let lint = &result.checks[0];
+        assert_eq!(lint.name, "lint");
+        assert_eq!(lint.status, VerificationCheckStatus::Failed);
+        assert_eq!(lint.exit_code, Some(2));
+        let test = &result.checks[1];
+        assert_eq!(test.name, "test");
+        assert_eq!(test.status, VerificationCheckStatus::Passed);
+
+        let processes = process::list_by_chat(&pool, &chat_id).await.unwrap();
+        assert_eq!(processes.len(), 2);
+        assert!(processes
+            .iter()
+            .all(|p| p.run_reason == RunReason::Verification));
+
+        let events = broadcaster.events();
+        assert!(events.iter().any(|event| matches!(
+            event,
+            Event::DataChanged {
+                entity: EntityType::Verification,
+                ..
+            }
+        )));
+
+        // The result is stored and returned with the chat
+        assert_eq!(
+            get_latest(&pool, &chat_id).await.unwrap(),
+            Some(result.clone())
+        );
+        let chat = chat::get(&pool, &chat_id).await.unwrap();
+        assert_eq!(chat.latest_verification, Some(result.clone()));
+
+        std::fs::remove_file(dir.path().join("ok.txt")).unwrap();
+        let second = run(&pool, &service, &chat_id).await.unwrap();
+        assert_eq!(
+            list(&pool, &chat_id).await.unwrap(),
+            vec![second.clone(), result]
+        );
+        assert_eq!(get_latest(&pool, &chat_id).await.unwrap(), Some(second));
+    }
+
+    #[tokio::test]
+    async fn test_run_requires_configured_checks() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let dir = TempDir::new().unwrap();
+        let chat_id = setup_chat(&pool, &dir, None).await;
+
+        let result = run(&pool, &ProcessService::new(), &chat_id).await;
+
+        assert!(matches!(result, Err(ServiceError::Validation(_))));
+        assert!(get_latest(&pool, &chat_id).await.unwrap().is_none());
+        assert!(chat::get(&pool, &chat_id)
+            .await
+            .unwrap()
+            .latest_verification
+            .is_none());
+    }
+}
@@ -3,6 +3,77 @@
 //! Handles parsing workflow markdown files, listing templates,
 //! managing built-in workflow templates, and variable substitution.
 //!
+//! ## Step Configuration
+//!
+//! A workflow may start with a YAML front-matter block giving its title,
+//! description and `defaults` for every step, and each step may carry a
+//! fenced `step` block overriding them:
+//!
+//! ````markdown
+//! ---
+//! title: Feature
+//! defaults:
+//!   executor_profile: Claude Code
+//!   auto_advance: true
+//! ---
+//!
+//! ### [ ] Step: Review
+//! ```step
+//! executor_profile: Codex
+//! chat_role: review
+//! model: o3
+//! hidden_prompt: Only comment, don't change code.
+//! required_artifacts: [review.md]
+//! timeout_secs: 900
+//! ```
+//! Review the implementation.
+//! ````
+//!
+//! The `step` block is not part of the step's description.
+//!
+//! ## Step Dependencies
+//!
+//! A step depends on the step before it, unless its `step` block lists the
+//! names of the steps it depends on in `depends_on` (`[]` for none). Steps
+//! whose dependencies are done can run in parallel, so a workflow can fan
+//! out after one step and join again on a later one:
+//!
+//! ````markdown
+//! ### [ ] Step: Write Docs
+//! ```step
+//! depends_on: [Implement]
+//! ```
+//! ````
+//!
+//! `parse` rejects references to unknown (or ambiguous) step names and
+//! dependency cycles.
+//!
+//! ## Variables
+//!
+//! Besides the built-in variables (`WorkflowVariable`), a template can
+//! declare its own in the front-matter and use them as `{@name}`
+//! placeholders:
+//!
+//! ```yaml
+//! variables:
+//!   ticket_url:
+//!     description: Link to the ticket being worked on
+//!   max_files:
+//!     type: number
+//!     default: 10
+//! ```
+//!
+//! Variables without a default are required unless `required: false` is
+//! set. Tasks give the values when they are created; `resolve_variables`
+//! checks them and `list_variables` reports every variable a template uses.
+//!
+//! ## Linting
+//!
+//! `parse` ignores lines that aren't valid step headers, so a typo like
+//! `###[ ] Step: Plan` silently drops a step. `lint` reports such problems
+//! with their line and column, and `list_templates` counts the errors of
+//! each template file.
+//!
 //! ## Logging
 //!
 //! This service uses structured logging at appropriate levels:
@@ -16,16 +87,256 @@
 //! All public functions return `ServiceResult<T>` for consistent error handling.
 //! Errors include context about the operation and relevant parameters.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
 
 use chrono::Utc;
+use serde::Deserialize;
 use tracing::{debug, error, info, warn};
 
-use openflow_contracts::{WorkflowContext, WorkflowStep, WorkflowStepStatus, WorkflowTemplate};
+use openflow_contracts::{
+    ChatRole, WorkflowContext, WorkflowDiagnostic, WorkflowDiagnosticCode, WorkflowStep,
+    WorkflowStepStatus, WorkflowTemplate, WorkflowVariable, WorkflowVariableInfo,
+    WorkflowVariableType,
+};
 
 use super::{ServiceError, ServiceResult};
 
+/// YAML front-matter of a workflow, between `---` lines at its start.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FrontMatter {
+    /// Workflow title, instead of the first `#` heading
+    pub title: Option<String>,
+    /// Workflow description, instead of the text after the title
+    pub description: Option<String>,
+    /// Configuration of every step, unless the step overrides it
+    pub defaults: StepConfig,
+    /// Variables the workflow declares, by name
+    pub variables: BTreeMap<String, VariableDeclaration>,
+}
+
+/// Declaration of a workflow variable in the front-matter `variables`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct VariableDeclaration {
+    /// Type of the variable's value
+    #[serde(rename = "type")]
+    pub value_type: WorkflowVariableType,
+    /// Value used when the task doesn't set one
+    #[serde(deserialize_with = "deserialize_scalar")]
+    pub default: Option<String>,
+    /// What the variable is for
+    pub description: Option<String>,
+    /// Whether tasks must set a value; defaults to having no default
+    pub required: Option<bool>,
+}
+
+impl VariableDeclaration {
+    /// Whether tasks must set a value for the variable.
+    pub fn is_required(&self) -> bool {
+        self.required.unwrap_or(self.default.is_none())
+    }
+}
+
+/// Deserialize a YAML scalar (string, number or boolean) as a string.
+fn deserialize_scalar<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    match Option::<serde_norway::Value>::deserialize(deserializer)? {
+        None | Some(serde_norway::Value::Null) => Ok(None),
+        Some(serde_norway::Value::String(value)) => Ok(Some(value)),
+        Some(serde_norway::Value::Number(value)) => Ok(Some(value.to_string())),
+        Some(serde_norway::Value::Bool(value)) => Ok(Some(value.to_string())),
+        Some(_) => Err(serde::de::Error::custom(
+            "expected a string, number or boolean",
+        )),
+    }
+}
+
+/// Configuration of a workflow step, from the front-matter `defaults` or a
+/// step's `step` block.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StepConfig {
+    /// Executor profile name or ID
+    pub executor_profile: Option<String>,
+    /// Role of the step's chat
+    pub chat_role: Option<ChatRole>,
+    /// Model instead of the profile's model
+    pub model: Option<String>,
+    /// Instructions given to the agent ahead of the step's prompt
+    pub hidden_prompt: Option<String>,
+    /// Files the step must produce in the task's artifacts folder
+    pub required_artifacts: Option<Vec<String>>,
+    /// Whether to start the next step when this one completes
+    pub auto_advance: Option<bool>,
+    /// Maximum runtime of the step's agent in seconds
+    pub timeout_secs: Option<u32>,
+    /// Names of the steps that must be done first, instead of the previous
+    /// step. Only valid in a step's `step` block.
+    pub depends_on: Option<Vec<String>>,
+}
+
+impl StepConfig {
+    /// Fill settings this configuration leaves unset from `defaults`.
+    pub fn or(self, defaults: &StepConfig) -> StepConfig {
+        StepConfig {
+            executor_profile: self
+                .executor_profile
+                .or_else(|| defaults.executor_profile.clone()),
+            chat_role: self.chat_role.or_else(|| defaults.chat_role.clone()),
+            model: self.model.or_else(|| defaults.model.clone()),
+            hidden_prompt: self
+                .hidden_prompt
+                .or_else(|| defaults.hidden_prompt.clone()),
+            required_artifacts: self
+                .required_artifacts
+                .or_else(|| defaults.required_artifacts.clone()),
+            auto_advance: self.auto_advance.or(defaults.auto_advance),
+            timeout_secs: self.timeout_secs.or(defaults.timeout_secs),
+            depends_on: self.depends_on,
+        }
+    }
+
+    /// Set the step's configuration fields.
+    fn apply(self, step: &mut WorkflowStep) {
+        step.executor_profile = self.executor_profile;
+        step.chat_role = self.chat_role;
+        step.model = self.model;
+        step.hidden_prompt = self.hidden_prompt;
+        step.required_artifacts = self.required_artifacts.unwrap_or_default();
+        step.auto_advance = self.auto_advance;
+        step.timeout_secs = self.timeout_secs;
+    }
+}
+
+/// Info string of the fenced block holding a step's configuration.
+const STEP_BLOCK: &str = "```step";
+
+/// Parse the YAML front-matter of a workflow, if it has one.
+///
+/// # Errors
+///
+/// Returns `ServiceError::Validation` if the front-matter is not closed or
+/// is not valid.
+pub fn parse_front_matter(content: &str) -> ServiceResult<Option<FrontMatter>> {
+    Ok(split_front_matter(content)?.0)
+}
+
+/// Split a workflow into its front-matter and the markdown after it.
+fn split_front_matter(content: &str) -> ServiceResult<(Option<FrontMatter>, &str)> {
+    let trimmed = content.trim_start();
+    let Some(rest) = trimmed
+        .strip_prefix("---\n")
+        .or_else(|| trimmed.strip_prefix("---\r\n"))
+    else {
+        return Ok((None, content));
+    };
+
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            let front_matter: FrontMatter = parse_yaml(&rest[..offset], "front-matter")?;
+            check_declarations(&front_matter.variables)?;
+            return Ok((Some(front_matter), &rest[offset + line.len()..]));
+        }
+        offset += line.len();
+    }
+
+    warn!("Workflow front-matter is not closed");
+    Err(ServiceError::Validation(
+        "Workflow front-matter is not closed with '---'".to_string(),
+    ))
+}
+
+/// Check that declared variables have valid names and defaults.
+fn check_declarations(variables: &BTreeMap<String, VariableDeclaration>) -> ServiceResult<()> {
+    if let Some((name, problem)) = declaration_problems(variables).into_iter().next() {
+        warn!(variable = %name, %problem, "Invalid workflow variable declaration");
+        return Err(ServiceError::Validation(format!(
+            "Workflow variable '{}' {}",
+            name, problem
+        )));
+    }
+    Ok(())
+}
+
+/// Find declared variables with invalid names or defaults, with what's
+/// wrong with them.
+fn declaration_problems(variables: &BTreeMap<String, VariableDeclaration>) -> Vec<(&str, String)> {
+    variables
+        .iter()
+        .filter_map(|(name, declaration)| {
+            let problem = if !is_variable_name(name) {
+                Some("is not a valid name (use letters, digits and '_')".to_string())
+            } else if WorkflowVariable::from_name(name).is_some() {
+                Some("is a built-in variable".to_string())
+            } else {
+                declaration
+                    .default
+                    .as_deref()
+                    .filter(|default| !declaration.value_type.accepts(default))
+                    .map(|default| {
+                        format!(
+                            "has default {:?}, which is not a {}",
+                            default, declaration.value_type
+                        )
+                    })
+            };
+            problem.map(|problem| (name.as_str(), problem))
+        })
+        .collect()
+}
+
+/// Whether a name can be used in a `{@name}` placeholder.
+fn is_variable_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Deserialize a YAML block, treating an empty block as all defaults.
+fn parse_yaml<T: Default + serde::de::DeserializeOwned>(
+    yaml: &str,
+    what: &str,
+) -> ServiceResult<T> {
+    if yaml.trim().is_empty() {
+        return Ok(T::default());
+    }
+    serde_norway::from_str(yaml).map_err(|e| {
+        warn!(error = %e, "Invalid workflow {}", what);
+        ServiceError::Validation(format!("Invalid workflow {}: {}", what, e))
+    })
+}
+
+/// Split a step's body into its `step` block configuration and description.
+fn split_step_config(name: &str, lines: &[String]) -> ServiceResult<(StepConfig, String)> {
+    let Some(start) = lines.iter().position(|l| l.trim() == STEP_BLOCK) else {
+        return Ok((StepConfig::default(), lines.join("\n").trim().to_string()));
+    };
+    let Some(len) = lines[start + 1..].iter().position(|l| l.trim() == "```") else {
+        return Err(ServiceError::Validation(format!(
+            "Step block of step '{}' is not closed with '```'",
+            name
+        )));
+    };
+    let end = start + 1 + len;
+
+    let config = parse_yaml(
+        &lines[start + 1..end].join("\n"),
+        &format!("configuration of step '{}'", name),
+    )?;
+    let description = lines[..start]
+        .iter()
+        .chain(&lines[end + 1..])
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string();
+    Ok((config, description))
+}
+
 /// Parse workflow steps from markdown content.
 ///
 /// Parses markdown looking for step headers in the format:
@@ -33,7 +344,14 @@ use super::{ServiceError, ServiceResult};
 /// `### [-] Step: Step Name` (in progress)
 /// `### [x] Step: Step Name` (completed)
 ///
-/// Returns a vector of parsed workflow steps with their descriptions.
+/// Returns a vector of parsed workflow steps with their descriptions. Each
+/// step's configuration is its `step` block over the front-matter
+/// `defaults` (see the module docs).
+///
+/// # Errors
+///
+/// Returns `ServiceError::Validation` if the front-matter or a `step` block
+/// is not valid YAML or has unknown keys.
 ///
 /// # Example
 ///
@@ -57,45 +375,38 @@ use super::{ServiceError, ServiceResult};
 pub fn parse(content: &str) -> ServiceResult<Vec<WorkflowStep>> {
     debug!(content_len = content.len(), "Parsing workflow content");
 
-    let mut steps = Vec::new();
-    let mut current_step: Option<(String, WorkflowStepStatus, Vec<String>)> = None;
-    let mut index = 0;
+    let (front_matter, body) = split_front_matter(content)?;
+    let defaults = front_matter.map(|f| f.defaults).unwrap_or_default();
+    if defaults.depends_on.is_some() {
+        return Err(ServiceError::Validation(
+            "depends_on can only be set in a step's step block, not in defaults".to_string(),
+        ));
+    }
 
-    for line in content.lines() {
+    let mut sections: Vec<(String, WorkflowStepStatus, Vec<String>)> = Vec::new();
+    for line in body.lines() {
         // Check if this line starts a new step
         if let Some((name, status)) = parse_step_header(line) {
             debug!(name = %name, ?status, "Found step header");
-            // Save previous step if exists
-            if let Some((prev_name, prev_status, prev_lines)) = current_step.take() {
-                let description = prev_lines.join("\n").trim().to_string();
-                steps.push(WorkflowStep {
-                    index,
-                    name: prev_name,
-                    description,
-                    status: prev_status,
-                    chat_id: None,
-                });
-                index += 1;
-            }
-            // Start new step
-            current_step = Some((name, status, Vec::new()));
-        } else if let Some((_, _, ref mut lines)) = current_step {
+            sections.push((name, status, Vec::new()));
+        } else if let Some((_, _, lines)) = sections.last_mut() {
             // Add line to current step's description
             lines.push(line.to_string());
         }
     }
 
-    // Don't forget the last step
-    if let Some((name, status, lines)) = current_step {
-        let description = lines.join("\n").trim().to_string();
-        steps.push(WorkflowStep {
-            index,
-            name,
-            description,
-            status,
-            chat_id: None,
-        });
+    let mut steps = Vec::with_capacity(sections.len());
+    let mut dependencies = Vec::with_capacity(sections.len());
+    for (index, (name, status, lines)) in (0..).zip(sections) {
+        let (config, description) = split_step_config(&name, &lines)?;
+        let mut step = WorkflowStep::new(index, name, description);
+        step.status = status;
+        let mut config = config.or(&defaults);
+        dependencies.push(config.depends_on.take());
+        config.apply(&mut step);
+        steps.push(step);
     }
+    resolve_dependencies(&mut steps, dependencies)?;
 
     // Log summary of parsed steps
     if steps.is_empty() {
@@ -124,6 +435,105 @@ pub fn parse(content: &str) -> ServiceResult<Vec<WorkflowStep>> {
     Ok(steps)
 }
 
+/// Set each step's `depends_on` from the step names it lists, or the
+/// previous step if it lists none, and check the dependencies for cycles.
+fn resolve_dependencies(
+    steps: &mut [WorkflowStep],
+    dependencies: Vec<Option<Vec<String>>>,
+) -> ServiceResult<()> {
+    for (i, names) in dependencies.into_iter().enumerate() {
+        let depends_on = match names {
+            None if i == 0 => Vec::new(),
+            None => vec![steps[i - 1].index],
+            Some(names) => {
+                let mut depends_on = Vec::with_capacity(names.len());
+                for name in &names {
+                    let index = find_step(steps, name.trim()).map_err(|problem| {
+                        warn!(step = %steps[i].name, dependency = %name, "Invalid step dependency");
+                        ServiceError::Validation(format!(
+                            "Step '{}' depends on {} step '{}'",
+                            steps[i].name, problem, name
+                        ))
+                    })?;
+                    if !depends_on.contains(&index) {
+                        depends_on.push(index);
+                    }
+                }
+                depends_on
+            }
+        };
+        steps[i].depends_on = depends_on;
+    }
+
+    if let Some(cycle) = find_cycle(steps) {
+        let names: Vec<&str> = cycle.iter().map(|&i| steps[i].name.as_str()).collect();
+        warn!(?names, "Workflow steps have a dependency cycle");
+        return Err(ServiceError::Validation(format!(
+            "Workflow steps depend on each other in a cycle: {}",
+            names.join(" -> ")
+        )));
+    }
+    Ok(())
+}
+
+/// Find the index of the step with the given name, or why there is none
+/// (`"unknown"` or `"ambiguous"`).
+fn find_step(steps: &[WorkflowStep], name: &str) -> Result<i32, &'static str> {
+    let mut matches = steps.iter().filter(|s| s.name == name);
+    match (matches.next(), matches.next()) {
+        (Some(step), None) => Ok(step.index),
+        (Some(_), Some(_)) => Err("ambiguous"),
+        (None, _) => Err("unknown"),
+    }
+}
+
+/// Find a dependency cycle, as positions of steps starting and ending with
+/// the same step.
+fn find_cycle(steps: &[WorkflowStep]) -> Option<Vec<usize>> {
+    #[derive(Clone, Copy, PartialEq)]
+    enum Mark {
+        Unvisited,
+        Visiting,
+        Done,
+    }
+
+    fn visit(
+        steps: &[WorkflowStep],
+        position: usize,
+        marks: &mut [Mark],
+        path: &mut Vec<usize>,
+    ) -> Option<Vec<usize>> {
+        match marks[position] {
+            Mark::Done => return None,
+            Mark::Visiting => {
+                let start = path.iter().position(|&p| p == position).unwrap_or(0);
+                let mut cycle = path[start..].to_vec();
+                cycle.push(position);
+                return Some(cycle);
+            }
+            Mark::Unvisited => {}
+        }
+
+        marks[position] = Mark::Visiting;
+        path.push(position);
+        for index in &steps[position].depends_on {
+            let Some(dependency) = steps.iter().position(|s| s.index == *index) else {
+                continue;
+            };
+            if let Some(cycle) = visit(steps, dependency, marks, path) {
+                return Some(cycle);
+            }
+        }
+        path.pop();
+        marks[position] = Mark::Done;
+        None
+    }
+
+    let mut marks = vec![Mark::Unvisited; steps.len()];
+    let mut path = Vec::new();
+    (0..steps.len()).find_map(|position| visit(steps, position, &mut marks, &mut path))
+}
+
 /// Parse a step header line into (name, status) if valid.
 ///
 /// Valid formats:
@@ -169,6 +579,498 @@ fn parse_step_header(line: &str) -> Option<(String, WorkflowStepStatus)> {
     Some((name, status))
 }
 
+/// Set the status marker of a step in workflow markdown.
+///
+/// Rewrites the `[ ]`/`[-]`/`[x]` marker in the header of the step with the
+/// given index (as numbered by `parse`) and leaves everything else as is.
+/// There is no marker for skipped steps, so they are written as `[x]`.
+///
+/// # Errors
+///
+/// Returns `ServiceError::Validation` if the content has no step with that
+/// index.
+pub fn set_step_status(
+    content: &str,
+    index: i32,
+    status: WorkflowStepStatus,
+) -> ServiceResult<String> {
+    let marker = match status {
+        WorkflowStepStatus::Pending => "[ ]",
+        WorkflowStepStatus::InProgress => "[-]",
+        WorkflowStepStatus::Completed | WorkflowStepStatus::Skipped => "[x]",
+    };
+
+    let mut result = String::with_capacity(content.len());
+    let mut step_index = 0;
+    let mut found = false;
+    for line in content.split_inclusive('\n') {
+        if parse_step_header(line).is_none() {
+            result.push_str(line);
+            continue;
+        }
+        if step_index == index {
+            // Valid headers start with "### " followed by the marker
+            let after_hashes = line.find("### ").unwrap_or(0) + 4;
+            let start =
+                after_hashes + line[after_hashes..].len() - line[after_hashes..].trim_start().len();
+            result.push_str(&line[..start]);
+            result.push_str(marker);
+            result.push_str(&line[start + 3..]);
+            found = true;
+        } else {
+            result.push_str(line);
+        }
+        step_index += 1;
+    }
+
+    if !found {
+        warn!(index, step_count = step_index, "Step to update not found");
+        return Err(ServiceError::Validation(format!(
+            "Workflow has no step with index {}",
+            index
+        )));
+    }
+
+    debug!(index, %status, "Set workflow step status");
+    Ok(result)
+}
+
+/// A step found while linting a workflow.
+struct LintStep<'a> {
+    /// Line and column of the step header
+    position: (u32, u32),
+    name: String,
+    /// Lines after the header up to the next step, with their line indexes
+    body: Vec<(usize, &'a str)>,
+}
+
+/// Lint a workflow template.
+///
+/// Reports problems with the line and column where they are, sorted by
+/// position:
+/// - errors for what makes `parse` fail or silently drop part of the
+///   workflow: malformed step headers (e.g. `###[ ] Step: Plan`), invalid
+///   front-matter or `step` blocks, and invalid step dependencies
+/// - warnings for duplicate step names, steps without a description,
+///   placeholders that are neither built-in nor declared, and a missing
+///   title
+///
+/// Lines in fenced code blocks are not checked for malformed step headers.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use openflow_core::services::workflow;
+///
+/// let diagnostics = workflow::lint("# Plan\n\n###[ ] Step: Spec\nWrite the spec.\n").unwrap();
+/// assert_eq!((diagnostics[0].line, diagnostics[0].column), (3, 4));
+/// ```
+pub fn lint(content: &str) -> ServiceResult<Vec<WorkflowDiagnostic>> {
+    debug!(content_len = content.len(), "Linting workflow content");
+
+    let lines: Vec<&str> = content.lines().collect();
+    let mut diagnostics = Vec::new();
+    let (front_matter, body_start) = lint_front_matter(&lines, &mut diagnostics);
+
+    let mut steps: Vec<LintStep> = Vec::new();
+    let mut in_fence = false;
+    for (i, &line) in lines.iter().enumerate().skip(body_start) {
+        if let Some((name, _)) = parse_step_header(line) {
+            let indent = line.len() - line.trim_start().len();
+            steps.push(LintStep {
+                position: (line_number(i), column(line, indent)),
+                name,
+                body: Vec::new(),
+            });
+            continue;
+        }
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        } else if let Some((offset, problem)) = step_header_problem(line).filter(|_| !in_fence) {
+            diagnostics.push(WorkflowDiagnostic::error(
+                line_number(i),
+                column(line, offset),
+                WorkflowDiagnosticCode::MalformedStepHeader,
+                format!(
+                    "Malformed step header: {}; expected '### [ ] Step: Name'",
+                    problem
+                ),
+            ));
+        }
+        if let Some(step) = steps.last_mut() {
+            step.body.push((i, line));
+        }
+    }
+
+    let mut dependencies = Vec::with_capacity(steps.len());
+    for (position, step) in steps.iter().enumerate() {
+        let (line, column) = step.position;
+        if let Some(earlier) = steps[..position].iter().find(|s| s.name == step.name) {
+            diagnostics.push(WorkflowDiagnostic::warning(
+                line,
+                column,
+                WorkflowDiagnosticCode::DuplicateStepName,
+                format!(
+                    "Step '{}' has the same name as the step on line {}",
+                    step.name, earlier.position.0
+                ),
+            ));
+        }
+        let (config, has_description) = lint_step_body(step, &lines, &mut diagnostics);
+        if !has_description {
+            diagnostics.push(WorkflowDiagnostic::warning(
+                line,
+                column,
+                WorkflowDiagnosticCode::EmptyStep,
+                format!("Step '{}' has no description", step.name),
+            ));
+        }
+        dependencies.push(config.and_then(|c| c.depends_on));
+    }
+    lint_dependencies(&steps, dependencies, &mut diagnostics);
+
+    if let Some(front_matter) = &front_matter {
+        for (i, line) in lines.iter().enumerate() {
+            for (offset, name) in placeholder_offsets(line) {
+                if WorkflowVariable::from_name(name).is_none()
+                    && !front_matter.variables.contains_key(name)
+                {
+                    diagnostics.push(WorkflowDiagnostic::warning(
+                        line_number(i),
+                        column(line, offset),
+                        WorkflowDiagnosticCode::UnknownVariable,
+                        format!(
+                            "Variable '{{@{}}}' is neither built-in nor declared in the front-matter, so every task must set it",
+                            name
+                        ),
+                    ));
+                }
+            }
+        }
+
+        let body = lines[body_start..].join("\n");
+        if front_matter.title.is_none() && extract_title(&body).is_none() {
+            diagnostics.push(WorkflowDiagnostic::warning(
+                1,
+                1,
+                WorkflowDiagnosticCode::MissingTitle,
+                "Workflow has no title; add a '# Title' heading or a front-matter title",
+            ));
+        }
+    }
+
+    diagnostics.sort_by_key(|d| (d.line, d.column));
+
+    let error_count = diagnostics.iter().filter(|d| d.is_error()).count();
+    debug!(
+        step_count = steps.len(),
+        error_count,
+        warning_count = diagnostics.len() - error_count,
+        "Linted workflow"
+    );
+    Ok(diagnostics)
+}
+
+/// Lint the front-matter of a workflow, if it has one.
+///
+/// Returns the front-matter (the default if there is none, `None` if it is
+/// not valid) and the index of the first line after it.
+fn lint_front_matter(
+    lines: &[&str],
+    diagnostics: &mut Vec<WorkflowDiagnostic>,
+) -> (Option<FrontMatter>, usize) {
+    let Some(start) = lines
+        .iter()
+        .position(|l| !l.trim().is_empty())
+        .filter(|&i| lines[i].trim_start() == "---")
+    else {
+        return (Some(FrontMatter::default()), 0);
+    };
+    let Some(len) = lines[start + 1..]
+        .iter()
+        .position(|l| l.trim_end() == "---")
+    else {
+        diagnostics.push(WorkflowDiagnostic::error(
+            line_number(start),
+            1,
+            WorkflowDiagnosticCode::InvalidFrontMatter,
+            "Front-matter is not closed with '---'",
+        ));
+        return (None, start + 1);
+    };
+    let end = start + 1 + len;
+
+    let Some(front_matter) = lint_yaml::<FrontMatter>(
+        lines,
+        start + 1..end,
+        WorkflowDiagnosticCode::InvalidFrontMatter,
+        "front-matter",
+        diagnostics,
+    ) else {
+        return (None, end + 1);
+    };
+
+    let key_position = |key: &str| {
+        (start + 1..end)
+            .find_map(|i| {
+                let line = lines[i];
+                let indented = line.trim_start();
+                indented
+                    .strip_prefix(key)
+                    .is_some_and(|rest| rest.trim_start().starts_with(':'))
+                    .then(|| (line_number(i), column(line, line.len() - indented.len())))
+            })
+            .unwrap_or((line_number(start), 1))
+    };
+    for (name, problem) in declaration_problems(&front_matter.variables) {
+        let (line, column) = key_position(name);
+        diagnostics.push(WorkflowDiagnostic::error(
+            line,
+            column,
+            WorkflowDiagnosticCode::InvalidFrontMatter,
+            format!("Workflow variable '{}' {}", name, problem),
+        ));
+    }
+    if front_matter.defaults.depends_on.is_some() {
+        let (line, column) = key_position("depends_on");
+        diagnostics.push(WorkflowDiagnostic::error(
+            line,
+            column,
+            WorkflowDiagnosticCode::InvalidFrontMatter,
+            "depends_on can only be set in a step's step block, not in defaults",
+        ));
+    }
+
+    (Some(front_matter), end + 1)
+}
+
+/// Lint a step's `step` block, if it has one.
+///
+/// Returns the step's configuration (`None` if the block is not valid) and
+/// whether the step has a description besides the block.
+fn lint_step_body(
+    step: &LintStep,
+    lines: &[&str],
+    diagnostics: &mut Vec<WorkflowDiagnostic>,
+) -> (Option<StepConfig>, bool) {
+    let has_text = |body: &[(usize, &str)]| body.iter().any(|(_, l)| !l.trim().is_empty());
+
+    let Some(start) = step.body.iter().position(|(_, l)| l.trim() == STEP_BLOCK) else {
+        return (Some(StepConfig::default()), has_text(&step.body));
+    };
+    let block_line = step.body[start].0;
+    let Some(len) = step.body[start + 1..]
+        .iter()
+        .position(|(_, l)| l.trim() == "```")
+    else {
+        let line = lines[block_line];
+        diagnostics.push(WorkflowDiagnostic::error(
+            line_number(block_line),
+            column(line, line.len() - line.trim_start().len()),
+            WorkflowDiagnosticCode::InvalidStepConfig,
+            format!(
+                "Step block of step '{}' is not closed with '```'",
+                step.name
+            ),
+        ));
+        return (None, has_text(&step.body[..start]));
+    };
+    let end = start + 1 + len;
+
+    let config = lint_yaml(
+        lines,
+        block_line + 1..step.body[end].0,
+        WorkflowDiagnosticCode::InvalidStepConfig,
+        &format!("configuration of step '{}'", step.name),
+        diagnostics,
+    );
+    let has_description = has_text(&step.body[..start]) || has_text(&step.body[end + 1..]);
+    (config, has_description)
+}
+
+/// Report step dependencies on unknown (or ambiguous) step names and
+/// dependency cycles, like `resolve_dependencies`.
+fn lint_dependencies(
+    steps: &[LintStep],
+    dependencies: Vec<Option<Vec<String>>>,
+    diagnostics: &mut Vec<WorkflowDiagnostic>,
+) {
+    let mut resolved: Vec<WorkflowStep> = (0..)
+        .zip(steps)
+        .map(|(index, step)| WorkflowStep::new(index, step.name.clone(), String::new()))
+        .collect();
+
+    for (i, names) in dependencies.into_iter().enumerate() {
+        let depends_on = match names {
+            None if i == 0 => Vec::new(),
+            None => vec![resolved[i - 1].index],
+            Some(names) => names
+                .iter()
+                .filter_map(|name| match find_step(&resolved, name.trim()) {
+                    Ok(index) => Some(index),
+                    Err(problem) => {
+                        let (line, column) = steps[i].position;
+                        diagnostics.push(WorkflowDiagnostic::error(
+                            line,
+                            column,
+                            WorkflowDiagnosticCode::InvalidDependency,
+                            format!(
+                                "Step '{}' depends on {} step '{}'",
+                                steps[i].name, problem, name
+                            ),
+                        ));
+                        None
+                    }
+                })
+                .collect(),
+        };
+        resolved[i].depends_on = depends_on;
+    }
+
+    if let Some(cycle) = find_cycle(&resolved) {
+        let names: Vec<&str> = cycle.iter().map(|&i| steps[i].name.as_str()).collect();
+        let (line, column) = steps[cycle[0]].position;
+        diagnostics.push(WorkflowDiagnostic::error(
+            line,
+            column,
+            WorkflowDiagnosticCode::InvalidDependency,
+            format!(
+                "Workflow steps depend on each other in a cycle: {}",
+                names.join(" -> ")
+            ),
+        ));
+    }
+}
+
+/// Deserialize the YAML block in the given lines like `parse_yaml`, or
+/// report where it is not valid.
+fn lint_yaml<T: Default + serde::de::DeserializeOwned>(
+    lines: &[&str],
+    range: std::ops::Range<usize>,
+    code: WorkflowDiagnosticCode,
+    what: &str,
+    diagnostics: &mut Vec<WorkflowDiagnostic>,
+) -> Option<T> {
+    let start = range.start;
+    let yaml = lines[range].join("\n");
+    if yaml.trim().is_empty() {
+        return Some(T::default());
+    }
+
+    serde_norway::from_str(&yaml)
+        .map_err(|e| {
+            // Locations are relative to the block, so point at the file instead
+            let (index, column) = e.location().map_or((start, 1), |l| {
+                (start + l.line().saturating_sub(1), l.column())
+            });
+            let message = e.to_string();
+            let message = message
+                .rfind(" at line ")
+                .map_or(message.as_str(), |end| &message[..end]);
+            diagnostics.push(WorkflowDiagnostic::error(
+                line_number(index),
+                column as u32,
+                code,
+                format!("Invalid {}: {}", what, message),
+            ));
+        })
+        .ok()
+}
+
+const STATUS_MARKERS: [&str; 4] = ["[ ]", "[-]", "[x]", "[X]"];
+
+/// Whether heading text (after the `#`s) is meant as a step header: it
+/// starts with a status marker, a `Step:` label, or a short bracketed token
+/// followed by `Step:`. Links and bracketed titles like `[Project]` are not.
+fn is_step_candidate(rest: &str) -> bool {
+    let starts_with_label = |text: &str| {
+        text.get(..5)
+            .is_some_and(|label| label.eq_ignore_ascii_case("step:"))
+    };
+    if STATUS_MARKERS.iter().any(|marker| rest.starts_with(marker)) || starts_with_label(rest) {
+        return true;
+    }
+    let Some(inner) = rest.strip_prefix('[') else {
+        return false;
+    };
+    match inner.find(']') {
+        Some(end) if inner[..end].chars().count() <= 3 => {
+            starts_with_label(inner[end + 1..].trim_start())
+        }
+        _ => false,
+    }
+}
+
+/// Why a heading that looks like a step header (see `is_step_candidate`) is
+/// not one, with the byte offset of the problem in the line.
+///
+/// Only meaningful for lines `parse_step_header` rejects.
+fn step_header_problem(line: &str) -> Option<(usize, &'static str)> {
+    let heading = line.trim_start();
+    let after_hashes = heading.trim_start_matches('#');
+    let hashes = heading.len() - after_hashes.len();
+    let rest = after_hashes.trim_start();
+    if hashes == 0 || !is_step_candidate(rest) {
+        return None;
+    }
+
+    let indent = line.len() - heading.len();
+    if hashes != 3 {
+        return Some((indent, "use exactly three '#'"));
+    }
+    if !after_hashes.starts_with(' ') {
+        return Some((indent + 3, "missing space after '###'"));
+    }
+    let Some(after_marker) = STATUS_MARKERS
+        .iter()
+        .find_map(|marker| rest.strip_prefix(marker))
+    else {
+        return Some((
+            line.len() - rest.len(),
+            "expected a status marker '[ ]', '[-]' or '[x]'",
+        ));
+    };
+    let label = after_marker.trim_start();
+    let label_offset = line.len() - label.len();
+    let Some(name) = label.strip_prefix("Step:") else {
+        return Some((label_offset, "expected 'Step:' after the status marker"));
+    };
+    if name.trim().is_empty() {
+        return Some((label_offset, "missing step name"));
+    }
+    None
+}
+
+/// Find the `{@name}` placeholders in a line, with their byte offsets.
+fn placeholder_offsets(line: &str) -> Vec<(usize, &str)> {
+    let mut placeholders = Vec::new();
+    let mut offset = 0;
+    while let Some(start) = line[offset..].find("{@") {
+        let name_start = offset + start + 2;
+        let Some(len) = line[name_start..].find('}') else {
+            break;
+        };
+        let name = &line[name_start..name_start + len];
+        if is_variable_name(name) {
+            placeholders.push((offset + start, name));
+            offset = name_start + len + 1;
+        } else {
+            offset = name_start;
+        }
+    }
+    placeholders
+}
+
+/// 1-based line number of a line index.
+fn line_number(index: usize) -> u32 {
+    index as u32 + 1
+}
+
+/// 1-based column of a byte offset in a line, counting characters.
+fn column(line: &str, offset: usize) -> u32 {
+    line[..offset].chars().count() as u32 + 1
+}
+
 /// List workflow templates from a folder.
 ///
 /// Scans the specified folder for `.md` files and parses them as workflow templates.
@@ -226,30 +1128,48 @@ pub async fn list_templates(folder_path: &Path) -> ServiceResult<Vec<WorkflowTem
             match std::fs::read_to_string(&path) {
                 Ok(content) => {
                     let file_name = path.file_stem().unwrap_or_default().to_string_lossy();
-                    let name = extract_title(&content).unwrap_or_else(|| {
-                        // Convert filename to title case
-                        file_name
-                            .split(['_', '-'])
-                            .map(|word| {
-                                let mut chars = word.chars();
-                                match chars.next() {
-                                    Some(first) => {
-                                        first.to_uppercase().chain(chars).collect::<String>()
+                    let (front_matter, body) = match split_front_matter(&content) {
+                        Ok((front_matter, body)) => (front_matter.unwrap_or_default(), body),
+                        Err(e) => {
+                            warn!(
+                                file = %path.display(),
+                                error = %e,
+                                "Failed to parse template front-matter"
+                            );
+                            (FrontMatter::default(), content.as_str())
+                        }
+                    };
+                    let name = front_matter
+                        .title
+                        .or_else(|| extract_title(body))
+                        .unwrap_or_else(|| {
+                            // Convert filename to title case
+                            file_name
+                                .split(['_', '-'])
+                                .map(|word| {
+                                    let mut chars = word.chars();
+                                    match chars.next() {
+                                        Some(first) => {
+                                            first.to_uppercase().chain(chars).collect::<String>()
+                                        }
+                                        None => String::new(),
                                     }
-                                    None => String::new(),
-                                }
-                            })
-                            .collect::<Vec<_>>()
-                            .join(" ")
-                    });
-
-                    let description = extract_description(&content);
+                                })
+                                .collect::<Vec<_>>()
+                                .join(" ")
+                        });
+
+                    let description = front_matter
+                        .description
+                        .or_else(|| extract_description(body));
                     let steps = parse(&content).unwrap_or_default();
+                    let error_count = lint(&content)?.iter().filter(|d| d.is_error()).count();
                     let now = Utc::now().to_rfc3339();
 
                     debug!(
                         template_name = %name,
                         step_count = steps.len(),
+                        error_count,
                         "Parsed template"
                     );
 
@@ -260,6 +1180,7 @@ pub async fn list_templates(folder_path: &Path) -> ServiceResult<Vec<WorkflowTem
                         content: content.clone(),
                         is_builtin: false,
                         steps,
+                        error_count: error_count as i32,
                         created_at: now.clone(),
                         updated_at: now,
                     });
@@ -429,6 +1350,144 @@ pub async fn get_template(
     Ok(template)
 }
 
+/// Find the names of the `{@name}` placeholders in content, in order of
+/// first use.
+pub fn find_placeholders(content: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    let mut rest = content;
+    while let Some(start) = rest.find("{@") {
+        rest = &rest[start + 2..];
+        let Some(end) = rest.find('}') else {
+            break;
+        };
+        let name = &rest[..end];
+        if is_variable_name(name) {
+            if !names.iter().any(|n| n == name) {
+                names.push(name.to_string());
+            }
+            rest = &rest[end + 1..];
+        }
+    }
+    names
+}
+
+/// List the variables of a workflow.
+///
+/// Reports every variable the front-matter declares and every placeholder
+/// the workflow uses, sorted by name. Placeholders that are neither built-in
+/// nor declared are reported as required, since nothing else fills them in.
+///
+/// # Errors
+///
+/// Returns `ServiceError::Validation` if the front-matter is not valid.
+pub fn list_variables(content: &str) -> ServiceResult<Vec<WorkflowVariableInfo>> {
+    let declared = parse_front_matter(content)?
+        .map(|f| f.variables)
+        .unwrap_or_default();
+    let used = find_placeholders(content);
+
+    let mut variables: Vec<WorkflowVariableInfo> = declared
+        .iter()
+        .map(|(name, declaration)| WorkflowVariableInfo {
+            name: name.clone(),
+            value_type: declaration.value_type,
+            default_value: declaration.default.clone(),
+            description: declaration.description.clone(),
+            required: declaration.is_required(),
+            builtin: false,
+            declared: true,
+            used: used.contains(name),
+        })
+        .collect();
+    for name in used.iter().filter(|name| !declared.contains_key(*name)) {
+        let builtin = WorkflowVariable::from_name(name).is_some();
+        variables.push(WorkflowVariableInfo {
+            name: name.clone(),
+            value_type: WorkflowVariableType::String,
+            default_value: None,
+            description: None,
+            required: !builtin,
+            builtin,
+            declared: false,
+            used: true,
+        });
+    }
+    variables.sort_by(|a, b| a.name.cmp(&b.name));
+
+    debug!(
+        variable_count = variables.len(),
+        placeholder_count = used.len(),
+        "Listed workflow variables"
+    );
+    Ok(variables)
+}
+
+/// Resolve the values of a workflow's variables from the values a task
+/// gives.
+///
+/// Returns the value of every variable that isn't built-in: the given
+/// value, else the declared default, else an empty string for optional
+/// variables. Given values for other names are ignored.
+///
+/// # Errors
+///
+/// Returns `ServiceError::Validation` if a required variable has no value
+/// or a value doesn't match the variable's type.
+pub fn resolve_variables(
+    content: &str,
+    values: &HashMap<String, String>,
+) -> ServiceResult<HashMap<String, String>> {
+    let mut resolved = HashMap::new();
+    let mut missing = Vec::new();
+    let mut invalid = Vec::new();
+
+    for variable in list_variables(content)?.into_iter().filter(|v| !v.builtin) {
+        let value = values
+            .get(&variable.name)
+            .cloned()
+            .or(variable.default_value);
+        match value {
+            Some(value) if !variable.value_type.accepts(&value) => {
+                invalid.push(format!(
+                    "'{}' must be a {}",
+                    variable.name, variable.value_type
+                ));
+            }
+            Some(value) => {
+                resolved.insert(variable.name, value);
+            }
+            None if variable.required => missing.push(variable.name),
+            None => {
+                resolved.insert(variable.name, String::new());
+            }
+        }
+    }
+
+    let mut problems = Vec::new();
+    if !missing.is_empty() {
+        problems.push(format!(
+            "missing values for workflow variables: {}",
+            missing.join(", ")
+        ));
+    }
+    if !invalid.is_empty() {
+        problems.push(format!(
+            "invalid workflow variables: {}",
+            invalid.join(", ")
+        ));
+    }
+    if !problems.is_empty() {
+        warn!(?missing, ?invalid, "Workflow variables not resolved");
+        return Err(ServiceError::Validation(problems.join("; ")));
+    }
+
+    debug!(
+        variable_count = resolved.len(),
+        "Resolved workflow variables"
+    );
+    Ok(resolved)
+}
+
 /// Substitute workflow variables in content using a HashMap.
 ///
 /// Variables are in format: {@variable_name}
@@ -622,6 +1681,7 @@ Execute the implementation plan step by step.
         content: content.to_string(),
         is_builtin: true,
         steps,
+        error_count: 0,
         created_at: timestamp.to_string(),
         updated_at: timestamp.to_string(),
     })
@@ -686,6 +1746,7 @@ Verify the fix is complete and doesn't cause regressions.
         content: content.to_string(),
         is_builtin: true,
         steps,
+        error_count: 0,
         created_at: timestamp.to_string(),
         updated_at: timestamp.to_string(),
     })
@@ -748,6 +1809,7 @@ Verify the refactoring is complete and behavior is preserved.
         content: content.to_string(),
         is_builtin: true,
         steps,
+        error_count: 0,
         created_at: timestamp.to_string(),
         updated_at: timestamp.to_string(),
     })
@@ -922,6 +1984,331 @@ Done.
         assert!(parse_step_header("### [ ] Step:   ").is_none());
     }
 
+    #[test]
+    fn test_parse_front_matter_and_step_config() {
+        let content = r#"---
+title: Reviewed Feature
+defaults:
+  executor_profile: Claude Code
+  auto_advance: true
+---
+
+# Feature
+
+### [ ] Step: Implement
+Implement it.
+
+### [ ] Step: Review
+```step
+executor_profile: Codex
+chat_role: review
+model: o3
+hidden_prompt: Only comment.
+required_artifacts: [review.md]
+auto_advance: false
+timeout_secs: 900
+```
+Review it.
+"#;
+        let front_matter = parse_front_matter(content).unwrap().unwrap();
+        assert_eq!(front_matter.title.as_deref(), Some("Reviewed Feature"));
+
+        let steps = parse(content).unwrap();
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[0].description, "Implement it.");
+        assert_eq!(steps[0].executor_profile.as_deref(), Some("Claude Code"));
+        assert_eq!(steps[0].chat_role, None);
+        assert_eq!(steps[0].auto_advance, Some(true));
+        assert!(steps[0].required_artifacts.is_empty());
+
+        assert_eq!(steps[1].description, "Review it.");
+        assert_eq!(steps[1].executor_profile.as_deref(), Some("Codex"));
+        assert_eq!(steps[1].chat_role, Some(ChatRole::Review));
+        assert_eq!(steps[1].model.as_deref(), Some("o3"));
+        assert_eq!(steps[1].hidden_prompt.as_deref(), Some("Only comment."));
+        assert_eq!(steps[1].required_artifacts, vec!["review.md".to_string()]);
+        assert_eq!(steps[1].auto_advance, Some(false));
+        assert_eq!(steps[1].timeout_secs, Some(900));
+    }
+
+    #[test]
+    fn test_parse_invalid_step_config() {
+        let unknown_key = "### [ ] Step: Review\n```step\nagent: Codex\n```\n";
+        assert!(matches!(
+            parse(unknown_key),
+            Err(ServiceError::Validation(_))
+        ));
+
+        let unclosed_block = "### [ ] Step: Review\n```step\nmodel: o3\n";
+        assert!(matches!(
+            parse(unclosed_block),
+            Err(ServiceError::Validation(_))
+        ));
+
+        let unclosed_front_matter = "---\ntitle: Feature\n\n### [ ] Step: Review\n";
+        assert!(matches!(
+            parse(unclosed_front_matter),
+            Err(ServiceError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_step_dependencies() {
+        let content = r#"
+### [ ] Step: Implement
+Implement it.
+
+### [ ] Step: Write Tests
+```step
+depends_on: [Implement]
+```
+
+### [ ] Step: Write Docs
+```step
+depends_on: [Implement]
+```
+
+### [ ] Step: Review
+```step
+depends_on: [Write Tests, Write Docs]
+```
+
+### [ ] Step: Merge
+"#;
+        let steps = parse(content).unwrap();
+
+        assert_eq!(steps[0].depends_on, Vec::<i32>::new());
+        assert_eq!(steps[1].depends_on, vec![0]);
+        assert_eq!(steps[2].depends_on, vec![0]);
+        assert_eq!(steps[3].depends_on, vec![1, 2]);
+        // Steps without depends_on follow the previous step
+        assert_eq!(steps[4].depends_on, vec![3]);
+    }
+
+    #[test]
+    fn test_parse_invalid_step_dependencies() {
+        let unknown = "### [ ] Step: Review\n```step\ndepends_on: [Implement]\n```\n";
+        let err = parse(unknown).unwrap_err();
+        assert!(err.to_string().contains("unknown step 'Implement'"));
+
+        let ambiguous = "### [ ] Step: Test\n\n### [ ] Step: Test\n\n### [ ] Step: Review\n```step\ndepends_on: [Test]\n```\n";
+        let err = parse(ambiguous).unwrap_err();
+        assert!(err.to_string().contains("ambiguous step 'Test'"));
+
+        let cycle =
+            "### [ ] Step: A\n```step\ndepends_on: [C]\n```\n### [ ] Step: B\n### [ ] Step: C\n";
+        let err = parse(cycle).unwrap_err();
+        assert!(err.to_string().contains("cycle: A -> C -> B -> A"));
+
+        let in_defaults = "---\ndefaults:\n  depends_on: []\n---\n### [ ] Step: A\n";
+        assert!(matches!(
+            parse(in_defaults),
+            Err(ServiceError::Validation(_))
+        ));
+    }
+
+    fn positions(diagnostics: &[WorkflowDiagnostic]) -> Vec<(u32, u32, WorkflowDiagnosticCode)> {
+        diagnostics
+            .iter()
+            .map(|d| (d.line, d.column, d.code))
+            .collect()
+    }
+
+    #[test]
+    fn test_lint_malformed_step_headers() {
+        let content = r#"# Plan
+
+## Steps
+
+###[ ] Step: Spec
+Write the spec.
+
+### [ ]Step
+Implement.
+
+#### [ ] Step: Deep
+Go deeper.
+
+  ### [?] Step: Ask
+Ask.
+
+```text
+###[ ] Step: Example
+```
+
+### [ ] Step: Done
+All good.
+
+### [x] Step:
+"#;
+        // parse only sees the one valid header
+        assert_eq!(parse(content).unwrap().len(), 1);
+
+        let diagnostics = lint(content).unwrap();
+        assert_eq!(
+            positions(&diagnostics),
+            vec![
+                (5, 4, WorkflowDiagnosticCode::MalformedStepHeader),
+                (8, 8, WorkflowDiagnosticCode::MalformedStepHeader),
+                (11, 1, WorkflowDiagnosticCode::MalformedStepHeader),
+                (14, 7, WorkflowDiagnosticCode::MalformedStepHeader),
+                (24, 9, WorkflowDiagnosticCode::MalformedStepHeader),
+            ]
+        );
+        assert!(diagnostics.iter().all(WorkflowDiagnostic::is_error));
+        assert_eq!(
+            diagnostics[0].message,
+            "Malformed step header: missing space after '###'; expected '### [ ] Step: Name'"
+        );
+        assert!(diagnostics[1]
+            .message
+            .contains("expected 'Step:' after the status marker"));
+        assert!(diagnostics[4].message.contains("missing step name"));
+    }
+
+    #[test]
+    fn test_lint_ignores_bracketed_headings() {
+        let content = r#"# [Project]
+
+## [API docs](https://example.com)
+
+### [Notes] on the rollout
+
+### [ ] Step: Implement
+Do it.
+"#;
+        assert_eq!(lint(content).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_lint_steps_and_variables() {
+        let content = r#"---
+variables:
+  ticket_url:
+    description: The ticket
+---
+### [ ] Step: Implement
+Fix {@ticket_url} in {@project_root}.
+
+### [ ] Step: Implement
+Use {@ticket_ur}.
+
+### [ ] Step: Review
+```step
+depends_on: [Test]
+```
+"#;
+        let diagnostics = lint(content).unwrap();
+        assert_eq!(
+            positions(&diagnostics),
+            vec![
+                (1, 1, WorkflowDiagnosticCode::MissingTitle),
+                (9, 1, WorkflowDiagnosticCode::DuplicateStepName),
+                (10, 5, WorkflowDiagnosticCode::UnknownVariable),
+                (12, 1, WorkflowDiagnosticCode::EmptyStep),
+                (12, 1, WorkflowDiagnosticCode::InvalidDependency),
+            ]
+        );
+        let errors: Vec<_> = diagnostics.iter().filter(|d| d.is_error()).collect();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].message,
+            "Step 'Review' depends on unknown step 'Test'"
+        );
+        assert!(diagnostics[1].message.contains("step on line 6"));
+        assert!(diagnostics[2].message.contains("{@ticket_ur}"));
+
+        let cycle =
+            "# Cycle\n### [ ] Step: A\n```step\ndepends_on: [B]\n```\nA.\n### [ ] Step: B\nB.\n";
+        let diagnostics = lint(cycle).unwrap();
+        assert_eq!(
+            positions(&diagnostics),
+            vec![(2, 1, WorkflowDiagnosticCode::InvalidDependency)]
+        );
+        assert!(diagnostics[0].message.contains("cycle: A -> B -> A"));
+    }
+
+    #[test]
+    fn test_lint_front_matter_and_step_blocks() {
+        let invalid_yaml = "---\ntitle: Broken\ndefaults:\n  timeout_secs: soon\n---\n";
+        let diagnostics = lint(invalid_yaml).unwrap();
+        assert_eq!(
+            positions(&diagnostics),
+            vec![(4, 17, WorkflowDiagnosticCode::InvalidFrontMatter)]
+        );
+        assert!(diagnostics[0]
+            .message
+            .starts_with("Invalid front-matter: defaults.timeout_secs"));
+        assert!(!diagnostics[0].message.contains("at line"));
+
+        let unclosed = "---\ntitle: Open\n# Plan\n";
+        assert_eq!(
+            positions(&lint(unclosed).unwrap()),
+            vec![(1, 1, WorkflowDiagnosticCode::InvalidFrontMatter)]
+        );
+
+        let builtin_name = "---\nvariables:\n  task_id:\n    default: x\n---\n# Plan\n";
+        let diagnostics = lint(builtin_name).unwrap();
+        assert_eq!(
+            positions(&diagnostics),
+            vec![(3, 3, WorkflowDiagnosticCode::InvalidFrontMatter)]
+        );
+        assert_eq!(
+            diagnostics[0].message,
+            "Workflow variable 'task_id' is a built-in variable"
+        );
+
+        let unknown_key = "# Plan\n### [ ] Step: A\n```step\nmodel: gpt\ntimeout: 5\n```\nDo it.\n";
+        assert_eq!(
+            positions(&lint(unknown_key).unwrap()),
+            vec![(5, 1, WorkflowDiagnosticCode::InvalidStepConfig)]
+        );
+
+        let unclosed_block = "# Plan\n### [ ] Step: A\nDo it.\n```step\nmodel: gpt\n";
+        assert_eq!(
+            positions(&lint(unclosed_block).unwrap()),
+            vec![(4, 1, WorkflowDiagnosticCode::InvalidStepConfig)]
+        );
+    }
+
+    #[test]
+    fn test_lint_builtin_templates() {
+        for template in get_builtin_templates().unwrap() {
+            assert_eq!(
+                lint(&template.content).unwrap(),
+                vec![],
+                "Template '{}' should lint clean",
+                template.name
+            );
+            assert_eq!(template.error_count, 0);
+        }
+    }
+
+    #[test]
+    fn test_set_step_status() {
+        let content =
+            "# Plan\n\n### [x] Step: First\nDone.\n\n  ### [ ] Step: Second\nDo it [ ] now.\n";
+
+        let started = set_step_status(content, 1, WorkflowStepStatus::InProgress).unwrap();
+        assert_eq!(
+            started,
+            "# Plan\n\n### [x] Step: First\nDone.\n\n  ### [-] Step: Second\nDo it [ ] now.\n"
+        );
+        let steps = parse(&started).unwrap();
+        assert_eq!(steps[1].status, WorkflowStepStatus::InProgress);
+
+        let completed = set_step_status(&started, 1, WorkflowStepStatus::Completed).unwrap();
+        assert_eq!(
+            parse(&completed).unwrap()[1].status,
+            WorkflowStepStatus::Completed
+        );
+
+        assert!(matches!(
+            set_step_status(content, 2, WorkflowStepStatus::Completed),
+            Err(ServiceError::Validation(_))
+        ));
+    }
+
     #[test]
     fn test_get_builtin_templates() {
         let templates = get_builtin_templates().unwrap();
@@ -1036,6 +2423,103 @@ Done.
         assert_eq!(result, "A B C D E F");
     }
 
+    const VARIABLES: &str = r#"---
+variables:
+  ticket_url:
+    description: Link to the ticket
+  max_files:
+    type: number
+    default: 10
+  notes:
+    required: false
+---
+
+### [ ] Step: Fix
+Fix {@ticket_url} in {@project_root}, touching at most {@max_files} files.
+Also see {@release_notes}. {@not a placeholder}
+"#;
+
+    #[test]
+    fn test_list_variables() {
+        let variables = list_variables(VARIABLES).unwrap();
+        let names: Vec<&str> = variables.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "max_files",
+                "notes",
+                "project_root",
+                "release_notes",
+                "ticket_url"
+            ]
+        );
+
+        let max_files = &variables[0];
+        assert_eq!(max_files.value_type, WorkflowVariableType::Number);
+        assert_eq!(max_files.default_value.as_deref(), Some("10"));
+        assert!(!max_files.required);
+        assert!(max_files.declared && max_files.used);
+
+        let notes = &variables[1];
+        assert!(!notes.required && !notes.used);
+
+        let project_root = &variables[2];
+        assert!(project_root.builtin && !project_root.required);
+
+        let release_notes = &variables[3];
+        assert!(release_notes.required && !release_notes.declared);
+
+        let ticket_url = &variables[4];
+        assert!(ticket_url.required);
+        assert_eq!(
+            ticket_url.description.as_deref(),
+            Some("Link to the ticket")
+        );
+    }
+
+    #[test]
+    fn test_resolve_variables() {
+        let mut values = HashMap::new();
+        values.insert(
+            "ticket_url".to_string(),
+            "https://example.com/T-1".to_string(),
+        );
+
+        let err = resolve_variables(VARIABLES, &values).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("missing values for workflow variables: release_notes"));
+
+        values.insert("release_notes".to_string(), "NOTES.md".to_string());
+        values.insert("max_files".to_string(), "many".to_string());
+        let err = resolve_variables(VARIABLES, &values).unwrap_err();
+        assert!(err.to_string().contains("'max_files' must be a number"));
+
+        values.remove("max_files");
+        let resolved = resolve_variables(VARIABLES, &values).unwrap();
+        assert_eq!(resolved["max_files"], "10");
+        assert_eq!(resolved["notes"], "");
+        assert_eq!(resolved["ticket_url"], "https://example.com/T-1");
+        assert!(!resolved.contains_key("project_root"));
+    }
+
+    #[test]
+    fn test_invalid_variable_declarations() {
+        for front_matter in [
+            "variables:\n  task_title: {}\n",
+            "variables:\n  ticket-url: {}\n",
+            "variables:\n  count:\n    type: number\n    default: ten\n",
+            "variables:\n  count:\n    type: integer\n",
+        ] {
+            let content = format!("---\n{}---\n### [ ] Step: A\n", front_matter);
+            assert!(
+                matches!(parse(&content), Err(ServiceError::Validation(_))),
+                "{} should be rejected",
+                front_matter
+            );
+        }
+    }
+
     #[test]
     fn test_extract_title() {
         let content = r#"
@@ -1170,6 +2654,60 @@ Instructions here.
         assert_eq!(templates[0].name, "My Custom Workflow");
     }
 
+    #[tokio::test]
+    async fn test_list_templates_uses_front_matter() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let workflow = r#"---
+# Shown in the template picker
+title: Reviewed Feature
+description: Implement, then review with another agent.
+---
+
+## Steps
+
+### [ ] Step: Implement
+Implement it.
+"#;
+
+        std::fs::write(temp_dir.path().join("reviewed.md"), workflow).unwrap();
+
+        let templates = list_templates(temp_dir.path()).await.unwrap();
+
+        assert_eq!(templates.len(), 1);
+        assert_eq!(templates[0].name, "Reviewed Feature");
+        assert_eq!(
+            templates[0].description.as_deref(),
+            Some("Implement, then review with another agent.")
+        );
+        assert_eq!(templates[0].steps.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_list_templates_counts_lint_errors() {
+        let temp_dir = TempDir::new().unwrap();
+
+        std::fs::write(
+            temp_dir.path().join("good.md"),
+            "# Good\n\n### [ ] Step: Spec\nWrite the spec.\n",
+        )
+        .unwrap();
+        std::fs::write(
+            temp_dir.path().join("typo.md"),
+            "# Typo\n\n###[ ] Step: Spec\nWrite the spec.\n\n### [ ]Step\nImplement.\n",
+        )
+        .unwrap();
+
+        let templates = list_templates(temp_dir.path()).await.unwrap();
+
+        assert_eq!(templates.len(), 2);
+        assert_eq!(templates[0].name, "Good");
+        assert_eq!(templates[0].error_count, 0);
+        assert_eq!(templates[1].name, "Typo");
+        assert_eq!(templates[1].error_count, 2);
+        assert!(templates[1].steps.is_empty());
+    }
+
     #[test]
     fn test_builtin_templates_have_variables() {
         let templates = get_builtin_templates().unwrap();
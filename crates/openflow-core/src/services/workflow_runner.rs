@@ -0,0 +1,1478 @@
+//! Workflow runner.
+//!
+//! Drives a task's workflow (`tasks.workflow_template`) from step to step:
+//!
+//! 1. `start` copies the template, with workflow variables substituted, into
+//!    a workflow document (`workflow.md`) in the task's artifacts folder and
+//!    creates a chat for every step, with the step's instructions as its
+//!    `initial_prompt` and the step's `workflow_step_index`
+//! 2. every step that is ready (its dependencies are done, see `workflow`)
+//!    is run with `run_step`: the task's executor is started in the step's
+//!    chat, and so in the chat's own worktree, and the step is marked in
+//!    progress (`### [-] Step:`). A step's worktree starts from the branches
+//!    of the steps it depends on, so it sees their committed work
+//! 3. when a step's process completes successfully, the step is marked
+//!    done (`### [x] Step:`) and, if the task has `auto_start_next_step` set,
+//!    the steps that became ready are run
+//!
+//! Steps that depend on the same step run in parallel. Ready steps are
+//! claimed (marked in progress) under the document lock before they are
+//! started, so a step joining parallel branches is started only once. A
+//! claimed step that fails to start is set back to pending.
+//!
+//! Steps can be configured in the template (see `workflow`): a step's
+//! executor profile, chat role and hidden prompt are set on its chat when it
+//! is created, its model and timeout are applied when it runs, it is only
+//! marked done once its required artifacts exist, and its `auto_advance`
+//! flag takes precedence over the task's `auto_start_next_step`.
+//!
+//! The markers in the workflow document record progress, so `start` resumes
+//! a workflow after a failed step or a restart by running the steps left in
+//! progress again. A step whose latest run succeeded while nothing was
+//! watching it is finished instead of run again. Every change of a step's status is broadcast as a `Workflow`
+//! update carrying the task's `WorkflowRun`.
+//!
+//! The template's own variables (see `workflow`) take their values from the
+//! task's `workflow_variables`, which `check_new_task` validates before the
+//! task is created. A step whose prompt still has a placeholder left is not
+//! run, so agents never see literal `{@name}`s.
+//!
+//! Templates are resolved from the task's `workflow_template`: built-in
+//! (`builtin:feature`) and file (`file:custom`) template IDs, or a path
+//! relative to the project root (`.openflow/workflows/feature.md`).
+//!
+//! # Logging
+//!
+//! This service uses the `log` crate for structured logging:
+//! - `debug!`: Template resolution, step chats, exit tracking
+//! - `info!`: Workflow started, step started, step completed
+//! - `warn!`: Failed steps, steps whose exit could not be tracked
+//! - `error!`: Operation failures (logged before returning error)
+//!
+//! # Error Handling
+//!
+//! All functions return `ServiceResult<T>`. `ServiceError::Validation` is
+//! returned if the task has no workflow template, the workflow has no steps,
+//! refers to an unknown executor profile or lacks variable values, or the
+//! step is already running, waiting on other steps or has unresolved
+//! placeholders.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::{Component, Path, PathBuf};
+use std::pin::Pin;
+use std::sync::LazyLock;
+
+use log::{debug, error, info, warn};
+use sqlx::SqlitePool;
+use tokio::sync::broadcast::{self, error::RecvError};
+
+use openflow_contracts::{
+    Chat, CreateChatRequest, CreateTaskRequest, ExecutionProcess, Project, RunReason, Task,
+    WorkflowContext, WorkflowRun, WorkflowStep, WorkflowStepStatus,
+};
+
+use super::process::{self, ProcessService};
+use super::{
+    artifact, chat, executor, executor_profile, project, task, workflow, worktree, ServiceError,
+    ServiceResult,
+};
+use crate::events::{EntityType, Event, EventBroadcaster};
+
+/// File name of the workflow document in a task's artifacts folder.
+pub const WORKFLOW_DOCUMENT: &str = "workflow.md";
+
+/// Serializes rewrites of workflow documents, so concurrent status changes
+/// don't overwrite each other.
+static DOCUMENT_LOCK: LazyLock<tokio::sync::Mutex<()>> =
+    LazyLock::new(|| tokio::sync::Mutex::new(()));
+
+/// Get the workflow run of a task.
+///
+/// # Errors
+///
+/// Returns `ServiceError::NotFound` if the task's workflow has not been
+/// started.
+pub async fn get(pool: &SqlitePool, task_id: &str) -> ServiceResult<WorkflowRun> {
+    let task = task::get_task(pool, task_id).await?;
+    let path = document_path(pool, task_id).await?;
+    let content = match tokio::fs::read_to_string(&path).await {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Err(ServiceError::NotFound {
+                entity: "WorkflowRun",
+                id: task_id.to_string(),
+            })
+        }
+        Err(e) => {
+            error!(
+                "get: failed to read workflow document {:?} for task_id={}: {}",
+                path, task_id, e
+            );
+            return Err(e.into());
+        }
+    };
+    load_run(pool, &task, &path, &content).await
+}
+
+/// Start or resume the workflow of a task.
+///
+/// On first use the workflow document and the step chats are created. Then
+/// the steps left in progress that aren't running are run again, and the
+/// steps that are ready are run. Returns the workflow run; if every step is
+/// done, nothing is started.
+///
+/// # Errors
+///
+/// Returns `ServiceError::Validation` if the task has no workflow template,
+/// the workflow has no steps or a step's executor profile does not exist,
+/// and `ServiceError::NotFound` if the template does not exist.
+pub async fn start(
+    pool: &SqlitePool,
+    process_service: &ProcessService,
+    task_id: &str,
+) -> ServiceResult<WorkflowRun> {
+    let task = task::get_task(pool, task_id).await?;
+    let project = project::get(pool, &task.project_id).await?;
+    let path = document_path(pool, task_id).await?;
+
+    if !tokio::fs::try_exists(&path).await? {
+        create_document(pool, &task, &project, &path).await?;
+    }
+    let content = tokio::fs::read_to_string(&path).await?;
+    let steps = workflow::parse(&content)?;
+    if steps.is_empty() {
+        return Err(ServiceError::Validation(format!(
+            "Workflow of task {} has no steps",
+            task_id
+        )));
+    }
+
+    // Create chats for steps that don't have one yet
+    let chats = chat::list(pool, task_id).await?;
+    for step in &steps {
+        if step_chat(&chats, step.index).is_some() {
+            continue;
+        }
+        let mut request = CreateChatRequest::for_task(task_id, &project.id)
+            .with_title(&step.name)
+            .with_step_index(step.index)
+            .with_initial_prompt(&step.description);
+        let profile_id = match &step.executor_profile {
+            Some(reference) => Some(step_profile(pool, task_id, step.index, reference).await?),
+            None => task.default_executor_profile_id.clone(),
+        };
+        if let Some(profile_id) = profile_id {
+            request = request.with_executor(profile_id);
+        }
+        if let Some(role) = &step.chat_role {
+            request = request.with_role(role.clone());
+        }
+        if let Some(hidden_prompt) = &step.hidden_prompt {
+            request = request.with_hidden_prompt(hidden_prompt);
+        }
+        let created = chat::create(pool, request).await?;
+        debug!(
+            "start: created chat_id={} for step index={} of task_id={}",
+            created.id, step.index, task_id
+        );
+        process_service.broadcaster().broadcast(Event::created(
+            EntityType::Chat,
+            created.id.clone(),
+            &created,
+        ));
+    }
+
+    let run = load_run(pool, &task, &path, &content).await?;
+    if run.is_complete() {
+        debug!("start: workflow of task_id={} is complete", task_id);
+        return Ok(run);
+    }
+
+    let mut started = Vec::new();
+    for step in run.steps.iter().filter(|s| s.is_active()) {
+        if let Some(chat_id) = &step.chat_id {
+            match latest_run(pool, chat_id).await? {
+                Some(process) if !process.is_finished() => {
+                    debug!(
+                        "start: step index={} of task_id={} is already running",
+                        step.index, task_id
+                    );
+                    continue;
+                }
+                Some(process) if process.is_successful() => {
+                    // The step's run finished while nothing was watching it,
+                    // e.g. across a restart, so record it instead of rerunning
+                    debug!(
+                        "start: recording finished process_id={} of step index={} of task_id={}",
+                        process.id, step.index, task_id
+                    );
+                    finish_step(pool, process_service, task_id, step.index, &process.id).await?;
+                    let run = get(pool, task_id).await?;
+                    if run.step(step.index).is_some_and(|s| !s.is_active()) {
+                        continue;
+                    }
+                }
+                _ => {}
+            }
+        }
+        start_step(pool, process_service, task_id, step.index).await?;
+        started.push(step.index);
+    }
+    let claimed = claim_ready_steps(pool, process_service.broadcaster().as_ref(), task_id).await?;
+    started.extend(start_claimed_steps(pool, process_service, task_id, claimed).await?);
+
+    info!(
+        "start: started workflow of task_id={} at step indices={:?}",
+        task_id, started
+    );
+    get(pool, task_id).await
+}
+
+/// Run a step of a task's workflow in its chat.
+///
+/// The task's executor (the chat's profile, else the task's default) is
+/// started with the step's prompt, after the chat's hidden prompt, in the
+/// chat's worktree, and the step is marked in progress. When the process
+/// exits successfully and the step's required artifacts exist, the step is
+/// marked done, and the next step is run if the step (or else the task)
+/// auto-advances.
+///
+/// # Errors
+///
+/// Returns `ServiceError::NotFound` if the workflow has not been started or
+/// has no such step, and `ServiceError::Validation` if the step is already
+/// running or a step it depends on is not done.
+pub async fn run_step(
+    pool: &SqlitePool,
+    process_service: &ProcessService,
+    task_id: &str,
+    step_index: i32,
+) -> ServiceResult<ExecutionProcess> {
+    start_step(pool, process_service, task_id, step_index).await
+}
+
+/// Future of `start_step`.
+type StepFuture<'a> = Pin<Box<dyn Future<Output = ServiceResult<ExecutionProcess>> + Send + 'a>>;
+
+/// Implementation of `run_step`.
+///
+/// Returns a boxed future because a step's exit watcher may start the next
+/// step, which would otherwise make the future's type recursive.
+fn start_step<'a>(
+    pool: &'a SqlitePool,
+    process_service: &'a ProcessService,
+    task_id: &'a str,
+    step_index: i32,
+) -> StepFuture<'a> {
+    Box::pin(async move {
+        let run = get(pool, task_id).await?;
+        let step = run.step(step_index).ok_or_else(|| ServiceError::NotFound {
+            entity: "WorkflowStep",
+            id: format!("{}/{}", task_id, step_index),
+        })?;
+        let chat_id = step.chat_id.clone().ok_or_else(|| {
+            ServiceError::Validation(format!(
+                "Step {} of task {} has no chat",
+                step_index, task_id
+            ))
+        })?;
+        if active_run(pool, &chat_id).await?.is_some() {
+            return Err(ServiceError::Validation(format!(
+                "Step {} of task {} is already running",
+                step_index, task_id
+            )));
+        }
+        if !step.dependencies_met(&run.steps) {
+            return Err(ServiceError::Validation(format!(
+                "Step {} of task {} is waiting on steps {:?}",
+                step_index, task_id, step.depends_on
+            )));
+        }
+
+        let task = task::get_task(pool, task_id).await?;
+        let project = project::get(pool, &task.project_id).await?;
+        let chat = chat::get_chat(pool, &chat_id).await?;
+
+        // Provision the worktree first so the prompt can refer to it
+        let chat = ensure_step_worktree(pool, task_id, step, &chat, &project).await?;
+        let mut context = WorkflowContext::new();
+        if let Some(worktree_path) = &chat.worktree_path {
+            context = context.with_worktree_path(worktree_path);
+        }
+        let mut prompt =
+            context.substitute(chat.initial_prompt.as_deref().unwrap_or(&step.description));
+        if let Some(hidden_prompt) = chat.hidden_prompt.as_deref().map(str::trim) {
+            if !hidden_prompt.is_empty() {
+                prompt = format!("{}\n\n{}", context.substitute(hidden_prompt), prompt);
+            }
+        }
+        let unresolved = workflow::find_placeholders(&prompt);
+        if !unresolved.is_empty() {
+            error!(
+                "run_step: step index={} of task_id={} has unresolved variables {:?}",
+                step_index, task_id, unresolved
+            );
+            return Err(ServiceError::Validation(format!(
+                "Prompt of step {} of task {} has unresolved variables: {}",
+                step_index,
+                task_id,
+                unresolved.join(", ")
+            )));
+        }
+        let profile_id = chat
+            .executor_profile_id
+            .clone()
+            .or_else(|| task.default_executor_profile_id.clone());
+
+        let mut executor_context =
+            executor::prepare_with_model(pool, &chat.id, &prompt, profile_id, step.model.clone())
+                .await?;
+        if let Some(timeout_secs) = step.timeout_secs {
+            executor_context.start_request.max_runtime_secs = Some(timeout_secs);
+        }
+        let broadcaster = process_service.broadcaster();
+        broadcaster.broadcast(Event::updated(
+            EntityType::Chat,
+            executor_context.chat.id.clone(),
+            &executor_context.chat,
+        ));
+        worktree::bootstrap(
+            pool,
+            process_service,
+            &executor_context.chat,
+            &executor_context.project,
+        )
+        .await?;
+
+        // Subscribe before starting so a quick exit isn't missed
+        let exits = process_service.subscribe_exits();
+        let process = process_service
+            .start(
+                pool,
+                executor_context.create_request,
+                executor_context.start_request,
+            )
+            .await?;
+        broadcaster.broadcast(Event::created(
+            EntityType::Process,
+            process.id.clone(),
+            &process,
+        ));
+
+        set_step_status(
+            pool,
+            broadcaster.as_ref(),
+            task_id,
+            step_index,
+            WorkflowStepStatus::InProgress,
+        )
+        .await?;
+        tokio::spawn(watch_step(
+            pool.clone(),
+            process_service.clone(),
+            exits,
+            task_id.to_string(),
+            step_index,
+            process.id.clone(),
+        ));
+
+        info!(
+            "run_step: started step index={} of task_id={} chat_id={} process_id={}",
+            step_index, task_id, chat_id, process.id
+        );
+        Ok(process)
+    })
+}
+
+/// Wait for a step's process to exit, then record the step's outcome.
+async fn watch_step(
+    pool: SqlitePool,
+    process_service: ProcessService,
+    mut exits: broadcast::Receiver<String>,
+    task_id: String,
+    step_index: i32,
+    process_id: String,
+) {
+    loop {
+        match exits.recv().await {
+            Ok(id) if id == process_id => break,
+            Ok(_) => continue,
+            Err(RecvError::Lagged(skipped)) => {
+                debug!(
+                    "watch_step: missed {} exits, checking process_id={}",
+                    skipped, process_id
+                );
+                match process::get(&pool, &process_id).await {
+                    Ok(process) if process.is_finished() => break,
+                    Ok(_) => continue,
+                    Err(e) => {
+                        warn!("watch_step: cannot load process_id={}: {}", process_id, e);
+                        return;
+                    }
+                }
+            }
+            Err(RecvError::Closed) => {
+                warn!(
+                    "watch_step: stopped tracking step index={} of task_id={}",
+                    step_index, task_id
+                );
+                return;
+            }
+        }
+    }
+
+    if let Err(e) = finish_step(&pool, &process_service, &task_id, step_index, &process_id).await {
+        error!(
+            "watch_step: failed to finish step index={} of task_id={}: {}",
+            step_index, task_id, e
+        );
+    }
+}
+
+/// Record the outcome of a step whose process has exited.
+///
+/// A successful run that produced the step's required artifacts marks the
+/// step done and, if the step (or else the task) auto-advances, runs the
+/// steps that became ready. Otherwise the step is left in progress so it can
+/// be run again.
+async fn finish_step(
+    pool: &SqlitePool,
+    process_service: &ProcessService,
+    task_id: &str,
+    step_index: i32,
+    process_id: &str,
+) -> ServiceResult<()> {
+    let process = process::get(pool, process_id).await?;
+    if !process.is_successful() {
+        warn!(
+            "finish_step: step index={} of task_id={} failed: process_id={} status={:?} exit_code={:?}",
+            step_index, task_id, process_id, process.status, process.exit_code
+        );
+        return Ok(());
+    }
+
+    let step = get(pool, task_id)
+        .await?
+        .step(step_index)
+        .cloned()
+        .ok_or_else(|| ServiceError::NotFound {
+            entity: "WorkflowStep",
+            id: format!("{}/{}", task_id, step_index),
+        })?;
+    let artifacts_path = PathBuf::from(artifact::get_artifacts_path(pool, task_id).await?);
+    let mut missing = Vec::new();
+    for name in &step.required_artifacts {
+        if !tokio::fs::try_exists(artifacts_path.join(name)).await? {
+            missing.push(name.as_str());
+        }
+    }
+    if !missing.is_empty() {
+        warn!(
+            "finish_step: step index={} of task_id={} is missing artifacts {:?}",
+            step_index, task_id, missing
+        );
+        return Ok(());
+    }
+
+    let run = set_step_status(
+        pool,
+        process_service.broadcaster().as_ref(),
+        task_id,
+        step_index,
+        WorkflowStepStatus::Completed,
+    )
+    .await?;
+    info!(
+        "finish_step: completed step index={} of task_id={}",
+        step_index, task_id
+    );
+
+    if run.is_complete() {
+        info!("finish_step: workflow of task_id={} is complete", task_id);
+        return Ok(());
+    }
+    let task = task::get_task(pool, task_id).await?;
+    if !step.auto_advance.unwrap_or(task.auto_start_next_step) {
+        return Ok(());
+    }
+    let claimed = claim_ready_steps(pool, process_service.broadcaster().as_ref(), task_id).await?;
+    start_claimed_steps(pool, process_service, task_id, claimed).await?;
+    Ok(())
+}
+
+/// Start steps claimed by `claim_ready_steps` and return the indices of the
+/// ones that started.
+///
+/// Every claimed step is attempted. A step that fails to start is set back
+/// to pending, so it isn't left in progress without a run, and the first
+/// failure is returned once all steps have been attempted.
+async fn start_claimed_steps(
+    pool: &SqlitePool,
+    process_service: &ProcessService,
+    task_id: &str,
+    claimed: Vec<i32>,
+) -> ServiceResult<Vec<i32>> {
+    let mut started = Vec::new();
+    let mut first_error = None;
+    for index in claimed {
+        match start_step(pool, process_service, task_id, index).await {
+            Ok(_) => started.push(index),
+            Err(e) => {
+                warn!(
+                    "start_claimed_steps: failed to start step index={} of task_id={}: {}",
+                    index, task_id, e
+                );
+                if let Err(reset_error) = set_step_status(
+                    pool,
+                    process_service.broadcaster().as_ref(),
+                    task_id,
+                    index,
+                    WorkflowStepStatus::Pending,
+                )
+                .await
+                {
+                    error!(
+                        "start_claimed_steps: failed to reset step index={} of task_id={}: {}",
+                        index, task_id, reset_error
+                    );
+                }
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+    match first_error {
+        Some(e) => Err(e),
+        None => Ok(started),
+    }
+}
+
+/// Mark the steps that are ready as in progress and return their indices.
+///
+/// Claiming reads and rewrites the workflow document under the document
+/// lock, so when parallel steps finish together only one of them claims the
+/// step that joins them.
+async fn claim_ready_steps(
+    pool: &SqlitePool,
+    broadcaster: &dyn EventBroadcaster,
+    task_id: &str,
+) -> ServiceResult<Vec<i32>> {
+    let task = task::get_task(pool, task_id).await?;
+    let path = document_path(pool, task_id).await?;
+
+    let (content, claimed) = {
+        let _guard = DOCUMENT_LOCK.lock().await;
+        let mut content = tokio::fs::read_to_string(&path).await?;
+        let steps = workflow::parse(&content)?;
+        let claimed: Vec<i32> = steps
+            .iter()
+            .filter(|s| s.can_start() && s.dependencies_met(&steps))
+            .map(|s| s.index)
+            .collect();
+        if claimed.is_empty() {
+            return Ok(claimed);
+        }
+        for index in &claimed {
+            content = workflow::set_step_status(&content, *index, WorkflowStepStatus::InProgress)?;
+        }
+        tokio::fs::write(&path, &content).await.map_err(|e| {
+            error!(
+                "claim_ready_steps: failed to write workflow document {:?}: {}",
+                path, e
+            );
+            e
+        })?;
+        (content, claimed)
+    };
+    debug!(
+        "claim_ready_steps: claimed step indices={:?} of task_id={}",
+        claimed, task_id
+    );
+
+    let run = load_run(pool, &task, &path, &content).await?;
+    broadcaster.broadcast(Event::updated(EntityType::Workflow, task_id, &run));
+    Ok(claimed)
+}
+
+/// Rewrite a step's marker in the workflow document and broadcast the run.
+async fn set_step_status(
+    pool: &SqlitePool,
+    broadcaster: &dyn EventBroadcaster,
+    task_id: &str,
+    step_index: i32,
+    status: WorkflowStepStatus,
+) -> ServiceResult<WorkflowRun> {
+    let task = task::get_task(pool, task_id).await?;
+    let path = document_path(pool, task_id).await?;
+
+    let content = {
+        let _guard = DOCUMENT_LOCK.lock().await;
+        let content = tokio::fs::read_to_string(&path).await?;
+        let content = workflow::set_step_status(&content, step_index, status)?;
+        tokio::fs::write(&path, &content).await.map_err(|e| {
+            error!(
+                "set_step_status: failed to write workflow document {:?}: {}",
+                path, e
+            );
+            e
+        })?;
+        content
+    };
+    debug!(
+        "set_step_status: step index={} of task_id={} is {}",
+        step_index, task_id, status
+    );
+
+    let run = load_run(pool, &task, &path, &content).await?;
+    broadcaster.broadcast(Event::updated(EntityType::Workflow, task_id, &run));
+    Ok(run)
+}
+
+/// Check the values a task gives for the variables of its workflow
+/// template.
+///
+/// Templates that cannot be found are not checked; starting the workflow
+/// reports them.
+///
+/// # Errors
+///
+/// Returns `ServiceError::Validation` if a required variable has no value
+/// or a value doesn't match the variable's type.
+pub async fn check_variables(
+    pool: &SqlitePool,
+    project_id: &str,
+    template_id: &str,
+    values: &HashMap<String, String>,
+) -> ServiceResult<()> {
+    let project = project::get(pool, project_id).await?;
+    let template = match load_template(template_id, &project).await {
+        Ok(template) => template,
+        Err(ServiceError::NotFound { .. }) => {
+            debug!(
+                "check_variables: template={} not found, not checking variables",
+                template_id
+            );
+            return Ok(());
+        }
+        Err(e) => return Err(e),
+    };
+    workflow::resolve_variables(&template, values)?;
+    Ok(())
+}
+
+/// Check the workflow variable values of a task about to be created.
+///
+/// Tasks without a workflow template are not checked. Called by the task
+/// routes and commands before `task::create`.
+///
+/// # Errors
+///
+/// See `check_variables`.
+pub async fn check_new_task(pool: &SqlitePool, request: &CreateTaskRequest) -> ServiceResult<()> {
+    let template = match request.workflow_template.as_deref().map(str::trim) {
+        Some(template) if !template.is_empty() => template,
+        _ => return Ok(()),
+    };
+    let values = request.workflow_variables.clone().unwrap_or_default();
+    check_variables(pool, &request.project_id, template, &values)
+        .await
+        .map_err(|e| {
+            warn!(
+                "check_new_task: workflow variables rejected for project_id={} template={}: {}",
+                request.project_id, template, e
+            );
+            e
+        })
+}
+
+/// Create the workflow document of a task from its template.
+async fn create_document(
+    pool: &SqlitePool,
+    task: &Task,
+    project: &Project,
+    path: &Path,
+) -> ServiceResult<()> {
+    let template_id = match task.workflow_template.as_deref().map(str::trim) {
+        Some(id) if !id.is_empty() => id,
+        _ => {
+            return Err(ServiceError::Validation(format!(
+                "Task {} has no workflow template",
+                task.id
+            )))
+        }
+    };
+    debug!(
+        "create_document: resolving template={} for task_id={}",
+        template_id, task.id
+    );
+    let template = load_template(template_id, project).await?;
+
+    let values = match task.workflow_variables.as_deref() {
+        Some(json) => serde_json::from_str(json).map_err(|e| {
+            error!(
+                "create_document: invalid workflow variables of task_id={}: {}",
+                task.id, e
+            );
+            ServiceError::Validation(format!(
+                "Task {} has invalid workflow variables: {}",
+                task.id, e
+            ))
+        })?,
+        None => HashMap::new(),
+    };
+    let values = workflow::resolve_variables(&template, &values)?;
+    let content = workflow::substitute_variables(&template, &values)?;
+
+    let artifacts_path = artifact::get_artifacts_path(pool, &task.id).await?;
+    let context = WorkflowContext::new()
+        .with_artifacts_path(&artifacts_path)
+        .with_project_root(&project.git_repo_path)
+        .with_task_id(&task.id)
+        .with_task_title(&task.title)
+        .with_project_name(&project.name);
+    let content = workflow::substitute_with_context(&content, &context)?;
+
+    tokio::fs::create_dir_all(&artifacts_path).await?;
+    tokio::fs::write(path, content).await.map_err(|e| {
+        error!(
+            "create_document: failed to write workflow document {:?}: {}",
+            path, e
+        );
+        e
+    })?;
+    debug!(
+        "create_document: created workflow document {:?} for task_id={}",
+        path, task.id
+    );
+    Ok(())
+}
+
+/// Load the content of a workflow template of a project.
+///
+/// Templates other than `builtin:` and `file:` ones are paths relative to
+/// the project's repository and must stay inside it.
+///
+/// # Errors
+///
+/// Returns `ServiceError::Validation` for a path outside the repository and
+/// `ServiceError::NotFound` if the template doesn't exist.
+async fn load_template(template_id: &str, project: &Project) -> ServiceResult<String> {
+    let repo_path = Path::new(&project.git_repo_path);
+
+    let content = if template_id.starts_with("builtin:") || template_id.starts_with("file:") {
+        let folder = repo_path.join(&project.workflows_folder);
+        workflow::get_template(template_id, Some(&folder))
+            .await?
+            .map(|template| template.content)
+    } else {
+        match repo_template_path(repo_path, template_id).await? {
+            Some(path) => tokio::fs::read_to_string(path).await.ok(),
+            None => None,
+        }
+    };
+    content.ok_or_else(|| ServiceError::NotFound {
+        entity: "WorkflowTemplate",
+        id: template_id.to_string(),
+    })
+}
+
+/// Resolve a template path relative to a repository, or `None` if nothing
+/// exists there.
+///
+/// Absolute paths and `..` are rejected, and so are paths that resolve
+/// outside the repository through a symlink.
+async fn repo_template_path(repo_path: &Path, template_id: &str) -> ServiceResult<Option<PathBuf>> {
+    let outside = || {
+        warn!(
+            "repo_template_path: rejected template={} outside {:?}",
+            template_id, repo_path
+        );
+        ServiceError::Validation(format!(
+            "Workflow template {} is not a path inside the project repository",
+            template_id
+        ))
+    };
+
+    let relative = Path::new(template_id);
+    if !relative
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+    {
+        return Err(outside());
+    }
+
+    let (Ok(repo), Ok(path)) = (
+        tokio::fs::canonicalize(repo_path).await,
+        tokio::fs::canonicalize(repo_path.join(relative)).await,
+    ) else {
+        return Ok(None);
+    };
+    if !path.starts_with(&repo) {
+        return Err(outside());
+    }
+    Ok(Some(path))
+}
+
+/// Build a task's workflow run from its workflow document.
+async fn load_run(
+    pool: &SqlitePool,
+    task: &Task,
+    path: &Path,
+    content: &str,
+) -> ServiceResult<WorkflowRun> {
+    let chats = chat::list(pool, &task.id).await?;
+    let steps = workflow::parse(content)?
+        .into_iter()
+        .map(|mut step| {
+            step.chat_id = step_chat(&chats, step.index).map(|c| c.id.clone());
+            step
+        })
+        .collect();
+    Ok(WorkflowRun {
+        task_id: task.id.clone(),
+        template: task.workflow_template.clone().unwrap_or_default(),
+        path: path.to_string_lossy().into_owned(),
+        steps,
+    })
+}
+
+/// Resolve a step's executor profile (name or ID) to a profile ID.
+async fn step_profile(
+    pool: &SqlitePool,
+    task_id: &str,
+    step_index: i32,
+    reference: &str,
+) -> ServiceResult<String> {
+    match executor_profile::find(pool, reference).await? {
+        Some(profile) => Ok(profile.id),
+        None => {
+            error!(
+                "step_profile: unknown executor profile {:?} for step index={} of task_id={}",
+                reference, step_index, task_id
+            );
+            Err(ServiceError::Validation(format!(
+                "Step {} of task {} uses unknown executor profile '{}'",
+                step_index, task_id, reference
+            )))
+        }
+    }
+}
+
+/// Provision the worktree of a step's chat.
+///
+/// A step without dependencies branches off the task's base branch. A step
+/// with dependencies branches off the branch of its first dependency, and
+/// the branches of its other dependencies are merged in, so a step joining
+/// parallel steps starts from the work of all of them.
+async fn ensure_step_worktree(
+    pool: &SqlitePool,
+    task_id: &str,
+    step: &WorkflowStep,
+    chat: &Chat,
+    project: &Project,
+) -> ServiceResult<Chat> {
+    if worktree::has_worktree(chat) || step.depends_on.is_empty() {
+        return worktree::ensure_for_chat(pool, chat, project).await;
+    }
+
+    let chats = chat::list(pool, task_id).await?;
+    let mut branches = Vec::with_capacity(step.depends_on.len());
+    for index in &step.depends_on {
+        let branch = step_chat(&chats, *index)
+            .and_then(|c| c.branch.clone())
+            .ok_or_else(|| {
+                ServiceError::Validation(format!(
+                    "Step {} of task {} has no branch for step {} to start from",
+                    index, task_id, step.index
+                ))
+            })?;
+        branches.push(branch);
+    }
+    debug!(
+        "ensure_step_worktree: step index={} of task_id={} starts from branches {:?}",
+        step.index, task_id, branches
+    );
+    worktree::ensure_for_chat_from(pool, chat, project, &branches).await
+}
+
+/// Find the chat of a step.
+fn step_chat(chats: &[Chat], step_index: i32) -> Option<&Chat> {
+    chats
+        .iter()
+        .find(|c| c.workflow_step_index == Some(step_index))
+}
+
+/// Get the coding agent run of a chat that is running or queued, if any.
+async fn active_run(pool: &SqlitePool, chat_id: &str) -> ServiceResult<Option<ExecutionProcess>> {
+    Ok(process::list_by_chat(pool, chat_id)
+        .await?
+        .into_iter()
+        .find(|p| p.run_reason == RunReason::Codingagent && !p.is_finished()))
+}
+
+/// Get the most recent coding agent run of a chat, if any.
+async fn latest_run(pool: &SqlitePool, chat_id: &str) -> ServiceResult<Option<ExecutionProcess>> {
+    Ok(process::list_by_chat(pool, chat_id)
+        .await?
+        .into_iter()
+        .find(|p| p.run_reason == RunReason::Codingagent))
+}
+
+/// Path of a task's workflow document.
+async fn document_path(pool: &SqlitePool, task_id: &str) -> ServiceResult<PathBuf> {
+    Ok(PathBuf::from(artifact::get_artifacts_path(pool, task_id).await?).join(WORKFLOW_DOCUMENT))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openflow_contracts::{
+        ChatRole, CreateExecutorProfileRequest, CreateProcessRequest, CreateProjectRequest,
+        CreateTaskRequest, StartProcessRequest, UpdateTaskRequest,
+    };
+    use openflow_db::create_test_db;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    use crate::services::{executor_profile, settings};
+
+    const TWO_STEPS: &str = "# Two Steps\n\n### [ ] Step: Write\nWrite {@task_title} into {@worktree_path}.\n\n### [ ] Step: Review\nReview it.\n";
+
+    const FAN_OUT: &str = r#"# Fan Out
+
+### [ ] Step: Implement
+Implement it.
+
+### [ ] Step: Tests
+```step
+depends_on: [Implement]
+```
+Write tests.
+
+### [ ] Step: Docs
+```step
+depends_on: [Implement]
+```
+Write docs.
+
+### [ ] Step: Review
+```step
+depends_on: [Tests, Docs]
+```
+Review it.
+"#;
+
+    const PARALLEL_START: &str = r#"# Parallel Start
+
+### [ ] Step: Tests
+Write tests.
+
+### [ ] Step: Docs
+```step
+depends_on: []
+```
+Write docs.
+"#;
+
+    const WITH_VARIABLES: &str = r#"---
+variables:
+  ticket_url:
+    description: Link to the ticket
+  max_files:
+    type: number
+    default: 3
+---
+
+### [ ] Step: Fix
+Fix {@ticket_url} in at most {@max_files} files.
+"#;
+
+    const CONFIGURED_STEPS: &str = r#"---
+defaults:
+  required_artifacts: [review.md]
+---
+
+### [ ] Step: Write
+```step
+required_artifacts: []
+hidden_prompt: Keep it short.
+```
+Write it.
+
+### [ ] Step: Review
+```step
+executor_profile: reviewer
+chat_role: review
+```
+Review it into {@artifacts_path}/review.md.
+"#;
+
+    struct Fixture {
+        pool: SqlitePool,
+        repo: TempDir,
+        _worktrees: TempDir,
+        project: Project,
+    }
+
+    /// A project in a fresh git repository whose default executor runs
+    /// `command`.
+    async fn setup(command: &str) -> Fixture {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let repo = TempDir::new().unwrap();
+        let worktrees = TempDir::new().unwrap();
+        for args in [
+            &["init", "-q", "-b", "main"][..],
+            &["commit", "-q", "--allow-empty", "-m", "init"][..],
+        ] {
+            let status = Command::new("git")
+                .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+                .args(args)
+                .current_dir(repo.path())
+                .status()
+                .expect("Failed to run git");
+            assert!(status.success());
+        }
+        settings::set(
+            &pool,
+            worktree::BASE_PATH_SETTING,
+            worktrees.path().to_str().unwrap(),
+        )
+        .await
+        .unwrap();
+        executor_profile::create(
+            &pool,
+            CreateExecutorProfileRequest::new("Agent", command).with_is_default(true),
+        )
+        .await
+        .unwrap();
+        let project = project::create(
+            &pool,
+            CreateProjectRequest {
+                name: "Workflow Project".to_string(),
+                git_repo_path: repo.path().to_string_lossy().into_owned(),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        Fixture {
+            pool,
+            repo,
+            _worktrees: worktrees,
+            project,
+        }
+    }
+
+    async fn create_task(fixture: &Fixture, template: &str, auto_start: bool) -> Task {
+        let mut request = CreateTaskRequest::new(&fixture.project.id, "Add greeting");
+        request.workflow_template = Some(template.to_string());
+        let task = task::create(&fixture.pool, request).await.unwrap();
+        task::update(
+            &fixture.pool,
+            &task.id,
+            UpdateTaskRequest {
+                auto_start_next_step: Some(auto_start),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap()
+    }
+
+    async fn wait_for_run(
+        pool: &SqlitePool,
+        task_id: &str,
+        done: impl Fn(&WorkflowRun) -> bool,
+    ) -> WorkflowRun {
+        for _ in 0..200 {
+            let run = get(pool, task_id).await.unwrap();
+            if done(&run) {
+                return run;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        }
+        panic!("workflow of task {} did not progress in time", task_id);
+    }
+
+    #[tokio::test]
+    async fn test_start_requires_workflow_template() {
+        let fixture = setup("true").await;
+        let task = task::create(
+            &fixture.pool,
+            CreateTaskRequest::new(&fixture.project.id, "No workflow"),
+        )
+        .await
+        .unwrap();
+
+        let result = start(&fixture.pool, &ProcessService::new(), &task.id).await;
+        assert!(matches!(result, Err(ServiceError::Validation(_))));
+        assert!(matches!(
+            get(&fixture.pool, &task.id).await,
+            Err(ServiceError::NotFound { .. })
+        ));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_start_runs_steps_to_completion() {
+        let fixture = setup("true").await;
+        std::fs::write(fixture.repo.path().join("two-steps.md"), TWO_STEPS).unwrap();
+        let task = create_task(&fixture, "two-steps.md", true).await;
+
+        let run = start(&fixture.pool, &ProcessService::new(), &task.id)
+            .await
+            .expect("Failed to start workflow");
+        assert_eq!(run.steps.len(), 2);
+        assert!(run.steps.iter().all(|s| s.chat_id.is_some()));
+
+        let run = wait_for_run(&fixture.pool, &task.id, WorkflowRun::is_complete).await;
+        let document = std::fs::read_to_string(&run.path).unwrap();
+        assert!(document.contains("### [x] Step: Write\nWrite Add greeting into {@worktree_path}."));
+        assert!(document.contains("### [x] Step: Review"));
+
+        let chats = chat::list(&fixture.pool, &task.id).await.unwrap();
+        assert_eq!(chats.len(), 2);
+        assert_eq!(chats[0].workflow_step_index, Some(0));
+        assert_eq!(
+            chats[0].initial_prompt.as_deref(),
+            Some("Write Add greeting into {@worktree_path}.")
+        );
+        for chat in &chats {
+            let processes = process::list_by_chat(&fixture.pool, &chat.id)
+                .await
+                .unwrap();
+            assert_eq!(processes.len(), 1);
+            assert!(processes[0].is_successful());
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_resume_finishes_steps_that_already_succeeded() {
+        let fixture = setup("true").await;
+        std::fs::write(fixture.repo.path().join("two-steps.md"), TWO_STEPS).unwrap();
+        let task = create_task(&fixture, "two-steps.md", false).await;
+        let service = ProcessService::new();
+
+        start(&fixture.pool, &service, &task.id)
+            .await
+            .expect("Failed to start workflow");
+        let run = wait_for_run(&fixture.pool, &task.id, |run| {
+            run.steps[0].status == WorkflowStepStatus::Completed
+        })
+        .await;
+
+        // Simulate a restart before the step's exit was recorded
+        let document = std::fs::read_to_string(&run.path).unwrap();
+        let document =
+            workflow::set_step_status(&document, 0, WorkflowStepStatus::InProgress).unwrap();
+        std::fs::write(&run.path, document).unwrap();
+
+        let run = start(&fixture.pool, &service, &task.id)
+            .await
+            .expect("Failed to resume workflow");
+        assert_eq!(run.steps[0].status, WorkflowStepStatus::Completed);
+        assert!(run.steps[1].is_active());
+        let processes =
+            process::list_by_chat(&fixture.pool, run.steps[0].chat_id.as_ref().unwrap())
+                .await
+                .unwrap();
+        assert_eq!(processes.len(), 1);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_parallel_steps_join() {
+        let fixture = setup("true").await;
+        std::fs::write(fixture.repo.path().join("fan-out.md"), FAN_OUT).unwrap();
+        let task = create_task(&fixture, "fan-out.md", true).await;
+        let service = ProcessService::new();
+
+        start(&fixture.pool, &service, &task.id)
+            .await
+            .expect("Failed to start workflow");
+        // The review waits for both branches
+        assert!(matches!(
+            run_step(&fixture.pool, &service, &task.id, 3).await,
+            Err(ServiceError::Validation(_))
+        ));
+
+        wait_for_run(&fixture.pool, &task.id, WorkflowRun::is_complete).await;
+
+        let chats = chat::list(&fixture.pool, &task.id).await.unwrap();
+        assert_eq!(chats.len(), 4);
+        assert_ne!(chats[1].worktree_path, chats[2].worktree_path);
+
+        let mut processes = Vec::new();
+        for chat in &chats {
+            let mut runs = process::list_by_chat(&fixture.pool, &chat.id)
+                .await
+                .unwrap();
+            assert_eq!(runs.len(), 1, "step {:?} ran more than once", chat.title);
+            processes.push(runs.remove(0));
+        }
+        let review_started = &processes[3].started_at;
+        for branch in &processes[1..3] {
+            assert!(branch.completed_at.as_ref().unwrap() <= review_started);
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_steps_start_from_their_dependencies_work() {
+        use std::os::unix::fs::PermissionsExt;
+
+        // Every step commits a file named after its prompt
+        let scripts = TempDir::new().unwrap();
+        let script = scripts.path().join("commit-step.sh");
+        std::fs::write(
+            &script,
+            "#!/bin/sh\nname=$(printf '%s' \"$1\" | tr -cd 'a-zA-Z')\n\
+             echo \"$1\" > \"$name.txt\"\ngit add \"$name.txt\"\ngit commit -qm \"$name\"\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let fixture = setup(script.to_str().unwrap()).await;
+        for (key, value) in [("user.name", "Test"), ("user.email", "test@example.com")] {
+            let status = Command::new("git")
+                .args(["config", key, value])
+                .current_dir(fixture.repo.path())
+                .status()
+                .unwrap();
+            assert!(status.success());
+        }
+        std::fs::write(fixture.repo.path().join("fan-out.md"), FAN_OUT).unwrap();
+        let task = create_task(&fixture, "fan-out.md", true).await;
+
+        start(&fixture.pool, &ProcessService::new(), &task.id)
+            .await
+            .expect("Failed to start workflow");
+        wait_for_run(&fixture.pool, &task.id, WorkflowRun::is_complete).await;
+
+        let chats = chat::list(&fixture.pool, &task.id).await.unwrap();
+        let worktree = |index: usize| PathBuf::from(chats[index].worktree_path.as_ref().unwrap());
+        // The parallel steps start from the work of step 0
+        assert!(worktree(1).join("Implementit.txt").exists());
+        assert!(worktree(2).join("Implementit.txt").exists());
+        assert!(!worktree(1).join("Writedocs.txt").exists());
+        // The joining step has the work of both parallel steps
+        for file in [
+            "Implementit.txt",
+            "Writetests.txt",
+            "Writedocs.txt",
+            "Reviewit.txt",
+        ] {
+            assert!(worktree(3).join(file).exists(), "{} is missing", file);
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_claimed_steps_start_despite_failures() {
+        let fixture = setup("true").await;
+        std::fs::write(fixture.repo.path().join("parallel.md"), PARALLEL_START).unwrap();
+        let task = create_task(&fixture, "parallel.md", false).await;
+        let service = ProcessService::new();
+
+        // The chat of the first step is busy, so that step cannot start
+        let busy_chat = chat::create(
+            &fixture.pool,
+            CreateChatRequest::for_task(&task.id, &fixture.project.id)
+                .with_title("Tests")
+                .with_step_index(0),
+        )
+        .await
+        .unwrap();
+        let busy = service
+            .start(
+                &fixture.pool,
+                CreateProcessRequest {
+                    run_reason: RunReason::Codingagent,
+                    ..CreateProcessRequest::terminal(&busy_chat.id, "busy")
+                },
+                StartProcessRequest::script("sleep 30"),
+            )
+            .await
+            .unwrap();
+
+        let result = start(&fixture.pool, &service, &task.id).await;
+        assert!(matches!(result, Err(ServiceError::Validation(_))));
+
+        // The other step still ran, and the first one is pending again
+        let run = wait_for_run(&fixture.pool, &task.id, |run| {
+            run.steps[1].status == WorkflowStepStatus::Completed
+        })
+        .await;
+        assert_eq!(run.steps[0].status, WorkflowStepStatus::Pending);
+
+        service.kill(&fixture.pool, &busy.id).await.unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_workflow_variables() {
+        let fixture = setup("true").await;
+        std::fs::write(fixture.repo.path().join("variables.md"), WITH_VARIABLES).unwrap();
+
+        let request = CreateTaskRequest::new(&fixture.project.id, "Fix ticket")
+            .with_workflow("variables.md")
+            .with_workflow_variable("max_files", "two");
+        let err = check_new_task(&fixture.pool, &request).await.unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("missing values for workflow variables: ticket_url"));
+        assert!(message.contains("'max_files' must be a number"));
+
+        let request = CreateTaskRequest::new(&fixture.project.id, "Fix ticket")
+            .with_workflow("variables.md")
+            .with_workflow_variable("ticket_url", "https://example.com/T-7");
+        check_new_task(&fixture.pool, &request).await.unwrap();
+        let task = task::create(&fixture.pool, request).await.unwrap();
+
+        let run = start(&fixture.pool, &ProcessService::new(), &task.id)
+            .await
+            .expect("Failed to start workflow");
+        assert_eq!(
+            run.steps[0].description,
+            "Fix https://example.com/T-7 in at most 3 files."
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_templates_stay_inside_repository() {
+        let fixture = setup("true").await;
+        let outside = TempDir::new().unwrap();
+        std::fs::write(outside.path().join("outside.md"), TWO_STEPS).unwrap();
+        std::fs::create_dir(fixture.repo.path().join("flows")).unwrap();
+        std::fs::write(fixture.repo.path().join("flows/inside.md"), TWO_STEPS).unwrap();
+        std::os::unix::fs::symlink(
+            outside.path().join("outside.md"),
+            fixture.repo.path().join("linked.md"),
+        )
+        .unwrap();
+        let escaping = format!(
+            "../{}/outside.md",
+            outside.path().file_name().unwrap().to_str().unwrap()
+        );
+        let absolute = outside.path().join("outside.md");
+
+        for template in [escaping.as_str(), absolute.to_str().unwrap(), "linked.md"] {
+            let request =
+                CreateTaskRequest::new(&fixture.project.id, "Escape").with_workflow(template);
+            let result = check_new_task(&fixture.pool, &request).await;
+            assert!(
+                matches!(result, Err(ServiceError::Validation(_))),
+                "template {} was accepted",
+                template
+            );
+        }
+
+        let task = create_task(&fixture, "./flows/inside.md", false).await;
+        let run = start(&fixture.pool, &ProcessService::new(), &task.id)
+            .await
+            .expect("Failed to start workflow");
+        assert_eq!(run.steps.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_start_rejects_unknown_step_profile() {
+        let fixture = setup("true").await;
+        std::fs::write(fixture.repo.path().join("configured.md"), CONFIGURED_STEPS).unwrap();
+        let task = create_task(&fixture, "configured.md", true).await;
+
+        let result = start(&fixture.pool, &ProcessService::new(), &task.id).await;
+        assert!(matches!(result, Err(ServiceError::Validation(_))));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_step_config_applies_to_chats_and_runs() {
+        let fixture = setup("true").await;
+        let reviewer = executor_profile::create(
+            &fixture.pool,
+            CreateExecutorProfileRequest::new("Reviewer", "true"),
+        )
+        .await
+        .unwrap();
+        std::fs::write(fixture.repo.path().join("configured.md"), CONFIGURED_STEPS).unwrap();
+        let task = create_task(&fixture, "configured.md", true).await;
+
+        let run = start(&fixture.pool, &ProcessService::new(), &task.id)
+            .await
+            .expect("Failed to start workflow");
+        assert_eq!(run.steps[1].required_artifacts, vec!["review.md"]);
+
+        let chats = chat::list(&fixture.pool, &task.id).await.unwrap();
+        assert_eq!(chats[0].chat_role, ChatRole::Main);
+        assert_eq!(chats[0].hidden_prompt.as_deref(), Some("Keep it short."));
+        assert_eq!(
+            chats[1].executor_profile_id.as_deref(),
+            Some(reviewer.id.as_str())
+        );
+        assert_eq!(chats[1].chat_role, ChatRole::Review);
+
+        // The review ran but didn't write review.md, so it stays in progress
+        let chat_id = chats[1].id.clone();
+        for _ in 0..200 {
+            let processes = process::list_by_chat(&fixture.pool, &chat_id)
+                .await
+                .unwrap();
+            if processes.iter().any(|p| p.is_finished()) {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        }
+        // Give the watcher a moment to handle the exit
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+
+        let run = get(&fixture.pool, &task.id).await.unwrap();
+        assert_eq!(run.steps[0].status, WorkflowStepStatus::Completed);
+        assert!(run.steps[1].is_active());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_failed_step_stays_in_progress() {
+        let fixture = setup("false").await;
+        let task = create_task(&fixture, "builtin:feature", true).await;
+        let service = ProcessService::new();
+
+        let run = start(&fixture.pool, &service, &task.id)
+            .await
+            .expect("Failed to start workflow");
+        assert_eq!(run.steps.len(), 4);
+        assert!(run.steps[0].is_active());
+        let artifacts_path = artifact::get_artifacts_path(&fixture.pool, &task.id)
+            .await
+            .unwrap();
+        assert_eq!(
+            run.path,
+            format!("{}/{}", artifacts_path, WORKFLOW_DOCUMENT)
+        );
+
+        let chat_id = run.steps[0].chat_id.clone().unwrap();
+        for _ in 0..200 {
+            if active_run(&fixture.pool, &chat_id).await.unwrap().is_none() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        }
+        // Give the watcher a moment to handle the exit
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+
+        let run = get(&fixture.pool, &task.id).await.unwrap();
+        assert!(run.steps[0].is_active());
+        assert!(run.steps[1..].iter().all(|s| s.can_start()));
+        let chat = chat::get_chat(&fixture.pool, &chat_id).await.unwrap();
+        assert!(chat
+            .initial_prompt
+            .unwrap()
+            .contains(&format!("{}/requirements.md", artifacts_path)));
+
+        // Running the step again is allowed once it has stopped
+        let process = run_step(&fixture.pool, &service, &task.id, 0)
+            .await
+            .unwrap();
+        assert_eq!(process.chat_id, chat_id);
+    }
+}
@@ -0,0 +1,1091 @@
+//! Chat worktree lifecycle service.
+//!
+//! Every chat runs its agents in its own git worktree so that parallel chats
+//! never edit the same checkout. This service provisions the worktree of a
+//! chat on demand and records it on the chat (`branch`, `worktree_path`,
+//! `base_branch`). New branches are cut from the task's or project's base
+//! branch, or, for workflow steps, from the branches of the steps they
+//! depend on (see `ensure_for_chat_from`).
+//!
+//! Worktrees are created under the directory configured by the
+//! `worktrees.base_path` setting (default `~/.openflow/worktrees`), following
+//! the conventions of `git::generate_branch_name` and
+//! `git::generate_worktree_path`.
+//!
+//! Before the first agent run a worktree is bootstrapped (see `bootstrap`):
+//! the project's `copy_files` are copied from the main repository and its
+//! `setup_script` is run as a `Setupscript` execution process. The chat's
+//! `setup_completed_at` is only set once setup succeeds.
+//!
+//! When a task is done or cancelled its worktrees are reclaimed (see
+//! `cleanup`): the project's `cleanup_script` is run, the worktree is
+//! removed and the chat is marked `worktree_deleted`. Branches are kept by
+//! default.
+//!
+//! # Logging
+//!
+//! This service uses the `log` crate for structured logging:
+//! - `debug!`: Detailed operation tracing (paths, branch names)
+//! - `info!`: Successful operations (worktree provisioned)
+//! - `error!`: Operation failures (logged before returning error)
+//!
+//! # Error Handling
+//!
+//! All functions return `ServiceResult<T>`. Git failures are reported as
+//! `ServiceError::Git`, failed setup scripts as `ServiceError::Process`.
+
+use std::path::{Component, Path};
+
+use chrono::Utc;
+use log::{debug, error, info, warn};
+use sqlx::SqlitePool;
+
+use openflow_contracts::{
+    Chat, CreateProcessRequest, ExecutionProcess, ProcessStatus, Project, ResourceLimits,
+    RunReason, StartProcessRequest, UpdateChatRequest,
+};
+
+use super::process::{self, ProcessService};
+use super::{chat, git, project, settings, task, ServiceError, ServiceResult};
+use crate::events::{EntityType, Event};
+
+/// Setting key for the directory chat worktrees are created in.
+pub const BASE_PATH_SETTING: &str = "worktrees.base_path";
+
+/// Default directory chat worktrees are created in.
+pub const DEFAULT_BASE_PATH: &str = "~/.openflow/worktrees";
+
+/// Check whether a chat has a usable worktree on disk.
+pub fn has_worktree(chat: &Chat) -> bool {
+    !chat.worktree_deleted
+        && chat
+            .worktree_path
+            .as_deref()
+            .is_some_and(|path| Path::new(path).is_dir())
+}
+
+/// Resolve the branch a chat's worktree is created from.
+///
+/// Uses the task's base branch override if set, otherwise the project's
+/// base branch.
+async fn resolve_base_branch(
+    pool: &SqlitePool,
+    chat: &Chat,
+    project: &Project,
+) -> ServiceResult<String> {
+    if let Some(task_id) = &chat.task_id {
+        let task = task::get(pool, task_id).await?.task;
+        if let Some(base_branch) = task.base_branch.filter(|b| !b.is_empty()) {
+            debug!(
+                "resolve_base_branch: using task override chat_id={} base_branch={}",
+                chat.id, base_branch
+            );
+            return Ok(base_branch);
+        }
+    }
+    Ok(project.base_branch.clone())
+}
+
+/// Ensure a chat has a git worktree, creating one if needed.
+///
+/// If the chat already has a worktree on disk it is returned unchanged.
+/// Otherwise a worktree is created in the project repository:
+///
+/// - If the chat's branch still exists (e.g. it was kept when the previous
+///   worktree was deleted), the branch is checked out in a new worktree.
+/// - Otherwise a new branch named by `git::generate_branch_name` is created
+///   from the base branch. Standalone chats use the chat ID in place of the
+///   task ID. If the name or path is already taken by another chat, the
+///   chat ID is appended to keep them unique.
+///
+/// The branch, worktree path and base branch are saved on the chat.
+///
+/// # Errors
+///
+/// Returns `ServiceError::Git` if the worktree cannot be created.
+pub async fn ensure_for_chat(
+    pool: &SqlitePool,
+    chat: &Chat,
+    project: &Project,
+) -> ServiceResult<Chat> {
+    provision(pool, chat, project, &[]).await
+}
+
+/// Ensure a chat has a git worktree like `ensure_for_chat`, but start a new
+/// branch from other branches instead of the base branch.
+///
+/// The branch is cut from the first of `branches` and the others are merged
+/// into it. Workflow steps use this to start from the work of the steps they
+/// depend on. If a merge fails, the new worktree and branch are removed
+/// again, so the next attempt starts over.
+///
+/// # Errors
+///
+/// Returns `ServiceError::Git` if the worktree cannot be created or a
+/// branch cannot be merged.
+pub async fn ensure_for_chat_from(
+    pool: &SqlitePool,
+    chat: &Chat,
+    project: &Project,
+    branches: &[String],
+) -> ServiceResult<Chat> {
+    provision(pool, chat, project, branches).await
+}
+
+/// Implementation of `ensure_for_chat` and `ensure_for_chat_from`.
+async fn provision(
+    pool: &SqlitePool,
+    chat: &Chat,
+    project: &Project,
+    branches: &[String],
+) -> ServiceResult<Chat> {
+    if has_worktree(chat) {
+        debug!(
+            "ensure_for_chat: chat_id={} already has worktree {:?}",
+            chat.id, chat.worktree_path
+        );
+        return Ok(chat.clone());
+    }
+
+    let repo_path = project.git_repo_path.as_str();
+    let base_path = settings::get_or_default(pool, BASE_PATH_SETTING, DEFAULT_BASE_PATH).await?;
+    let base_branch = match branches.first() {
+        Some(branch) => branch.clone(),
+        None => resolve_base_branch(pool, chat, project).await?,
+    };
+    let owner_id = chat.task_id.as_deref().unwrap_or(&chat.id);
+    let role = chat.chat_role.to_string();
+    let unique_role = format!("{}-{}", role, short_id(&chat.id));
+
+    debug!(
+        "ensure_for_chat: provisioning worktree chat_id={} repo_path={} base_branch={}",
+        chat.id, repo_path, base_branch
+    );
+
+    let reusable_branch = match &chat.branch {
+        Some(branch) if git::branch_exists(repo_path, branch).await? => Some(branch.clone()),
+        _ => None,
+    };
+
+    let (branch, worktree_path) = if let Some(branch) = reusable_branch {
+        let mut path = git::generate_worktree_path(&base_path, &project.id, owner_id, &role);
+        if Path::new(&path).exists() {
+            path = git::generate_worktree_path(&base_path, &project.id, owner_id, &unique_role);
+        }
+        git::create_worktree_for_branch(repo_path, &branch, &path)
+            .await
+            .map_err(|e| {
+                error!(
+                    "ensure_for_chat: failed to restore worktree for chat_id={}: {}",
+                    chat.id, e
+                );
+                e
+            })?;
+        (branch, path)
+    } else {
+        let mut branch = git::generate_branch_name(owner_id, &role)?;
+        let mut path = git::generate_worktree_path(&base_path, &project.id, owner_id, &role);
+        if git::branch_exists(repo_path, &branch).await? || Path::new(&path).exists() {
+            debug!(
+                "ensure_for_chat: branch {} or path {} taken, using chat-specific names",
+                branch, path
+            );
+            branch = git::generate_branch_name(owner_id, &unique_role)?;
+            path = git::generate_worktree_path(&base_path, &project.id, owner_id, &unique_role);
+        }
+        git::create_worktree(repo_path, &branch, &base_branch, &path)
+            .await
+            .map_err(|e| {
+                error!(
+                    "ensure_for_chat: failed to create worktree for chat_id={}: {}",
+                    chat.id, e
+                );
+                e
+            })?;
+        if let Err(e) = merge_branches(&path, branches.get(1..).unwrap_or_default()).await {
+            error!(
+                "ensure_for_chat: failed to merge branches into worktree for chat_id={}: {}",
+                chat.id, e
+            );
+            if let Err(e) = git::delete_worktree(repo_path, &path).await {
+                warn!("ensure_for_chat: failed to remove worktree {}: {}", path, e);
+            }
+            if let Err(e) = git::delete_branch(repo_path, &branch).await {
+                warn!("ensure_for_chat: failed to delete branch {}: {}", branch, e);
+            }
+            return Err(e);
+        }
+        (branch, path)
+    };
+
+    let request = UpdateChatRequest {
+        base_branch: Some(base_branch),
+        branch: Some(branch.clone()),
+        worktree_path: Some(worktree_path.clone()),
+        worktree_deleted: Some(false),
+        ..Default::default()
+    };
+    let updated = chat::update(pool, &chat.id, request).await?;
+
+    info!(
+        "ensure_for_chat: provisioned worktree chat_id={} branch={} worktree_path={}",
+        chat.id, branch, worktree_path
+    );
+    Ok(updated)
+}
+
+/// Merge branches, one after the other, into a new worktree.
+async fn merge_branches(worktree_path: &str, branches: &[String]) -> ServiceResult<()> {
+    for branch in branches {
+        debug!(
+            "merge_branches: merging {} into worktree {}",
+            branch, worktree_path
+        );
+        git::merge_branch(worktree_path, branch).await?;
+    }
+    Ok(())
+}
+
+/// Copy the project's `copy_files` from the main repository into a worktree.
+///
+/// Entries are paths relative to the repository root and may name files or
+/// directories. Files that are not tracked by git (e.g. `.env`) are the
+/// typical use. Missing entries and paths escaping the repository are
+/// skipped with a warning. Returns the number of entries copied.
+///
+/// # Errors
+///
+/// Returns `ServiceError::Io` if an existing entry cannot be copied.
+pub fn copy_files(project: &Project, worktree_path: &str) -> ServiceResult<usize> {
+    let repo = Path::new(&project.git_repo_path);
+    let worktree = Path::new(worktree_path);
+    let mut copied = 0;
+
+    for entry in project.get_copy_files() {
+        let relative = Path::new(entry.trim());
+        let is_safe = relative
+            .components()
+            .all(|c| matches!(c, Component::Normal(_) | Component::CurDir));
+        if entry.trim().is_empty() || !is_safe {
+            warn!(
+                "copy_files: skipping invalid path {:?} for project_id={}",
+                entry, project.id
+            );
+            continue;
+        }
+
+        let source = repo.join(relative);
+        if !source.exists() {
+            warn!(
+                "copy_files: {} does not exist in project_id={}, skipping",
+                source.display(),
+                project.id
+            );
+            continue;
+        }
+
+        let target = worktree.join(relative);
+        copy_path(&source, &target).map_err(|e| {
+            error!(
+                "copy_files: failed to copy {} to {}: {}",
+                source.display(),
+                target.display(),
+                e
+            );
+            e
+        })?;
+        debug!(
+            "copy_files: copied {} to {}",
+            source.display(),
+            target.display()
+        );
+        copied += 1;
+    }
+
+    Ok(copied)
+}
+
+/// Recursively copy a file or directory, creating parent directories.
+fn copy_path(source: &Path, target: &Path) -> std::io::Result<()> {
+    if source.is_dir() {
+        std::fs::create_dir_all(target)?;
+        for entry in std::fs::read_dir(source)? {
+            let entry = entry?;
+            copy_path(&entry.path(), &target.join(entry.file_name()))?;
+        }
+    } else {
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::copy(source, target)?;
+    }
+    Ok(())
+}
+
+/// Bootstrap a chat's worktree before the first agent run.
+///
+/// Copies the project's `copy_files` into the worktree and runs the
+/// project's `setup_script` there as a `Setupscript` execution process,
+/// waiting for it to finish. Output is streamed and persisted like any other
+/// process. `setup_completed_at` is set on the chat only if the script
+/// succeeds (or the project has none), so a failed setup is retried on the
+/// next run.
+///
+/// If setup has already completed, the chat is returned unchanged. If a
+/// setup script is already running for the chat (e.g. started by a
+/// concurrent run), this waits for it instead of starting another.
+///
+/// # Errors
+///
+/// Returns `ServiceError::Validation` if the chat has no worktree and
+/// `ServiceError::Process` if the setup script fails or cannot be started.
+pub async fn bootstrap(
+    pool: &SqlitePool,
+    process_service: &ProcessService,
+    chat: &Chat,
+    project: &Project,
+) -> ServiceResult<Chat> {
+    if chat.setup_completed_at.is_some() {
+        debug!("bootstrap: setup already completed for chat_id={}", chat.id);
+        return Ok(chat.clone());
+    }
+
+    let worktree_path = match chat.worktree_path.as_deref() {
+        Some(path) if has_worktree(chat) => path,
+        _ => {
+            return Err(ServiceError::Validation(format!(
+                "Chat {} has no worktree to bootstrap",
+                chat.id
+            )))
+        }
+    };
+
+    let running_setup = process::list_running_by_chat(pool, &chat.id)
+        .await?
+        .into_iter()
+        .find(|p| p.run_reason == RunReason::Setupscript);
+
+    let setup = if let Some(running) = running_setup {
+        info!(
+            "bootstrap: waiting for running setup script process_id={} chat_id={}",
+            running.id, chat.id
+        );
+        Some(process::wait_until_finished(pool, &running.id).await?)
+    } else {
+        let copied = copy_files(project, worktree_path)?;
+        debug!(
+            "bootstrap: copied {} entries into worktree chat_id={}",
+            copied, chat.id
+        );
+
+        let script = project.setup_script.trim();
+        if script.is_empty() {
+            None
+        } else {
+            Some(
+                run_script(
+                    pool,
+                    process_service,
+                    CreateProcessRequest::setup_script(&chat.id, script),
+                    worktree_path,
+                    script,
+                    project.get_script_limits().setup,
+                )
+                .await?,
+            )
+        }
+    };
+
+    if let Some(setup) = setup {
+        if setup.status != ProcessStatus::Completed {
+            error!(
+                "bootstrap: setup script failed chat_id={} process_id={} status={:?} exit_code={:?}",
+                chat.id, setup.id, setup.status, setup.exit_code
+            );
+            return Err(ServiceError::Process(format!(
+                "Setup script for chat {} did not complete (status: {}, exit code: {})",
+                chat.id,
+                setup.status,
+                setup
+                    .exit_code
+                    .map_or_else(|| "none".to_string(), |code| code.to_string())
+            )));
+        }
+    }
+
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f").to_string();
+    let updated =
+        chat::update(pool, &chat.id, UpdateChatRequest::mark_setup_completed(now)).await?;
+    process_service.broadcaster().broadcast(Event::updated(
+        EntityType::Chat,
+        updated.id.clone(),
+        &updated,
+    ));
+
+    info!("bootstrap: worktree ready for chat_id={}", chat.id);
+    Ok(updated)
+}
+
+/// Clean up a chat's worktree.
+///
+/// Stops any processes still running for the chat (including its dev
+/// server), runs the project's
+/// `cleanup_script` in the worktree as a `Cleanupscript` execution process,
+/// removes the worktree with `git::delete_worktree` and marks the chat's
+/// worktree as deleted. The branch is kept so the worktree can be restored
+/// later (see `ensure_for_chat`) unless `delete_branch` is set.
+///
+/// A failing cleanup script is logged but does not prevent the worktree from
+/// being removed. Chats without a worktree are returned unchanged.
+///
+/// # Errors
+///
+/// Returns `ServiceError::Git` if the worktree or branch cannot be deleted.
+pub async fn cleanup(
+    pool: &SqlitePool,
+    process_service: &ProcessService,
+    chat: &Chat,
+    project: &Project,
+    delete_branch: bool,
+) -> ServiceResult<Chat> {
+    let worktree_path = match chat.worktree_path.as_deref() {
+        Some(path) if !chat.worktree_deleted => path,
+        _ => {
+            debug!("cleanup: chat_id={} has no worktree to clean up", chat.id);
+            return Ok(chat.clone());
+        }
+    };
+    info!(
+        "cleanup: cleaning up worktree chat_id={} worktree_path={}",
+        chat.id, worktree_path
+    );
+
+    // Queued runs are cancelled too, so they don't start in a removed worktree
+    let active = process::list_by_chat(pool, &chat.id)
+        .await?
+        .into_iter()
+        .filter(|process| !process.is_finished());
+    for running in active {
+        if let Err(e) = process_service.kill(pool, &running.id).await {
+            warn!(
+                "cleanup: failed to stop process_id={} for chat_id={}: {}",
+                running.id, chat.id, e
+            );
+        }
+    }
+
+    let repo_path = project.git_repo_path.as_str();
+    if Path::new(worktree_path).is_dir() {
+        let script = project.cleanup_script.as_deref().unwrap_or_default().trim();
+        if !script.is_empty() {
+            let result = run_script(
+                pool,
+                process_service,
+                CreateProcessRequest::cleanup_script(&chat.id, script),
+                worktree_path,
+                script,
+                project.get_script_limits().cleanup,
+            )
+            .await;
+            match result {
+                Ok(run) if run.status == ProcessStatus::Completed => {}
+                Ok(run) => warn!(
+                    "cleanup: cleanup script failed chat_id={} process_id={} status={:?} exit_code={:?}",
+                    chat.id, run.id, run.status, run.exit_code
+                ),
+                Err(e) => warn!(
+                    "cleanup: failed to run cleanup script for chat_id={}: {}",
+                    chat.id, e
+                ),
+            }
+        }
+
+        git::delete_worktree(repo_path, worktree_path)
+            .await
+            .map_err(|e| {
+                error!(
+                    "cleanup: failed to delete worktree for chat_id={}: {}",
+                    chat.id, e
+                );
+                e
+            })?;
+    } else {
+        debug!(
+            "cleanup: worktree {} already gone for chat_id={}",
+            worktree_path, chat.id
+        );
+    }
+
+    if delete_branch {
+        if let Some(branch) = &chat.branch {
+            if git::branch_exists(repo_path, branch).await? {
+                git::delete_branch(repo_path, branch).await?;
+            }
+        }
+    }
+
+    let updated = chat::update(pool, &chat.id, UpdateChatRequest::mark_worktree_deleted()).await?;
+    process_service.broadcaster().broadcast(Event::updated(
+        EntityType::Chat,
+        updated.id.clone(),
+        &updated,
+    ));
+
+    info!(
+        "cleanup: removed worktree chat_id={} branch_deleted={}",
+        chat.id, delete_branch
+    );
+    Ok(updated)
+}
+
+/// Clean up the worktrees of every chat of a task.
+///
+/// Called when a task moves to done or cancelled. See `cleanup`.
+///
+/// Every chat is attempted even if an earlier one fails; the first error is
+/// returned once all chats have been processed.
+pub async fn cleanup_task(
+    pool: &SqlitePool,
+    process_service: &ProcessService,
+    task_id: &str,
+    delete_branch: bool,
+) -> ServiceResult<Vec<Chat>> {
+    let task = task::get(pool, task_id).await?.task;
+    let project = project::get(pool, &task.project_id).await?;
+
+    let mut chats = Vec::new();
+    let mut first_error = None;
+    for chat in chat::list(pool, task_id).await? {
+        match cleanup(pool, process_service, &chat, &project, delete_branch).await {
+            Ok(cleaned) => chats.push(cleaned),
+            Err(e) => {
+                error!(
+                    "cleanup_task: failed to clean up chat_id={} for task_id={}: {}",
+                    chat.id, task_id, e
+                );
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+
+    info!(
+        "cleanup_task: cleaned up {} chats for task_id={}",
+        chats.len(),
+        task_id
+    );
+    match first_error {
+        Some(e) => Err(e),
+        None => Ok(chats),
+    }
+}
+
+/// Run a project script in a worktree and wait for it to exit.
+///
+/// The script runs in pipe mode through the platform shell as the process
+/// described by `create_request`, whose creation is broadcast, with optional
+/// resource `limits`. Returns the finished process record.
+pub async fn run_script(
+    pool: &SqlitePool,
+    process_service: &ProcessService,
+    create_request: CreateProcessRequest,
+    worktree_path: &str,
+    script: &str,
+    limits: Option<ResourceLimits>,
+) -> ServiceResult<ExecutionProcess> {
+    info!(
+        "run_script: running script reason={} chat_id={} cwd={}",
+        create_request.run_reason, create_request.chat_id, worktree_path
+    );
+
+    let process = process_service
+        .start(
+            pool,
+            create_request,
+            StartProcessRequest {
+                resource_limits: limits,
+                ..StartProcessRequest::script(script).with_cwd(worktree_path)
+            },
+        )
+        .await?;
+    process_service.broadcaster().broadcast(Event::created(
+        EntityType::Process,
+        process.id.clone(),
+        &process,
+    ));
+
+    process::wait_until_finished(pool, &process.id).await
+}
+
+fn short_id(id: &str) -> &str {
+    id.get(..8).unwrap_or(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openflow_contracts::{CreateChatRequest, CreateProjectRequest, CreateTaskRequest};
+    use openflow_db::create_test_db;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    /// Helper to create a git repository with one commit.
+    fn setup_repo() -> TempDir {
+        let dir = TempDir::new().expect("Failed to create temp dir");
+        let run = |args: &[&str]| {
+            Command::new("git")
+                .args(args)
+                .current_dir(dir.path())
+                .output()
+                .expect("Failed to run git");
+        };
+        run(&["init", "-b", "main"]);
+        run(&["config", "user.email", "test@example.com"]);
+        run(&["config", "user.name", "Test User"]);
+        std::fs::write(dir.path().join("README.md"), "# Test\n").unwrap();
+        run(&["add", "."]);
+        run(&["commit", "-m", "Initial commit"]);
+        dir
+    }
+
+    /// Helper to create a project for the repository and point worktrees at
+    /// a temporary directory.
+    async fn setup_project(pool: &SqlitePool, repo: &TempDir, worktrees: &TempDir) -> Project {
+        settings::set(pool, BASE_PATH_SETTING, worktrees.path().to_str().unwrap())
+            .await
+            .expect("Failed to set base path");
+
+        project::create(
+            pool,
+            CreateProjectRequest {
+                name: "Repo".to_string(),
+                git_repo_path: repo.path().to_string_lossy().into_owned(),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Failed to create project")
+    }
+
+    async fn create_chat(pool: &SqlitePool, project: &Project, task_id: Option<String>) -> Chat {
+        chat::create(
+            pool,
+            CreateChatRequest {
+                project_id: project.id.clone(),
+                task_id,
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Failed to create chat")
+    }
+
+    #[tokio::test]
+    async fn test_ensure_for_chat_provisions_worktree() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let repo = setup_repo();
+        let worktrees = TempDir::new().unwrap();
+        let project = setup_project(&pool, &repo, &worktrees).await;
+        let task = task::create(
+            &pool,
+            CreateTaskRequest {
+                project_id: project.id.clone(),
+                title: "Task".to_string(),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Failed to create task");
+        let chat = create_chat(&pool, &project, Some(task.id.clone())).await;
+
+        let provisioned = ensure_for_chat(&pool, &chat, &project)
+            .await
+            .expect("Failed to provision worktree");
+
+        let expected_path = format!(
+            "{}/{}/{}-main",
+            worktrees.path().display(),
+            project.id,
+            task.id
+        );
+        let expected_branch = format!("openflow/{}/main", task.id);
+        assert_eq!(
+            provisioned.worktree_path.as_deref(),
+            Some(expected_path.as_str())
+        );
+        assert_eq!(
+            provisioned.branch.as_deref(),
+            Some(expected_branch.as_str())
+        );
+        assert_eq!(provisioned.base_branch, "main");
+        assert!(has_worktree(&provisioned));
+        assert_eq!(
+            git::get_current_branch(&expected_path).await.unwrap(),
+            expected_branch
+        );
+
+        // A second call reuses the existing worktree
+        let again = ensure_for_chat(&pool, &provisioned, &project)
+            .await
+            .unwrap();
+        assert_eq!(again.worktree_path, provisioned.worktree_path);
+    }
+
+    #[tokio::test]
+    async fn test_ensure_for_chat_unique_per_chat() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let repo = setup_repo();
+        let worktrees = TempDir::new().unwrap();
+        let project = setup_project(&pool, &repo, &worktrees).await;
+        let task = task::create(
+            &pool,
+            CreateTaskRequest {
+                project_id: project.id.clone(),
+                title: "Task".to_string(),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let first = create_chat(&pool, &project, Some(task.id.clone())).await;
+        let second = create_chat(&pool, &project, Some(task.id.clone())).await;
+
+        let first = ensure_for_chat(&pool, &first, &project).await.unwrap();
+        let second = ensure_for_chat(&pool, &second, &project).await.unwrap();
+
+        assert_ne!(first.branch, second.branch);
+        assert_ne!(first.worktree_path, second.worktree_path);
+        assert!(second.branch.unwrap().ends_with(short_id(&second.id)));
+    }
+
+    #[tokio::test]
+    async fn test_ensure_for_chat_from_merges_branches() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let repo = setup_repo();
+        let worktrees = TempDir::new().unwrap();
+        let project = setup_project(&pool, &repo, &worktrees).await;
+        let run = |args: &[&str]| {
+            let output = Command::new("git")
+                .args(args)
+                .current_dir(repo.path())
+                .output()
+                .expect("Failed to run git");
+            assert!(output.status.success(), "git {:?} failed", args);
+        };
+        for (branch, file, content) in [
+            ("left", "README.md", "# Left\n"),
+            ("right", "README.md", "# Right\n"),
+            ("extra", "EXTRA.md", "Extra\n"),
+        ] {
+            run(&["checkout", "-q", "-b", branch, "main"]);
+            std::fs::write(repo.path().join(file), content).unwrap();
+            run(&["add", file]);
+            run(&["commit", "-qm", branch]);
+        }
+        run(&["checkout", "-q", "main"]);
+
+        // Conflicting branches leave no worktree or branch behind
+        let chat = create_chat(&pool, &project, None).await;
+        let branches = ["left".to_string(), "right".to_string()];
+        let result = ensure_for_chat_from(&pool, &chat, &project, &branches).await;
+        assert!(matches!(result, Err(ServiceError::Git(_))));
+        let chat = chat::get_chat(&pool, &chat.id).await.unwrap();
+        assert!(!has_worktree(&chat));
+        let branch = git::generate_branch_name(&chat.id, "main").unwrap();
+        assert!(!git::branch_exists(repo.path().to_str().unwrap(), &branch)
+            .await
+            .unwrap());
+
+        let branches = ["left".to_string(), "extra".to_string()];
+        let chat = ensure_for_chat_from(&pool, &chat, &project, &branches)
+            .await
+            .expect("Failed to provision worktree");
+        let path = Path::new(chat.worktree_path.as_deref().unwrap());
+        assert_eq!(chat.base_branch, "left");
+        assert_eq!(
+            std::fs::read_to_string(path.join("README.md")).unwrap(),
+            "# Left\n"
+        );
+        assert!(path.join("EXTRA.md").exists());
+    }
+
+    #[tokio::test]
+    async fn test_ensure_for_chat_standalone_and_restore_branch() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let repo = setup_repo();
+        let worktrees = TempDir::new().unwrap();
+        let project = setup_project(&pool, &repo, &worktrees).await;
+        let chat = create_chat(&pool, &project, None).await;
+
+        let provisioned = ensure_for_chat(&pool, &chat, &project).await.unwrap();
+        let branch = provisioned.branch.clone().unwrap();
+        assert_eq!(branch, format!("openflow/{}/main", chat.id));
+
+        // Remove the worktree but keep its branch
+        let path = provisioned.worktree_path.clone().unwrap();
+        git::delete_worktree(&project.git_repo_path, &path)
+            .await
+            .unwrap();
+        let deleted = chat::update(&pool, &chat.id, UpdateChatRequest::mark_worktree_deleted())
+            .await
+            .unwrap();
+        assert!(!has_worktree(&deleted));
+
+        let restored = ensure_for_chat(&pool, &deleted, &project).await.unwrap();
+        assert_eq!(restored.branch.as_deref(), Some(branch.as_str()));
+        assert!(!restored.worktree_deleted);
+        assert!(has_worktree(&restored));
+    }
+
+    #[tokio::test]
+    async fn test_ensure_for_chat_not_a_repository() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let not_repo = TempDir::new().unwrap();
+        let worktrees = TempDir::new().unwrap();
+        let project = setup_project(&pool, &not_repo, &worktrees).await;
+        let chat = create_chat(&pool, &project, None).await;
+
+        let result = ensure_for_chat(&pool, &chat, &project).await;
+
+        assert!(matches!(result, Err(crate::services::ServiceError::Git(_))));
+        let unchanged = chat::get_chat(&pool, &chat.id).await.unwrap();
+        assert!(unchanged.worktree_path.is_none());
+    }
+
+    /// Helper to create a chat with a provisioned worktree.
+    async fn provisioned_chat(pool: &SqlitePool, project: &Project) -> Chat {
+        let chat = create_chat(pool, project, None).await;
+        ensure_for_chat(pool, &chat, project)
+            .await
+            .expect("Failed to provision worktree")
+    }
+
+    #[tokio::test]
+    async fn test_copy_files() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let repo = setup_repo();
+        let worktrees = TempDir::new().unwrap();
+        let mut project = setup_project(&pool, &repo, &worktrees).await;
+        std::fs::write(repo.path().join(".env"), "SECRET=1\n").unwrap();
+        std::fs::create_dir_all(repo.path().join("config/local")).unwrap();
+        std::fs::write(repo.path().join("config/local/dev.toml"), "dev = true\n").unwrap();
+        project.copy_files =
+            Some(r#"[".env", "config/local", "missing.txt", "../outside"]"#.to_string());
+        let chat = provisioned_chat(&pool, &project).await;
+        let path = Path::new(chat.worktree_path.as_deref().unwrap());
+
+        let copied = copy_files(&project, path.to_str().unwrap()).unwrap();
+
+        assert_eq!(copied, 2);
+        assert_eq!(
+            std::fs::read_to_string(path.join(".env")).unwrap(),
+            "SECRET=1\n"
+        );
+        assert!(path.join("config/local/dev.toml").is_file());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_bootstrap_runs_setup_script() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let repo = setup_repo();
+        let worktrees = TempDir::new().unwrap();
+        let mut project = setup_project(&pool, &repo, &worktrees).await;
+        std::fs::write(repo.path().join(".env"), "KEY=value\n").unwrap();
+        project.copy_files = Some(r#"[".env"]"#.to_string());
+        project.setup_script = "cat .env > setup.log && echo setup-done".to_string();
+        let chat = provisioned_chat(&pool, &project).await;
+        let service = ProcessService::new();
+
+        let ready = bootstrap(&pool, &service, &chat, &project)
+            .await
+            .expect("Failed to bootstrap");
+
+        assert!(ready.setup_completed_at.is_some());
+        let path = Path::new(ready.worktree_path.as_deref().unwrap());
+        assert_eq!(
+            std::fs::read_to_string(path.join("setup.log")).unwrap(),
+            "KEY=value\n"
+        );
+
+        let processes = process::list_by_chat(&pool, &chat.id).await.unwrap();
+        assert_eq!(processes.len(), 1);
+        assert_eq!(processes[0].run_reason, RunReason::Setupscript);
+        assert_eq!(processes[0].status, ProcessStatus::Completed);
+
+        // Setup is not run again once completed
+        bootstrap(&pool, &service, &ready, &project).await.unwrap();
+        assert_eq!(
+            process::list_by_chat(&pool, &chat.id).await.unwrap().len(),
+            1
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_bootstrap_failed_setup_script() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let repo = setup_repo();
+        let worktrees = TempDir::new().unwrap();
+        let mut project = setup_project(&pool, &repo, &worktrees).await;
+        project.setup_script = "echo broken >&2; exit 3".to_string();
+        let chat = provisioned_chat(&pool, &project).await;
+        let service = ProcessService::new();
+
+        let result = bootstrap(&pool, &service, &chat, &project).await;
+
+        assert!(matches!(result, Err(ServiceError::Process(_))));
+        let unchanged = chat::get_chat(&pool, &chat.id).await.unwrap();
+        assert!(unchanged.setup_completed_at.is_none());
+        let processes = process::list_by_chat(&pool, &chat.id).await.unwrap();
+        assert_eq!(processes[0].status, ProcessStatus::Failed);
+        assert_eq!(processes[0].exit_code, Some(3));
+    }
+
+    #[tokio::test]
+    async fn test_bootstrap_without_setup_script() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let repo = setup_repo();
+        let worktrees = TempDir::new().unwrap();
+        let project = setup_project(&pool, &repo, &worktrees).await;
+        let chat = provisioned_chat(&pool, &project).await;
+
+        let ready = bootstrap(&pool, &ProcessService::new(), &chat, &project)
+            .await
+            .unwrap();
+
+        assert!(ready.setup_completed_at.is_some());
+        assert!(process::list_by_chat(&pool, &chat.id)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_bootstrap_requires_worktree() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let repo = setup_repo();
+        let worktrees = TempDir::new().unwrap();
+        let project = setup_project(&pool, &repo, &worktrees).await;
+        let chat = create_chat(&pool, &project, None).await;
+
+        let result = bootstrap(&pool, &ProcessService::new(), &chat, &project).await;
+
+        assert!(matches!(result, Err(ServiceError::Validation(_))));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_cleanup_runs_script_and_keeps_branch() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let repo = setup_repo();
+        let worktrees = TempDir::new().unwrap();
+        let marker = TempDir::new().unwrap();
+        let mut project = setup_project(&pool, &repo, &worktrees).await;
+        project.cleanup_script = Some(format!("pwd > {}/cleaned", marker.path().display()));
+        let chat = provisioned_chat(&pool, &project).await;
+        let path = chat.worktree_path.clone().unwrap();
+        let branch = chat.branch.clone().unwrap();
+        let service = ProcessService::new();
+
+        let cleaned = cleanup(&pool, &service, &chat, &project, false)
+            .await
+            .expect("Failed to clean up");
+
+        assert!(cleaned.worktree_deleted);
+        assert!(!has_worktree(&cleaned));
+        assert!(!Path::new(&path).exists());
+        assert!(git::branch_exists(&project.git_repo_path, &branch)
+            .await
+            .unwrap());
+        let cleaned_in = std::fs::read_to_string(marker.path().join("cleaned")).unwrap();
+        assert!(cleaned_in
+            .trim()
+            .ends_with(Path::new(&path).file_name().unwrap().to_str().unwrap()));
+
+        let processes = process::list_by_chat(&pool, &chat.id).await.unwrap();
+        assert_eq!(processes.len(), 1);
+        assert_eq!(processes[0].run_reason, RunReason::Cleanupscript);
+
+        // Cleaning up again is a no-op
+        let again = cleanup(&pool, &service, &cleaned, &project, false)
+            .await
+            .unwrap();
+        assert!(again.worktree_deleted);
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_task_deletes_branches() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let repo = setup_repo();
+        let worktrees = TempDir::new().unwrap();
+        let project = setup_project(&pool, &repo, &worktrees).await;
+        let task = task::create(
+            &pool,
+            CreateTaskRequest {
+                project_id: project.id.clone(),
+                title: "Task".to_string(),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        let first = create_chat(&pool, &project, Some(task.id.clone())).await;
+        let second = create_chat(&pool, &project, Some(task.id.clone())).await;
+        let first = ensure_for_chat(&pool, &first, &project).await.unwrap();
+        ensure_for_chat(&pool, &second, &project).await.unwrap();
+
+        let cleaned = cleanup_task(&pool, &ProcessService::new(), &task.id, true)
+            .await
+            .expect("Failed to clean up task");
+
+        assert_eq!(cleaned.len(), 2);
+        for chat in &cleaned {
+            assert!(chat.worktree_deleted);
+            assert!(!Path::new(chat.worktree_path.as_deref().unwrap()).exists());
+        }
+        assert!(
+            !git::branch_exists(&project.git_repo_path, first.branch.as_deref().unwrap())
+                .await
+                .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_task_continues_after_failure() {
+        let pool = create_test_db().await.expect("Failed to create test db");
+        let repo = setup_repo();
+        let worktrees = TempDir::new().unwrap();
+        let project = setup_project(&pool, &repo, &worktrees).await;
+        let task = task::create(
+            &pool,
+            CreateTaskRequest {
+                project_id: project.id.clone(),
+                title: "Task".to_string(),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        let first = create_chat(&pool, &project, Some(task.id.clone())).await;
+        let second = create_chat(&pool, &project, Some(task.id.clone())).await;
+        let first = ensure_for_chat(&pool, &first, &project).await.unwrap();
+        let second = ensure_for_chat(&pool, &second, &project).await.unwrap();
+        // Without its .git file the first worktree can no longer be removed
+        let broken = Path::new(first.worktree_path.as_deref().unwrap());
+        std::fs::remove_file(broken.join(".git")).unwrap();
+
+        let result = cleanup_task(&pool, &ProcessService::new(), &task.id, false).await;
+
+        assert!(result.is_err());
+        assert!(
+            !chat::get_chat(&pool, &first.id)
+                .await
+                .unwrap()
+                .worktree_deleted
+        );
+        let cleaned = chat::get_chat(&pool, &second.id).await.unwrap();
+        assert!(cleaned.worktree_deleted);
+        assert!(!Path::new(second.worktree_path.as_deref().unwrap()).exists());
+    }
+}
@@ -0,0 +1,18 @@
+-- ===========================================
+-- OpenFlow Migration: Process Outputs
+-- Version: 007
+-- Description: Persists process output chunks so clients that connect
+--              late or reconnect can replay the full log of a run
+-- ===========================================
+
+-- Output chunks captured from execution processes, in arrival order.
+-- The autoincrement id doubles as the replay cursor (`since`).
+CREATE TABLE process_outputs (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    process_id      TEXT NOT NULL REFERENCES execution_processes(id) ON DELETE CASCADE,
+    output_type     TEXT NOT NULL DEFAULT 'stdout'
+                    CHECK (output_type IN ('stdout','stderr')),
+    content         TEXT NOT NULL,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
+CREATE INDEX idx_process_outputs_process_id ON process_outputs(process_id, id);
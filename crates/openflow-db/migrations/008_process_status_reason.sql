@@ -0,0 +1,10 @@
+-- ===========================================
+-- OpenFlow Migration: Process Status Reason
+-- Version: 008
+-- Description: Adds status_reason to execution_processes to explain
+--              why a process reached its final status
+-- ===========================================
+
+-- Human-readable explanation of the final status, e.g. a process that was
+-- orphaned by a crash and found dead at startup
+ALTER TABLE execution_processes ADD COLUMN status_reason TEXT;
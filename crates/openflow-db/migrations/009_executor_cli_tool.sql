@@ -0,0 +1,10 @@
+-- ===========================================
+-- OpenFlow Migration: Executor CLI Tool
+-- Version: 009
+-- Description: Adds cli_tool to executor_profiles so each
+--              profile can select its executor adapter
+-- ===========================================
+
+-- CLI tool type driving the profile (claude_code, gemini_cli, codex_cli, ...)
+-- NULL means the tool is inferred from the profile command
+ALTER TABLE executor_profiles ADD COLUMN cli_tool TEXT;
@@ -0,0 +1,10 @@
+-- ===========================================
+-- OpenFlow Migration: Process Dev Server URL
+-- Version: 010
+-- Description: Adds dev_server_url to execution_processes to record the
+--              URL a dev server process is listening on
+-- ===========================================
+
+-- Detected from the dev server's output or by probing its assigned port;
+-- NULL for other processes and until the server is reachable
+ALTER TABLE execution_processes ADD COLUMN dev_server_url TEXT;
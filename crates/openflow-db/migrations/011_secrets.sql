@@ -0,0 +1,16 @@
+-- ===========================================
+-- OpenFlow Migration: Secrets
+-- Version: 011
+-- Description: Adds an encrypted store for API keys and tokens that can be
+--              referenced as ${secret:NAME} instead of kept in app_settings
+-- ===========================================
+
+-- Secret values encrypted with ChaCha20-Poly1305. The key lives in a file
+-- in the app data directory, never in the database.
+CREATE TABLE secrets (
+    name            TEXT PRIMARY KEY,
+    nonce           BLOB NOT NULL,
+    ciphertext      BLOB NOT NULL,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
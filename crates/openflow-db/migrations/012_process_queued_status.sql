@@ -0,0 +1,68 @@
+-- ===========================================
+-- OpenFlow Migration: Process Queued Status
+-- Version: 012
+-- Description: Allows 'queued' as an execution_processes status for runs
+--              waiting in the run queue for a free slot
+-- ===========================================
+
+-- SQLite can't alter a CHECK constraint, so the table is rebuilt.
+-- process_outputs is set aside first: dropping execution_processes would
+-- otherwise cascade-delete every output row, and foreign keys can't be
+-- switched off inside the migration transaction.
+CREATE TABLE process_outputs_backup AS SELECT * FROM process_outputs;
+DROP TABLE process_outputs;
+
+CREATE TABLE execution_processes_new (
+    id                  TEXT PRIMARY KEY,
+    chat_id             TEXT NOT NULL REFERENCES chats(id) ON DELETE CASCADE,
+    executor_profile_id TEXT REFERENCES executor_profiles(id) ON DELETE SET NULL,
+    status              TEXT NOT NULL DEFAULT 'running'
+                        CHECK (status IN ('queued','running','completed','failed','killed')),
+    exit_code           INTEGER,
+    executor_action     TEXT NOT NULL DEFAULT '',  -- What action triggered this
+    run_reason          TEXT NOT NULL DEFAULT 'codingagent'
+                        CHECK (run_reason IN ('setupscript','cleanupscript','codingagent','devserver','terminal','verification')),
+    before_head_commit  TEXT,  -- Git HEAD before execution
+    after_head_commit   TEXT,  -- Git HEAD after execution
+    pid                 INTEGER,  -- OS process ID
+    started_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    completed_at        TEXT,
+    created_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    status_reason       TEXT,
+    dev_server_url      TEXT
+);
+
+INSERT INTO execution_processes_new (
+    id, chat_id, executor_profile_id, status, exit_code, executor_action,
+    run_reason, before_head_commit, after_head_commit, pid, started_at,
+    completed_at, created_at, updated_at, status_reason, dev_server_url
+)
+SELECT
+    id, chat_id, executor_profile_id, status, exit_code, executor_action,
+    run_reason, before_head_commit, after_head_commit, pid, started_at,
+    completed_at, created_at, updated_at, status_reason, dev_server_url
+FROM execution_processes;
+
+DROP TABLE execution_processes;
+ALTER TABLE execution_processes_new RENAME TO execution_processes;
+
+CREATE INDEX idx_execution_processes_chat_id ON execution_processes(chat_id);
+CREATE INDEX idx_execution_processes_status ON execution_processes(status);
+
+CREATE TABLE process_outputs (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    process_id      TEXT NOT NULL REFERENCES execution_processes(id) ON DELETE CASCADE,
+    output_type     TEXT NOT NULL DEFAULT 'stdout'
+                    CHECK (output_type IN ('stdout','stderr')),
+    content         TEXT NOT NULL,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
+
+INSERT INTO process_outputs (id, process_id, output_type, content, created_at)
+SELECT id, process_id, output_type, content, created_at
+FROM process_outputs_backup;
+
+DROP TABLE process_outputs_backup;
+
+CREATE INDEX idx_process_outputs_process_id ON process_outputs(process_id, id);
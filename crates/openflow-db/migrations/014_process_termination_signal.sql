@@ -0,0 +1,10 @@
+-- ===========================================
+-- OpenFlow Migration: Process Termination Signal
+-- Version: 014
+-- Description: Adds termination_signal to execution_processes to record
+--              which signal finally stopped a killed process group
+-- ===========================================
+
+-- Name of the last signal sent before the process group exited, e.g.
+-- SIGINT when it shut down gracefully or SIGKILL when it had to be forced
+ALTER TABLE execution_processes ADD COLUMN termination_signal TEXT;
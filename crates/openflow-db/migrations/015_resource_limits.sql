@@ -0,0 +1,12 @@
+-- ===========================================
+-- OpenFlow Migration: Resource Limits
+-- Version: 015
+-- Description: Adds resource limits (rlimits) to executor profiles and
+--              project scripts
+-- ===========================================
+
+-- JSON object of ResourceLimits applied to every run of the profile
+ALTER TABLE executor_profiles ADD COLUMN resource_limits TEXT;
+
+-- JSON object of ResourceLimits per project script (setup, dev, cleanup)
+ALTER TABLE projects ADD COLUMN script_limits TEXT;
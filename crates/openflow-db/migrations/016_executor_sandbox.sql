@@ -0,0 +1,8 @@
+-- ===========================================
+-- OpenFlow Migration: Executor Sandbox
+-- Version: 016
+-- Description: Adds opt-in sandbox settings to executor profiles
+-- ===========================================
+
+-- JSON object of SandboxSettings (enabled, allowNetwork, readOnlyPaths)
+ALTER TABLE executor_profiles ADD COLUMN sandbox TEXT;
@@ -0,0 +1,26 @@
+-- ===========================================
+-- OpenFlow Migration: Process Recordings
+-- Version: 017
+-- Description: Stores asciicast v2 recordings of PTY processes so terminal
+--              sessions can be replayed or attached to bug reports
+-- ===========================================
+
+-- One recording per PTY process, holding the asciicast header
+CREATE TABLE process_recordings (
+    process_id      TEXT PRIMARY KEY REFERENCES execution_processes(id) ON DELETE CASCADE,
+    width           INTEGER NOT NULL,
+    height          INTEGER NOT NULL,
+    started_at      TEXT NOT NULL
+);
+
+-- Recorded events: 'o' (output) and 'r' (resize, data "COLSxROWS").
+-- `time` is in seconds since the recording started.
+CREATE TABLE process_recording_events (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    process_id      TEXT NOT NULL REFERENCES process_recordings(process_id) ON DELETE CASCADE,
+    time            REAL NOT NULL,
+    event_type      TEXT NOT NULL CHECK (event_type IN ('o','r')),
+    data            TEXT NOT NULL
+);
+CREATE INDEX idx_process_recording_events_process_id
+    ON process_recording_events(process_id, time, id);
@@ -0,0 +1,9 @@
+-- ===========================================
+-- OpenFlow Migration: Workflow Variables
+-- Version: 018
+-- Description: Stores the values a task gives the variables declared by its
+--              workflow template
+-- ===========================================
+
+-- JSON object of variable values by name, e.g. {"ticket_url": "..."}
+ALTER TABLE tasks ADD COLUMN workflow_variables TEXT;
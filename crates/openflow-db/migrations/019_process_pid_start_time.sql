@@ -0,0 +1,11 @@
+-- ===========================================
+-- OpenFlow Migration: Process PID Start Time
+-- Version: 019
+-- Description: Adds pid_start_time to execution_processes so a stored PID
+--              can be told apart from an unrelated process that reused it
+-- ===========================================
+
+-- Start time of the OS process recorded with its PID, e.g. the starttime
+-- field of /proc/<pid>/stat prefixed with the boot id on Linux. NULL when it
+-- could not be read, in which case the PID is never trusted after a restart
+ALTER TABLE execution_processes ADD COLUMN pid_start_time TEXT;
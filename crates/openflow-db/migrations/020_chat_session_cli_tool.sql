@@ -0,0 +1,13 @@
+-- ===========================================
+-- OpenFlow Migration: Chat Session CLI Tool
+-- Version: 020
+-- Description: Adds session_cli_tool to chats so a stored session is only
+--              resumed by the CLI tool that created it
+-- ===========================================
+
+-- CLI tool type that created claude_session_id (claude_code, codex_cli, ...)
+-- NULL means the tool is unknown and the session is never resumed
+ALTER TABLE chats ADD COLUMN session_cli_tool TEXT;
+
+-- Sessions stored so far were recorded from Claude Code stream-json output
+UPDATE chats SET session_cli_tool = 'claude_code' WHERE claude_session_id IS NOT NULL;
@@ -0,0 +1,19 @@
+-- ===========================================
+-- OpenFlow Migration: Verification Results
+-- Version: 021
+-- Description: Stores the results of verification runs so a chat's latest
+--              result can be shown after the run's event has passed
+-- ===========================================
+
+-- One row per verification run of a chat. `checks` holds the JSON array of
+-- VerificationCheck results, ordered by check name.
+CREATE TABLE verification_results (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    chat_id         TEXT NOT NULL REFERENCES chats(id) ON DELETE CASCADE,
+    passed          INTEGER NOT NULL,
+    checks          TEXT NOT NULL,
+    started_at      TEXT NOT NULL,
+    completed_at    TEXT NOT NULL
+);
+CREATE INDEX idx_verification_results_chat_id
+    ON verification_results(chat_id, id);
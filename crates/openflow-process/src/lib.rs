@@ -12,6 +12,10 @@
 //! - `pty`: PTY (pseudo-terminal) management using portable-pty
 //! - `native`: Native PTY executor implementation
 //! - `spawn`: Basic process spawning with pipe-based I/O
+//! - `signal`: Process group termination with signal escalation
+//! - `limits`: Resource limits (rlimits) for spawned processes
+//! - `sandbox`: Filesystem and network sandbox (bubblewrap) for spawned processes
+//! - `recording`: asciicast v2 recordings of PTY sessions
 //! - `output`: Output handling utilities (buffering, aggregation)
 //!
 //! # Architecture
@@ -53,21 +57,35 @@
 
 pub mod error;
 pub mod executor;
+pub mod limits;
 pub mod native;
 pub mod output;
 pub mod pty;
+pub mod recording;
+pub mod sandbox;
+pub mod signal;
 pub mod spawn;
 pub mod types;
 
 // Re-export commonly used items
 pub use error::{ProcessError, ProcessResult};
 pub use executor::{ChannelSink, CollectorSink, NullSink, OutputSink, ProcessExecutor};
+pub use limits::{ResourceLimit, ResourceLimits};
 pub use native::NativePtyExecutor;
 pub use output::{
     output_channel, read_chunks, read_chunks_async, read_lines, read_lines_async, status_channel,
     OutputAggregator, OutputBuffer, OutputCollector, OutputConfig, OutputReceiver, OutputStreamer,
     DEFAULT_BUFFER_SIZE, MAX_LINE_LENGTH,
 };
-pub use pty::{PtyConfig, PtyError, PtyManager, PtyResult, PtySize};
+pub use pty::{
+    PtyConfig, PtyError, PtyManager, PtyResize, PtyResult, PtySize, PtySnapshot, ScrollbackBuffer,
+    ScrollbackReader, DEFAULT_SCROLLBACK_BYTES,
+};
+pub use recording::{
+    write_cast, AsciicastEvent, AsciicastEventType, AsciicastHeader, ASCIICAST_CONTENT_TYPE,
+    ASCIICAST_VERSION,
+};
+pub use sandbox::SandboxConfig;
+pub use signal::{terminate_groups, TerminationSignal, DEFAULT_GRACE_PERIOD};
 pub use spawn::{PipeProcessExecutor, PipeSpawnConfig, ProcessSpawner, SpawnError, SpawnResult};
 pub use types::{OutputChunk, OutputType, ProcessHandle, ProcessStatus, SpawnConfig};
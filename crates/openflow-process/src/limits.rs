@@ -0,0 +1,451 @@
+//! Resource limits for spawned processes.
+//!
+//! `ResourceLimits` caps what a process (and everything it starts, since
+//! rlimits are inherited) may use: address space, CPU time, open files and
+//! processes. Limits are applied with `setrlimit` in the child before it
+//! runs the command (pipe processes), or by running the command through the
+//! `prlimit` utility, which sets them before it execs the command (PTY
+//! processes, Linux only).
+//!
+//! Processes rarely say which limit they hit, so `ResourceLimits::exceeded`
+//! infers it from the signal that ended the process: SIGXCPU for CPU time,
+//! SIGSEGV or SIGABRT for address space. SIGKILL is never attributed to a
+//! limit, since the OOM killer, kill escalation and timeouts send it too.
+//! Running out of open files or processes only shows as errors in the output,
+//! which `ResourceLimits::suspected` reports as a hint.
+//!
+//! # Platform Support
+//!
+//! - Linux: all limits, for pipe and PTY processes (PTY processes need
+//!   `prlimit` from util-linux on PATH)
+//! - Other Unix: all limits for pipe processes; PTY processes are started
+//!   without limits (logged as a warning)
+//! - Windows: not supported; limits are ignored
+
+use std::fmt;
+
+/// Optional limits on the resources a process tree may use.
+///
+/// Unset (`None`) and zero values mean no limit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResourceLimits {
+    /// Maximum address space (virtual memory) per process, in bytes.
+    pub address_space: Option<u64>,
+    /// Maximum CPU time per process, in seconds.
+    pub cpu_time: Option<u64>,
+    /// Maximum number of open file descriptors per process.
+    pub open_files: Option<u64>,
+    /// Maximum number of processes for the user (counts all of the user's
+    /// processes, not only this tree).
+    pub processes: Option<u64>,
+}
+
+/// A single kind of resource limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResourceLimit {
+    /// `RLIMIT_AS`
+    AddressSpace,
+    /// `RLIMIT_CPU`
+    CpuTime,
+    /// `RLIMIT_NOFILE`
+    OpenFiles,
+    /// `RLIMIT_NPROC`
+    Processes,
+}
+
+/// Utility that runs a command with resource limits set.
+#[cfg(target_os = "linux")]
+const PRLIMIT: &str = "prlimit";
+
+/// How the exit status names the signals a process gets when it runs into a
+/// limit: the signal name (`std` exit statuses) or its `strsignal`
+/// description (PTY exit statuses).
+const ADDRESS_SPACE_SIGNALS: &[&str] = &["SIGSEGV", "Segmentation fault", "SIGABRT", "Aborted"];
+const CPU_TIME_SIGNALS: &[&str] = &["SIGXCPU", "CPU time limit exceeded"];
+
+/// Output that processes print when they run into a limit.
+const ADDRESS_SPACE_MARKERS: &[&str] = &[
+    "Cannot allocate memory",
+    "memory allocation of",
+    "out of memory",
+    "Out of memory",
+    "bad_alloc",
+    "MemoryError",
+];
+const CPU_TIME_MARKERS: &[&str] = &[
+    "SIGXCPU",
+    "CPU time limit exceeded",
+    "Cputime limit exceeded",
+];
+const OPEN_FILES_MARKERS: &[&str] = &["Too many open files", "EMFILE"];
+const PROCESSES_MARKERS: &[&str] = &[
+    "fork: retry",
+    "Cannot fork",
+    "Resource temporarily unavailable",
+];
+
+impl ResourceLimit {
+    /// All limit kinds, in the order they are checked.
+    pub const ALL: [ResourceLimit; 4] = [
+        ResourceLimit::CpuTime,
+        ResourceLimit::AddressSpace,
+        ResourceLimit::OpenFiles,
+        ResourceLimit::Processes,
+    ];
+
+    /// Human-readable name, e.g. `CPU time`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ResourceLimit::AddressSpace => "memory",
+            ResourceLimit::CpuTime => "CPU time",
+            ResourceLimit::OpenFiles => "open files",
+            ResourceLimit::Processes => "process",
+        }
+    }
+
+    fn exit_signals(&self) -> &'static [&'static str] {
+        match self {
+            ResourceLimit::AddressSpace => ADDRESS_SPACE_SIGNALS,
+            ResourceLimit::CpuTime => CPU_TIME_SIGNALS,
+            ResourceLimit::OpenFiles | ResourceLimit::Processes => &[],
+        }
+    }
+
+    fn markers(&self) -> &'static [&'static str] {
+        match self {
+            ResourceLimit::AddressSpace => ADDRESS_SPACE_MARKERS,
+            ResourceLimit::CpuTime => CPU_TIME_MARKERS,
+            ResourceLimit::OpenFiles => OPEN_FILES_MARKERS,
+            ResourceLimit::Processes => PROCESSES_MARKERS,
+        }
+    }
+
+    /// Name of the `prlimit` option that sets this limit.
+    #[cfg(target_os = "linux")]
+    fn prlimit_option(&self) -> &'static str {
+        match self {
+            ResourceLimit::AddressSpace => "as",
+            ResourceLimit::CpuTime => "cpu",
+            ResourceLimit::OpenFiles => "nofile",
+            ResourceLimit::Processes => "nproc",
+        }
+    }
+
+    #[cfg(unix)]
+    fn resource(&self) -> RlimitResource {
+        match self {
+            ResourceLimit::AddressSpace => libc::RLIMIT_AS,
+            ResourceLimit::CpuTime => libc::RLIMIT_CPU,
+            ResourceLimit::OpenFiles => libc::RLIMIT_NOFILE,
+            ResourceLimit::Processes => libc::RLIMIT_NPROC,
+        }
+    }
+}
+
+impl fmt::Display for ResourceLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(all(unix, any(target_env = "gnu", target_env = "uclibc")))]
+type RlimitResource = libc::__rlimit_resource_t;
+#[cfg(not(all(unix, any(target_env = "gnu", target_env = "uclibc"))))]
+#[cfg(unix)]
+type RlimitResource = libc::c_int;
+
+impl ResourceLimits {
+    /// Whether no limit is set.
+    pub fn is_empty(&self) -> bool {
+        ResourceLimit::ALL
+            .iter()
+            .all(|limit| self.get(*limit).is_none())
+    }
+
+    /// The configured value of a limit, ignoring zero.
+    pub fn get(&self, limit: ResourceLimit) -> Option<u64> {
+        let value = match limit {
+            ResourceLimit::AddressSpace => self.address_space,
+            ResourceLimit::CpuTime => self.cpu_time,
+            ResourceLimit::OpenFiles => self.open_files,
+            ResourceLimit::Processes => self.processes,
+        };
+        value.filter(|value| *value > 0)
+    }
+
+    /// Describe a limit for status messages, e.g. `CPU time limit of 30s`.
+    pub fn describe(&self, limit: ResourceLimit) -> String {
+        match (limit, self.get(limit)) {
+            (ResourceLimit::AddressSpace, Some(bytes)) => {
+                format!("memory limit of {} MB", bytes / (1024 * 1024))
+            }
+            (ResourceLimit::CpuTime, Some(secs)) => format!("CPU time limit of {}s", secs),
+            (ResourceLimit::OpenFiles, Some(count)) => format!("open files limit of {}", count),
+            (ResourceLimit::Processes, Some(count)) => format!("process limit of {}", count),
+            (limit, None) => format!("{} limit", limit),
+        }
+    }
+
+    /// Work out which limit, if any, made a failed process exit.
+    ///
+    /// `exit` describes how the process ended (e.g. the `Display` of its
+    /// exit status, which names the signal that killed it). Only limits that
+    /// are set are considered, and only the signal decides: a process that
+    /// merely prints "out of memory" did not necessarily hit a limit.
+    pub fn exceeded(&self, exit: &str) -> Option<ResourceLimit> {
+        ResourceLimit::ALL.into_iter().find(|limit| {
+            self.get(*limit).is_some()
+                && limit
+                    .exit_signals()
+                    .iter()
+                    .any(|signal| exit.contains(signal))
+        })
+    }
+
+    /// Guess which set limit a failed process may have run into from the
+    /// tail of its `output`.
+    ///
+    /// Only a hint for logging: the same errors are printed for reasons that
+    /// have nothing to do with the limits. See `exceeded`.
+    pub fn suspected(&self, output: &str) -> Option<ResourceLimit> {
+        ResourceLimit::ALL.into_iter().find(|limit| {
+            self.get(*limit).is_some()
+                && limit.markers().iter().any(|marker| output.contains(marker))
+        })
+    }
+
+    /// Apply the limits to the calling process.
+    ///
+    /// Meant to run in a forked child before `exec`, so it only makes
+    /// system calls. Limits above the current hard limit are clamped to it.
+    /// The CPU hard limit is set one second above the soft limit so the
+    /// process receives SIGXCPU (which identifies the limit) before SIGKILL.
+    #[cfg(unix)]
+    pub fn apply_to_current_process(&self) -> std::io::Result<()> {
+        for limit in ResourceLimit::ALL {
+            let Some(value) = self.get(limit) else {
+                continue;
+            };
+            let resource = limit.resource();
+            let mut current = libc::rlimit {
+                rlim_cur: 0,
+                rlim_max: 0,
+            };
+            // SAFETY: `current` is a valid, writable rlimit.
+            if unsafe { libc::getrlimit(resource, &mut current) } != 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            let new = Self::clamped(limit, value, current.rlim_max);
+            // SAFETY: `new` is a valid rlimit.
+            if unsafe { libc::setrlimit(resource, &new) } != 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+        }
+        Ok(())
+    }
+
+    /// Wrap a command so that it runs with the limits applied.
+    ///
+    /// For processes that can't run code between `fork` and `exec` (PTY
+    /// processes): the command is run through `prlimit`, which sets the
+    /// limits on itself and then execs the command, so nothing it starts runs
+    /// unlimited. Values are clamped to this process's hard limits, which the
+    /// child inherits. Returns the program and arguments to spawn instead.
+    ///
+    /// # Errors
+    ///
+    /// `NotFound` if `prlimit` is not installed, `Unsupported` on platforms
+    /// other than Linux.
+    pub fn wrap(&self, command: &str, args: &[String]) -> std::io::Result<(String, Vec<String>)> {
+        #[cfg(target_os = "linux")]
+        {
+            let prlimit = crate::sandbox::find_program(PRLIMIT).ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    "resource limits for PTY processes require prlimit on PATH",
+                )
+            })?;
+            let mut wrapped = Vec::new();
+            for limit in ResourceLimit::ALL {
+                let Some(value) = self.get(limit) else {
+                    continue;
+                };
+                let mut current = libc::rlimit {
+                    rlim_cur: 0,
+                    rlim_max: 0,
+                };
+                // SAFETY: `current` is a valid, writable rlimit.
+                if unsafe { libc::getrlimit(limit.resource(), &mut current) } != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                let new = Self::clamped(limit, value, current.rlim_max);
+                wrapped.push(format!(
+                    "--{}={}:{}",
+                    limit.prlimit_option(),
+                    new.rlim_cur,
+                    new.rlim_max
+                ));
+            }
+            wrapped.push("--".to_string());
+            wrapped.push(command.to_string());
+            wrapped.extend(args.iter().cloned());
+            Ok((prlimit.to_string_lossy().into_owned(), wrapped))
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = (command, args);
+            Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "resource limits for PTY processes are only supported on Linux",
+            ))
+        }
+    }
+
+    #[cfg(unix)]
+    fn clamped(limit: ResourceLimit, value: u64, hard: libc::rlim_t) -> libc::rlimit {
+        let value = value as libc::rlim_t;
+        let max = match limit {
+            ResourceLimit::CpuTime => value.saturating_add(1),
+            _ => value,
+        };
+        libc::rlimit {
+            rlim_cur: value.min(hard),
+            rlim_max: max.min(hard),
+        }
+    }
+}
+
+impl From<&openflow_contracts::ResourceLimits> for ResourceLimits {
+    fn from(limits: &openflow_contracts::ResourceLimits) -> Self {
+        Self {
+            address_space: limits
+                .address_space_mb
+                .map(|mb| u64::from(mb) * 1024 * 1024),
+            cpu_time: limits.cpu_secs.map(u64::from),
+            open_files: limits.open_files.map(u64::from),
+            processes: limits.processes.map(u64::from),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_empty_ignores_zero() {
+        assert!(ResourceLimits::default().is_empty());
+        let limits = ResourceLimits {
+            cpu_time: Some(0),
+            ..Default::default()
+        };
+        assert!(limits.is_empty());
+        let limits = ResourceLimits {
+            open_files: Some(64),
+            ..Default::default()
+        };
+        assert!(!limits.is_empty());
+    }
+
+    #[test]
+    fn test_exceeded_only_considers_set_limits() {
+        let limits = ResourceLimits {
+            open_files: Some(64),
+            ..Default::default()
+        };
+        assert_eq!(limits.exceeded("signal: 24 (SIGXCPU)"), None);
+
+        let limits = ResourceLimits {
+            cpu_time: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(
+            limits.exceeded("signal: 24 (SIGXCPU)"),
+            Some(ResourceLimit::CpuTime)
+        );
+        assert_eq!(
+            limits.exceeded("Terminated by CPU time limit exceeded"),
+            Some(ResourceLimit::CpuTime)
+        );
+        // SIGKILL is also sent by the OOM killer, kill escalation and timeouts
+        assert_eq!(limits.exceeded("signal: 9 (SIGKILL)"), None);
+        assert_eq!(limits.exceeded("Terminated by Killed"), None);
+        assert_eq!(
+            limits.describe(ResourceLimit::CpuTime),
+            "CPU time limit of 1s"
+        );
+    }
+
+    #[test]
+    fn test_output_is_only_a_hint() {
+        let limits = ResourceLimits {
+            address_space: Some(512 * 1024 * 1024),
+            open_files: Some(64),
+            ..Default::default()
+        };
+        let output = "error: Too many open files (os error 24)\nOut of memory";
+
+        assert_eq!(limits.exceeded("exit status: 1"), None);
+        assert_eq!(limits.suspected(output), Some(ResourceLimit::AddressSpace));
+        assert_eq!(
+            limits.exceeded("signal: 11 (SIGSEGV) (core dumped)"),
+            Some(ResourceLimit::AddressSpace)
+        );
+    }
+
+    #[test]
+    fn test_from_contract_limits() {
+        let limits = ResourceLimits::from(&openflow_contracts::ResourceLimits {
+            address_space_mb: Some(512),
+            cpu_secs: Some(30),
+            open_files: None,
+            processes: Some(100),
+        });
+        assert_eq!(limits.address_space, Some(512 * 1024 * 1024));
+        assert_eq!(limits.cpu_time, Some(30));
+        assert_eq!(limits.open_files, None);
+        assert_eq!(limits.processes, Some(100));
+        assert_eq!(
+            limits.describe(ResourceLimit::AddressSpace),
+            "memory limit of 512 MB"
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_wrap_runs_command_through_prlimit() {
+        let limits = ResourceLimits {
+            cpu_time: Some(30),
+            open_files: Some(64),
+            ..Default::default()
+        };
+
+        let (program, args) = limits
+            .wrap("sh", &["-c".to_string(), "ulimit -n".to_string()])
+            .unwrap();
+
+        assert!(program.ends_with("prlimit"));
+        assert_eq!(args[0], "--cpu=30:31");
+        assert_eq!(args[1], "--nofile=64:64");
+        assert_eq!(&args[2..], ["--", "sh", "-c", "ulimit -n"]);
+        let output = std::process::Command::new(program)
+            .args(args)
+            .output()
+            .unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "64");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_pipe_process_runs_with_limits() {
+        use crate::spawn::{PipeSpawnConfig, ProcessSpawner};
+
+        let config = PipeSpawnConfig::new("sh", &["-c", "ulimit -n"]).with_limits(ResourceLimits {
+            open_files: Some(64),
+            ..Default::default()
+        });
+        let (stdout, _, code) = ProcessSpawner::run_capture(config).unwrap();
+        assert_eq!(code, 0);
+        assert_eq!(stdout.trim(), "64");
+    }
+}
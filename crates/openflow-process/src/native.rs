@@ -267,6 +267,8 @@ impl NativePtyExecutor {
 
         pty_config = pty_config.with_env(env);
         pty_config = pty_config.with_size(config.cols, config.rows);
+        pty_config = pty_config.with_limits(config.limits);
+        pty_config.sandbox = config.sandbox.clone();
 
         pty_config
     }
@@ -7,18 +7,25 @@
 //! # Features
 //!
 //! - Cross-platform PTY support (Linux, macOS, Windows)
-//! - Terminal resize support
+//! - Terminal resize support, with resize events for session recordings
 //! - Input/output streaming
+//! - Bounded scrollback per PTY, so clients can reattach mid-run (`snapshot`)
 //! - Process lifecycle management
 
 use portable_pty::{
     native_pty_system, Child, CommandBuilder, MasterPty, PtySize as PortablePtySize,
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::io::{Read, Write};
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use thiserror::Error;
+use tokio::sync::mpsc;
+
+use crate::limits::ResourceLimits;
+use crate::sandbox::{self, SandboxConfig};
+use crate::signal::{self, TerminationSignal};
 
 /// Errors that can occur during PTY operations.
 #[derive(Debug, Error)]
@@ -54,6 +61,9 @@ pub enum PtyError {
 /// Result type for PTY operations.
 pub type PtyResult<T> = Result<T, PtyError>;
 
+/// Default number of bytes of recent output kept per PTY.
+pub const DEFAULT_SCROLLBACK_BYTES: usize = 256 * 1024;
+
 /// Configuration for creating a PTY.
 #[derive(Debug, Clone)]
 pub struct PtyConfig {
@@ -69,6 +79,12 @@ pub struct PtyConfig {
     pub cols: u16,
     /// Initial terminal height in rows.
     pub rows: u16,
+    /// Resource limits for the process tree.
+    pub limits: ResourceLimits,
+    /// Sandbox to run the process in, if any.
+    pub sandbox: Option<SandboxConfig>,
+    /// Bytes of recent output to keep for `PtyManager::snapshot`.
+    pub scrollback_bytes: usize,
 }
 
 impl Default for PtyConfig {
@@ -80,6 +96,9 @@ impl Default for PtyConfig {
             env: HashMap::new(),
             cols: 80,
             rows: 24,
+            limits: ResourceLimits::default(),
+            sandbox: None,
+            scrollback_bytes: DEFAULT_SCROLLBACK_BYTES,
         }
     }
 }
@@ -112,6 +131,28 @@ impl PtyConfig {
         self.rows = rows;
         self
     }
+
+    /// Set resource limits.
+    ///
+    /// portable-pty cannot run code in the child before `exec`, so the
+    /// command is run through `prlimit` (Linux only, see
+    /// `ResourceLimits::wrap`).
+    pub fn with_limits(mut self, limits: ResourceLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Run the process in a sandbox.
+    pub fn with_sandbox(mut self, sandbox: SandboxConfig) -> Self {
+        self.sandbox = Some(sandbox);
+        self
+    }
+
+    /// Set how many bytes of recent output to keep.
+    pub fn with_scrollback(mut self, bytes: usize) -> Self {
+        self.scrollback_bytes = bytes;
+        self
+    }
 }
 
 /// PTY size in columns and rows.
@@ -138,6 +179,126 @@ impl From<PtySize> for PortablePtySize {
     }
 }
 
+/// A terminal resize, reported to `PtyManager::subscribe_resizes` subscribers.
+#[derive(Debug, Clone)]
+pub struct PtyResize {
+    /// New terminal size.
+    pub size: PtySize,
+    /// When the PTY was resized, in RFC 3339 format.
+    pub timestamp: String,
+}
+
+/// Bounded ring buffer of a PTY's most recent output.
+///
+/// Once full, the oldest bytes are dropped as new output arrives.
+#[derive(Debug)]
+pub struct ScrollbackBuffer {
+    data: VecDeque<u8>,
+    capacity: usize,
+    truncated: bool,
+    total: u64,
+}
+
+impl ScrollbackBuffer {
+    /// Create an empty buffer holding at most `capacity` bytes.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            data: VecDeque::with_capacity(capacity.min(DEFAULT_SCROLLBACK_BYTES)),
+            capacity,
+            truncated: false,
+            total: 0,
+        }
+    }
+
+    /// Append output, dropping the oldest bytes beyond the capacity.
+    pub fn push(&mut self, bytes: &[u8]) {
+        let keep = bytes.len().min(self.capacity);
+        let overflow = (self.data.len() + keep).saturating_sub(self.capacity);
+        if overflow > 0 || keep < bytes.len() {
+            self.truncated = true;
+        }
+        self.data.drain(..overflow);
+        self.data.extend(&bytes[bytes.len() - keep..]);
+        self.total += bytes.len() as u64;
+    }
+
+    /// The buffered output, oldest first.
+    pub fn contents(&self) -> Vec<u8> {
+        self.data.iter().copied().collect()
+    }
+
+    /// Number of buffered bytes.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Whether no output is buffered.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Whether older output has been dropped.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Number of bytes pushed since the buffer was created, including
+    /// dropped ones. This is the output offset at the end of `contents`.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+}
+
+/// Recent output and current size of a PTY.
+///
+/// Lets a client that attaches mid-run render the terminal before it
+/// starts following live output.
+#[derive(Debug, Clone)]
+pub struct PtySnapshot {
+    /// Most recent output, oldest first.
+    pub data: Vec<u8>,
+    /// Current terminal size.
+    pub size: PtySize,
+    /// Whether older output no longer fits in the scrollback.
+    pub truncated: bool,
+    /// Bytes of output read from the PTY up to the end of `data`.
+    pub offset: u64,
+}
+
+/// Reader for a PTY's output that copies everything it reads into the
+/// PTY's scrollback.
+///
+/// Created with `PtyManager::output_reader`.
+pub struct ScrollbackReader {
+    inner: Box<dyn Read + Send>,
+    scrollback: Arc<Mutex<ScrollbackBuffer>>,
+    offset: u64,
+}
+
+impl ScrollbackReader {
+    /// Output offset at the end of the last read, counted like
+    /// `PtySnapshot::offset`.
+    ///
+    /// Output read through other readers of the same PTY is counted too, so
+    /// the last read is always the scrollback from `offset - n` to `offset`.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+}
+
+impl Read for ScrollbackReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        if n > 0 {
+            if let Ok(mut scrollback) = self.scrollback.lock() {
+                scrollback.push(&buf[..n]);
+                self.offset = scrollback.total();
+            }
+        }
+        Ok(n)
+    }
+}
+
 /// Represents an active PTY instance.
 struct PtyInstance {
     /// The master side of the PTY for reading/writing.
@@ -148,6 +309,25 @@ struct PtyInstance {
     size: PtySize,
     /// The writer for sending input to the PTY (taken once from master).
     writer: Option<Box<dyn Write + Send>>,
+    /// Recent output, filled by the readers handed out for this PTY.
+    scrollback: Arc<Mutex<ScrollbackBuffer>>,
+    /// Subscribers notified when the PTY is resized.
+    resize_subscribers: Vec<mpsc::UnboundedSender<PtyResize>>,
+}
+
+impl PtyInstance {
+    /// Clone a reader for the PTY output that records into the scrollback.
+    fn reader(&self) -> PtyResult<ScrollbackReader> {
+        let inner = self
+            .master
+            .try_clone_reader()
+            .map_err(|e| PtyError::ReadFailed(e.to_string()))?;
+        Ok(ScrollbackReader {
+            inner,
+            scrollback: Arc::clone(&self.scrollback),
+            offset: 0,
+        })
+    }
 }
 
 /// Manager for PTY instances.
@@ -200,9 +380,41 @@ impl PtyManager {
             .openpty(size)
             .map_err(|e| PtyError::CreateFailed(e.to_string()))?;
 
-        // Build the command
-        let mut cmd = CommandBuilder::new(&config.command);
-        cmd.args(&config.args);
+        // Build the command, inside the sandbox if one is configured
+        let (program, args) = sandbox::command_line(
+            config.sandbox.as_ref(),
+            &config.command,
+            &config.args,
+            config.cwd.as_deref(),
+            &config.env,
+        )
+        .map_err(|e| PtyError::SpawnFailed(e.to_string()))?;
+
+        // Run it through prlimit so the limits apply before the command execs
+        let (program, args) = if config.limits.is_empty() {
+            (program, args)
+        } else {
+            match config.limits.wrap(&program, &args) {
+                Ok(wrapped) => wrapped,
+                Err(e)
+                    if matches!(
+                        e.kind(),
+                        std::io::ErrorKind::Unsupported | std::io::ErrorKind::NotFound
+                    ) =>
+                {
+                    tracing::warn!(pty_id = %id, error = %e, "Starting PTY process without resource limits");
+                    (program, args)
+                }
+                Err(e) => {
+                    return Err(PtyError::SpawnFailed(format!(
+                        "failed to apply resource limits: {}",
+                        e
+                    )))
+                }
+            }
+        };
+        let mut cmd = CommandBuilder::new(program);
+        cmd.args(&args);
 
         // Set working directory if specified
         if let Some(cwd) = &config.cwd {
@@ -235,6 +447,8 @@ impl PtyManager {
                 rows: config.rows,
             },
             writer: Some(writer),
+            scrollback: Arc::new(Mutex::new(ScrollbackBuffer::new(config.scrollback_bytes))),
+            resize_subscribers: Vec::new(),
         };
 
         instances.insert(id.to_string(), instance);
@@ -262,9 +476,37 @@ impl PtyManager {
 
         instance.size = size;
 
+        let resize = PtyResize {
+            size,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        };
+        instance
+            .resize_subscribers
+            .retain(|subscriber| subscriber.send(resize.clone()).is_ok());
+
         Ok(())
     }
 
+    /// Subscribe to resizes of a PTY instance.
+    ///
+    /// Every successful `resize` is sent to the returned receiver. The
+    /// channel closes when the PTY is closed.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - Identifier of the PTY
+    pub fn subscribe_resizes(&self, id: &str) -> PtyResult<mpsc::UnboundedReceiver<PtyResize>> {
+        let mut instances = self.instances.lock().map_err(|_| PtyError::LockPoisoned)?;
+
+        let instance = instances
+            .get_mut(id)
+            .ok_or_else(|| PtyError::NotFound(id.to_string()))?;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        instance.resize_subscribers.push(tx);
+        Ok(rx)
+    }
+
     /// Write data to a PTY instance.
     ///
     /// # Arguments
@@ -312,10 +554,7 @@ impl PtyManager {
             .get_mut(id)
             .ok_or_else(|| PtyError::NotFound(id.to_string()))?;
 
-        let mut reader = instance
-            .master
-            .try_clone_reader()
-            .map_err(|e| PtyError::ReadFailed(e.to_string()))?;
+        let mut reader = instance.reader()?;
 
         reader
             .read(buffer)
@@ -330,18 +569,54 @@ impl PtyManager {
     ///
     /// # Returns
     ///
-    /// Returns a boxed Read implementation for the PTY output.
+    /// Returns a boxed Read implementation for the PTY output. Everything
+    /// read through it is also kept in the PTY's scrollback.
     pub fn try_clone_reader(&self, id: &str) -> PtyResult<Box<dyn Read + Send>> {
-        let mut instances = self.instances.lock().map_err(|_| PtyError::LockPoisoned)?;
+        Ok(Box::new(self.output_reader(id)?))
+    }
+
+    /// Get a reader for the PTY output that also reports the output offset
+    /// of what it read (see `ScrollbackReader::offset`).
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - Identifier of the PTY
+    pub fn output_reader(&self, id: &str) -> PtyResult<ScrollbackReader> {
+        let instances = self.instances.lock().map_err(|_| PtyError::LockPoisoned)?;
 
         let instance = instances
-            .get_mut(id)
+            .get(id)
             .ok_or_else(|| PtyError::NotFound(id.to_string()))?;
 
-        instance
-            .master
-            .try_clone_reader()
-            .map_err(|e| PtyError::ReadFailed(e.to_string()))
+        instance.reader()
+    }
+
+    /// Get the recent output and current size of a PTY instance.
+    ///
+    /// Only output that has been read from the PTY (through `read` or a
+    /// reader from `try_clone_reader`) is included.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - Identifier of the PTY
+    pub fn snapshot(&self, id: &str) -> PtyResult<PtySnapshot> {
+        let instances = self.instances.lock().map_err(|_| PtyError::LockPoisoned)?;
+
+        let instance = instances
+            .get(id)
+            .ok_or_else(|| PtyError::NotFound(id.to_string()))?;
+
+        let scrollback = instance
+            .scrollback
+            .lock()
+            .map_err(|_| PtyError::LockPoisoned)?;
+
+        Ok(PtySnapshot {
+            data: scrollback.contents(),
+            size: instance.size,
+            truncated: scrollback.is_truncated(),
+            offset: scrollback.total(),
+        })
     }
 
     /// Check if the process in the PTY has exited.
@@ -406,6 +681,71 @@ impl PtyManager {
             .map_err(|e| PtyError::ProcessExited(e.to_string()))
     }
 
+    /// Get the process groups to signal when stopping the PTY's process.
+    ///
+    /// The child is a session leader, so its process ID is also its group
+    /// ID. Shells run jobs in a separate foreground group, which is
+    /// included when it differs.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - Identifier of the PTY
+    pub fn process_groups(&self, id: &str) -> PtyResult<Vec<u32>> {
+        let instances = self.instances.lock().map_err(|_| PtyError::LockPoisoned)?;
+
+        let instance = instances
+            .get(id)
+            .ok_or_else(|| PtyError::NotFound(id.to_string()))?;
+
+        let mut groups: Vec<u32> = instance.child.process_id().into_iter().collect();
+        #[cfg(unix)]
+        if let Some(leader) = instance.master.process_group_leader() {
+            if let Ok(leader) = u32::try_from(leader) {
+                if !groups.contains(&leader) {
+                    groups.push(leader);
+                }
+            }
+        }
+
+        Ok(groups)
+    }
+
+    /// Stop the process in the PTY and everything it started.
+    ///
+    /// Escalates SIGINT → SIGTERM → SIGKILL with `grace` between signals
+    /// (see `signal::terminate_groups`). Blocks until the process groups
+    /// have exited or SIGKILL was sent. The PTY itself stays open; call
+    /// `close` to release it.
+    ///
+    /// # Returns
+    ///
+    /// The last signal sent, or `None` if the process had already exited.
+    pub fn terminate(&self, id: &str, grace: Duration) -> PtyResult<Option<TerminationSignal>> {
+        let groups = self.process_groups(id)?;
+        Ok(signal::terminate_groups(&groups, grace, || {
+            let _ = self.try_wait(id);
+        }))
+    }
+
+    /// Get the OS process ID of the process running in the PTY.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - Identifier of the PTY
+    ///
+    /// # Returns
+    ///
+    /// Returns `None` if the platform does not expose a process ID.
+    pub fn process_id(&self, id: &str) -> PtyResult<Option<u32>> {
+        let instances = self.instances.lock().map_err(|_| PtyError::LockPoisoned)?;
+
+        let instance = instances
+            .get(id)
+            .ok_or_else(|| PtyError::NotFound(id.to_string()))?;
+
+        Ok(instance.child.process_id())
+    }
+
     /// Get the current size of a PTY instance.
     ///
     /// # Arguments
@@ -644,6 +984,27 @@ mod tests {
         manager.close("test-pty").unwrap();
     }
 
+    #[test]
+    fn test_pty_process_id() {
+        let manager = PtyManager::new();
+        let config = PtyConfig {
+            command: "echo".to_string(),
+            args: vec!["hello".to_string()],
+            ..Default::default()
+        };
+
+        manager.create("test-pty", config).unwrap();
+
+        let pid = manager.process_id("test-pty").unwrap();
+        assert!(pid.is_some());
+        assert!(matches!(
+            manager.process_id("nonexistent"),
+            Err(PtyError::NotFound(_))
+        ));
+
+        manager.close("test-pty").unwrap();
+    }
+
     #[test]
     fn test_pty_close_all() {
         let manager = PtyManager::new();
@@ -665,6 +1026,79 @@ mod tests {
         assert_eq!(manager.count().unwrap(), 0);
     }
 
+    #[test]
+    fn test_scrollback_buffer_drops_oldest() {
+        let mut scrollback = ScrollbackBuffer::new(8);
+        scrollback.push(b"hello");
+        assert_eq!(scrollback.contents(), b"hello");
+        assert!(!scrollback.is_truncated());
+
+        scrollback.push(b" world");
+        assert_eq!(scrollback.contents(), b"lo world");
+        assert_eq!(scrollback.len(), 8);
+        assert!(scrollback.is_truncated());
+        assert_eq!(scrollback.total(), 11);
+
+        scrollback.push(b"0123456789");
+        assert_eq!(scrollback.contents(), b"23456789");
+        assert_eq!(scrollback.total(), 21);
+    }
+
+    #[test]
+    fn test_pty_snapshot() {
+        let manager = PtyManager::new();
+        let config = PtyConfig::new("echo", &["scrollback"]).with_size(100, 30);
+        manager.create("test-pty", config).unwrap();
+
+        let mut reader = manager.try_clone_reader("test-pty").unwrap();
+        let mut output = String::new();
+        let mut buffer = [0u8; 1024];
+        while !output.contains("scrollback") {
+            match reader.read(&mut buffer) {
+                Ok(0) | Err(_) => break,
+                Ok(n) => output.push_str(&String::from_utf8_lossy(&buffer[..n])),
+            }
+        }
+
+        let snapshot = manager.snapshot("test-pty").unwrap();
+        assert!(String::from_utf8_lossy(&snapshot.data).contains("scrollback"));
+        assert_eq!(snapshot.size.cols, 100);
+        assert_eq!(snapshot.size.rows, 30);
+        assert!(!snapshot.truncated);
+        assert_eq!(snapshot.offset, snapshot.data.len() as u64);
+        assert!(matches!(
+            manager.snapshot("nonexistent"),
+            Err(PtyError::NotFound(_))
+        ));
+
+        manager.close("test-pty").unwrap();
+    }
+
+    #[test]
+    fn test_resize_notifies_subscribers() {
+        let manager = PtyManager::new();
+        let config = PtyConfig::new("sleep", &["10"]);
+        manager.create("test-pty", config).unwrap();
+
+        let mut resizes = manager.subscribe_resizes("test-pty").unwrap();
+        manager
+            .resize(
+                "test-pty",
+                PtySize {
+                    cols: 132,
+                    rows: 43,
+                },
+            )
+            .unwrap();
+
+        let resize = resizes.try_recv().unwrap();
+        assert_eq!((resize.size.cols, resize.size.rows), (132, 43));
+        assert!(chrono::DateTime::parse_from_rfc3339(&resize.timestamp).is_ok());
+
+        manager.close("test-pty").unwrap();
+        assert!(resizes.try_recv().is_err());
+    }
+
     #[test]
     fn test_pty_error_display() {
         let err = PtyError::NotFound("test".to_string());
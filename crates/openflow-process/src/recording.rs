@@ -0,0 +1,229 @@
+//! Terminal session recordings in asciicast v2 format.
+//!
+//! An asciicast v2 recording is a JSON header line followed by one JSON array
+//! per event, and can be replayed with standard players such as asciinema:
+//!
+//! ```text
+//! {"version":2,"width":80,"height":24,"timestamp":1700000000}
+//! [0.248,"o","$ cargo build\r\n"]
+//! [1.032,"r","120x32"]
+//! ```
+//!
+//! Event times are seconds since the recording started. They are computed
+//! from the timestamps of `OutputChunk`s (`o` events) and `PtyResize` events
+//! from `PtyManager::resize` (`r` events), so events recorded out of order
+//! still replay correctly once sorted by time.
+
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::pty::{PtyResize, PtySize};
+use crate::types::OutputChunk;
+
+/// asciicast format version written in the header.
+pub const ASCIICAST_VERSION: u32 = 2;
+
+/// MIME type of asciicast recordings.
+pub const ASCIICAST_CONTENT_TYPE: &str = "application/x-asciicast";
+
+/// Header line of an asciicast v2 recording.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AsciicastHeader {
+    /// Format version, always `ASCIICAST_VERSION`.
+    pub version: u32,
+    /// Initial terminal width in columns.
+    pub width: u16,
+    /// Initial terminal height in rows.
+    pub height: u16,
+    /// When the recording started, as a Unix timestamp.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<i64>,
+    /// Title shown by players.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+impl AsciicastHeader {
+    /// Create a header for a recording of a terminal of `size` started at
+    /// `started_at`.
+    pub fn new(size: PtySize, started_at: DateTime<Utc>) -> Self {
+        Self {
+            version: ASCIICAST_VERSION,
+            width: size.cols,
+            height: size.rows,
+            timestamp: Some(started_at.timestamp()),
+            title: None,
+        }
+    }
+
+    /// Set the title.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+}
+
+/// Kind of an asciicast event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AsciicastEventType {
+    /// Data written to the terminal (`o`).
+    Output,
+    /// Terminal resized (`r`), with data `COLSxROWS`.
+    Resize,
+}
+
+impl AsciicastEventType {
+    /// Event code used in recordings, e.g. `o`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AsciicastEventType::Output => "o",
+            AsciicastEventType::Resize => "r",
+        }
+    }
+
+    /// Parse an event code.
+    pub fn parse(code: &str) -> Option<Self> {
+        match code {
+            "o" => Some(AsciicastEventType::Output),
+            "r" => Some(AsciicastEventType::Resize),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for AsciicastEventType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A single event of an asciicast recording.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AsciicastEvent {
+    /// Seconds since the recording started.
+    pub time: f64,
+    /// Kind of event.
+    pub event_type: AsciicastEventType,
+    /// Output data, or the new size for resize events.
+    pub data: String,
+}
+
+impl AsciicastEvent {
+    /// Create an output event for a chunk of terminal output.
+    pub fn output(started_at: DateTime<Utc>, chunk: &OutputChunk) -> Self {
+        Self {
+            time: elapsed_secs(started_at, &chunk.timestamp),
+            event_type: AsciicastEventType::Output,
+            data: chunk.content.clone(),
+        }
+    }
+
+    /// Create a resize event.
+    pub fn resize(started_at: DateTime<Utc>, resize: &PtyResize) -> Self {
+        Self {
+            time: elapsed_secs(started_at, &resize.timestamp),
+            event_type: AsciicastEventType::Resize,
+            data: format!("{}x{}", resize.size.cols, resize.size.rows),
+        }
+    }
+
+    /// Format the event as a recording line (without newline).
+    pub fn to_line(&self) -> String {
+        serde_json::json!([self.time, self.event_type.as_str(), self.data]).to_string()
+    }
+}
+
+/// Write a complete recording: the header, then the events in order.
+pub fn write_cast(header: &AsciicastHeader, events: &[AsciicastEvent]) -> String {
+    let mut cast = serde_json::to_string(header).unwrap_or_default();
+    cast.push('\n');
+    for event in events {
+        cast.push_str(&event.to_line());
+        cast.push('\n');
+    }
+    cast
+}
+
+/// Seconds from `started_at` to an RFC 3339 `timestamp`, with microsecond
+/// precision. Unparseable or earlier timestamps count as the start.
+fn elapsed_secs(started_at: DateTime<Utc>, timestamp: &str) -> f64 {
+    DateTime::parse_from_rfc3339(timestamp)
+        .ok()
+        .and_then(|at| (at.with_timezone(&Utc) - started_at).num_microseconds())
+        .map(|micros| micros.max(0) as f64 / 1_000_000.0)
+        .unwrap_or(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_header_line() {
+        let started_at = DateTime::parse_from_rfc3339("2024-01-15T10:30:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let header = AsciicastHeader::new(
+            PtySize {
+                cols: 120,
+                rows: 32,
+            },
+            started_at,
+        );
+        assert_eq!(
+            serde_json::to_string(&header).unwrap(),
+            r#"{"version":2,"width":120,"height":32,"timestamp":1705314600}"#
+        );
+    }
+
+    #[test]
+    fn test_event_times_are_relative_to_start() {
+        let started_at = Utc::now();
+        let mut chunk = OutputChunk::stdout("p", "hi\r\n");
+        chunk.timestamp = (started_at + Duration::milliseconds(1500)).to_rfc3339();
+        let output = AsciicastEvent::output(started_at, &chunk);
+        assert_eq!(output.time, 1.5);
+        assert_eq!(output.to_line(), r#"[1.5,"o","hi\r\n"]"#);
+
+        let resize = AsciicastEvent::resize(
+            started_at,
+            &PtyResize {
+                size: PtySize {
+                    cols: 100,
+                    rows: 40,
+                },
+                timestamp: (started_at - Duration::seconds(1)).to_rfc3339(),
+            },
+        );
+        assert_eq!(resize.time, 0.0);
+        assert_eq!(resize.to_line(), r#"[0.0,"r","100x40"]"#);
+    }
+
+    #[test]
+    fn test_write_cast() {
+        let header = AsciicastHeader {
+            version: ASCIICAST_VERSION,
+            width: 80,
+            height: 24,
+            timestamp: None,
+            title: Some("build".to_string()),
+        };
+        let events = vec![AsciicastEvent {
+            time: 0.25,
+            event_type: AsciicastEventType::Output,
+            data: "ok".to_string(),
+        }];
+        assert_eq!(
+            write_cast(&header, &events),
+            "{\"version\":2,\"width\":80,\"height\":24,\"title\":\"build\"}\n[0.25,\"o\",\"ok\"]\n"
+        );
+        assert_eq!(
+            AsciicastEventType::parse("r"),
+            Some(AsciicastEventType::Resize)
+        );
+        assert_eq!(AsciicastEventType::parse("x"), None);
+    }
+}
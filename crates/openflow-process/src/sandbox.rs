@@ -0,0 +1,303 @@
+//! Filesystem and network sandbox for spawned processes.
+//!
+//! A `SandboxConfig` confines a process with Linux namespaces through
+//! bubblewrap (`bwrap`): the command is run by `bwrap` instead of directly.
+//! Inside the sandbox:
+//!
+//! - the whole filesystem is mounted read-only
+//! - HOME, `/tmp` and `/var/tmp` are private, empty tmpfs mounts
+//! - `writable_paths` (e.g. the chat worktree) are mounted read-write
+//! - `protected_paths` under a writable path (e.g. git hooks) stay read-only
+//! - `read_only_paths` hidden by the private mounts are mounted back
+//! - the network is unshared unless `allow_network` is set
+//!
+//! The sandbox stays in the caller's process group and dies with its
+//! parent, so it is stopped like any other process (see `signal`).
+//!
+//! # Platform Support
+//!
+//! - Linux: requires `bwrap` on PATH
+//! - Other platforms: not supported; spawning a sandboxed process fails
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Program used to enter the sandbox.
+pub const BWRAP: &str = "bwrap";
+
+/// Confinement for a spawned process tree.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SandboxConfig {
+    /// Paths the process may write to.
+    pub writable_paths: Vec<PathBuf>,
+    /// Paths under HOME or tmp to keep visible, read-only.
+    pub read_only_paths: Vec<PathBuf>,
+    /// Paths under a writable path that stay read-only.
+    pub protected_paths: Vec<PathBuf>,
+    /// Whether the process may use the network.
+    pub allow_network: bool,
+}
+
+impl SandboxConfig {
+    /// Create a sandbox with no writable paths and no network.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow writing to a path.
+    pub fn with_writable_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.writable_paths.push(path.into());
+        self
+    }
+
+    /// Keep a path under HOME or tmp visible, read-only.
+    pub fn with_read_only_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.read_only_paths.push(path.into());
+        self
+    }
+
+    /// Keep a path under a writable path read-only.
+    pub fn with_protected_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.protected_paths.push(path.into());
+        self
+    }
+
+    /// Set whether the process may use the network.
+    pub fn with_network(mut self, allow: bool) -> Self {
+        self.allow_network = allow;
+        self
+    }
+
+    /// Wrap a command so that it runs inside the sandbox.
+    ///
+    /// HOME is taken from `env` if set there, otherwise from the current
+    /// process. Returns the program and arguments to spawn instead.
+    ///
+    /// # Errors
+    ///
+    /// `NotFound` if `bwrap` is not installed, `Unsupported` on platforms
+    /// other than Linux.
+    pub fn wrap(
+        &self,
+        command: &str,
+        args: &[String],
+        cwd: Option<&Path>,
+        env: &HashMap<String, String>,
+    ) -> std::io::Result<(String, Vec<String>)> {
+        #[cfg(target_os = "linux")]
+        {
+            let bwrap = find_program(BWRAP).ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    "sandboxing requires bubblewrap (bwrap) on PATH",
+                )
+            })?;
+            let home = env
+                .get("HOME")
+                .cloned()
+                .or_else(|| std::env::var("HOME").ok())
+                .map(PathBuf::from);
+            let args = self.bwrap_args(command, args, cwd, home.as_deref());
+            Ok((bwrap.to_string_lossy().into_owned(), args))
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = (command, args, cwd, env);
+            Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "sandboxing is only supported on Linux",
+            ))
+        }
+    }
+
+    /// Build the `bwrap` arguments that run `command` in the sandbox.
+    ///
+    /// Mounts are applied in order, so paths made writable or visible again
+    /// come after the private HOME and tmp mounts that would hide them, and
+    /// protected paths come after the writable mounts they are under.
+    pub fn bwrap_args(
+        &self,
+        command: &str,
+        args: &[String],
+        cwd: Option<&Path>,
+        home: Option<&Path>,
+    ) -> Vec<String> {
+        let mut bwrap: Vec<String> = Vec::new();
+        let mut push = |parts: &[&str]| bwrap.extend(parts.iter().map(|part| part.to_string()));
+
+        push(&["--ro-bind", "/", "/"]);
+        push(&["--dev", "/dev"]);
+        push(&["--proc", "/proc"]);
+        push(&["--tmpfs", "/tmp"]);
+        push(&["--tmpfs", "/var/tmp"]);
+        if let Some(home) = home.and_then(Path::to_str) {
+            if home != "/" {
+                push(&["--tmpfs", home]);
+            }
+        }
+        for path in self.read_only_paths.iter().filter_map(|path| path.to_str()) {
+            push(&["--ro-bind-try", path, path]);
+        }
+        for path in self.writable_paths.iter().filter_map(|path| path.to_str()) {
+            push(&["--bind", path, path]);
+        }
+        for path in self.protected_paths.iter().filter_map(|path| path.to_str()) {
+            push(&["--ro-bind-try", path, path]);
+        }
+
+        push(&["--unshare-pid", "--unshare-ipc"]);
+        if !self.allow_network {
+            push(&["--unshare-net"]);
+        }
+        push(&["--die-with-parent"]);
+        if let Some(cwd) = cwd.and_then(Path::to_str) {
+            push(&["--chdir", cwd]);
+        }
+        push(&["--", command]);
+
+        bwrap.extend(args.iter().cloned());
+        bwrap
+    }
+}
+
+/// Resolve the program and arguments to spawn, wrapping them in `sandbox`
+/// if one is given.
+pub fn command_line(
+    sandbox: Option<&SandboxConfig>,
+    command: &str,
+    args: &[String],
+    cwd: Option<&Path>,
+    env: &HashMap<String, String>,
+) -> std::io::Result<(String, Vec<String>)> {
+    match sandbox {
+        Some(sandbox) => sandbox.wrap(command, args, cwd, env),
+        None => Ok((command.to_string(), args.to_vec())),
+    }
+}
+
+/// Find a program on PATH.
+#[cfg(target_os = "linux")]
+pub(crate) fn find_program(name: &str) -> Option<PathBuf> {
+    let path = std::env::var_os("PATH")?;
+    std::env::split_paths(&path)
+        .map(|dir| dir.join(name))
+        .find(|candidate| candidate.is_file())
+}
+
+impl From<&openflow_contracts::ProcessSandbox> for SandboxConfig {
+    fn from(sandbox: &openflow_contracts::ProcessSandbox) -> Self {
+        Self {
+            writable_paths: sandbox.writable_paths.iter().map(PathBuf::from).collect(),
+            read_only_paths: sandbox.read_only_paths.iter().map(PathBuf::from).collect(),
+            protected_paths: sandbox.protected_paths.iter().map(PathBuf::from).collect(),
+            allow_network: sandbox.allow_network,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(args: &[String], value: &str) -> usize {
+        args.iter()
+            .position(|arg| arg == value)
+            .unwrap_or_else(|| panic!("{} not in {:?}", value, args))
+    }
+
+    #[test]
+    fn test_bwrap_args_confine_filesystem() {
+        let sandbox = SandboxConfig::new()
+            .with_writable_path("/home/me/worktrees/chat")
+            .with_read_only_path("/home/me/.nvm");
+        let args = sandbox.bwrap_args(
+            "claude",
+            &["-p".to_string(), "hello".to_string()],
+            Some(Path::new("/home/me/worktrees/chat")),
+            Some(Path::new("/home/me")),
+        );
+
+        assert_eq!(&args[..3], ["--ro-bind", "/", "/"]);
+        // HOME is private, and paths under it are mounted back afterwards
+        let home = position(&args, "/home/me");
+        assert_eq!(args[home - 1], "--tmpfs");
+        assert!(home < position(&args, "/home/me/.nvm"));
+        let worktree = position(&args, "--bind");
+        assert!(home < worktree);
+        assert_eq!(args[worktree + 1], "/home/me/worktrees/chat");
+
+        assert!(args.contains(&"--unshare-net".to_string()));
+        assert!(!args.contains(&"--dev-bind".to_string()));
+        let separator = position(&args, "--");
+        assert_eq!(&args[separator + 1..], ["claude", "-p", "hello"]);
+    }
+
+    #[test]
+    fn test_bwrap_args_protect_paths_after_writable_mounts() {
+        let sandbox = SandboxConfig::new()
+            .with_writable_path("/repo")
+            .with_protected_path("/repo/.git/hooks");
+        let args = sandbox.bwrap_args("sh", &[], None, None);
+
+        let repo = position(&args, "/repo");
+        let hooks = position(&args, "/repo/.git/hooks");
+        assert_eq!(args[repo - 1], "--bind");
+        assert_eq!(args[hooks - 1], "--ro-bind-try");
+        assert!(repo < hooks);
+    }
+
+    #[test]
+    fn test_bwrap_args_allow_network() {
+        let sandbox = SandboxConfig::new().with_network(true);
+        let args = sandbox.bwrap_args("sh", &[], None, None);
+        assert!(!args.contains(&"--unshare-net".to_string()));
+        assert!(!args.contains(&"--chdir".to_string()));
+    }
+
+    #[test]
+    fn test_command_line_without_sandbox() {
+        let args = vec!["-c".to_string(), "true".to_string()];
+        let (program, wrapped) = command_line(None, "sh", &args, None, &HashMap::new()).unwrap();
+        assert_eq!(program, "sh");
+        assert_eq!(wrapped, args);
+    }
+
+    #[test]
+    fn test_from_contract_sandbox() {
+        let sandbox = SandboxConfig::from(&openflow_contracts::ProcessSandbox {
+            writable_paths: vec!["/work".to_string()],
+            read_only_paths: vec![],
+            protected_paths: vec!["/work/.git/hooks".to_string()],
+            allow_network: true,
+        });
+        assert_eq!(sandbox.writable_paths, vec![PathBuf::from("/work")]);
+        assert_eq!(
+            sandbox.protected_paths,
+            vec![PathBuf::from("/work/.git/hooks")]
+        );
+        assert!(sandbox.allow_network);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    #[ignore = "requires bubblewrap (bwrap)"]
+    fn test_sandboxed_process_cannot_write_outside_worktree() {
+        use crate::spawn::{PipeSpawnConfig, ProcessSpawner};
+
+        let worktree = tempfile::tempdir().unwrap();
+        let outside = tempfile::tempdir_in(std::env::current_dir().unwrap()).unwrap();
+        let script = format!(
+            "touch inside && touch {}/outside; echo $?",
+            outside.path().display()
+        );
+        let config = PipeSpawnConfig::new("sh", &["-c", &script])
+            .with_cwd(worktree.path())
+            .with_sandbox(SandboxConfig::new().with_writable_path(worktree.path()));
+
+        let (stdout, _, code) = ProcessSpawner::run_capture(config).unwrap();
+        assert_eq!(code, 0);
+        assert_ne!(stdout.trim(), "0");
+        assert!(worktree.path().join("inside").exists());
+        assert!(!outside.path().join("outside").exists());
+    }
+}
@@ -0,0 +1,289 @@
+//! Process group termination.
+//!
+//! Processes are started as the leader of their own process group, so that
+//! the dev servers, test runners and language servers they spawn can be
+//! stopped together with them:
+//!
+//! - Pipe processes: `ProcessSpawner::spawn` puts the child in a new group
+//! - PTY processes: portable-pty makes the child a session leader, and
+//!   `PtyManager::process_groups` also reports the terminal's foreground
+//!   group, which shells use for the jobs they run
+//!
+//! `terminate_groups` stops a set of groups by escalating SIGINT, then
+//! SIGTERM, then SIGKILL, giving them a grace period to exit after each
+//! signal. It reports the last signal that was needed.
+//!
+//! On Windows there are no process groups to signal; the process tree is
+//! killed with `taskkill /T /F` instead.
+
+use std::fmt;
+use std::time::Duration;
+
+/// Default time a process group gets to exit before the next signal.
+pub const DEFAULT_GRACE_PERIOD: Duration = Duration::from_secs(5);
+
+/// How often to check whether a signalled group has exited.
+#[cfg(unix)]
+const EXIT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Signals sent to stop a process group, in escalation order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TerminationSignal {
+    /// SIGINT, as if the user pressed Ctrl-C.
+    Interrupt,
+    /// SIGTERM, asking the process to shut down.
+    Terminate,
+    /// SIGKILL, which cannot be caught.
+    Kill,
+}
+
+impl TerminationSignal {
+    /// All signals in the order they are sent.
+    pub const ESCALATION: [TerminationSignal; 3] = [
+        TerminationSignal::Interrupt,
+        TerminationSignal::Terminate,
+        TerminationSignal::Kill,
+    ];
+
+    /// Conventional signal name, e.g. `SIGINT`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TerminationSignal::Interrupt => "SIGINT",
+            TerminationSignal::Terminate => "SIGTERM",
+            TerminationSignal::Kill => "SIGKILL",
+        }
+    }
+
+    #[cfg(unix)]
+    fn as_raw(&self) -> libc::c_int {
+        match self {
+            TerminationSignal::Interrupt => libc::SIGINT,
+            TerminationSignal::Terminate => libc::SIGTERM,
+            TerminationSignal::Kill => libc::SIGKILL,
+        }
+    }
+}
+
+impl fmt::Display for TerminationSignal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Stop the given process groups, escalating SIGINT → SIGTERM → SIGKILL.
+///
+/// After SIGINT and SIGTERM the groups get `grace` to exit before the next
+/// signal is sent. `reap` is called before every liveness check so the
+/// caller can collect exited children; a zombie group leader otherwise
+/// keeps its group alive.
+///
+/// A group ID that is not a group leader (e.g. a process adopted from an
+/// older session) is signalled as a single process.
+///
+/// # Returns
+///
+/// The last signal sent, or `None` if no group was alive to begin with.
+///
+/// This function blocks; call it from a blocking context.
+pub fn terminate_groups(
+    groups: &[u32],
+    grace: Duration,
+    mut reap: impl FnMut(),
+) -> Option<TerminationSignal> {
+    #[cfg(unix)]
+    {
+        let mut last = None;
+        for signal in TerminationSignal::ESCALATION {
+            reap();
+            if !groups.iter().any(|group| is_group_alive(*group)) {
+                break;
+            }
+            for group in groups {
+                signal_group(*group, signal);
+            }
+            last = Some(signal);
+            if signal == TerminationSignal::Kill || wait_for_exit(groups, grace, &mut reap) {
+                break;
+            }
+        }
+        last
+    }
+    #[cfg(windows)]
+    {
+        use std::process::{Command, Stdio};
+
+        let _ = grace;
+        let mut killed = false;
+        for group in groups {
+            killed |= Command::new("taskkill")
+                .args(["/T", "/F", "/PID", &group.to_string()])
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()
+                .map(|status| status.success())
+                .unwrap_or(false);
+        }
+        reap();
+        killed.then_some(TerminationSignal::Kill)
+    }
+}
+
+/// Send a signal to a process group.
+///
+/// Falls back to signalling the process itself when `pgid` does not lead a
+/// group. Returns whether the signal was delivered.
+#[cfg(unix)]
+pub fn signal_group(pgid: u32, signal: TerminationSignal) -> bool {
+    let Ok(pgid) = libc::pid_t::try_from(pgid) else {
+        return false;
+    };
+    if pgid <= 0 {
+        return false;
+    }
+    // SAFETY: kill(2) has no memory safety requirements.
+    unsafe { libc::kill(-pgid, signal.as_raw()) == 0 || libc::kill(pgid, signal.as_raw()) == 0 }
+}
+
+/// Whether any process in the group (or the process itself, if it does
+/// not lead a group) is still running.
+#[cfg(unix)]
+pub fn is_group_alive(pgid: u32) -> bool {
+    let Ok(pgid) = libc::pid_t::try_from(pgid) else {
+        return false;
+    };
+    if pgid <= 0 || !(probe(-pgid) || probe(pgid)) {
+        return false;
+    }
+    // Zombies stay in their group until reaped, which never happens in
+    // containers without an init process; only count running members
+    #[cfg(target_os = "linux")]
+    if let Some(alive) = has_running_members(pgid) {
+        return alive;
+    }
+    true
+}
+
+/// Scan `/proc` for a process in group `pgid` (or with PID `pgid`) that is
+/// not a zombie. Returns `None` if `/proc` cannot be read.
+#[cfg(target_os = "linux")]
+fn has_running_members(pgid: libc::pid_t) -> Option<bool> {
+    let entries = std::fs::read_dir("/proc").ok()?;
+    Some(entries.flatten().any(|entry| {
+        let Some(pid) = entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.parse::<libc::pid_t>().ok())
+        else {
+            return false;
+        };
+        let Ok(stat) = std::fs::read_to_string(format!("/proc/{}/stat", pid)) else {
+            return false;
+        };
+        // Format: pid (comm) state ppid pgrp ...; comm may contain spaces
+        let Some((_, fields)) = stat.rsplit_once(')') else {
+            return false;
+        };
+        let mut fields = fields.split_whitespace();
+        let state = fields.next();
+        let pgrp = fields
+            .nth(1)
+            .and_then(|pgrp| pgrp.parse::<libc::pid_t>().ok());
+        state != Some("Z") && (pgrp == Some(pgid) || pid == pgid)
+    }))
+}
+
+/// Check whether signals can be addressed to `pid` (a group if negative).
+#[cfg(unix)]
+fn probe(pid: libc::pid_t) -> bool {
+    // SAFETY: signal 0 only performs the existence and permission checks.
+    let result = unsafe { libc::kill(pid, 0) };
+    result == 0 || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+/// Wait up to `grace` for all groups to exit.
+#[cfg(unix)]
+fn wait_for_exit(groups: &[u32], grace: Duration, reap: &mut impl FnMut()) -> bool {
+    let deadline = std::time::Instant::now() + grace;
+    loop {
+        reap();
+        if !groups.iter().any(|group| is_group_alive(*group)) {
+            return true;
+        }
+        let now = std::time::Instant::now();
+        if now >= deadline {
+            return false;
+        }
+        std::thread::sleep(EXIT_POLL_INTERVAL.min(deadline - now));
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::process::CommandExt;
+    use std::process::{Child, Command, Stdio};
+
+    fn spawn_group(script: &str) -> Child {
+        let child = Command::new("sh")
+            .args(["-c", script])
+            .stdout(Stdio::null())
+            .process_group(0)
+            .spawn()
+            .expect("Failed to spawn sh");
+        // Give the shell time to install its traps
+        std::thread::sleep(Duration::from_millis(200));
+        child
+    }
+
+    fn reaper(child: &mut Child) -> impl FnMut() + '_ {
+        move || {
+            let _ = child.try_wait();
+        }
+    }
+
+    #[test]
+    fn test_signal_names() {
+        assert_eq!(TerminationSignal::Interrupt.as_str(), "SIGINT");
+        assert_eq!(TerminationSignal::Terminate.to_string(), "SIGTERM");
+        assert_eq!(
+            TerminationSignal::ESCALATION.last(),
+            Some(&TerminationSignal::Kill)
+        );
+    }
+
+    #[test]
+    fn test_interrupt_is_enough_for_well_behaved_group() {
+        let mut child = spawn_group("sleep 30");
+        let pgid = child.id();
+
+        let signal = terminate_groups(&[pgid], Duration::from_secs(2), reaper(&mut child));
+        assert_eq!(signal, Some(TerminationSignal::Interrupt));
+        assert!(!is_group_alive(pgid));
+    }
+
+    #[test]
+    fn test_escalates_to_kill_and_stops_grandchildren() {
+        // The shell and its background grandchild ignore SIGINT and SIGTERM
+        let mut child = spawn_group("trap '' INT TERM; sleep 30 & wait");
+        let pgid = child.id();
+
+        let signal = terminate_groups(&[pgid], Duration::from_millis(200), reaper(&mut child));
+        assert_eq!(signal, Some(TerminationSignal::Kill));
+
+        std::thread::sleep(Duration::from_millis(100));
+        let _ = child.try_wait();
+        assert!(!is_group_alive(pgid));
+    }
+
+    #[test]
+    fn test_exited_group_is_not_signalled() {
+        let mut child = spawn_group("exit 0");
+        let pgid = child.id();
+        child.wait().unwrap();
+
+        assert_eq!(
+            terminate_groups(&[pgid], Duration::from_millis(100), || {}),
+            None
+        );
+    }
+}
@@ -70,6 +70,8 @@ use tracing::{debug, info, warn};
 
 use crate::error::{ProcessError, ProcessResult};
 use crate::executor::{OutputSink, ProcessExecutor};
+use crate::limits::ResourceLimits;
+use crate::sandbox::{self, SandboxConfig};
 use crate::types::{OutputChunk, OutputType, ProcessHandle, ProcessStatus, SpawnConfig};
 
 /// Errors that can occur during process spawning.
@@ -101,6 +103,10 @@ pub struct PipeSpawnConfig {
     pub env: HashMap<String, String>,
     /// Whether to inherit the parent's environment.
     pub inherit_env: bool,
+    /// Resource limits for the process tree.
+    pub limits: ResourceLimits,
+    /// Sandbox to run the process in, if any.
+    pub sandbox: Option<SandboxConfig>,
 }
 
 impl Default for PipeSpawnConfig {
@@ -111,6 +117,8 @@ impl Default for PipeSpawnConfig {
             cwd: None,
             env: HashMap::new(),
             inherit_env: true,
+            limits: ResourceLimits::default(),
+            sandbox: None,
         }
     }
 }
@@ -153,6 +161,18 @@ impl PipeSpawnConfig {
         self.inherit_env = inherit;
         self
     }
+
+    /// Set resource limits.
+    pub fn with_limits(mut self, limits: ResourceLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Run the process in a sandbox.
+    pub fn with_sandbox(mut self, sandbox: SandboxConfig) -> Self {
+        self.sandbox = Some(sandbox);
+        self
+    }
 }
 
 /// Process spawner for creating child processes with pipe-based I/O.
@@ -168,14 +188,14 @@ impl ProcessSpawner {
     ///
     /// * `config` - Configuration for the process to spawn
     ///
+    /// On Unix the child leads a new process group (see `signal`). With a
+    /// sandbox configured, the command is run through `bwrap`.
+    ///
     /// # Returns
     ///
     /// Returns a `Child` handle to the spawned process.
     pub fn spawn(config: PipeSpawnConfig) -> SpawnResult<Child> {
-        let mut cmd = Command::new(&config.command);
-
-        // Set arguments
-        cmd.args(&config.args);
+        let mut cmd = build_command(&config)?;
 
         // Set working directory if specified
         if let Some(cwd) = &config.cwd {
@@ -198,6 +218,14 @@ impl ProcessSpawner {
         cmd.stdout(Stdio::piped());
         cmd.stderr(Stdio::piped());
 
+        // Lead a new process group so the whole tree can be signalled
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::CommandExt;
+            cmd.process_group(0);
+        }
+        apply_limits(&mut cmd, config.limits);
+
         // Spawn the process
         cmd.spawn().map_err(SpawnError::SpawnFailed)
     }
@@ -227,9 +255,7 @@ impl ProcessSpawner {
     ///
     /// Returns (stdout, stderr, exit_code).
     pub fn run_capture(config: PipeSpawnConfig) -> SpawnResult<(String, String, i32)> {
-        let mut cmd = Command::new(&config.command);
-
-        cmd.args(&config.args);
+        let mut cmd = build_command(&config)?;
 
         if let Some(cwd) = &config.cwd {
             if !cwd.exists() {
@@ -244,6 +270,7 @@ impl ProcessSpawner {
         for (key, value) in &config.env {
             cmd.env(key, value);
         }
+        apply_limits(&mut cmd, config.limits);
 
         let output = cmd.output()?;
 
@@ -277,6 +304,38 @@ impl ProcessSpawner {
     }
 }
 
+/// Create the command for a configuration, inside its sandbox if any.
+fn build_command(config: &PipeSpawnConfig) -> SpawnResult<Command> {
+    let (program, args) = sandbox::command_line(
+        config.sandbox.as_ref(),
+        &config.command,
+        &config.args,
+        config.cwd.as_deref(),
+        &config.env,
+    )?;
+    let mut cmd = Command::new(program);
+    cmd.args(args);
+    Ok(cmd)
+}
+
+/// Apply resource limits in the child before it runs the command.
+fn apply_limits(cmd: &mut Command, limits: ResourceLimits) {
+    if limits.is_empty() {
+        return;
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        // SAFETY: the hook only calls getrlimit/setrlimit, which are
+        // async-signal-safe, and does not allocate.
+        unsafe {
+            cmd.pre_exec(move || limits.apply_to_current_process());
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = cmd;
+}
+
 /// Convert from SpawnConfig to PipeSpawnConfig.
 ///
 /// This allows using the general SpawnConfig with pipe-based spawning.
@@ -288,6 +347,8 @@ impl From<SpawnConfig> for PipeSpawnConfig {
             cwd: config.cwd,
             env: config.env,
             inherit_env: config.inherit_env,
+            limits: config.limits,
+            sandbox: config.sandbox,
         }
     }
 }
@@ -306,6 +367,8 @@ impl From<PipeSpawnConfig> for SpawnConfig {
             cols: 80,
             rows: 24,
             use_pty: false,
+            limits: config.limits,
+            sandbox: config.sandbox,
         }
     }
 }
@@ -8,6 +8,9 @@ use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
+use crate::limits::ResourceLimits;
+use crate::sandbox::SandboxConfig;
+
 /// Process status.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -61,6 +64,10 @@ pub struct SpawnConfig {
     pub rows: u16,
     /// Whether to use a PTY (pseudo-terminal).
     pub use_pty: bool,
+    /// Resource limits for the process tree.
+    pub limits: ResourceLimits,
+    /// Sandbox to run the process in, if any.
+    pub sandbox: Option<SandboxConfig>,
 }
 
 impl SpawnConfig {
@@ -75,6 +82,8 @@ impl SpawnConfig {
             cols: 80,
             rows: 24,
             use_pty: true,
+            limits: ResourceLimits::default(),
+            sandbox: None,
         }
     }
 
@@ -120,6 +129,18 @@ impl SpawnConfig {
         self
     }
 
+    /// Set resource limits.
+    pub fn with_limits(mut self, limits: ResourceLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Run the process in a sandbox.
+    pub fn with_sandbox(mut self, sandbox: SandboxConfig) -> Self {
+        self.sandbox = Some(sandbox);
+        self
+    }
+
     /// Get the full command line as a string.
     pub fn command_line(&self) -> String {
         if self.args.is_empty() {
@@ -141,6 +162,8 @@ impl Default for SpawnConfig {
             cols: 80,
             rows: 24,
             use_pty: true,
+            limits: ResourceLimits::default(),
+            sandbox: None,
         }
     }
 }
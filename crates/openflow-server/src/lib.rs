@@ -95,7 +95,8 @@ use std::sync::Arc;
 
 use axum::Router;
 use openflow_core::events::EventBroadcaster;
-use openflow_core::services::process::ProcessService;
+use openflow_core::services::process::{OrphanPolicy, ProcessService};
+use openflow_core::services::secret::SecretCipher;
 use openflow_db::{DbConfig, SqlitePool};
 
 use crate::ws::WsBroadcaster;
@@ -164,12 +165,40 @@ pub async fn start_server_with_shutdown(
 
     tracing::info!("Database initialized at {:?}", db_path);
 
+    // The secrets key file lives next to the database
+    let data_dir = db_path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or_else(|| std::path::Path::new("."));
+    let secret_cipher =
+        SecretCipher::from_directory(data_dir).map_err(|e| ServerError::Config(e.to_string()))?;
+
     // Create services
     // Use WsBroadcaster so events are sent to WebSocket clients
     let client_manager = ClientManager::new();
     let broadcaster: Arc<dyn EventBroadcaster> = WsBroadcaster::arc(client_manager.clone());
     // IMPORTANT: ProcessService must use the same broadcaster to stream PTY output events
-    let process_service = Arc::new(ProcessService::with_broadcaster(broadcaster.clone()));
+    let process_service = Arc::new(
+        ProcessService::with_broadcaster(broadcaster.clone()).with_secret_cipher(secret_cipher),
+    );
+
+    // Reconcile processes left running by a previous session (e.g. after a crash)
+    let orphan_policy = OrphanPolicy::from_settings(&pool).await.unwrap_or_default();
+    match process_service
+        .reconcile_orphans(&pool, orphan_policy)
+        .await
+    {
+        Ok(report) if report.total() > 0 => tracing::info!(
+            "Reconciled {} orphaned process(es): failed={} killed={} adopted={} cancelled={}",
+            report.total(),
+            report.failed.len(),
+            report.killed.len(),
+            report.adopted.len(),
+            report.cancelled.len()
+        ),
+        Ok(_) => {}
+        Err(e) => tracing::warn!("Failed to reconcile orphaned processes: {}", e),
+    }
 
     // Create app state
     let state = AppState::new(
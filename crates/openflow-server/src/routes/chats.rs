@@ -7,9 +7,12 @@ use axum::{
     routing::{get, post},
     Json, Router,
 };
-use openflow_contracts::{Chat, ChatWithMessages, CreateChatRequest, UpdateChatRequest};
+use openflow_contracts::{
+    Chat, ChatWithMessages, CleanupWorktreeRequest, CreateChatRequest, ExecutionProcess,
+    UpdateChatRequest, VerificationResult,
+};
 use openflow_core::events::{EntityType, Event};
-use openflow_core::services::chat;
+use openflow_core::services::{chat, dev_server, project, verification, worktree};
 use serde::Deserialize;
 
 use crate::{error::ServerResult, state::AppState};
@@ -41,6 +44,15 @@ pub fn routes() -> Router<AppState> {
         .route("/:id/archive", post(archive))
         .route("/:id/unarchive", post(unarchive))
         .route("/:id/toggle-step", post(toggle_step_complete))
+        .route("/:id/cleanup", post(cleanup))
+        .route("/:id/verify", post(verify))
+        .route("/:id/verifications", get(list_verifications))
+        .route(
+            "/:id/dev-server",
+            get(get_dev_server)
+                .post(start_dev_server)
+                .delete(stop_dev_server),
+        )
 }
 
 /// GET /api/chats?taskId=xxx
@@ -128,8 +140,9 @@ async fn update(
 
 /// DELETE /api/chats/{id}
 ///
-/// Delete a chat by ID.
+/// Delete a chat by ID. Its dev server is stopped first.
 async fn delete_one(State(state): State<AppState>, Path(id): Path<String>) -> ServerResult<()> {
+    dev_server::stop(&state.pool, &state.process_service, &id).await?;
     chat::delete(&state.pool, &id).await?;
 
     // Broadcast data changed event
@@ -140,12 +153,13 @@ async fn delete_one(State(state): State<AppState>, Path(id): Path<String>) -> Se
 
 /// POST /api/chats/{id}/archive
 ///
-/// Archive a chat.
+/// Archive a chat. Its dev server is stopped.
 async fn archive(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> ServerResult<Json<Chat>> {
     let chat = chat::archive(&state.pool, &id).await?;
+    dev_server::stop(&state.pool, &state.process_service, &id).await?;
 
     // Broadcast data changed event
     state.broadcast(Event::updated(EntityType::Chat, chat.id.clone(), &chat));
@@ -168,6 +182,89 @@ async fn unarchive(
     Ok(Json(chat))
 }
 
+/// POST /api/chats/{id}/cleanup
+///
+/// Run the project's cleanup script and remove the chat's worktree.
+/// The branch is kept unless `deleteBranch` is set.
+async fn cleanup(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    request: Option<Json<CleanupWorktreeRequest>>,
+) -> ServerResult<Json<Chat>> {
+    let request = request.map(|Json(r)| r).unwrap_or_default();
+    let chat = chat::get_chat(&state.pool, &id).await?;
+    let project = project::get(&state.pool, &chat.project_id).await?;
+
+    // The chat update is broadcast by the worktree service
+    let chat = worktree::cleanup(
+        &state.pool,
+        &state.process_service,
+        &chat,
+        &project,
+        request.delete_branch,
+    )
+    .await?;
+
+    Ok(Json(chat))
+}
+
+/// POST /api/chats/{id}/verify
+///
+/// Run the project's verification checks in the chat's worktree.
+/// Responds once all checks have finished; the result is also broadcast.
+async fn verify(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> ServerResult<Json<VerificationResult>> {
+    let result = verification::run(&state.pool, &state.process_service, &id).await?;
+    Ok(Json(result))
+}
+
+/// GET /api/chats/{id}/verifications
+///
+/// List the chat's verification results, newest first.
+async fn list_verifications(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> ServerResult<Json<Vec<VerificationResult>>> {
+    let results = verification::list(&state.pool, &id).await?;
+    Ok(Json(results))
+}
+
+/// GET /api/chats/{id}/dev-server
+///
+/// Get the chat's running dev server, if any.
+async fn get_dev_server(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> ServerResult<Json<Option<ExecutionProcess>>> {
+    let process = dev_server::get_running(&state.pool, &id).await?;
+    Ok(Json(process))
+}
+
+/// POST /api/chats/{id}/dev-server
+///
+/// Start the project's dev server in the chat's worktree, or return the
+/// one already running. The URL is broadcast once detected.
+async fn start_dev_server(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> ServerResult<Json<ExecutionProcess>> {
+    let process = dev_server::start(&state.pool, &state.process_service, &id).await?;
+    Ok(Json(process))
+}
+
+/// DELETE /api/chats/{id}/dev-server
+///
+/// Stop the chat's dev server. Returns the stopped process, if one was running.
+async fn stop_dev_server(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> ServerResult<Json<Option<ExecutionProcess>>> {
+    let process = dev_server::stop(&state.pool, &state.process_service, &id).await?;
+    Ok(Json(process))
+}
+
 /// POST /api/chats/{id}/toggle-step
 ///
 /// Toggle a workflow step's completion status.
@@ -239,6 +336,7 @@ mod tests {
                 always_included_rules: None,
                 workflows_folder: None,
                 verification_config: None,
+                script_limits: None,
             };
             project::create(&self.pool, request)
                 .await
@@ -253,6 +351,7 @@ mod tests {
                 title: title.to_string(),
                 description: None,
                 workflow_template: None,
+                workflow_variables: None,
                 parent_task_id: None,
                 base_branch: None,
             };
@@ -491,6 +590,27 @@ mod tests {
             Some("Get Test Chat".to_string())
         );
         assert!(chat_with_messages.messages.is_empty());
+        assert!(chat_with_messages.latest_verification.is_none());
+
+        // The chat has not been verified yet
+        let response = ctx
+            .app()
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!("/chats/{}/verifications", created.id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let results: Vec<VerificationResult> = serde_json::from_slice(&body).unwrap();
+        assert!(results.is_empty());
     }
 
     #[tokio::test]
@@ -1227,4 +1347,48 @@ mod tests {
             assert_eq!(chat.chat_role, expected_role);
         }
     }
+
+    #[tokio::test]
+    async fn test_cleanup_chat_marks_worktree_deleted() {
+        let ctx = TestContext::new().await;
+        let project_id = ctx.create_project("Test Project").await;
+        let created = chat::create(
+            &ctx.pool,
+            CreateChatRequest {
+                project_id,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        // The worktree directory is already gone, so only the record is updated
+        chat::update(
+            &ctx.pool,
+            &created.id,
+            UpdateChatRequest::with_worktree("/path/to/missing-worktree", "openflow/x/main"),
+        )
+        .await
+        .unwrap();
+
+        let response = ctx
+            .app()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!("/chats/{}/cleanup", created.id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let chat: Chat = serde_json::from_slice(&body).unwrap();
+        assert!(chat.worktree_deleted);
+        assert_eq!(chat.branch.as_deref(), Some("openflow/x/main"));
+    }
 }
@@ -22,7 +22,7 @@ use openflow_contracts::{
     UpdateExecutorProfileRequest,
 };
 use openflow_core::events::{EntityType, Event};
-use openflow_core::services::{executor, executor_profile};
+use openflow_core::services::{executor, executor_profile, worktree};
 
 use crate::{error::ServerResult, state::AppState};
 
@@ -48,6 +48,8 @@ pub fn routes() -> Router<AppState> {
 /// POST /api/executor/run
 ///
 /// Run an executor (AI agent) in a chat.
+///
+/// The first run in a new worktree waits for the project's setup script.
 async fn run(
     State(state): State<AppState>,
     Json(request): Json<RunExecutorRequest>,
@@ -61,6 +63,22 @@ async fn run(
     )
     .await?;
 
+    // The chat may have been given a worktree while preparing
+    state.broadcast(Event::updated(
+        EntityType::Chat,
+        context.chat.id.clone(),
+        &context.chat,
+    ));
+
+    // Copy files and run the setup script before the agent starts
+    worktree::bootstrap(
+        &state.pool,
+        &state.process_service,
+        &context.chat,
+        &context.project,
+    )
+    .await?;
+
     // Start the process
     let process = state
         .process_service
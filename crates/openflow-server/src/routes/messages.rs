@@ -275,6 +275,7 @@ mod tests {
                 always_included_rules: None,
                 workflows_folder: None,
                 verification_config: None,
+                script_limits: None,
             };
             let proj = project::create(&self.pool, project_req)
                 .await
@@ -286,6 +287,7 @@ mod tests {
                 title: "Test Task".to_string(),
                 description: None,
                 workflow_template: None,
+                workflow_variables: None,
                 parent_task_id: None,
                 base_branch: None,
             };
@@ -16,6 +16,7 @@
 //! - `/api/git` - Git operations (worktrees, diff, commits)
 //! - `/api/github` - GitHub operations (PRs, CLI status)
 //! - `/api/settings` - Settings management
+//! - `/api/secrets` - Encrypted secrets (write-only)
 //! - `/api/workflows` - Workflow templates
 //! - `/api/search` - Full-text search
 //! - `/api/terminal` - Terminal spawning
@@ -31,6 +32,7 @@ pub mod messages;
 pub mod processes;
 pub mod projects;
 pub mod search;
+pub mod secrets;
 pub mod settings;
 pub mod tasks;
 pub mod terminal;
@@ -83,6 +85,7 @@ fn api_routes() -> Router<AppState> {
         .nest("/git", git::routes())
         .nest("/github", github::routes())
         .nest("/settings", settings::routes())
+        .nest("/secrets", secrets::routes())
         .nest("/workflows", workflows::routes())
         .nest("/search", search::routes())
         .nest("/terminal", terminal::routes())
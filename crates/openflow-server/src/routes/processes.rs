@@ -6,7 +6,11 @@
 //!
 //! - `GET /api/processes` - List processes with optional filters
 //! - `GET /api/processes/running` - List all running processes
+//! - `GET /api/processes/queue` - List queued coding agent runs with their positions
 //! - `GET /api/processes/:id` - Get a process by ID
+//! - `GET /api/processes/:id/output` - Replay persisted output (`since`/`limit` cursors)
+//! - `GET /api/processes/:id/snapshot` - Recent terminal output and size, for reattaching
+//! - `GET /api/processes/:id/recording.cast` - Download the asciicast recording of a PTY process
 //! - `DELETE /api/processes/:id` - Delete a process record
 //! - `POST /api/processes/:id/kill` - Kill a running process
 //! - `POST /api/processes/:id/input` - Send input to a process
@@ -14,12 +18,15 @@
 
 use axum::{
     extract::{Path, Query, State},
+    http::header,
+    response::IntoResponse,
     routing::{get, post},
     Json, Router,
 };
-use openflow_contracts::ExecutionProcess;
+use openflow_contracts::{ExecutionProcess, ProcessOutputChunk, ProcessSnapshot, QueuedProcess};
 use openflow_core::events::{EntityType, Event};
-use openflow_core::services::process;
+use openflow_core::services::{process, recording};
+use openflow_process::ASCIICAST_CONTENT_TYPE;
 use serde::Deserialize;
 
 use crate::{error::ServerResult, state::AppState};
@@ -34,6 +41,16 @@ pub struct ListProcessesQuery {
     pub running_only: Option<bool>,
 }
 
+/// Query parameters for replaying process output
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetOutputQuery {
+    /// Only return chunks after this cursor (chunk id)
+    pub since: Option<i64>,
+    /// Maximum number of chunks to return
+    pub limit: Option<i32>,
+}
+
 /// Request body for sending input
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -54,7 +71,11 @@ pub fn routes() -> Router<AppState> {
     Router::new()
         .route("/", get(list))
         .route("/running", get(list_running))
+        .route("/queue", get(list_queue))
         .route("/:id", get(get_one).delete(delete_one))
+        .route("/:id/output", get(get_output))
+        .route("/:id/snapshot", get(get_snapshot))
+        .route("/:id/recording.cast", get(get_recording))
         .route("/:id/kill", post(kill))
         .route("/:id/input", post(send_input))
         .route("/:id/resize", post(resize))
@@ -85,6 +106,14 @@ async fn list_running(State(state): State<AppState>) -> ServerResult<Json<Vec<Ex
     Ok(Json(processes))
 }
 
+/// GET /api/processes/queue
+///
+/// List coding agent runs waiting in the run queue, in the order they will
+/// start.
+async fn list_queue(State(state): State<AppState>) -> ServerResult<Json<Vec<QueuedProcess>>> {
+    Ok(Json(state.process_service.queue().await))
+}
+
 /// GET /api/processes/{id}
 ///
 /// Get a process by ID.
@@ -96,6 +125,51 @@ async fn get_one(
     Ok(Json(proc))
 }
 
+/// GET /api/processes/{id}/output
+///
+/// Replay persisted output chunks for a process, oldest first.
+async fn get_output(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<GetOutputQuery>,
+) -> ServerResult<Json<Vec<ProcessOutputChunk>>> {
+    let chunks = process::list_output(&state.pool, &id, query.since, query.limit).await?;
+    Ok(Json(chunks))
+}
+
+/// GET /api/processes/{id}/snapshot
+///
+/// Get the recent terminal output and size of a running PTY process, so a
+/// client attaching mid-run can render the screen before following live
+/// output events.
+async fn get_snapshot(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> ServerResult<Json<ProcessSnapshot>> {
+    let snapshot = state.process_service.snapshot(&id).await?;
+    Ok(Json(snapshot))
+}
+
+/// GET /api/processes/{id}/recording.cast
+///
+/// Download the asciicast v2 recording of a PTY process, for replaying in
+/// standard players. Only processes started while session recording was
+/// enabled have one.
+async fn get_recording(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> ServerResult<impl IntoResponse> {
+    let cast = recording::export(&state.pool, &id).await?;
+    let disposition = format!("attachment; filename=\"{}.cast\"", id);
+    Ok((
+        [
+            (header::CONTENT_TYPE, ASCIICAST_CONTENT_TYPE.to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        cast,
+    ))
+}
+
 /// DELETE /api/processes/{id}
 ///
 /// Delete a process record by ID.
@@ -212,6 +286,7 @@ mod tests {
                 always_included_rules: None,
                 workflows_folder: None,
                 verification_config: None,
+                script_limits: None,
             };
             project::create(&self.pool, request)
                 .await
@@ -467,6 +542,31 @@ mod tests {
         assert_eq!(processes[0].status, ProcessStatus::Running);
     }
 
+    #[tokio::test]
+    async fn test_list_queue_empty() {
+        let ctx = TestContext::new().await;
+
+        let response = ctx
+            .app()
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/processes/queue")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let queue: Vec<QueuedProcess> = serde_json::from_slice(&body).unwrap();
+        assert!(queue.is_empty());
+    }
+
     #[tokio::test]
     async fn test_get_process() {
         let ctx = TestContext::new().await;
@@ -517,6 +617,118 @@ mod tests {
         assert_eq!(response.status(), StatusCode::NOT_FOUND);
     }
 
+    #[tokio::test]
+    async fn test_get_process_output() {
+        let ctx = TestContext::new().await;
+        let chat_id = ctx.create_chat().await;
+        let created = ctx.create_process(&chat_id, "With Output").await;
+
+        let first = process::append_output(
+            &ctx.pool,
+            &created.id,
+            openflow_contracts::OutputType::Stdout,
+            "line 1\n",
+        )
+        .await
+        .unwrap();
+        process::append_output(
+            &ctx.pool,
+            &created.id,
+            openflow_contracts::OutputType::Stderr,
+            "line 2\n",
+        )
+        .await
+        .unwrap();
+
+        // Full replay
+        let response = ctx
+            .app()
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!("/processes/{}/output", created.id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let chunks: Vec<ProcessOutputChunk> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].content, "line 1\n");
+        assert!(chunks[1].is_stderr());
+
+        // Resume from the first chunk's cursor
+        let response = ctx
+            .app()
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!(
+                        "/processes/{}/output?since={}&limit=10",
+                        created.id, first.id
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let chunks: Vec<ProcessOutputChunk> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].content, "line 2\n");
+    }
+
+    #[tokio::test]
+    async fn test_get_process_output_not_found() {
+        let ctx = TestContext::new().await;
+
+        let response = ctx
+            .app()
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/processes/non-existent-id/output")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_get_process_output_invalid_limit() {
+        let ctx = TestContext::new().await;
+        let chat_id = ctx.create_chat().await;
+        let created = ctx.create_process(&chat_id, "Bad Limit").await;
+
+        let response = ctx
+            .app()
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!("/processes/{}/output?limit=0", created.id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
     #[tokio::test]
     async fn test_delete_process() {
         let ctx = TestContext::new().await;
@@ -708,6 +920,66 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_get_snapshot_not_running() {
+        let ctx = TestContext::new().await;
+        let chat_id = ctx.create_chat().await;
+        let created = ctx.create_process(&chat_id, "Snapshot Test").await;
+
+        let response = ctx
+            .app()
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!("/processes/{}/snapshot", created.id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_get_recording() {
+        let ctx = TestContext::new().await;
+        let chat_id = ctx.create_chat().await;
+        let created = ctx.create_process(&chat_id, "Recorded").await;
+        let uri = format!("/processes/{}/recording.cast", created.id);
+
+        let response = ctx
+            .app()
+            .oneshot(Request::builder().uri(&uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        recording::start(
+            &ctx.pool,
+            &created.id,
+            openflow_process::PtySize { cols: 80, rows: 24 },
+        )
+        .await
+        .unwrap();
+        let response = ctx
+            .app()
+            .oneshot(Request::builder().uri(&uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            ASCIICAST_CONTENT_TYPE
+        );
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let cast = String::from_utf8(body.to_vec()).unwrap();
+        assert!(cast.starts_with(r#"{"version":2,"width":80,"height":24,"#));
+    }
+
     #[tokio::test]
     async fn test_list_processes_order() {
         let ctx = TestContext::new().await;
@@ -408,6 +408,7 @@ mod tests {
             always_included_rules: None,
             workflows_folder: None,
             verification_config: None,
+            script_limits: None,
         };
         let created = project::create(&state.pool, create_request).await.unwrap();
 
@@ -451,6 +452,7 @@ mod tests {
             always_included_rules: None,
             workflows_folder: None,
             verification_config: None,
+            script_limits: None,
         };
         let created = project::create(&state.pool, create_request).await.unwrap();
 
@@ -497,6 +499,7 @@ mod tests {
             always_included_rules: None,
             workflows_folder: None,
             verification_config: None,
+            script_limits: None,
         };
         let created = project::create(&state.pool, create_request).await.unwrap();
         project::archive(&state.pool, &created.id).await.unwrap();
@@ -544,6 +547,7 @@ mod tests {
             always_included_rules: None,
             workflows_folder: None,
             verification_config: None,
+            script_limits: None,
         };
         let created = project::create(&state.pool, create_request).await.unwrap();
         project::archive(&state.pool, &created.id).await.unwrap();
@@ -0,0 +1,176 @@
+//! Secrets Routes
+//!
+//! REST API endpoints for the encrypted secrets store. Secrets are
+//! write-only: values can be set but are never returned.
+//!
+//! # Endpoints
+//!
+//! | Method | Path | Description |
+//! |--------|------|-------------|
+//! | GET | /api/secrets | List secret names |
+//! | PUT | /api/secrets/:name | Create or replace a secret |
+//! | DELETE | /api/secrets/:name | Delete a secret |
+
+use axum::{
+    extract::{Path, State},
+    routing::{get, put},
+    Json, Router,
+};
+use openflow_contracts::{Secret, SetSecretRequest};
+use openflow_core::events::{EntityType, Event};
+use openflow_core::services::secret;
+use serde::Deserialize;
+
+use crate::{error::ServerResult, state::AppState};
+
+/// Request body for setting a secret value
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetSecretValueRequest {
+    pub value: String,
+}
+
+/// Create secrets routes
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list))
+        .route("/:name", put(set_one).delete(delete_one))
+}
+
+/// GET /api/secrets
+///
+/// List all secrets by name.
+async fn list(State(state): State<AppState>) -> ServerResult<Json<Vec<Secret>>> {
+    let secrets = secret::list(&state.pool).await?;
+    Ok(Json(secrets))
+}
+
+/// PUT /api/secrets/{name}
+///
+/// Create or replace a secret. Only the name and timestamps are returned.
+async fn set_one(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    Json(request): Json<SetSecretValueRequest>,
+) -> ServerResult<Json<Secret>> {
+    let cipher = state.process_service.secret_cipher()?;
+    let stored = secret::set(
+        &state.pool,
+        cipher,
+        SetSecretRequest::new(name, request.value),
+    )
+    .await?;
+
+    state.broadcast(Event::updated(EntityType::Secret, &stored.name, &stored));
+
+    Ok(Json(stored))
+}
+
+/// DELETE /api/secrets/{name}
+///
+/// Delete a secret.
+async fn delete_one(State(state): State<AppState>, Path(name): Path<String>) -> ServerResult<()> {
+    secret::delete(&state.pool, &name).await?;
+
+    state.broadcast(Event::deleted(EntityType::Secret, name));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use openflow_core::services::process::ProcessService;
+    use openflow_core::services::secret::SecretCipher;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    /// Create a test app state with a secrets key
+    async fn test_state() -> AppState {
+        let pool = openflow_db::create_test_db().await.unwrap();
+        let process_service =
+            Arc::new(ProcessService::new().with_secret_cipher(SecretCipher::generate()));
+        AppState::new_test(pool, process_service)
+    }
+
+    #[tokio::test]
+    async fn test_set_list_delete_secret() {
+        let state = test_state().await;
+        let app = Router::new()
+            .nest("/secrets", routes())
+            .with_state(state.clone());
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("PUT")
+                    .uri("/secrets/API_KEY")
+                    .header("content-type", "application/json")
+                    .body(Body::from(r#"{"value": "sk-hidden"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert!(!String::from_utf8_lossy(&body).contains("sk-hidden"));
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/secrets")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let secrets: Vec<Secret> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(secrets.len(), 1);
+        assert_eq!(secrets[0].name, "API_KEY");
+        assert!(!String::from_utf8_lossy(&body).contains("sk-hidden"));
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("DELETE")
+                    .uri("/secrets/API_KEY")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(secret::list(&state.pool).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_set_secret_rejects_invalid_name() {
+        let state = test_state().await;
+        let app = Router::new().nest("/secrets", routes()).with_state(state);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("PUT")
+                    .uri("/secrets/not-valid")
+                    .header("content-type", "application/json")
+                    .body(Body::from(r#"{"value": "x"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
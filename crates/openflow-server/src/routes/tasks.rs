@@ -1,6 +1,7 @@
 //! Task Routes
 //!
-//! REST API endpoints for task CRUD operations and artifact management.
+//! REST API endpoints for task CRUD operations, artifact management and
+//! running a task's workflow.
 
 use axum::{
     extract::{Path, Query, State},
@@ -8,10 +9,11 @@ use axum::{
     Json, Router,
 };
 use openflow_contracts::{
-    ArtifactFile, CreateTaskRequest, Task, TaskStatus, TaskWithChats, UpdateTaskRequest,
+    ArtifactFile, Chat, CleanupWorktreeRequest, CreateTaskRequest, ExecutionProcess, Task,
+    TaskStatus, TaskWithChats, UpdateTaskRequest, WorkflowRun,
 };
 use openflow_core::events::{EntityType, Event};
-use openflow_core::services::{artifact, task};
+use openflow_core::services::{artifact, task, workflow_runner, worktree};
 use serde::Deserialize;
 
 use crate::{error::ServerResult, state::AppState};
@@ -36,6 +38,13 @@ pub fn routes() -> Router<AppState> {
         .route("/:id/archive", post(archive))
         .route("/:id/unarchive", post(unarchive))
         .route("/:id/duplicate", post(duplicate))
+        .route("/:id/cleanup", post(cleanup))
+        .route("/:id/workflow", get(get_workflow))
+        .route("/:id/workflow/start", post(start_workflow))
+        .route(
+            "/:id/workflow/steps/:stepIndex/run",
+            post(run_workflow_step),
+        )
         // Artifact routes (using camelCase path params to match frontend)
         .route("/:taskId/artifacts", get(list_artifacts))
         .route("/:taskId/artifacts/:fileName", get(read_artifact))
@@ -71,11 +80,13 @@ async fn get_one(
 
 /// POST /api/tasks
 ///
-/// Create a new task.
+/// Create a new task. Values for the workflow template's variables are
+/// checked first.
 async fn create(
     State(state): State<AppState>,
     Json(request): Json<CreateTaskRequest>,
 ) -> ServerResult<Json<Task>> {
+    workflow_runner::check_new_task(&state.pool, &request).await?;
     let task = task::create(&state.pool, request).await?;
 
     // Broadcast data changed event
@@ -87,16 +98,32 @@ async fn create(
 /// PATCH /api/tasks/{id}
 ///
 /// Update an existing task.
+///
+/// Moving a task to done or cancelled reclaims its chat worktrees in the
+/// background (branches are kept).
 async fn update(
     State(state): State<AppState>,
     Path(id): Path<String>,
     Json(request): Json<UpdateTaskRequest>,
 ) -> ServerResult<Json<Task>> {
+    let finishing = request.status.as_ref().is_some_and(TaskStatus::is_terminal);
     let task = task::update(&state.pool, &id, request).await?;
 
     // Broadcast data changed event
     state.broadcast(Event::updated(EntityType::Task, task.id.clone(), &task));
 
+    if finishing {
+        let state = state.clone();
+        let task_id = task.id.clone();
+        tokio::spawn(async move {
+            if let Err(e) =
+                worktree::cleanup_task(&state.pool, &state.process_service, &task_id, false).await
+            {
+                tracing::warn!("Failed to clean up worktrees for task {}: {}", task_id, e);
+            }
+        });
+    }
+
     Ok(Json(task))
 }
 
@@ -112,6 +139,29 @@ async fn delete_one(State(state): State<AppState>, Path(id): Path<String>) -> Se
     Ok(())
 }
 
+/// POST /api/tasks/{id}/cleanup
+///
+/// Run the project's cleanup script and remove the worktrees of all of the
+/// task's chats. Branches are kept unless `deleteBranch` is set.
+async fn cleanup(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    request: Option<Json<CleanupWorktreeRequest>>,
+) -> ServerResult<Json<Vec<Chat>>> {
+    let request = request.map(|Json(r)| r).unwrap_or_default();
+
+    // Chat updates are broadcast by the worktree service
+    let chats = worktree::cleanup_task(
+        &state.pool,
+        &state.process_service,
+        &id,
+        request.delete_branch,
+    )
+    .await?;
+
+    Ok(Json(chats))
+}
+
 /// POST /api/tasks/{id}/archive
 ///
 /// Archive a task.
@@ -157,6 +207,45 @@ async fn duplicate(
     Ok(Json(task))
 }
 
+// =============================================================================
+// Workflow Routes
+// =============================================================================
+
+/// GET /api/tasks/{id}/workflow
+///
+/// Get the task's workflow run with the status and chat of every step.
+async fn get_workflow(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> ServerResult<Json<WorkflowRun>> {
+    let run = workflow_runner::get(&state.pool, &id).await?;
+    Ok(Json(run))
+}
+
+/// POST /api/tasks/{id}/workflow/start
+///
+/// Start or resume the task's workflow: creates the step chats on first use
+/// and runs the current step. Progress is broadcast as workflow updates.
+async fn start_workflow(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> ServerResult<Json<WorkflowRun>> {
+    let run = workflow_runner::start(&state.pool, &state.process_service, &id).await?;
+    Ok(Json(run))
+}
+
+/// POST /api/tasks/{id}/workflow/steps/{stepIndex}/run
+///
+/// Run a step of the task's workflow in its chat.
+async fn run_workflow_step(
+    State(state): State<AppState>,
+    Path((id, step_index)): Path<(String, i32)>,
+) -> ServerResult<Json<ExecutionProcess>> {
+    let process =
+        workflow_runner::run_step(&state.pool, &state.process_service, &id, step_index).await?;
+    Ok(Json(process))
+}
+
 // =============================================================================
 // Artifact Routes
 // =============================================================================
@@ -240,6 +329,7 @@ mod tests {
                 always_included_rules: None,
                 workflows_folder: None,
                 verification_config: None,
+                script_limits: None,
             };
             project::create(&self.pool, request)
                 .await
@@ -1014,4 +1104,93 @@ mod tests {
         // Should return 400 Bad Request due to missing required query param
         assert_eq!(response.status(), StatusCode::BAD_REQUEST);
     }
+
+    #[tokio::test]
+    async fn test_workflow_not_started() {
+        let ctx = TestContext::new().await;
+        let project_id = ctx.create_project("Test Project").await;
+        let task = task::create(
+            &ctx.pool,
+            CreateTaskRequest::new(&project_id, "Without workflow"),
+        )
+        .await
+        .unwrap();
+
+        let response = ctx
+            .app()
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!("/tasks/{}/workflow", task.id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        // The task has no workflow template to start
+        let response = ctx
+            .app()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!("/tasks/{}/workflow/start", task.id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_create_task_checks_workflow_variables() {
+        let ctx = TestContext::new().await;
+        let repo = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            repo.path().join("ticket.md"),
+            "---\nvariables:\n  ticket_url: {}\n---\n\n### [ ] Step: Fix\nFix {@ticket_url}.\n",
+        )
+        .unwrap();
+        let project = project::create(
+            &ctx.pool,
+            CreateProjectRequest {
+                name: "Workflow Project".to_string(),
+                git_repo_path: repo.path().to_string_lossy().into_owned(),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let create = |variables: serde_json::Value| {
+            let body = serde_json::json!({
+                "projectId": project.id,
+                "title": "Fix ticket",
+                "workflowTemplate": "ticket.md",
+                "workflowVariables": variables,
+            });
+            Request::builder()
+                .method("POST")
+                .uri("/tasks")
+                .header("Content-Type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        let response = ctx
+            .app()
+            .oneshot(create(serde_json::json!({})))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = ctx
+            .app()
+            .oneshot(create(serde_json::json!({ "ticket_url": "T-7" })))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
 }
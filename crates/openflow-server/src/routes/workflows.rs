@@ -12,6 +12,8 @@
 //! | GET | /api/workflows/templates/:id | Get a template by ID (built-in or file-based) |
 //! | POST | /api/workflows/parse | Parse workflow markdown content into steps |
 //! | POST | /api/workflows/parse/steps | Parse workflow content (alias for /parse) |
+//! | POST | /api/workflows/variables | List the variables a workflow declares or uses |
+//! | POST | /api/workflows/lint | Lint workflow content, with line-numbered diagnostics |
 //! | POST | /api/workflows/substitute | Substitute variables in content using HashMap |
 //! | POST | /api/workflows/substitute/context | Substitute variables using WorkflowContext |
 
@@ -20,7 +22,9 @@ use axum::{
     routing::{get, post},
     Json, Router,
 };
-use openflow_contracts::{WorkflowContext, WorkflowStep, WorkflowTemplate};
+use openflow_contracts::{
+    WorkflowContext, WorkflowDiagnostic, WorkflowStep, WorkflowTemplate, WorkflowVariableInfo,
+};
 use openflow_core::services::workflow;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -75,6 +79,8 @@ pub fn routes() -> Router<AppState> {
         .route("/templates/:id", get(get_template))
         .route("/parse", post(parse_content))
         .route("/parse/steps", post(parse_steps))
+        .route("/variables", post(list_variables))
+        .route("/lint", post(lint))
         .route("/substitute", post(substitute_variables))
         .route("/substitute/context", post(substitute_with_context))
 }
@@ -146,6 +152,28 @@ async fn parse_steps(
     Ok(Json(steps))
 }
 
+/// POST /api/workflows/variables
+///
+/// List the variables declared in the workflow's front-matter and every
+/// `{@name}` placeholder it uses.
+async fn list_variables(
+    Json(request): Json<ParseWorkflowRequest>,
+) -> ServerResult<Json<Vec<WorkflowVariableInfo>>> {
+    let variables = workflow::list_variables(&request.content)?;
+    Ok(Json(variables))
+}
+
+/// POST /api/workflows/lint
+///
+/// Lint workflow content. Returns errors and warnings with their line and
+/// column, e.g. for step headers `parse` would silently skip.
+async fn lint(
+    Json(request): Json<ParseWorkflowRequest>,
+) -> ServerResult<Json<Vec<WorkflowDiagnostic>>> {
+    let diagnostics = workflow::lint(&request.content)?;
+    Ok(Json(diagnostics))
+}
+
 /// POST /api/workflows/substitute
 ///
 /// Substitute variables in workflow content using a HashMap.
@@ -621,6 +649,82 @@ Third step description.
         assert_eq!(steps[2].status, WorkflowStepStatus::Completed);
     }
 
+    #[tokio::test]
+    async fn test_list_variables() {
+        let state = test_state().await;
+        let app = Router::new().nest("/workflows", routes()).with_state(state);
+
+        let content = "---\nvariables:\n  ticket_url:\n    description: Ticket\n---\n### [ ] Step: Fix\nFix {@ticket_url} in {@worktree_path}.\n";
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/workflows/variables")
+                    .header("content-type", "application/json")
+                    .body(Body::from(
+                        serde_json::to_string(&ParseWorkflowRequest {
+                            content: content.to_string(),
+                        })
+                        .unwrap(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let variables: Vec<WorkflowVariableInfo> = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(variables.len(), 2);
+        assert_eq!(variables[0].name, "ticket_url");
+        assert!(variables[0].required && variables[0].declared);
+        assert_eq!(variables[1].name, "worktree_path");
+        assert!(variables[1].builtin);
+    }
+
+    #[tokio::test]
+    async fn test_lint() {
+        let state = test_state().await;
+        let app = Router::new().nest("/workflows", routes()).with_state(state);
+
+        let content = "# Plan\n\n###[ ] Step: Spec\nWrite the spec.\n";
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/workflows/lint")
+                    .header("content-type", "application/json")
+                    .body(Body::from(
+                        serde_json::to_string(&ParseWorkflowRequest {
+                            content: content.to_string(),
+                        })
+                        .unwrap(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let diagnostics: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(diagnostics.as_array().unwrap().len(), 1);
+        assert_eq!(diagnostics[0]["line"], 3);
+        assert_eq!(diagnostics[0]["column"], 4);
+        assert_eq!(diagnostics[0]["severity"], "error");
+        assert_eq!(diagnostics[0]["code"], "malformed_step_header");
+    }
+
     #[tokio::test]
     async fn test_parse_content_empty() {
         let state = test_state().await;
@@ -138,6 +138,7 @@ impl WsBroadcaster {
                 output_type,
                 content,
                 timestamp,
+                offset,
             } => {
                 let channel = process_output_channel(&process_id);
                 let contract_event = ProcessOutputEvent {
@@ -145,6 +146,7 @@ impl WsBroadcaster {
                     output_type: convert_output_type(output_type),
                     content,
                     timestamp,
+                    offset,
                 };
                 let message = WsServerMessage::process_output(&contract_event);
                 (channel, message)
@@ -244,6 +246,7 @@ fn convert_process_status(
     status: CoreProcessStatus,
 ) -> openflow_contracts::entities::process::ProcessStatus {
     match status {
+        CoreProcessStatus::Queued => openflow_contracts::entities::process::ProcessStatus::Queued,
         CoreProcessStatus::Starting => {
             openflow_contracts::entities::process::ProcessStatus::Running
         }
@@ -253,6 +256,9 @@ fn convert_process_status(
         }
         CoreProcessStatus::Failed => openflow_contracts::entities::process::ProcessStatus::Failed,
         CoreProcessStatus::Killed => openflow_contracts::entities::process::ProcessStatus::Killed,
+        CoreProcessStatus::TimedOut => {
+            openflow_contracts::entities::process::ProcessStatus::TimedOut
+        }
     }
 }
 
@@ -267,6 +273,9 @@ fn convert_entity_type(entity: CoreEntityType) -> openflow_contracts::events::En
         CoreEntityType::Setting => openflow_contracts::events::EntityType::Setting,
         CoreEntityType::Process => openflow_contracts::events::EntityType::Process,
         CoreEntityType::Worktree => openflow_contracts::events::EntityType::Worktree,
+        CoreEntityType::Verification => openflow_contracts::events::EntityType::Verification,
+        CoreEntityType::Secret => openflow_contracts::events::EntityType::Secret,
+        CoreEntityType::Workflow => openflow_contracts::events::EntityType::Workflow,
     }
 }
 
@@ -401,6 +410,7 @@ mod tests {
             output_type: CoreOutputType::Stdout,
             content: "Hello, World!".to_string(),
             timestamp: "2024-01-01T00:00:00Z".to_string(),
+            offset: Some(13),
         };
 
         let (channel, message) = broadcaster.event_to_message(event);
@@ -410,10 +420,11 @@ mod tests {
 
         if let WsServerMessage::Event {
             channel: msg_channel,
-            ..
+            payload,
         } = message
         {
             assert_eq!(msg_channel, "process-output-proc-123");
+            assert_eq!(payload["offset"], 13);
         }
     }
 
@@ -508,6 +519,7 @@ mod tests {
             output_type: CoreOutputType::Stdout,
             content: "test output".to_string(),
             timestamp: chrono::Utc::now().to_rfc3339(),
+            offset: None,
         };
 
         broadcaster.broadcast_async_impl(event).await;
@@ -603,6 +615,7 @@ mod tests {
             output_type: CoreOutputType::Stderr,
             content: "error".to_string(),
             timestamp: chrono::Utc::now().to_rfc3339(),
+            offset: None,
         };
 
         broadcaster.broadcast_async_impl(data_event).await;
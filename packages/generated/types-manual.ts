@@ -8,7 +8,7 @@
  * crates/openflow-contracts/src/entities/
  */
 
-import type { Chat, Message } from './types';
+import type { Chat, Message, VerificationResult } from './types';
 
 /**
  * Chat with all its messages
@@ -24,6 +24,8 @@ import type { Chat, Message } from './types';
 export interface ChatWithMessages extends Chat {
   /** All messages in this chat, ordered by created_at ASC */
   messages: Message[];
+  /** Latest verification result of the chat (null if it was never verified) */
+  latestVerification?: VerificationResult | null;
 }
 
 /**
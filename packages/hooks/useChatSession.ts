@@ -14,7 +14,7 @@ import type { Chat, Message, Project } from '@openflow/generated';
 import { MessageRole } from '@openflow/generated';
 import { createLogger } from '@openflow/utils';
 import { useCallback, useEffect, useRef, useState } from 'react';
-import { useChat, useUpdateChat } from './useChats';
+import { useChat } from './useChats';
 import { type PermissionRequest, useClaudeEvents } from './useClaudeEvents';
 import { useExecutorProfiles, useRunExecutor } from './useExecutorProfiles';
 import { useCreateMessage, useMessages } from './useMessages';
@@ -32,19 +32,6 @@ const logger = createLogger('useChatSession');
 // Types
 // ============================================================================
 
-/** Tool info for persistence */
-interface ToolCall {
-  id: string;
-  name: string;
-  input: Record<string, unknown>;
-}
-
-interface ToolResult {
-  toolUseId: string;
-  content: string;
-  isError?: boolean;
-}
-
 /** Claude event type (matches useClaudeEvents) */
 interface ClaudeEvent {
   type: 'system' | 'assistant' | 'user' | 'result';
@@ -183,56 +170,6 @@ function filterToCurrentTurn(
   return events.slice(currentTurnStartIndex);
 }
 
-/**
- * Extract text content and tool data from Claude events for persistence.
- */
-function extractContentFromEvents(
-  events: ClaudeEvent[],
-  persistedAssistantCount = 0
-): {
-  textContent: string;
-  toolCalls: ToolCall[];
-  toolResults: ToolResult[];
-} {
-  const currentTurnEvents = filterToCurrentTurn(events, persistedAssistantCount);
-
-  const textParts: string[] = [];
-  const toolCalls: ToolCall[] = [];
-  const toolResults: ToolResult[] = [];
-
-  for (const event of currentTurnEvents) {
-    if (event.type === 'assistant' && event.message?.content) {
-      for (const block of event.message.content) {
-        if (block.type === 'text' && block.text) {
-          textParts.push(block.text);
-        } else if (block.type === 'tool_use' && block.id && block.name) {
-          toolCalls.push({
-            id: block.id,
-            name: block.name,
-            input: block.input ?? {},
-          });
-        }
-      }
-    } else if (event.type === 'user' && event.message?.content) {
-      for (const block of event.message.content) {
-        if (block.type === 'tool_result' && block.tool_use_id) {
-          toolResults.push({
-            toolUseId: block.tool_use_id,
-            content: block.content ?? '',
-            isError: block.is_error,
-          });
-        }
-      }
-    }
-  }
-
-  return {
-    textContent: textParts.join('\n\n'),
-    toolCalls,
-    toolResults,
-  };
-}
-
 /**
  * Process events into display items for rendering.
  */
@@ -337,7 +274,6 @@ export function useChatSession({ chatId, onError }: UseChatSessionOptions): Chat
   const [inputValue, setInputValue] = useState('');
   const messagesEndRef = useRef<HTMLDivElement>(null);
   const textareaRef = useRef<HTMLTextAreaElement>(null);
-  const savedProcessRef = useRef<string | null>(null);
 
   // Data fetching
   const { data: chatData, isLoading: isLoadingChat } = useChat(chatId);
@@ -366,7 +302,6 @@ export function useChatSession({ chatId, onError }: UseChatSessionOptions): Chat
   const runExecutor = useRunExecutor();
   const createMessage = useCreateMessage();
   const sendInput = useSendInput();
-  const updateChat = useUpdateChat();
   const killProcess = useKillProcess();
 
   // Claude events for streaming output
@@ -377,7 +312,6 @@ export function useChatSession({ chatId, onError }: UseChatSessionOptions): Chat
     isComplete,
     permissionRequest,
     clearPermissionRequest,
-    sessionId,
   } = useClaudeEvents(activeProcessId);
 
   // Log streaming state changes
@@ -387,12 +321,16 @@ export function useChatSession({ chatId, onError }: UseChatSessionOptions): Chat
     }
   }, [isRunning, activeProcessId]);
 
+  // The assistant response and the session ID are recorded by the server as
+  // the process streams its output, so there is nothing to persist here once
+  // it completes
   useEffect(() => {
     if (isComplete && activeProcessId) {
       logger.debug('Claude process completed', {
         processId: activeProcessId,
         eventCount: claudeEvents.length,
       });
+      setActiveProcessId(null);
     }
   }, [isComplete, activeProcessId, claudeEvents.length]);
 
@@ -409,101 +347,6 @@ export function useChatSession({ chatId, onError }: UseChatSessionOptions): Chat
   );
   const displayItems = processEventsToDisplayItems(currentTurnEvents);
 
-  // Save assistant response to database when process completes
-  useEffect(() => {
-    if (!isComplete || claudeEvents.length === 0 || !chatId || !activeProcessId) {
-      return;
-    }
-
-    if (savedProcessRef.current === activeProcessId) {
-      return;
-    }
-
-    const { textContent, toolCalls, toolResults } = extractContentFromEvents(
-      claudeEvents as ClaudeEvent[],
-      persistedAssistantCount
-    );
-
-    if (textContent || toolCalls.length > 0) {
-      savedProcessRef.current = activeProcessId;
-      logger.debug('Persisting assistant response', {
-        chatId,
-        processId: activeProcessId,
-        textLength: textContent.length,
-        toolCallCount: toolCalls.length,
-        toolResultCount: toolResults.length,
-      });
-
-      createMessage.mutate(
-        {
-          chatId,
-          role: MessageRole.Assistant,
-          content: textContent,
-          toolCalls: toolCalls.length > 0 ? JSON.stringify(toolCalls) : undefined,
-          toolResults: toolResults.length > 0 ? JSON.stringify(toolResults) : undefined,
-        },
-        {
-          onSuccess: () => {
-            logger.info('Assistant response persisted successfully', {
-              chatId,
-              processId: activeProcessId,
-            });
-            setActiveProcessId(null);
-          },
-          onError: (error) => {
-            logger.error('Failed to persist assistant response', {
-              chatId,
-              processId: activeProcessId,
-              error: error instanceof Error ? error.message : String(error),
-            });
-            toast.error(
-              'Failed to Save Response',
-              'The assistant response could not be saved. Please try again.'
-            );
-            setActiveProcessId(null);
-          },
-        }
-      );
-    } else {
-      setActiveProcessId(null);
-    }
-  }, [
-    isComplete,
-    claudeEvents,
-    chatId,
-    activeProcessId,
-    createMessage,
-    persistedAssistantCount,
-    toast,
-  ]);
-
-  // Save Claude session ID to chat for session resumption
-  useEffect(() => {
-    if (!sessionId || !chatId || !chat) return;
-    if (chat.claudeSessionId) return;
-
-    logger.debug('Saving Claude session ID to chat', { chatId, sessionId });
-    updateChat.mutate(
-      {
-        id: chatId,
-        request: { claudeSessionId: sessionId },
-      },
-      {
-        onSuccess: () => {
-          logger.info('Claude session ID saved successfully', { chatId, sessionId });
-        },
-        onError: (error) => {
-          logger.error('Failed to save Claude session ID', {
-            chatId,
-            sessionId,
-            error: error instanceof Error ? error.message : String(error),
-          });
-          // Don't show toast for this - it's not user-facing critical
-        },
-      }
-    );
-  }, [sessionId, chatId, chat, updateChat]);
-
   // Auto-scroll to bottom when new content arrives
   // biome-ignore lint/correctness/useExhaustiveDependencies: intentionally trigger on content changes
   useEffect(() => {
@@ -5,7 +5,7 @@
 // Generated by: scripts/generate-queries.ts
 // Source: crates/openflow-contracts/src/endpoints/mod.rs
 // Domain: chats
-// Generated at: 2026-01-03T02:06:04.724Z
+// Generated at: 2026-10-17T06:45:00.966Z
 //
 // This file contains type-safe query functions for the chats domain.
 // Each function wraps a Tauri IPC command with validation and logging.
@@ -18,11 +18,18 @@
 import type {
   Chat,
   ChatWithMessages,
+  CleanupWorktreeRequest,
   CreateChatRequest,
+  ExecutionProcess,
   UpdateChatRequest,
+  VerificationResult,
 } from '@openflow/generated';
 import { createLogger } from '@openflow/utils';
-import { createChatRequestSchema, updateChatRequestSchema } from '@openflow/validation';
+import {
+  cleanupWorktreeRequestSchema,
+  createChatRequestSchema,
+  updateChatRequestSchema,
+} from '@openflow/validation';
 import { invoke } from '../utils.js';
 
 const logger = createLogger('queries:chats:generated');
@@ -54,6 +61,38 @@ export async function archiveChat(id: string): Promise<Chat> {
   }
 }
 
+/**
+ * Run the cleanup script and remove a chat's worktree
+ *
+ * @endpoint POST /api/chats/:id/cleanup
+ * @command cleanup_chat_worktree
+ * @param id - Path parameter: id
+ * @param request - Request body (validated with Zod)
+ * @returns Promise resolving to Chat
+ * @throws Error if validation or query fails
+ */
+export async function cleanupChatWorktree(
+  id: string,
+  request: CleanupWorktreeRequest
+): Promise<Chat> {
+  logger.debug('Calling cleanup_chat_worktree', { id });
+
+  try {
+    const validated = cleanupWorktreeRequestSchema.parse(request);
+    const result = await invoke<Chat>('cleanup_chat_worktree', { id: id, request: validated });
+
+    logger.info('cleanup_chat_worktree completed', {
+      id: (result as unknown as Record<string, unknown>).id,
+    });
+
+    return result;
+  } catch (error) {
+    const errorMessage = error instanceof Error ? error.message : String(error);
+    logger.error('cleanup_chat_worktree failed', { error: errorMessage });
+    throw error;
+  }
+}
+
 /**
  * Create a new chat
  *
@@ -128,6 +167,33 @@ export async function getChat(id: string): Promise<ChatWithMessages> {
   }
 }
 
+/**
+ * Get the running dev server of a chat, if any
+ *
+ * @endpoint GET /api/chats/:id/dev-server
+ * @command get_dev_server
+ * @param id - Path parameter: id
+ * @returns Promise resolving to ExecutionProcess
+ * @throws Error if validation or query fails
+ */
+export async function getDevServer(id: string): Promise<ExecutionProcess> {
+  logger.debug('Calling get_dev_server', { id });
+
+  try {
+    const result = await invoke<ExecutionProcess>('get_dev_server', { id: id });
+
+    logger.info('get_dev_server completed', {
+      id: (result as unknown as Record<string, unknown>).id,
+    });
+
+    return result;
+  } catch (error) {
+    const errorMessage = error instanceof Error ? error.message : String(error);
+    logger.error('get_dev_server failed', { error: errorMessage });
+    throw error;
+  }
+}
+
 /**
  * List all archived chats
  *
@@ -152,6 +218,31 @@ export async function listArchivedChats(): Promise<Chat[]> {
   }
 }
 
+/**
+ * List the verification results of a chat, newest first
+ *
+ * @endpoint GET /api/chats/:id/verifications
+ * @command list_chat_verifications
+ * @param id - Path parameter: id
+ * @returns Promise resolving to VerificationResult[]
+ * @throws Error if validation or query fails
+ */
+export async function listChatVerifications(id: string): Promise<VerificationResult[]> {
+  logger.debug('Calling list_chat_verifications', { id });
+
+  try {
+    const result = await invoke<VerificationResult[]>('list_chat_verifications', { id: id });
+
+    logger.info('list_chat_verifications completed', { count: result.length });
+
+    return result;
+  } catch (error) {
+    const errorMessage = error instanceof Error ? error.message : String(error);
+    logger.error('list_chat_verifications failed', { error: errorMessage });
+    throw error;
+  }
+}
+
 /**
  * List chats for a task
  *
@@ -227,6 +318,33 @@ export async function listStandaloneChats(projectId?: string): Promise<Chat[]> {
   }
 }
 
+/**
+ * Start the project's dev server in a chat's worktree
+ *
+ * @endpoint POST /api/chats/:id/dev-server
+ * @command start_dev_server
+ * @param id - Path parameter: id
+ * @returns Promise resolving to ExecutionProcess
+ * @throws Error if validation or query fails
+ */
+export async function startDevServer(id: string): Promise<ExecutionProcess> {
+  logger.debug('Calling start_dev_server', { id });
+
+  try {
+    const result = await invoke<ExecutionProcess>('start_dev_server', { id: id });
+
+    logger.info('start_dev_server completed', {
+      id: (result as unknown as Record<string, unknown>).id,
+    });
+
+    return result;
+  } catch (error) {
+    const errorMessage = error instanceof Error ? error.message : String(error);
+    logger.error('start_dev_server failed', { error: errorMessage });
+    throw error;
+  }
+}
+
 /**
  * Start a workflow step by creating its worktree
  *
@@ -254,6 +372,33 @@ export async function startWorkflowStep(id: string): Promise<Chat> {
   }
 }
 
+/**
+ * Stop the dev server of a chat
+ *
+ * @endpoint DELETE /api/chats/:id/dev-server
+ * @command stop_dev_server
+ * @param id - Path parameter: id
+ * @returns Promise resolving to ExecutionProcess
+ * @throws Error if validation or query fails
+ */
+export async function stopDevServer(id: string): Promise<ExecutionProcess> {
+  logger.debug('Calling stop_dev_server', { id });
+
+  try {
+    const result = await invoke<ExecutionProcess>('stop_dev_server', { id: id });
+
+    logger.info('stop_dev_server completed', {
+      id: (result as unknown as Record<string, unknown>).id,
+    });
+
+    return result;
+  } catch (error) {
+    const errorMessage = error instanceof Error ? error.message : String(error);
+    logger.error('stop_dev_server failed', { error: errorMessage });
+    throw error;
+  }
+}
+
 /**
  * Toggle completion status of a workflow step chat
  *
@@ -334,3 +479,28 @@ export async function updateChat(id: string, request: UpdateChatRequest): Promis
     throw error;
   }
 }
+
+/**
+ * Run the project's verification checks in a chat's worktree
+ *
+ * @endpoint POST /api/chats/:id/verify
+ * @command verify_chat
+ * @param id - Path parameter: id
+ * @returns Promise resolving to VerificationResult
+ * @throws Error if validation or query fails
+ */
+export async function verifyChat(id: string): Promise<VerificationResult> {
+  logger.debug('Calling verify_chat', { id });
+
+  try {
+    const result = await invoke<VerificationResult>('verify_chat', { id: id });
+
+    logger.info('verify_chat completed', { id: (result as unknown as Record<string, unknown>).id });
+
+    return result;
+  } catch (error) {
+    const errorMessage = error instanceof Error ? error.message : String(error);
+    logger.error('verify_chat failed', { error: errorMessage });
+    throw error;
+  }
+}
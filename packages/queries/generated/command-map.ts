@@ -4,7 +4,7 @@
 //
 // Generated by: scripts/generate-queries.ts
 // Source: crates/openflow-contracts/src/endpoints/mod.rs
-// Generated at: 2026-01-03T02:06:04.726Z
+// Generated at: 2026-10-17T06:45:00.978Z
 //
 // Maps Tauri command names to HTTP endpoints for the HTTP transport.
 // Used when running in browser mode without Tauri.
@@ -81,6 +81,20 @@ export const COMMAND_MAP: Record<string, EndpointMapping> = {
     queryParams: [],
     hasRequestBody: false,
   },
+  cleanup_chat_worktree: {
+    method: 'POST',
+    path: '/api/chats/:id/cleanup',
+    pathParams: ['id'],
+    queryParams: [],
+    hasRequestBody: true,
+  },
+  cleanup_task_worktrees: {
+    method: 'POST',
+    path: '/api/tasks/:id/cleanup',
+    pathParams: ['id'],
+    queryParams: [],
+    hasRequestBody: true,
+  },
   create_chat: {
     method: 'POST',
     path: '/api/chats',
@@ -158,6 +172,13 @@ export const COMMAND_MAP: Record<string, EndpointMapping> = {
     queryParams: [],
     hasRequestBody: false,
   },
+  delete_secret: {
+    method: 'DELETE',
+    path: '/api/secrets/:name',
+    pathParams: ['name'],
+    queryParams: [],
+    hasRequestBody: false,
+  },
   delete_setting: {
     method: 'DELETE',
     path: '/api/settings/:key',
@@ -186,6 +207,13 @@ export const COMMAND_MAP: Record<string, EndpointMapping> = {
     queryParams: [],
     hasRequestBody: false,
   },
+  export_process_recording: {
+    method: 'GET',
+    path: '/api/processes/:id/recording.cast',
+    pathParams: ['id'],
+    queryParams: [],
+    hasRequestBody: false,
+  },
   get_all_settings: {
     method: 'GET',
     path: '/api/settings',
@@ -228,6 +256,13 @@ export const COMMAND_MAP: Record<string, EndpointMapping> = {
     queryParams: [],
     hasRequestBody: false,
   },
+  get_dev_server: {
+    method: 'GET',
+    path: '/api/chats/:id/dev-server',
+    pathParams: ['id'],
+    queryParams: [],
+    hasRequestBody: false,
+  },
   get_diff: {
     method: 'GET',
     path: '/api/git/diff',
@@ -263,6 +298,20 @@ export const COMMAND_MAP: Record<string, EndpointMapping> = {
     queryParams: [],
     hasRequestBody: false,
   },
+  get_process_output: {
+    method: 'GET',
+    path: '/api/processes/:id/output',
+    pathParams: ['id'],
+    queryParams: ['since', 'limit'],
+    hasRequestBody: false,
+  },
+  get_process_snapshot: {
+    method: 'GET',
+    path: '/api/processes/:id/snapshot',
+    pathParams: ['id'],
+    queryParams: [],
+    hasRequestBody: false,
+  },
   get_project: {
     method: 'GET',
     path: '/api/projects/:id',
@@ -305,6 +354,13 @@ export const COMMAND_MAP: Record<string, EndpointMapping> = {
     queryParams: [],
     hasRequestBody: false,
   },
+  get_task_workflow: {
+    method: 'GET',
+    path: '/api/tasks/:id/workflow',
+    pathParams: ['id'],
+    queryParams: [],
+    hasRequestBody: false,
+  },
   get_workflow_template: {
     method: 'GET',
     path: '/api/workflows/templates/:id',
@@ -333,6 +389,13 @@ export const COMMAND_MAP: Record<string, EndpointMapping> = {
     queryParams: [],
     hasRequestBody: false,
   },
+  lint_workflow: {
+    method: 'POST',
+    path: '/api/workflows/lint',
+    pathParams: [],
+    queryParams: ['content'],
+    hasRequestBody: false,
+  },
   list_archived_chats: {
     method: 'GET',
     path: '/api/chats/archived',
@@ -347,6 +410,13 @@ export const COMMAND_MAP: Record<string, EndpointMapping> = {
     queryParams: [],
     hasRequestBody: false,
   },
+  list_chat_verifications: {
+    method: 'GET',
+    path: '/api/chats/:id/verifications',
+    pathParams: ['id'],
+    queryParams: [],
+    hasRequestBody: false,
+  },
   list_chats: {
     method: 'GET',
     path: '/api/chats',
@@ -389,6 +459,13 @@ export const COMMAND_MAP: Record<string, EndpointMapping> = {
     queryParams: [],
     hasRequestBody: false,
   },
+  list_run_queue: {
+    method: 'GET',
+    path: '/api/processes/queue',
+    pathParams: [],
+    queryParams: [],
+    hasRequestBody: false,
+  },
   list_running_processes: {
     method: 'GET',
     path: '/api/processes/running',
@@ -396,6 +473,13 @@ export const COMMAND_MAP: Record<string, EndpointMapping> = {
     queryParams: [],
     hasRequestBody: false,
   },
+  list_secrets: {
+    method: 'GET',
+    path: '/api/secrets',
+    pathParams: [],
+    queryParams: [],
+    hasRequestBody: false,
+  },
   list_standalone_chats: {
     method: 'GET',
     path: '/api/chats/standalone',
@@ -417,6 +501,13 @@ export const COMMAND_MAP: Record<string, EndpointMapping> = {
     queryParams: ['projectId'],
     hasRequestBody: false,
   },
+  list_workflow_variables: {
+    method: 'POST',
+    path: '/api/workflows/variables',
+    pathParams: [],
+    queryParams: ['content'],
+    hasRequestBody: false,
+  },
   list_worktrees: {
     method: 'GET',
     path: '/api/git/worktrees',
@@ -466,6 +557,13 @@ export const COMMAND_MAP: Record<string, EndpointMapping> = {
     queryParams: [],
     hasRequestBody: true,
   },
+  run_task_workflow_step: {
+    method: 'POST',
+    path: '/api/tasks/:id/workflow/steps/:stepIndex/run',
+    pathParams: ['id', 'stepIndex'],
+    queryParams: [],
+    hasRequestBody: false,
+  },
   running_process_count: {
     method: 'GET',
     path: '/api/processes/count/running',
@@ -501,6 +599,13 @@ export const COMMAND_MAP: Record<string, EndpointMapping> = {
     queryParams: ['inputTokens', 'outputTokens'],
     hasRequestBody: false,
   },
+  set_secret: {
+    method: 'PUT',
+    path: '/api/secrets/:name',
+    pathParams: ['name'],
+    queryParams: [],
+    hasRequestBody: true,
+  },
   set_setting: {
     method: 'PUT',
     path: '/api/settings/:key',
@@ -522,6 +627,20 @@ export const COMMAND_MAP: Record<string, EndpointMapping> = {
     queryParams: [],
     hasRequestBody: true,
   },
+  start_dev_server: {
+    method: 'POST',
+    path: '/api/chats/:id/dev-server',
+    pathParams: ['id'],
+    queryParams: [],
+    hasRequestBody: false,
+  },
+  start_task_workflow: {
+    method: 'POST',
+    path: '/api/tasks/:id/workflow/start',
+    pathParams: ['id'],
+    queryParams: [],
+    hasRequestBody: false,
+  },
   start_workflow_step: {
     method: 'POST',
     path: '/api/chats/:id/start-step',
@@ -529,6 +648,13 @@ export const COMMAND_MAP: Record<string, EndpointMapping> = {
     queryParams: [],
     hasRequestBody: false,
   },
+  stop_dev_server: {
+    method: 'DELETE',
+    path: '/api/chats/:id/dev-server',
+    pathParams: ['id'],
+    queryParams: [],
+    hasRequestBody: false,
+  },
   toggle_step_complete: {
     method: 'POST',
     path: '/api/chats/:id/toggle-step',
@@ -585,6 +711,13 @@ export const COMMAND_MAP: Record<string, EndpointMapping> = {
     queryParams: [],
     hasRequestBody: true,
   },
+  verify_chat: {
+    method: 'POST',
+    path: '/api/chats/:id/verify',
+    pathParams: ['id'],
+    queryParams: [],
+    hasRequestBody: false,
+  },
 };
 
 /**
@@ -3,7 +3,7 @@
 // =============================================================================
 //
 // Generated by: scripts/generate-queries.ts
-// Generated at: 2026-01-03T02:06:04.726Z
+// Generated at: 2026-10-17T04:34:28.108Z
 //
 // Re-exports all generated query functions by domain.
 //
@@ -19,6 +19,7 @@ export * from './messages.js';
 export * from './processes.js';
 export * from './projects.js';
 export * from './search.js';
+export * from './secrets.js';
 export * from './settings.js';
 export * from './system.js';
 export * from './tasks.js';
@@ -5,7 +5,7 @@
 // Generated by: scripts/generate-queries.ts
 // Source: crates/openflow-contracts/src/endpoints/mod.rs
 // Domain: processes
-// Generated at: 2026-01-03T02:06:04.725Z
+// Generated at: 2026-10-17T05:45:29.721Z
 //
 // This file contains type-safe query functions for the processes domain.
 // Each function wraps a Tauri IPC command with validation and logging.
@@ -17,6 +17,9 @@
 
 import type {
   ExecutionProcess,
+  ProcessOutputChunk,
+  ProcessSnapshot,
+  QueuedProcess,
   ResizeProcessRequest,
   SendProcessInputRequest,
 } from '@openflow/generated';
@@ -26,6 +29,31 @@ import { invoke } from '../utils.js';
 
 const logger = createLogger('queries:processes:generated');
 
+/**
+ * Download the asciicast v2 recording of a PTY process
+ *
+ * @endpoint GET /api/processes/:id/recording.cast
+ * @command export_process_recording
+ * @param id - Path parameter: id
+ * @returns Promise resolving to string
+ * @throws Error if validation or query fails
+ */
+export async function exportProcessRecording(id: string): Promise<string> {
+  logger.debug('Calling export_process_recording', { id });
+
+  try {
+    const result = await invoke<string>('export_process_recording', { id: id });
+
+    logger.info('export_process_recording completed');
+
+    return result;
+  } catch (error) {
+    const errorMessage = error instanceof Error ? error.message : String(error);
+    logger.error('export_process_recording failed', { error: errorMessage });
+    throw error;
+  }
+}
+
 /**
  * Get a process by ID
  *
@@ -51,6 +79,68 @@ export async function getProcess(id: string): Promise<ExecutionProcess> {
   }
 }
 
+/**
+ * Replay persisted output chunks for a process
+ *
+ * @endpoint GET /api/processes/:id/output
+ * @command get_process_output
+ * @param id - Path parameter: id
+ * @param since - Query parameter: since (optional)
+ * @param limit - Query parameter: limit (optional)
+ * @returns Promise resolving to ProcessOutputChunk[]
+ * @throws Error if validation or query fails
+ */
+export async function getProcessOutput(
+  id: string,
+  since?: string,
+  limit?: string
+): Promise<ProcessOutputChunk[]> {
+  logger.debug('Calling get_process_output', { id });
+
+  try {
+    const result = await invoke<ProcessOutputChunk[]>('get_process_output', {
+      id: id,
+      since: since,
+      limit: limit,
+    });
+
+    logger.info('get_process_output completed', { count: result.length });
+
+    return result;
+  } catch (error) {
+    const errorMessage = error instanceof Error ? error.message : String(error);
+    logger.error('get_process_output failed', { error: errorMessage });
+    throw error;
+  }
+}
+
+/**
+ * Get recent terminal output and size of a running process, for reattaching
+ *
+ * @endpoint GET /api/processes/:id/snapshot
+ * @command get_process_snapshot
+ * @param id - Path parameter: id
+ * @returns Promise resolving to ProcessSnapshot
+ * @throws Error if validation or query fails
+ */
+export async function getProcessSnapshot(id: string): Promise<ProcessSnapshot> {
+  logger.debug('Calling get_process_snapshot', { id });
+
+  try {
+    const result = await invoke<ProcessSnapshot>('get_process_snapshot', { id: id });
+
+    logger.info('get_process_snapshot completed', {
+      id: (result as unknown as Record<string, unknown>).id,
+    });
+
+    return result;
+  } catch (error) {
+    const errorMessage = error instanceof Error ? error.message : String(error);
+    logger.error('get_process_snapshot failed', { error: errorMessage });
+    throw error;
+  }
+}
+
 /**
  * Check if a process is running
  *
@@ -124,6 +214,30 @@ export async function listProcesses(chatId?: string): Promise<ExecutionProcess[]
   }
 }
 
+/**
+ * List coding agent runs waiting in the run queue
+ *
+ * @endpoint GET /api/processes/queue
+ * @command list_run_queue
+ * @returns Promise resolving to QueuedProcess[]
+ * @throws Error if validation or query fails
+ */
+export async function listRunQueue(): Promise<QueuedProcess[]> {
+  logger.debug('Calling list_run_queue');
+
+  try {
+    const result = await invoke<QueuedProcess[]>('list_run_queue');
+
+    logger.info('list_run_queue completed', { count: result.length });
+
+    return result;
+  } catch (error) {
+    const errorMessage = error instanceof Error ? error.message : String(error);
+    logger.error('list_run_queue failed', { error: errorMessage });
+    throw error;
+  }
+}
+
 /**
  * List all running processes
  *
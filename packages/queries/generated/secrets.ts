@@ -0,0 +1,97 @@
+// =============================================================================
+// AUTO-GENERATED FILE - DO NOT EDIT MANUALLY
+// =============================================================================
+//
+// Generated by: scripts/generate-queries.ts
+// Source: crates/openflow-contracts/src/endpoints/mod.rs
+// Domain: secrets
+// Generated at: 2026-10-17T04:34:28.103Z
+//
+// This file contains type-safe query functions for the secrets domain.
+// Each function wraps a Tauri IPC command with validation and logging.
+//
+// To regenerate: pnpm generate:queries
+//
+// @see CLAUDE.md - Query Layer Patterns section
+// =============================================================================
+
+import type { Secret, SetSecretRequest } from '@openflow/generated';
+import { createLogger } from '@openflow/utils';
+import { setSecretRequestSchema } from '@openflow/validation';
+import { invoke } from '../utils.js';
+
+const logger = createLogger('queries:secrets:generated');
+
+/**
+ * Delete a secret
+ *
+ * @endpoint DELETE /api/secrets/:name
+ * @command delete_secret
+ * @param name - Path parameter: name
+ * @returns Promise resolving to void
+ * @throws Error if validation or query fails
+ */
+export async function deleteSecret(name: string): Promise<void> {
+  logger.debug('Calling delete_secret', { name });
+
+  try {
+    await invoke<void>('delete_secret', { name: name });
+
+    logger.info('delete_secret completed successfully');
+  } catch (error) {
+    const errorMessage = error instanceof Error ? error.message : String(error);
+    logger.error('delete_secret failed', { error: errorMessage });
+    throw error;
+  }
+}
+
+/**
+ * List all secrets by name
+ *
+ * @endpoint GET /api/secrets
+ * @command list_secrets
+ * @returns Promise resolving to Secret[]
+ * @throws Error if validation or query fails
+ */
+export async function listSecrets(): Promise<Secret[]> {
+  logger.debug('Calling list_secrets');
+
+  try {
+    const result = await invoke<Secret[]>('list_secrets');
+
+    logger.info('list_secrets completed', { count: result.length });
+
+    return result;
+  } catch (error) {
+    const errorMessage = error instanceof Error ? error.message : String(error);
+    logger.error('list_secrets failed', { error: errorMessage });
+    throw error;
+  }
+}
+
+/**
+ * Create or replace a secret
+ *
+ * @endpoint PUT /api/secrets/:name
+ * @command set_secret
+ * @param name - Path parameter: name
+ * @param request - Request body (validated with Zod)
+ * @returns Promise resolving to Secret
+ * @throws Error if validation or query fails
+ */
+export async function setSecret(name: string, request: SetSecretRequest): Promise<Secret> {
+  logger.debug('Calling set_secret', { name });
+
+  try {
+    const validated = setSecretRequestSchema.parse(request);
+    const result = await invoke<Secret>('set_secret', { name: name, request: validated });
+
+    logger.info('set_secret completed', { id: (result as unknown as Record<string, unknown>).id });
+
+    return result;
+  } catch (error) {
+    const errorMessage = error instanceof Error ? error.message : String(error);
+    logger.error('set_secret failed', { error: errorMessage });
+    throw error;
+  }
+}
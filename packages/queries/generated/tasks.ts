@@ -5,7 +5,7 @@
 // Generated by: scripts/generate-queries.ts
 // Source: crates/openflow-contracts/src/endpoints/mod.rs
 // Domain: tasks
-// Generated at: 2026-01-03T02:06:04.726Z
+// Generated at: 2026-10-17T05:49:54.327Z
 //
 // This file contains type-safe query functions for the tasks domain.
 // Each function wraps a Tauri IPC command with validation and logging.
@@ -16,13 +16,21 @@
 // =============================================================================
 
 import type {
+  Chat,
+  CleanupWorktreeRequest,
   CreateTaskRequest,
+  ExecutionProcess,
   Task,
   TaskWithChats,
   UpdateTaskRequest,
+  WorkflowRun,
 } from '@openflow/generated';
 import { createLogger } from '@openflow/utils';
-import { createTaskRequestSchema, updateTaskRequestSchema } from '@openflow/validation';
+import {
+  cleanupWorktreeRequestSchema,
+  createTaskRequestSchema,
+  updateTaskRequestSchema,
+} from '@openflow/validation';
 import { invoke } from '../utils.js';
 
 const logger = createLogger('queries:tasks:generated');
@@ -54,6 +62,36 @@ export async function archiveTask(id: string): Promise<Task> {
   }
 }
 
+/**
+ * Run the cleanup script and remove the worktrees of a task's chats
+ *
+ * @endpoint POST /api/tasks/:id/cleanup
+ * @command cleanup_task_worktrees
+ * @param id - Path parameter: id
+ * @param request - Request body (validated with Zod)
+ * @returns Promise resolving to Chat[]
+ * @throws Error if validation or query fails
+ */
+export async function cleanupTaskWorktrees(
+  id: string,
+  request: CleanupWorktreeRequest
+): Promise<Chat[]> {
+  logger.debug('Calling cleanup_task_worktrees', { id });
+
+  try {
+    const validated = cleanupWorktreeRequestSchema.parse(request);
+    const result = await invoke<Chat[]>('cleanup_task_worktrees', { id: id, request: validated });
+
+    logger.info('cleanup_task_worktrees completed', { count: result.length });
+
+    return result;
+  } catch (error) {
+    const errorMessage = error instanceof Error ? error.message : String(error);
+    logger.error('cleanup_task_worktrees failed', { error: errorMessage });
+    throw error;
+  }
+}
+
 /**
  * Create a new task
  *
@@ -155,6 +193,33 @@ export async function getTask(id: string): Promise<TaskWithChats> {
   }
 }
 
+/**
+ * Get the workflow run of a task with the status and chat of every step
+ *
+ * @endpoint GET /api/tasks/:id/workflow
+ * @command get_task_workflow
+ * @param id - Path parameter: id
+ * @returns Promise resolving to WorkflowRun
+ * @throws Error if validation or query fails
+ */
+export async function getTaskWorkflow(id: string): Promise<WorkflowRun> {
+  logger.debug('Calling get_task_workflow', { id });
+
+  try {
+    const result = await invoke<WorkflowRun>('get_task_workflow', { id: id });
+
+    logger.info('get_task_workflow completed', {
+      id: (result as unknown as Record<string, unknown>).id,
+    });
+
+    return result;
+  } catch (error) {
+    const errorMessage = error instanceof Error ? error.message : String(error);
+    logger.error('get_task_workflow failed', { error: errorMessage });
+    throw error;
+  }
+}
+
 /**
  * List tasks, optionally filtered by project
  *
@@ -184,6 +249,67 @@ export async function listTasks(projectId?: string, includeArchived?: string): P
   }
 }
 
+/**
+ * Run a step of a task's workflow in its chat
+ *
+ * @endpoint POST /api/tasks/:id/workflow/steps/:stepIndex/run
+ * @command run_task_workflow_step
+ * @param id - Path parameter: id
+ * @param stepIndex - Path parameter: stepIndex
+ * @returns Promise resolving to ExecutionProcess
+ * @throws Error if validation or query fails
+ */
+export async function runTaskWorkflowStep(
+  id: string,
+  stepIndex: string
+): Promise<ExecutionProcess> {
+  logger.debug('Calling run_task_workflow_step', { id, stepIndex });
+
+  try {
+    const result = await invoke<ExecutionProcess>('run_task_workflow_step', {
+      id: id,
+      stepIndex: stepIndex,
+    });
+
+    logger.info('run_task_workflow_step completed', {
+      id: (result as unknown as Record<string, unknown>).id,
+    });
+
+    return result;
+  } catch (error) {
+    const errorMessage = error instanceof Error ? error.message : String(error);
+    logger.error('run_task_workflow_step failed', { error: errorMessage });
+    throw error;
+  }
+}
+
+/**
+ * Start or resume the workflow of a task
+ *
+ * @endpoint POST /api/tasks/:id/workflow/start
+ * @command start_task_workflow
+ * @param id - Path parameter: id
+ * @returns Promise resolving to WorkflowRun
+ * @throws Error if validation or query fails
+ */
+export async function startTaskWorkflow(id: string): Promise<WorkflowRun> {
+  logger.debug('Calling start_task_workflow', { id });
+
+  try {
+    const result = await invoke<WorkflowRun>('start_task_workflow', { id: id });
+
+    logger.info('start_task_workflow completed', {
+      id: (result as unknown as Record<string, unknown>).id,
+    });
+
+    return result;
+  } catch (error) {
+    const errorMessage = error instanceof Error ? error.message : String(error);
+    logger.error('start_task_workflow failed', { error: errorMessage });
+    throw error;
+  }
+}
+
 /**
  * Unarchive a task
  *
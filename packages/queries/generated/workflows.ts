@@ -5,7 +5,7 @@
 // Generated by: scripts/generate-queries.ts
 // Source: crates/openflow-contracts/src/endpoints/mod.rs
 // Domain: workflows
-// Generated at: 2026-01-03T02:06:04.726Z
+// Generated at: 2026-10-17T06:04:19.407Z
 //
 // This file contains type-safe query functions for the workflows domain.
 // Each function wraps a Tauri IPC command with validation and logging.
@@ -15,7 +15,11 @@
 // @see CLAUDE.md - Query Layer Patterns section
 // =============================================================================
 
-import type { WorkflowTemplate } from '@openflow/generated';
+import type {
+  WorkflowDiagnostic,
+  WorkflowTemplate,
+  WorkflowVariableInfo,
+} from '@openflow/generated';
 import { createLogger } from '@openflow/utils';
 import { invoke } from '../utils.js';
 
@@ -72,6 +76,31 @@ export async function getWorkflowTemplate(id: string): Promise<WorkflowTemplate>
   }
 }
 
+/**
+ * Lint workflow content, with line-numbered diagnostics
+ *
+ * @endpoint POST /api/workflows/lint
+ * @command lint_workflow
+ * @param content - Query parameter: content (optional)
+ * @returns Promise resolving to WorkflowDiagnostic[]
+ * @throws Error if validation or query fails
+ */
+export async function lintWorkflow(content?: string): Promise<WorkflowDiagnostic[]> {
+  logger.debug('Calling lint_workflow');
+
+  try {
+    const result = await invoke<WorkflowDiagnostic[]>('lint_workflow', { content: content });
+
+    logger.info('lint_workflow completed', { count: result.length });
+
+    return result;
+  } catch (error) {
+    const errorMessage = error instanceof Error ? error.message : String(error);
+    logger.error('lint_workflow failed', { error: errorMessage });
+    throw error;
+  }
+}
+
 /**
  * List workflow templates for a project
  *
@@ -99,6 +128,33 @@ export async function listWorkflowTemplates(projectId?: string): Promise<Workflo
   }
 }
 
+/**
+ * List the variables a workflow declares or uses
+ *
+ * @endpoint POST /api/workflows/variables
+ * @command list_workflow_variables
+ * @param content - Query parameter: content (optional)
+ * @returns Promise resolving to WorkflowVariableInfo[]
+ * @throws Error if validation or query fails
+ */
+export async function listWorkflowVariables(content?: string): Promise<WorkflowVariableInfo[]> {
+  logger.debug('Calling list_workflow_variables');
+
+  try {
+    const result = await invoke<WorkflowVariableInfo[]>('list_workflow_variables', {
+      content: content,
+    });
+
+    logger.info('list_workflow_variables completed', { count: result.length });
+
+    return result;
+  } catch (error) {
+    const errorMessage = error instanceof Error ? error.message : String(error);
+    logger.error('list_workflow_variables failed', { error: errorMessage });
+    throw error;
+  }
+}
+
 /**
  * Parse workflow content into a template
  *
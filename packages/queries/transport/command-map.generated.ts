@@ -4,7 +4,7 @@
 //
 // Generated by: scripts/generate-command-map.ts
 // Source: crates/openflow-contracts/src/endpoints/mod.rs
-// Generated at: 2026-01-03T02:06:05.041Z
+// Generated at: 2026-10-17T06:45:01.149Z
 //
 // This file maps Tauri IPC command names to HTTP endpoint metadata.
 // Used by the HTTP transport layer when running in browser mode.
@@ -126,6 +126,24 @@ export const COMMAND_MAP: Readonly<Record<string, EndpointMapping>> = {
     hasRequestBody: false,
     responseType: 'CliInstalledResponse',
   },
+  /** Run the cleanup script and remove a chat's worktree */
+  cleanup_chat_worktree: {
+    method: 'POST',
+    path: '/api/chats/:id/cleanup',
+    pathParams: ['id'] as const,
+    queryParams: [] as const,
+    hasRequestBody: true,
+    responseType: 'Chat',
+  },
+  /** Run the cleanup script and remove the worktrees of a task's chats */
+  cleanup_task_worktrees: {
+    method: 'POST',
+    path: '/api/tasks/:id/cleanup',
+    pathParams: ['id'] as const,
+    queryParams: [] as const,
+    hasRequestBody: true,
+    responseType: 'Chat[]',
+  },
   /** Create a new chat */
   create_chat: {
     method: 'POST',
@@ -225,6 +243,15 @@ export const COMMAND_MAP: Readonly<Record<string, EndpointMapping>> = {
     hasRequestBody: false,
     responseType: 'void',
   },
+  /** Delete a secret */
+  delete_secret: {
+    method: 'DELETE',
+    path: '/api/secrets/:name',
+    pathParams: ['name'] as const,
+    queryParams: [] as const,
+    hasRequestBody: false,
+    responseType: 'void',
+  },
   /** Delete a setting */
   delete_setting: {
     method: 'DELETE',
@@ -261,6 +288,15 @@ export const COMMAND_MAP: Readonly<Record<string, EndpointMapping>> = {
     hasRequestBody: false,
     responseType: 'Task',
   },
+  /** Download the asciicast v2 recording of a PTY process */
+  export_process_recording: {
+    method: 'GET',
+    path: '/api/processes/:id/recording.cast',
+    pathParams: ['id'] as const,
+    queryParams: [] as const,
+    hasRequestBody: false,
+    responseType: 'string',
+  },
   /** Get all settings */
   get_all_settings: {
     method: 'GET',
@@ -315,6 +351,15 @@ export const COMMAND_MAP: Readonly<Record<string, EndpointMapping>> = {
     hasRequestBody: false,
     responseType: 'ExecutorProfile',
   },
+  /** Get the running dev server of a chat, if any */
+  get_dev_server: {
+    method: 'GET',
+    path: '/api/chats/:id/dev-server',
+    pathParams: ['id'] as const,
+    queryParams: [] as const,
+    hasRequestBody: false,
+    responseType: 'ExecutionProcess',
+  },
   /** Get git diff for a worktree */
   get_diff: {
     method: 'GET',
@@ -360,6 +405,24 @@ export const COMMAND_MAP: Readonly<Record<string, EndpointMapping>> = {
     hasRequestBody: false,
     responseType: 'ExecutionProcess',
   },
+  /** Replay persisted output chunks for a process */
+  get_process_output: {
+    method: 'GET',
+    path: '/api/processes/:id/output',
+    pathParams: ['id'] as const,
+    queryParams: ['since', 'limit'] as const,
+    hasRequestBody: false,
+    responseType: 'ProcessOutputChunk[]',
+  },
+  /** Get recent terminal output and size of a running process, for reattaching */
+  get_process_snapshot: {
+    method: 'GET',
+    path: '/api/processes/:id/snapshot',
+    pathParams: ['id'] as const,
+    queryParams: [] as const,
+    hasRequestBody: false,
+    responseType: 'ProcessSnapshot',
+  },
   /** Get a project by ID */
   get_project: {
     method: 'GET',
@@ -414,6 +477,15 @@ export const COMMAND_MAP: Readonly<Record<string, EndpointMapping>> = {
     hasRequestBody: false,
     responseType: 'FileDiff[]',
   },
+  /** Get the workflow run of a task with the status and chat of every step */
+  get_task_workflow: {
+    method: 'GET',
+    path: '/api/tasks/:id/workflow',
+    pathParams: ['id'] as const,
+    queryParams: [] as const,
+    hasRequestBody: false,
+    responseType: 'WorkflowRun',
+  },
   /** Get a workflow template by ID */
   get_workflow_template: {
     method: 'GET',
@@ -450,6 +522,15 @@ export const COMMAND_MAP: Readonly<Record<string, EndpointMapping>> = {
     hasRequestBody: false,
     responseType: 'void',
   },
+  /** Lint workflow content, with line-numbered diagnostics */
+  lint_workflow: {
+    method: 'POST',
+    path: '/api/workflows/lint',
+    pathParams: [] as const,
+    queryParams: ['content'] as const,
+    hasRequestBody: false,
+    responseType: 'WorkflowDiagnostic[]',
+  },
   /** List all archived chats */
   list_archived_chats: {
     method: 'GET',
@@ -468,6 +549,15 @@ export const COMMAND_MAP: Readonly<Record<string, EndpointMapping>> = {
     hasRequestBody: false,
     responseType: 'Project[]',
   },
+  /** List the verification results of a chat, newest first */
+  list_chat_verifications: {
+    method: 'GET',
+    path: '/api/chats/:id/verifications',
+    pathParams: ['id'] as const,
+    queryParams: [] as const,
+    hasRequestBody: false,
+    responseType: 'VerificationResult[]',
+  },
   /** List chats for a task */
   list_chats: {
     method: 'GET',
@@ -522,6 +612,15 @@ export const COMMAND_MAP: Readonly<Record<string, EndpointMapping>> = {
     hasRequestBody: false,
     responseType: 'Project[]',
   },
+  /** List coding agent runs waiting in the run queue */
+  list_run_queue: {
+    method: 'GET',
+    path: '/api/processes/queue',
+    pathParams: [] as const,
+    queryParams: [] as const,
+    hasRequestBody: false,
+    responseType: 'QueuedProcess[]',
+  },
   /** List all running processes */
   list_running_processes: {
     method: 'GET',
@@ -531,6 +630,15 @@ export const COMMAND_MAP: Readonly<Record<string, EndpointMapping>> = {
     hasRequestBody: false,
     responseType: 'ExecutionProcess[]',
   },
+  /** List all secrets by name */
+  list_secrets: {
+    method: 'GET',
+    path: '/api/secrets',
+    pathParams: [] as const,
+    queryParams: [] as const,
+    hasRequestBody: false,
+    responseType: 'Secret[]',
+  },
   /** List standalone chats (not attached to tasks) */
   list_standalone_chats: {
     method: 'GET',
@@ -558,6 +666,15 @@ export const COMMAND_MAP: Readonly<Record<string, EndpointMapping>> = {
     hasRequestBody: false,
     responseType: 'WorkflowTemplate[]',
   },
+  /** List the variables a workflow declares or uses */
+  list_workflow_variables: {
+    method: 'POST',
+    path: '/api/workflows/variables',
+    pathParams: [] as const,
+    queryParams: ['content'] as const,
+    hasRequestBody: false,
+    responseType: 'WorkflowVariableInfo[]',
+  },
   /** List git worktrees for a repository */
   list_worktrees: {
     method: 'GET',
@@ -621,6 +738,15 @@ export const COMMAND_MAP: Readonly<Record<string, EndpointMapping>> = {
     hasRequestBody: true,
     responseType: 'ExecutionProcess',
   },
+  /** Run a step of a task's workflow in its chat */
+  run_task_workflow_step: {
+    method: 'POST',
+    path: '/api/tasks/:id/workflow/steps/:stepIndex/run',
+    pathParams: ['id', 'stepIndex'] as const,
+    queryParams: [] as const,
+    hasRequestBody: false,
+    responseType: 'ExecutionProcess',
+  },
   /** Get count of running processes */
   running_process_count: {
     method: 'GET',
@@ -666,6 +792,15 @@ export const COMMAND_MAP: Readonly<Record<string, EndpointMapping>> = {
     hasRequestBody: false,
     responseType: 'Message',
   },
+  /** Create or replace a secret */
+  set_secret: {
+    method: 'PUT',
+    path: '/api/secrets/:name',
+    pathParams: ['name'] as const,
+    queryParams: [] as const,
+    hasRequestBody: true,
+    responseType: 'Secret',
+  },
   /** Set a setting value */
   set_setting: {
     method: 'PUT',
@@ -693,6 +828,24 @@ export const COMMAND_MAP: Readonly<Record<string, EndpointMapping>> = {
     hasRequestBody: true,
     responseType: 'ExecutionProcess',
   },
+  /** Start the project's dev server in a chat's worktree */
+  start_dev_server: {
+    method: 'POST',
+    path: '/api/chats/:id/dev-server',
+    pathParams: ['id'] as const,
+    queryParams: [] as const,
+    hasRequestBody: false,
+    responseType: 'ExecutionProcess',
+  },
+  /** Start or resume the workflow of a task */
+  start_task_workflow: {
+    method: 'POST',
+    path: '/api/tasks/:id/workflow/start',
+    pathParams: ['id'] as const,
+    queryParams: [] as const,
+    hasRequestBody: false,
+    responseType: 'WorkflowRun',
+  },
   /** Start a workflow step by creating its worktree */
   start_workflow_step: {
     method: 'POST',
@@ -702,6 +855,15 @@ export const COMMAND_MAP: Readonly<Record<string, EndpointMapping>> = {
     hasRequestBody: false,
     responseType: 'Chat',
   },
+  /** Stop the dev server of a chat */
+  stop_dev_server: {
+    method: 'DELETE',
+    path: '/api/chats/:id/dev-server',
+    pathParams: ['id'] as const,
+    queryParams: [] as const,
+    hasRequestBody: false,
+    responseType: 'ExecutionProcess',
+  },
   /** Toggle completion status of a workflow step chat */
   toggle_step_complete: {
     method: 'POST',
@@ -774,6 +936,15 @@ export const COMMAND_MAP: Readonly<Record<string, EndpointMapping>> = {
     hasRequestBody: true,
     responseType: 'Task',
   },
+  /** Run the project's verification checks in a chat's worktree */
+  verify_chat: {
+    method: 'POST',
+    path: '/api/chats/:id/verify',
+    pathParams: ['id'] as const,
+    queryParams: [] as const,
+    hasRequestBody: false,
+    responseType: 'VerificationResult',
+  },
 } as const;
 
 /**
@@ -906,10 +1077,10 @@ export function getRequestBody(
 // =============================================================================
 // Statistics
 // =============================================================================
-// Total commands: 80
-// - DELETE: 7
-// - GET: 40
+// Total commands: 99
+// - DELETE: 9
+// - GET: 48
 // - PATCH: 6
-// - POST: 26
-// - PUT: 1
+// - POST: 34
+// - PUT: 2
 // =============================================================================
@@ -389,6 +389,13 @@ export function createHttpTransport(config?: HttpTransportConfig): Transport {
           return undefined as T;
         }
 
+        // Downloads (e.g. asciicast recordings) are returned as text
+        const contentType = response.headers.get('Content-Type') ?? '';
+        if (contentType && !contentType.includes('json')) {
+          logger.debug('HTTP request completed (text)', { cmd, contentType });
+          return text as T;
+        }
+
         // Parse JSON response
         const result = JSON.parse(text) as T;
 
@@ -4,7 +4,7 @@
 //
 // Generated by: scripts/generate-zod.ts
 // Source: crates/openflow-contracts/src/**/*.rs
-// Generated at: 2026-01-03T02:06:04.404Z
+// Generated at: 2026-10-17T06:32:44.833Z
 //
 // This file contains Zod validation schemas auto-generated from Rust contract
 // types. Validation rules are extracted from @validate: annotations in doc
@@ -60,7 +60,10 @@ export const entityTypeSchema = z.enum([
   'process',
   'setting',
   'workflowtemplate',
+  'workflow',
   'worktree',
+  'verification',
+  'secret',
 ]);
 
 /**
@@ -106,7 +109,14 @@ export const outputTypeSchema = z.enum(['stdout', 'stderr']);
  * Zod schema for ProcessStatus enum
  * @generated from entities/process.rs
  */
-export const processStatusSchema = z.enum(['running', 'completed', 'failed', 'killed']);
+export const processStatusSchema = z.enum([
+  'queued',
+  'running',
+  'completed',
+  'failed',
+  'killed',
+  'timedout',
+]);
 
 /**
  * Zod schema for RunReason enum
@@ -133,6 +143,33 @@ export const searchResultTypeSchema = z.enum(['task', 'project', 'chat', 'messag
  */
 export const taskStatusSchema = z.enum(['todo', 'inprogress', 'inreview', 'done', 'cancelled']);
 
+/**
+ * Zod schema for VerificationCheckStatus enum
+ * @generated from entities/verification.rs
+ */
+export const verificationCheckStatusSchema = z.enum(['passed', 'failed', 'error']);
+
+/**
+ * Zod schema for WorkflowDiagnosticCode enum
+ * @generated from entities/workflow.rs
+ */
+export const workflowDiagnosticCodeSchema = z.enum([
+  'malformedstepheader',
+  'duplicatestepname',
+  'emptystep',
+  'unknownvariable',
+  'missingtitle',
+  'invalidfrontmatter',
+  'invalidstepconfig',
+  'invaliddependency',
+]);
+
+/**
+ * Zod schema for WorkflowDiagnosticSeverity enum
+ * @generated from entities/workflow.rs
+ */
+export const workflowDiagnosticSeveritySchema = z.enum(['error', 'warning']);
+
 /**
  * Zod schema for WorkflowStepStatus enum
  * @generated from entities/workflow.rs
@@ -152,6 +189,12 @@ export const workflowVariableSchema = z.enum([
   'projectname',
 ]);
 
+/**
+ * Zod schema for WorkflowVariableType enum
+ * @generated from entities/workflow.rs
+ */
+export const workflowVariableTypeSchema = z.enum(['string', 'number', 'boolean']);
+
 /**
  * Zod schema for WorktreeStatus enum
  * @generated from entities/git.rs
@@ -193,6 +236,15 @@ export const applyWorkflowToTaskRequestSchema = z.object({
   templateId: z.string(),
 });
 
+/**
+ * Zod schema for CleanupWorktreeRequest
+ * @request
+ * @generated from requests/chat.rs
+ */
+export const cleanupWorktreeRequestSchema = z.object({
+  deleteBranch: z.boolean(),
+});
+
 /**
  * Zod schema for CreateChatRequest
  * @request
@@ -224,6 +276,11 @@ export const createExecutorProfileRequestSchema = z.object({
   args: z.string().optional().nullable(),
   env: z.string().optional().nullable(),
   model: z.string().optional().nullable(),
+  cliTool: cliToolTypeSchema.optional().nullable(),
+  maxRuntimeSecs: z.number().int().nonnegative().optional().nullable(),
+  idleTimeoutSecs: z.number().int().nonnegative().optional().nullable(),
+  resourceLimits: z.string().optional().nullable(),
+  sandbox: z.string().optional().nullable(),
   isDefault: z.boolean().optional().nullable(),
 });
 
@@ -273,6 +330,7 @@ export const createProjectRequestSchema = z.object({
   alwaysIncludedRules: z.string().optional().nullable(),
   workflowsFolder: z.string().optional().nullable(),
   verificationConfig: z.string().optional().nullable(),
+  scriptLimits: z.string().optional().nullable(),
 });
 
 /**
@@ -298,6 +356,10 @@ export const createTaskRequestSchema = z.object({
   title: z.string(),
   description: z.string().optional().nullable(),
   workflowTemplate: z.string().optional().nullable(),
+  workflowVariables: z
+    .string() /* std::collections::HashMap<String, String> */
+    .optional()
+    .nullable(),
   parentTaskId: z.string().optional().nullable(),
   baseBranch: z.string().optional().nullable(),
 });
@@ -436,6 +498,16 @@ export const getHeadCommitRequestSchema = z.object({
   worktreePath: z.string(),
 });
 
+/**
+ * Zod schema for GetProcessOutputRequest
+ * @request
+ * @generated from requests/process.rs
+ */
+export const getProcessOutputRequestSchema = z.object({
+  since: z.number().int().optional().nullable(),
+  limit: z.number().int().optional().nullable(),
+});
+
 /**
  * Zod schema for GetSettingOrDefaultRequest
  * @request
@@ -615,6 +687,16 @@ export const setMessageStreamingRequestSchema = z.object({
   isStreaming: z.boolean(),
 });
 
+/**
+ * Zod schema for SetSecretRequest
+ * @request
+ * @generated from requests/secret.rs
+ */
+export const setSecretRequestSchema = z.object({
+  name: z.string(),
+  value: z.string(),
+});
+
 /**
  * Zod schema for SetSettingRequest
  * @request
@@ -667,6 +749,16 @@ export const startProcessRequestSchema = z.object({
   usePty: z.boolean(),
   ptyCols: z.number().int().nonnegative().optional().nullable(),
   ptyRows: z.number().int().nonnegative().optional().nullable(),
+  maxRuntimeSecs: z.number().int().nonnegative().optional().nullable(),
+  idleTimeoutSecs: z.number().int().nonnegative().optional().nullable(),
+  resourceLimits: z
+    .string() /* ResourceLimits */
+    .optional()
+    .nullable(),
+  sandbox: z
+    .string() /* ProcessSandbox */
+    .optional()
+    .nullable(),
 });
 
 /**
@@ -692,6 +784,7 @@ export const substituteWorkflowVariablesRequestSchema = z.object({
 export const updateChatRequestSchema = z.object({
   title: z.string().optional().nullable(),
   executorProfileId: z.string().optional().nullable(),
+  baseBranch: z.string().optional().nullable(),
   branch: z.string().optional().nullable(),
   worktreePath: z.string().optional().nullable(),
   worktreeDeleted: z.boolean().optional().nullable(),
@@ -699,6 +792,7 @@ export const updateChatRequestSchema = z.object({
   initialPrompt: z.string().optional().nullable(),
   hiddenPrompt: z.string().optional().nullable(),
   claudeSessionId: z.string().optional().nullable(),
+  sessionCliTool: cliToolTypeSchema.optional().nullable(),
 });
 
 /**
@@ -713,6 +807,11 @@ export const updateExecutorProfileRequestSchema = z.object({
   args: z.string().optional().nullable(),
   env: z.string().optional().nullable(),
   model: z.string().optional().nullable(),
+  cliTool: cliToolTypeSchema.optional().nullable(),
+  maxRuntimeSecs: z.number().int().nonnegative().optional().nullable(),
+  idleTimeoutSecs: z.number().int().nonnegative().optional().nullable(),
+  resourceLimits: z.string().optional().nullable(),
+  sandbox: z.string().optional().nullable(),
   isDefault: z.boolean().optional().nullable(),
 });
 
@@ -761,6 +860,7 @@ export const updateProjectRequestSchema = z.object({
   alwaysIncludedRules: z.string().optional().nullable(),
   workflowsFolder: z.string().optional().nullable(),
   verificationConfig: z.string().optional().nullable(),
+  scriptLimits: z.string().optional().nullable(),
 });
 
 /**
@@ -857,6 +957,7 @@ export const chatSchema = z.object({
   mainChatId: z.string().optional().nullable(),
   workflowStepIndex: z.number().int().optional().nullable(),
   claudeSessionId: z.string().optional().nullable(),
+  sessionCliTool: cliToolTypeSchema.optional().nullable(),
   archivedAt: z.string().optional().nullable(),
   createdAt: z.string(),
   updatedAt: z.string(),
@@ -966,11 +1067,14 @@ export const executionProcessSchema = z.object({
   executorProfileId: z.string().optional().nullable(),
   status: processStatusSchema,
   exitCode: z.number().int().optional().nullable(),
+  statusReason: z.string().optional().nullable(),
+  terminationSignal: z.string().optional().nullable(),
   executorAction: z.string(),
   runReason: runReasonSchema,
   beforeHeadCommit: z.string().optional().nullable(),
   afterHeadCommit: z.string().optional().nullable(),
   pid: z.number().int().optional().nullable(),
+  devServerUrl: z.string().optional().nullable(),
   startedAt: z.string(),
   completedAt: z.string().optional().nullable(),
   createdAt: z.string(),
@@ -989,6 +1093,11 @@ export const executorProfileSchema = z.object({
   args: z.string().optional().nullable(),
   env: z.string().optional().nullable(),
   model: z.string().optional().nullable(),
+  cliTool: cliToolTypeSchema.optional().nullable(),
+  maxRuntimeSecs: z.number().int().nonnegative().optional().nullable(),
+  idleTimeoutSecs: z.number().int().nonnegative().optional().nullable(),
+  resourceLimits: z.string().optional().nullable(),
+  sandbox: z.string().optional().nullable(),
   isDefault: z.boolean(),
   createdAt: z.string(),
   updatedAt: z.string(),
@@ -1086,6 +1195,18 @@ export const messageSummarySchema = z.object({
   createdAt: z.string(),
 });
 
+/**
+ * Zod schema for ProcessOutputChunk
+ * @generated from entities/process.rs
+ */
+export const processOutputChunkSchema = z.object({
+  id: z.number().int(),
+  processId: z.string(),
+  outputType: outputTypeSchema,
+  content: z.string(),
+  createdAt: z.string(),
+});
+
 /**
  * Zod schema for ProcessOutputEvent
  * @generated from entities/process.rs
@@ -1095,6 +1216,31 @@ export const processOutputEventSchema = z.object({
   outputType: outputTypeSchema,
   content: z.string(),
   timestamp: z.string(),
+  offset: z.number().int().optional().nullable(),
+});
+
+/**
+ * Zod schema for ProcessSandbox
+ * @generated from entities/sandbox.rs
+ */
+export const processSandboxSchema = z.object({
+  writablePaths: z.array(z.string()),
+  readOnlyPaths: z.array(z.string()),
+  protectedPaths: z.array(z.string()),
+  allowNetwork: z.boolean(),
+});
+
+/**
+ * Zod schema for ProcessSnapshot
+ * @generated from entities/process.rs
+ */
+export const processSnapshotSchema = z.object({
+  processId: z.string(),
+  content: z.string(),
+  cols: z.number().int().nonnegative(),
+  rows: z.number().int().nonnegative(),
+  truncated: z.boolean(),
+  offset: z.number().int(),
 });
 
 /**
@@ -1141,11 +1287,31 @@ export const projectSchema = z.object({
   alwaysIncludedRules: z.string().optional().nullable(),
   workflowsFolder: z.string(),
   verificationConfig: z.string().optional().nullable(),
+  scriptLimits: z.string().optional().nullable(),
   archivedAt: z.string().optional().nullable(),
   createdAt: z.string(),
   updatedAt: z.string(),
 });
 
+/**
+ * Zod schema for ProjectScriptLimits
+ * @generated from entities/limits.rs
+ */
+export const projectScriptLimitsSchema = z.object({
+  setup: z
+    .string() /* ResourceLimits */
+    .optional()
+    .nullable(),
+  dev: z
+    .string() /* ResourceLimits */
+    .optional()
+    .nullable(),
+  cleanup: z
+    .string() /* ResourceLimits */
+    .optional()
+    .nullable(),
+});
+
 /**
  * Zod schema for ProjectSummary
  * @generated from entities/project.rs
@@ -1169,6 +1335,38 @@ export const pullRequestResultSchema = z.object({
   branch: z.string(),
 });
 
+/**
+ * Zod schema for QueuedProcess
+ * @generated from entities/process.rs
+ */
+export const queuedProcessSchema = z.object({
+  processId: z.string(),
+  chatId: z.string(),
+  projectId: z.string(),
+  position: z.number().int().nonnegative(),
+});
+
+/**
+ * Zod schema for ResourceLimits
+ * @generated from entities/limits.rs
+ */
+export const resourceLimitsSchema = z.object({
+  addressSpaceMb: z.number().int().nonnegative().optional().nullable(),
+  cpuSecs: z.number().int().nonnegative().optional().nullable(),
+  openFiles: z.number().int().nonnegative().optional().nullable(),
+  processes: z.number().int().nonnegative().optional().nullable(),
+});
+
+/**
+ * Zod schema for SandboxSettings
+ * @generated from entities/sandbox.rs
+ */
+export const sandboxSettingsSchema = z.object({
+  enabled: z.boolean(),
+  allowNetwork: z.boolean(),
+  readOnlyPaths: z.array(z.string()),
+});
+
 /**
  * Zod schema for SearchResult
  * @generated from entities/search.rs
@@ -1182,6 +1380,16 @@ export const searchResultSchema = z.object({
   score: z.number(),
 });
 
+/**
+ * Zod schema for Secret
+ * @generated from entities/secret.rs
+ */
+export const secretSchema = z.object({
+  name: z.string(),
+  createdAt: z.string(),
+  updatedAt: z.string(),
+});
+
 /**
  * Zod schema for Setting
  * @generated from entities/settings.rs
@@ -1211,6 +1419,7 @@ export const taskSchema = z.object({
   description: z.string().optional().nullable(),
   status: taskStatusSchema,
   workflowTemplate: z.string().optional().nullable(),
+  workflowVariables: z.string().optional().nullable(),
   actionsRequiredCount: z.number().int(),
   parentTaskId: z.string().optional().nullable(),
   autoStartNextStep: z.boolean(),
@@ -1245,6 +1454,31 @@ export const taskWithChatsSchema = z.object({
   chats: z.array(z.string()),
 });
 
+/**
+ * Zod schema for VerificationCheck
+ * @generated from entities/verification.rs
+ */
+export const verificationCheckSchema = z.object({
+  name: z.string(),
+  command: z.string(),
+  status: verificationCheckStatusSchema,
+  exitCode: z.number().int().optional().nullable(),
+  processId: z.string().optional().nullable(),
+  error: z.string().optional().nullable(),
+});
+
+/**
+ * Zod schema for VerificationResult
+ * @generated from entities/verification.rs
+ */
+export const verificationResultSchema = z.object({
+  chatId: z.string(),
+  passed: z.boolean(),
+  checks: z.array(z.string()),
+  startedAt: z.string(),
+  completedAt: z.string(),
+});
+
 /**
  * Zod schema for WorkflowContext
  * @generated from entities/workflow.rs
@@ -1258,6 +1492,29 @@ export const workflowContextSchema = z.object({
   projectName: z.string().optional().nullable(),
 });
 
+/**
+ * Zod schema for WorkflowDiagnostic
+ * @generated from entities/workflow.rs
+ */
+export const workflowDiagnosticSchema = z.object({
+  line: z.number().int().nonnegative(),
+  column: z.number().int().nonnegative(),
+  severity: workflowDiagnosticSeveritySchema,
+  code: workflowDiagnosticCodeSchema,
+  message: z.string(),
+});
+
+/**
+ * Zod schema for WorkflowRun
+ * @generated from entities/workflow.rs
+ */
+export const workflowRunSchema = z.object({
+  taskId: z.string(),
+  template: z.string(),
+  path: z.string(),
+  steps: z.array(z.string()),
+});
+
 /**
  * Zod schema for WorkflowStep
  * @generated from entities/workflow.rs
@@ -1268,6 +1525,14 @@ export const workflowStepSchema = z.object({
   description: z.string(),
   status: workflowStepStatusSchema,
   chatId: z.string().optional().nullable(),
+  executorProfile: z.string().optional().nullable(),
+  chatRole: chatRoleSchema.optional().nullable(),
+  model: z.string().optional().nullable(),
+  hiddenPrompt: z.string().optional().nullable(),
+  requiredArtifacts: z.array(z.string()),
+  autoAdvance: z.boolean().optional().nullable(),
+  timeoutSecs: z.number().int().nonnegative().optional().nullable(),
+  dependsOn: z.array(z.number().int()),
 });
 
 /**
@@ -1281,6 +1546,7 @@ export const workflowTemplateSchema = z.object({
   content: z.string(),
   isBuiltin: z.boolean(),
   steps: z.array(z.string()),
+  errorCount: z.number().int(),
   createdAt: z.string(),
   updatedAt: z.string(),
 });
@@ -1295,6 +1561,22 @@ export const workflowTemplateSummarySchema = z.object({
   description: z.string().optional().nullable(),
   isBuiltin: z.boolean(),
   stepCount: z.number().int(),
+  errorCount: z.number().int(),
+});
+
+/**
+ * Zod schema for WorkflowVariableInfo
+ * @generated from entities/workflow.rs
+ */
+export const workflowVariableInfoSchema = z.object({
+  name: z.string(),
+  valueType: workflowVariableTypeSchema,
+  defaultValue: z.string().optional().nullable(),
+  description: z.string().optional().nullable(),
+  required: z.boolean(),
+  builtin: z.boolean(),
+  declared: z.boolean(),
+  used: z.boolean(),
 });
 
 /**
@@ -1336,8 +1618,12 @@ export type ProcessStatusGenerated = z.infer<typeof processStatusSchema>;
 export type RunReasonGenerated = z.infer<typeof runReasonSchema>;
 export type SearchResultTypeGenerated = z.infer<typeof searchResultTypeSchema>;
 export type TaskStatusGenerated = z.infer<typeof taskStatusSchema>;
+export type VerificationCheckStatusGenerated = z.infer<typeof verificationCheckStatusSchema>;
+export type WorkflowDiagnosticCodeGenerated = z.infer<typeof workflowDiagnosticCodeSchema>;
+export type WorkflowDiagnosticSeverityGenerated = z.infer<typeof workflowDiagnosticSeveritySchema>;
 export type WorkflowStepStatusGenerated = z.infer<typeof workflowStepStatusSchema>;
 export type WorkflowVariableGenerated = z.infer<typeof workflowVariableSchema>;
+export type WorkflowVariableTypeGenerated = z.infer<typeof workflowVariableTypeSchema>;
 export type WorktreeStatusGenerated = z.infer<typeof worktreeStatusSchema>;
 export type WsClientMessageGenerated = z.infer<typeof wsClientMessageSchema>;
 export type WsServerMessageGenerated = z.infer<typeof wsServerMessageSchema>;
@@ -1351,6 +1637,7 @@ export type AuthStatusResponseGenerated = z.infer<typeof authStatusResponseSchem
 export type BranchGenerated = z.infer<typeof branchSchema>;
 export type ChatGenerated = z.infer<typeof chatSchema>;
 export type ChatSummaryGenerated = z.infer<typeof chatSummarySchema>;
+export type CleanupWorktreeRequestGenerated = z.infer<typeof cleanupWorktreeRequestSchema>;
 export type CliInstalledResponseGenerated = z.infer<typeof cliInstalledResponseSchema>;
 export type CommitGenerated = z.infer<typeof commitSchema>;
 export type CommitSummaryGenerated = z.infer<typeof commitSummarySchema>;
@@ -1391,6 +1678,7 @@ export type GetCommitsRequestGenerated = z.infer<typeof getCommitsRequestSchema>
 export type GetCurrentBranchRequestGenerated = z.infer<typeof getCurrentBranchRequestSchema>;
 export type GetDiffRequestGenerated = z.infer<typeof getDiffRequestSchema>;
 export type GetHeadCommitRequestGenerated = z.infer<typeof getHeadCommitRequestSchema>;
+export type GetProcessOutputRequestGenerated = z.infer<typeof getProcessOutputRequestSchema>;
 export type GetSettingOrDefaultRequestGenerated = z.infer<typeof getSettingOrDefaultRequestSchema>;
 export type GetSettingRequestGenerated = z.infer<typeof getSettingRequestSchema>;
 export type GetTaskCommitsRequestGenerated = z.infer<typeof getTaskCommitsRequestSchema>;
@@ -1410,22 +1698,31 @@ export type ListWorktreesRequestGenerated = z.infer<typeof listWorktreesRequestS
 export type MessageGenerated = z.infer<typeof messageSchema>;
 export type MessageSummaryGenerated = z.infer<typeof messageSummarySchema>;
 export type ParseWorkflowRequestGenerated = z.infer<typeof parseWorkflowRequestSchema>;
+export type ProcessOutputChunkGenerated = z.infer<typeof processOutputChunkSchema>;
 export type ProcessOutputEventGenerated = z.infer<typeof processOutputEventSchema>;
+export type ProcessSandboxGenerated = z.infer<typeof processSandboxSchema>;
+export type ProcessSnapshotGenerated = z.infer<typeof processSnapshotSchema>;
 export type ProcessStatusEventGenerated = z.infer<typeof processStatusEventSchema>;
 export type ProcessSummaryGenerated = z.infer<typeof processSummarySchema>;
 export type ProjectGenerated = z.infer<typeof projectSchema>;
+export type ProjectScriptLimitsGenerated = z.infer<typeof projectScriptLimitsSchema>;
 export type ProjectSummaryGenerated = z.infer<typeof projectSummarySchema>;
 export type PullRequestResultGenerated = z.infer<typeof pullRequestResultSchema>;
 export type PushBranchRequestGenerated = z.infer<typeof pushBranchRequestSchema>;
+export type QueuedProcessGenerated = z.infer<typeof queuedProcessSchema>;
 export type ResizeProcessRequestGenerated = z.infer<typeof resizeProcessRequestSchema>;
+export type ResourceLimitsGenerated = z.infer<typeof resourceLimitsSchema>;
 export type RunExecutorRequestGenerated = z.infer<typeof runExecutorRequestSchema>;
+export type SandboxSettingsGenerated = z.infer<typeof sandboxSettingsSchema>;
 export type SearchRequestGenerated = z.infer<typeof searchRequestSchema>;
 export type SearchResultGenerated = z.infer<typeof searchResultSchema>;
+export type SecretGenerated = z.infer<typeof secretSchema>;
 export type SendProcessInputRequestGenerated = z.infer<typeof sendProcessInputRequestSchema>;
 export type SetDefaultExecutorProfileRequestGenerated = z.infer<
   typeof setDefaultExecutorProfileRequestSchema
 >;
 export type SetMessageStreamingRequestGenerated = z.infer<typeof setMessageStreamingRequestSchema>;
+export type SetSecretRequestGenerated = z.infer<typeof setSecretRequestSchema>;
 export type SetSettingRequestGenerated = z.infer<typeof setSettingRequestSchema>;
 export type SettingGenerated = z.infer<typeof settingSchema>;
 export type SettingExistsRequestGenerated = z.infer<typeof settingExistsRequestSchema>;
@@ -1450,9 +1747,14 @@ export type UpdateWorkflowStepRequestGenerated = z.infer<typeof updateWorkflowSt
 export type UpdateWorkflowTemplateRequestGenerated = z.infer<
   typeof updateWorkflowTemplateRequestSchema
 >;
+export type VerificationCheckGenerated = z.infer<typeof verificationCheckSchema>;
+export type VerificationResultGenerated = z.infer<typeof verificationResultSchema>;
 export type WorkflowContextGenerated = z.infer<typeof workflowContextSchema>;
+export type WorkflowDiagnosticGenerated = z.infer<typeof workflowDiagnosticSchema>;
+export type WorkflowRunGenerated = z.infer<typeof workflowRunSchema>;
 export type WorkflowStepGenerated = z.infer<typeof workflowStepSchema>;
 export type WorkflowTemplateGenerated = z.infer<typeof workflowTemplateSchema>;
 export type WorkflowTemplateSummaryGenerated = z.infer<typeof workflowTemplateSummarySchema>;
+export type WorkflowVariableInfoGenerated = z.infer<typeof workflowVariableInfoSchema>;
 export type WorktreeGenerated = z.infer<typeof worktreeSchema>;
 export type WorktreeSummaryGenerated = z.infer<typeof worktreeSummarySchema>;
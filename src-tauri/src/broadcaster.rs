@@ -3,6 +3,7 @@
 //! Implements the `EventBroadcaster` trait using Tauri's native event system.
 //! This allows events to be broadcast to all connected frontend windows.
 
+use crate::claude_events::{self, ClaudeEventStreams};
 use openflow_core::events::{DataAction, Event, EventBroadcaster, OutputType, ProcessStatus};
 use serde::Serialize;
 use std::sync::Arc;
@@ -18,6 +19,7 @@ struct ProcessOutputPayload {
     output_type: String,
     content: String,
     timestamp: String,
+    offset: Option<i64>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -46,14 +48,27 @@ struct DataChangedPayload {
 /// - `process-output-{process_id}` for process output
 /// - `process-status-{process_id}` for process status changes
 /// - `data-changed` for entity CRUD operations
+///
+/// Output of watched Claude runs is also parsed into Claude events (see
+/// `claude_events`).
 pub struct TauriBroadcaster<R: Runtime> {
     app_handle: AppHandle<R>,
+    claude_events: Arc<ClaudeEventStreams>,
 }
 
 impl<R: Runtime> TauriBroadcaster<R> {
     /// Create a new Tauri broadcaster with the given app handle.
     pub fn new(app_handle: AppHandle<R>) -> Self {
-        Self { app_handle }
+        Self {
+            app_handle,
+            claude_events: Arc::new(ClaudeEventStreams::new()),
+        }
+    }
+
+    /// Parse the output of the Claude runs watched in `claude_events`.
+    pub fn with_claude_events(mut self, claude_events: Arc<ClaudeEventStreams>) -> Self {
+        self.claude_events = claude_events;
+        self
     }
 
     /// Create a new Tauri broadcaster wrapped in Arc.
@@ -70,21 +85,30 @@ impl<R: Runtime> EventBroadcaster for TauriBroadcaster<R> {
                 output_type,
                 content,
                 timestamp,
+                offset,
             } => {
+                let claude_outputs = self.claude_events.output(
+                    &process_id,
+                    &content,
+                    offset.map(|offset| offset as u64),
+                );
+
                 let channel = format!("process-output-{}", process_id);
                 let payload = ProcessOutputPayload {
-                    process_id,
+                    process_id: process_id.clone(),
                     output_type: match output_type {
                         OutputType::Stdout => "stdout".to_string(),
                         OutputType::Stderr => "stderr".to_string(),
                     },
                     content,
                     timestamp,
+                    offset,
                 };
 
                 if let Err(e) = self.app_handle.emit(&channel, &payload) {
                     log::error!("Failed to emit process output event: {}", e);
                 }
+                claude_events::emit(&self.app_handle, &process_id, claude_outputs);
             }
 
             Event::ProcessStatus {
@@ -92,15 +116,29 @@ impl<R: Runtime> EventBroadcaster for TauriBroadcaster<R> {
                 status,
                 exit_code,
             } => {
+                // Parse the last line of a Claude run before reporting its exit
+                if matches!(
+                    status,
+                    ProcessStatus::Completed
+                        | ProcessStatus::Failed
+                        | ProcessStatus::Killed
+                        | ProcessStatus::TimedOut
+                ) {
+                    let claude_outputs = self.claude_events.finish(&process_id);
+                    claude_events::emit(&self.app_handle, &process_id, claude_outputs);
+                }
+
                 let channel = format!("process-status-{}", process_id);
                 let payload = ProcessStatusPayload {
                     process_id,
                     status: match status {
+                        ProcessStatus::Queued => "queued".to_string(),
                         ProcessStatus::Starting => "starting".to_string(),
                         ProcessStatus::Running => "running".to_string(),
                         ProcessStatus::Completed => "completed".to_string(),
                         ProcessStatus::Failed => "failed".to_string(),
                         ProcessStatus::Killed => "killed".to_string(),
+                        ProcessStatus::TimedOut => "timedout".to_string(),
                     },
                     exit_code,
                 };
@@ -158,12 +196,14 @@ mod tests {
             output_type: "stdout".to_string(),
             content: "Hello, world!".to_string(),
             timestamp: "2024-01-01T00:00:00Z".to_string(),
+            offset: Some(13),
         };
 
         let json = serde_json::to_string(&payload).unwrap();
         assert!(json.contains("\"processId\":\"proc-123\""));
         assert!(json.contains("\"outputType\":\"stdout\""));
         assert!(json.contains("\"content\":\"Hello, world!\""));
+        assert!(json.contains("\"offset\":13"));
     }
 
     #[test]
@@ -0,0 +1,422 @@
+//! Claude Code stream-json events for the desktop app.
+//!
+//! The output of Claude runs started with `run_executor` is parsed into
+//! events for the chat UI. It is taken from the `ProcessOutput` events the
+//! core PTY streamer broadcasts (see `TauriBroadcaster`), so the UI sees the
+//! same output that is persisted and recorded as the chat transcript. Nothing
+//! else reads from the PTY.
+//!
+//! Parsed output is emitted on these channels:
+//!
+//! - `claude-event-{process_id}` for stream-json events
+//! - `raw-output-{process_id}` for lines that are not stream-json
+//! - `permission-request-{process_id}` for permission prompts
+//! - `session-id-{process_id}` for the session ID from the `init` event
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Runtime};
+
+/// Claude Code stream-json event types.
+/// These match the JSON lines output from `claude --output-format stream-json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClaudeEvent {
+    #[serde(rename = "system")]
+    System {
+        subtype: String,
+        /// Session ID from Claude Code (present in "init" subtype events)
+        #[serde(default)]
+        session_id: Option<String>,
+        #[serde(flatten)]
+        data: serde_json::Value,
+    },
+    #[serde(rename = "assistant")]
+    Assistant { message: AssistantMessage },
+    #[serde(rename = "user")]
+    User { message: UserMessage },
+    #[serde(rename = "result")]
+    Result {
+        subtype: String,
+        #[serde(flatten)]
+        data: serde_json::Value,
+    },
+}
+
+/// Assistant message - content is an array of content blocks (text, tool_use, etc.)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssistantMessage {
+    /// Content blocks array - can contain text blocks, tool_use blocks, etc.
+    #[serde(default)]
+    pub content: Option<serde_json::Value>,
+    /// Additional fields we pass through
+    #[serde(flatten)]
+    pub extra: serde_json::Value,
+}
+
+/// User message - content is an array of content blocks (tool_result, etc.)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserMessage {
+    /// Content blocks array - typically contains tool_result blocks
+    #[serde(default)]
+    pub content: Option<serde_json::Value>,
+    /// Additional fields we pass through
+    #[serde(flatten)]
+    pub extra: serde_json::Value,
+}
+
+/// Permission request event emitted when Claude asks for permission.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PermissionRequestEvent {
+    pub process_id: String,
+    pub tool_name: String,
+    pub file_path: Option<String>,
+    pub description: String,
+}
+
+/// Parsed line of Claude output, to be emitted on its channel.
+#[derive(Debug, Clone)]
+pub enum ClaudeOutput {
+    Event(ClaudeEvent),
+    SessionId(String),
+    PermissionRequest(PermissionRequestEvent),
+    Raw(String),
+}
+
+/// Output of a watched process.
+#[derive(Debug, Default)]
+struct ClaudeStream {
+    /// Output offset parsed up to, once caught up (see `catch_up`).
+    parsed_to: Option<u64>,
+    /// Output broadcast before catching up, with its offsets.
+    held: Vec<(String, Option<u64>)>,
+    /// Incomplete last line.
+    line: String,
+    /// Whether the process exited before catching up.
+    finished: bool,
+}
+
+impl ClaudeStream {
+    /// Parse output ending at `offset`, skipping what was parsed already.
+    fn feed(
+        &mut self,
+        process_id: &str,
+        content: &str,
+        offset: Option<u64>,
+        outputs: &mut Vec<ClaudeOutput>,
+    ) {
+        let parsed_to = self.parsed_to.unwrap_or(0);
+        let content = match offset {
+            Some(offset) if offset <= parsed_to => return,
+            Some(offset) => {
+                self.parsed_to = Some(offset);
+                let start = offset.saturating_sub(content.len() as u64);
+                let skip = parsed_to.saturating_sub(start) as usize;
+                content.get(skip..).unwrap_or(content)
+            }
+            None => content,
+        };
+
+        self.line.push_str(content);
+        while let Some(end) = self.line.find('\n') {
+            let line: String = self.line.drain(..=end).collect();
+            parse_line(process_id, &line, outputs);
+        }
+    }
+
+    /// Parse the incomplete last line, if any.
+    fn flush(&mut self, process_id: &str, outputs: &mut Vec<ClaudeOutput>) {
+        let line = std::mem::take(&mut self.line);
+        parse_line(process_id, &line, outputs);
+    }
+}
+
+/// Parses the output of watched Claude runs line by line.
+///
+/// A run is watched from `watch` until its process exits (`finish`). Output
+/// of processes that are not watched, like terminals, is ignored.
+#[derive(Debug, Default)]
+pub struct ClaudeEventStreams {
+    streams: Mutex<HashMap<String, ClaudeStream>>,
+}
+
+impl ClaudeEventStreams {
+    /// Create an empty set of streams.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start watching the output of a process.
+    ///
+    /// Output broadcast from now on is held until `catch_up` is called with
+    /// a snapshot taken after this call, so no line is missed or repeated.
+    pub fn watch(&self, process_id: &str) {
+        if let Ok(mut streams) = self.streams.lock() {
+            streams.entry(process_id.to_string()).or_default();
+        }
+    }
+
+    /// Parse a watched process's output up to a snapshot (its content and
+    /// the output offset at its end), then the output held since `watch`.
+    ///
+    /// `snapshot` is `None` if the process has no output to snapshot yet,
+    /// e.g. because its run is queued.
+    pub fn catch_up(&self, process_id: &str, snapshot: Option<(&str, u64)>) -> Vec<ClaudeOutput> {
+        let mut outputs = Vec::new();
+        let Ok(mut streams) = self.streams.lock() else {
+            return outputs;
+        };
+        let Some(stream) = streams.get_mut(process_id) else {
+            return outputs;
+        };
+
+        stream.parsed_to = Some(0);
+        if let Some((content, offset)) = snapshot {
+            stream.feed(process_id, content, Some(offset), &mut outputs);
+        }
+        for (content, offset) in std::mem::take(&mut stream.held) {
+            stream.feed(process_id, &content, offset, &mut outputs);
+        }
+        if stream.finished {
+            stream.flush(process_id, &mut outputs);
+            streams.remove(process_id);
+        }
+        outputs
+    }
+
+    /// Parse output broadcast for a process. `offset` is the output offset
+    /// at the end of `content`, if known.
+    pub fn output(
+        &self,
+        process_id: &str,
+        content: &str,
+        offset: Option<u64>,
+    ) -> Vec<ClaudeOutput> {
+        let mut outputs = Vec::new();
+        let Ok(mut streams) = self.streams.lock() else {
+            return outputs;
+        };
+        if let Some(stream) = streams.get_mut(process_id) {
+            if stream.parsed_to.is_some() {
+                stream.feed(process_id, content, offset, &mut outputs);
+            } else {
+                stream.held.push((content.to_string(), offset));
+            }
+        }
+        outputs
+    }
+
+    /// Stop watching a process that exited, parsing its last line.
+    pub fn finish(&self, process_id: &str) -> Vec<ClaudeOutput> {
+        let mut outputs = Vec::new();
+        let Ok(mut streams) = self.streams.lock() else {
+            return outputs;
+        };
+        match streams.get_mut(process_id) {
+            Some(stream) if stream.parsed_to.is_none() => stream.finished = true,
+            Some(stream) => {
+                stream.flush(process_id, &mut outputs);
+                streams.remove(process_id);
+            }
+            None => {}
+        }
+        outputs
+    }
+}
+
+/// Emit parsed output of a process on its channels.
+pub fn emit<R: Runtime>(app_handle: &AppHandle<R>, process_id: &str, outputs: Vec<ClaudeOutput>) {
+    for output in outputs {
+        let result = match &output {
+            ClaudeOutput::Event(event) => {
+                app_handle.emit(&format!("claude-event-{}", process_id), event)
+            }
+            ClaudeOutput::SessionId(session_id) => {
+                app_handle.emit(&format!("session-id-{}", process_id), session_id)
+            }
+            ClaudeOutput::PermissionRequest(request) => {
+                app_handle.emit(&format!("permission-request-{}", process_id), request)
+            }
+            ClaudeOutput::Raw(line) => app_handle.emit(&format!("raw-output-{}", process_id), line),
+        };
+        if let Err(e) = result {
+            log::error!("Failed to emit Claude output event: {}", e);
+        }
+    }
+}
+
+/// Parse a line of Claude output.
+fn parse_line(process_id: &str, line: &str, outputs: &mut Vec<ClaudeOutput>) {
+    // Strip ANSI escape codes and control characters
+    let clean_line = strip_ansi_codes(line);
+    let trimmed = clean_line.trim();
+    if trimmed.is_empty() {
+        return;
+    }
+
+    // Check for permission prompts (non-JSON lines from Claude Code)
+    // Claude Code shows prompts like: "Allow Claude to write to file.txt? (y/n)"
+    if trimmed.contains("Allow") && (trimmed.contains("(y/n)") || trimmed.contains("? [y/n]")) {
+        outputs.push(ClaudeOutput::PermissionRequest(PermissionRequestEvent {
+            process_id: process_id.to_string(),
+            tool_name: extract_tool_name(trimmed),
+            file_path: extract_file_path(trimmed),
+            description: trimmed.to_string(),
+        }));
+        return;
+    }
+
+    match serde_json::from_str::<ClaudeEvent>(trimmed) {
+        Ok(event) => {
+            // Emit the session ID from system "init" events
+            if let ClaudeEvent::System {
+                subtype,
+                session_id: Some(sid),
+                ..
+            } = &event
+            {
+                if subtype == "init" {
+                    outputs.push(ClaudeOutput::SessionId(sid.clone()));
+                }
+            }
+            outputs.push(ClaudeOutput::Event(event));
+        }
+        // Fallback: emit raw line for unparsed output (already cleaned)
+        Err(_) => outputs.push(ClaudeOutput::Raw(trimmed.to_string())),
+    }
+}
+
+/// Extract tool name from permission prompt.
+fn extract_tool_name(prompt: &str) -> String {
+    // Common patterns: "Allow Claude to write", "Allow Claude to read", "Allow Claude to execute"
+    if prompt.contains("write") || prompt.contains("Write") {
+        "Write".to_string()
+    } else if prompt.contains("read") || prompt.contains("Read") {
+        "Read".to_string()
+    } else if prompt.contains("execute")
+        || prompt.contains("Execute")
+        || prompt.contains("bash")
+        || prompt.contains("Bash")
+    {
+        "Bash".to_string()
+    } else {
+        "Tool".to_string()
+    }
+}
+
+/// Extract file path from permission prompt.
+fn extract_file_path(prompt: &str) -> Option<String> {
+    // Look for path-like strings (starting with / or containing common path patterns)
+    // This is a simple heuristic - Claude Code prompts often include the file path
+    for word in prompt.split_whitespace() {
+        let word = word.trim_matches(|c| c == '?' || c == '"' || c == '\'' || c == '`');
+        if word.starts_with('/') || word.contains(":\\") {
+            return Some(word.to_string());
+        }
+    }
+    None
+}
+
+/// Regex pattern for ANSI escape sequences.
+/// Matches: CSI sequences, OSC sequences, and other escape sequences.
+static ANSI_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(concat!(
+        r"\x1b\[[0-9;]*[A-Za-z]",            // CSI sequences (colors, cursor, etc.)
+        r"|\x1b\][^\x07]*\x07",              // OSC sequences (terminated by BEL)
+        r"|\x1b[PX^_][^\x1b]*\x1b\\",        // DCS/SOS/PM/APC sequences
+        r"|\x1b[\[\]()#;?]*[0-9;]*[A-Za-z]", // Other escape sequences
+        r"|\x1b.",                           // Simple escape sequences
+        r"|[\x00-\x08\x0b\x0c\x0e-\x1f]",    // Control characters (except newline, tab, CR)
+    ))
+    .expect("Invalid ANSI regex pattern")
+});
+
+/// Strip ANSI escape codes and control characters from a string.
+fn strip_ansi_codes(s: &str) -> String {
+    ANSI_REGEX.replace_all(s, "").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn events(outputs: &[ClaudeOutput]) -> Vec<String> {
+        outputs
+            .iter()
+            .map(|output| match output {
+                ClaudeOutput::Event(ClaudeEvent::System { subtype, .. }) => {
+                    format!("system:{}", subtype)
+                }
+                ClaudeOutput::Event(ClaudeEvent::Result { subtype, .. }) => {
+                    format!("result:{}", subtype)
+                }
+                ClaudeOutput::Event(_) => "message".to_string(),
+                ClaudeOutput::SessionId(sid) => format!("session:{}", sid),
+                ClaudeOutput::PermissionRequest(request) => {
+                    format!("permission:{}", request.tool_name)
+                }
+                ClaudeOutput::Raw(line) => format!("raw:{}", line),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_lines_split_across_output_events() {
+        let streams = ClaudeEventStreams::new();
+        streams.watch("p1");
+        assert!(streams.catch_up("p1", None).is_empty());
+
+        let init = "{\"type\":\"system\",\"subtype\":\"init\",\"session_id\":\"s1\"}\r\n";
+        let (head, tail) = init.split_at(20);
+        assert!(streams.output("p1", head, Some(20)).is_empty());
+        let mut outputs = streams.output("p1", tail, Some(init.len() as u64));
+        outputs.extend(streams.output("p1", "\x1b[32mdone", Some(init.len() as u64 + 10)));
+        outputs.extend(streams.finish("p1"));
+
+        assert_eq!(
+            events(&outputs),
+            vec!["session:s1", "system:init", "raw:done"]
+        );
+        // Unwatched and finished processes are ignored
+        assert!(streams.output("p1", "late\n", Some(100)).is_empty());
+        assert!(streams.output("p2", "terminal\n", Some(9)).is_empty());
+    }
+
+    #[test]
+    fn test_catch_up_skips_output_in_snapshot() {
+        let streams = ClaudeEventStreams::new();
+        streams.watch("p1");
+
+        // Broadcast while the snapshot is taken: the first chunk is already
+        // in the snapshot, the second straddles its end
+        assert!(streams.output("p1", "one\n", Some(4)).is_empty());
+        assert!(streams.output("p1", "two\nthree\n", Some(14)).is_empty());
+
+        let outputs = streams.catch_up("p1", Some(("one\ntwo\nthr", 11)));
+        assert_eq!(events(&outputs), vec!["raw:one", "raw:two", "raw:three"]);
+
+        let outputs = streams.output("p1", "Allow Claude to write /tmp/x? (y/n)\n", Some(50));
+        assert_eq!(events(&outputs), vec!["permission:Write"]);
+    }
+
+    #[test]
+    fn test_exit_before_catch_up() {
+        let streams = ClaudeEventStreams::new();
+        streams.watch("p1");
+        assert!(streams
+            .output(
+                "p1",
+                "{\"type\":\"result\",\"subtype\":\"success\"}",
+                Some(38)
+            )
+            .is_empty());
+        assert!(streams.finish("p1").is_empty());
+
+        let outputs = streams.catch_up("p1", None);
+        assert_eq!(events(&outputs), vec!["result:success"]);
+        assert!(streams.catch_up("p1", None).is_empty());
+    }
+}
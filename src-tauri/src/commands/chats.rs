@@ -7,9 +7,10 @@ use tauri::State;
 
 use crate::commands::AppState;
 use openflow_contracts::{
-    Chat, ChatWithMessages, CreateChatRequest, ExecutionProcess, UpdateChatRequest,
+    Chat, ChatWithMessages, CleanupWorktreeRequest, CreateChatRequest, ExecutionProcess,
+    UpdateChatRequest, VerificationResult,
 };
-use openflow_core::services::chat;
+use openflow_core::services::{chat, dev_server, project, verification, worktree};
 
 /// List chats for a task.
 ///
@@ -63,10 +64,16 @@ pub async fn update_chat(
 
 /// Delete a chat by ID.
 ///
-/// This will cascade delete all associated messages.
+/// This will cascade delete all associated messages. The chat's dev server
+/// is stopped first.
 #[tauri::command]
 pub async fn delete_chat(state: State<'_, AppState>, id: String) -> Result<(), String> {
-    let pool = state.db.lock().await;
+    // Stopping the dev server can take up to two grace periods, so don't
+    // hold the `db` guard
+    let pool = state.get_pool().clone();
+    dev_server::stop(&pool, &state.process_service, &id)
+        .await
+        .map_err(|e| e.to_string())?;
     chat::delete(&pool, &id).await.map_err(|e| e.to_string())
 }
 
@@ -75,8 +82,14 @@ pub async fn delete_chat(state: State<'_, AppState>, id: String) -> Result<(), S
 /// Sets the archived_at timestamp. Archived chats are hidden from list queries.
 #[tauri::command]
 pub async fn archive_chat(state: State<'_, AppState>, id: String) -> Result<Chat, String> {
-    let pool = state.db.lock().await;
-    chat::archive(&pool, &id).await.map_err(|e| e.to_string())
+    // Stopping the dev server can take up to two grace periods, so don't
+    // hold the `db` guard
+    let pool = state.get_pool().clone();
+    let chat = chat::archive(&pool, &id).await.map_err(|e| e.to_string())?;
+    dev_server::stop(&pool, &state.process_service, &id)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(chat)
 }
 
 /// Unarchive a chat by ID.
@@ -151,6 +164,103 @@ pub async fn toggle_step_complete(state: State<'_, AppState>, id: String) -> Res
         .map_err(|e| e.to_string())
 }
 
+/// Clean up a chat's worktree.
+///
+/// Runs the project's cleanup script in the worktree and removes it.
+/// The branch is kept unless `delete_branch` is set in the request.
+#[tauri::command]
+pub async fn cleanup_chat_worktree(
+    state: State<'_, AppState>,
+    id: String,
+    request: Option<CleanupWorktreeRequest>,
+) -> Result<Chat, String> {
+    // The cleanup script can run for a while, so don't hold the `db` guard
+    let pool = state.get_pool().clone();
+    let request = request.unwrap_or_default();
+    let chat = chat::get_chat(&pool, &id)
+        .await
+        .map_err(|e| e.to_string())?;
+    let project = project::get(&pool, &chat.project_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    worktree::cleanup(
+        &pool,
+        &state.process_service,
+        &chat,
+        &project,
+        request.delete_branch,
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Run the project's verification checks in a chat's worktree.
+///
+/// Each configured check runs as its own process; returns once all checks
+/// have finished.
+#[tauri::command]
+pub async fn verify_chat(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<VerificationResult, String> {
+    // Checks can run for a while, so don't hold the `db` guard
+    let pool = state.get_pool().clone();
+    verification::run(&pool, &state.process_service, &id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// List the verification results of a chat, newest first.
+#[tauri::command]
+pub async fn list_chat_verifications(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<Vec<VerificationResult>, String> {
+    let pool = state.db.lock().await;
+    verification::list(&pool, &id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Get the running dev server of a chat, if any.
+#[tauri::command]
+pub async fn get_dev_server(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<Option<ExecutionProcess>, String> {
+    let pool = state.db.lock().await;
+    dev_server::get_running(&pool, &id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Start the project's dev server in a chat's worktree.
+///
+/// Returns the already running dev server if there is one. The URL is
+/// filled in and broadcast once detected.
+#[tauri::command]
+pub async fn start_dev_server(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<ExecutionProcess, String> {
+    let pool = state.get_pool().clone();
+    dev_server::start(&pool, &state.process_service, &id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Stop the dev server of a chat.
+#[tauri::command]
+pub async fn stop_dev_server(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<Option<ExecutionProcess>, String> {
+    let pool = state.get_pool().clone();
+    dev_server::stop(&pool, &state.process_service, &id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 /// Start a workflow step execution for a chat.
 ///
 /// This triggers the executor to run on the chat's initial prompt.
@@ -3,20 +3,14 @@
 //! These commands provide the IPC interface for executor profile management
 //! and running AI coding CLI tools (Claude Code, Gemini CLI, etc.).
 
-use std::io::{BufRead, BufReader};
-use std::sync::{Arc, LazyLock};
-
-use regex::Regex;
-use serde::{Deserialize, Serialize};
-use tauri::{Emitter, State};
+use tauri::State;
 
+use crate::claude_events;
 use crate::commands::AppState;
 use openflow_contracts::{
-    CreateExecutorProfileRequest, ExecutionProcess, ExecutorProfile, ProcessStatus,
-    ProcessStatusEvent, UpdateExecutorProfileRequest,
+    CreateExecutorProfileRequest, ExecutionProcess, ExecutorProfile, UpdateExecutorProfileRequest,
 };
-use openflow_core::services::process::ProcessService;
-use openflow_core::services::{executor, executor_profile};
+use openflow_core::services::{executor, executor_profile, worktree};
 
 /// List all executor profiles.
 ///
@@ -101,54 +95,6 @@ pub async fn delete_executor_profile(state: State<'_, AppState>, id: String) ->
         .map_err(|e| e.to_string())
 }
 
-/// Claude Code stream-json event types.
-/// These match the JSON lines output from `claude --output-format stream-json`.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(tag = "type", rename_all = "snake_case")]
-pub enum ClaudeEvent {
-    #[serde(rename = "system")]
-    System {
-        subtype: String,
-        /// Session ID from Claude Code (present in "init" subtype events)
-        #[serde(default)]
-        session_id: Option<String>,
-        #[serde(flatten)]
-        data: serde_json::Value,
-    },
-    #[serde(rename = "assistant")]
-    Assistant { message: AssistantMessage },
-    #[serde(rename = "user")]
-    User { message: UserMessage },
-    #[serde(rename = "result")]
-    Result {
-        subtype: String,
-        #[serde(flatten)]
-        data: serde_json::Value,
-    },
-}
-
-/// Assistant message - content is an array of content blocks (text, tool_use, etc.)
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AssistantMessage {
-    /// Content blocks array - can contain text blocks, tool_use blocks, etc.
-    #[serde(default)]
-    pub content: Option<serde_json::Value>,
-    /// Additional fields we pass through
-    #[serde(flatten)]
-    pub extra: serde_json::Value,
-}
-
-/// User message - content is an array of content blocks (tool_result, etc.)
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct UserMessage {
-    /// Content blocks array - typically contains tool_result blocks
-    #[serde(default)]
-    pub content: Option<serde_json::Value>,
-    /// Additional fields we pass through
-    #[serde(flatten)]
-    pub extra: serde_json::Value,
-}
-
 /// Prepare and start an executor process.
 ///
 /// This helper encapsulates the prepare and start steps for cleaner command handlers.
@@ -158,11 +104,23 @@ async fn prepare_and_start_executor(
     prompt: String,
     executor_profile_id: Option<String>,
 ) -> Result<ExecutionProcess, String> {
-    let pool = state.db.lock().await;
+    // Use a cloned pool rather than the `db` guard: bootstrap waits for the
+    // setup script, which would otherwise block every other command
+    let pool = state.get_pool().clone();
     let context = executor::prepare(&pool, &chat_id, &prompt, executor_profile_id)
         .await
         .map_err(|e| e.to_string())?;
 
+    // Copy files and run the setup script before the agent starts
+    worktree::bootstrap(
+        &pool,
+        &state.process_service,
+        &context.chat,
+        &context.project,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
     state
         .process_service
         .start(&pool, context.create_request, context.start_request)
@@ -173,7 +131,9 @@ async fn prepare_and_start_executor(
 /// Run an executor (AI coding CLI) for a chat session.
 ///
 /// Spawns a CLI process using the specified executor profile and sends the
-/// prompt to the AI coding agent. Output is streamed via Tauri events.
+/// prompt to the AI coding agent. Output is streamed via Tauri events; the
+/// Claude events are parsed from the output the process service broadcasts
+/// (see `claude_events`).
 #[tauri::command]
 pub async fn run_executor(
     state: State<'_, AppState>,
@@ -183,154 +143,17 @@ pub async fn run_executor(
     executor_profile_id: Option<String>,
 ) -> Result<ExecutionProcess, String> {
     let process = prepare_and_start_executor(&state, chat_id, prompt, executor_profile_id).await?;
-    spawn_json_output_streamer(
-        Arc::clone(&state.process_service),
-        app_handle,
-        process.id.clone(),
-    );
-    Ok(process)
-}
-
-/// Permission request event emitted when Claude asks for permission.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct PermissionRequestEvent {
-    pub process_id: String,
-    pub tool_name: String,
-    pub file_path: Option<String>,
-    pub description: String,
-}
-
-/// Spawns a background task to read JSON lines from the PTY and emit Tauri events.
-fn spawn_json_output_streamer(
-    process_service: Arc<ProcessService>,
-    app_handle: tauri::AppHandle,
-    process_id: String,
-) {
-    std::thread::spawn(move || {
-        let pty_manager = process_service.pty_manager();
 
-        // Get reader from PTY
-        let reader = match pty_manager.try_clone_reader(&process_id) {
-            Ok(r) => BufReader::new(r),
-            Err(e) => {
-                eprintln!("Failed to get PTY reader for {}: {}", process_id, e);
-                return;
-            }
-        };
-
-        let event_channel = format!("claude-event-{}", process_id);
-        let raw_channel = format!("raw-output-{}", process_id);
-        let permission_channel = format!("permission-request-{}", process_id);
-        let session_channel = format!("session-id-{}", process_id);
-
-        // Read line by line (each line should be a JSON object)
-        for line in reader.lines() {
-            let line = match line {
-                Ok(l) => l,
-                Err(_) => break, // EOF or error
-            };
-
-            // Strip ANSI escape codes and control characters
-            let clean_line = strip_ansi_codes(&line);
-            let trimmed = clean_line.trim();
-
-            if trimmed.is_empty() {
-                continue;
-            }
-
-            // Check for permission prompts (non-JSON lines from Claude Code)
-            // Claude Code shows prompts like: "Allow Claude to write to file.txt? (y/n)"
-            if trimmed.contains("Allow")
-                && (trimmed.contains("(y/n)") || trimmed.contains("? [y/n]"))
-            {
-                // Extract tool name and file path from permission prompt
-                let permission_event = PermissionRequestEvent {
-                    process_id: process_id.clone(),
-                    tool_name: extract_tool_name(trimmed),
-                    file_path: extract_file_path(trimmed),
-                    description: trimmed.to_string(),
-                };
-                let _ = app_handle.emit(&permission_channel, &permission_event);
-                continue;
-            }
-
-            // Try to parse as ClaudeEvent
-            if let Ok(event) = serde_json::from_str::<ClaudeEvent>(trimmed) {
-                // Check for session ID in system "init" events and emit it
-                if let ClaudeEvent::System {
-                    subtype,
-                    session_id: Some(ref sid),
-                    ..
-                } = &event
-                {
-                    if subtype == "init" {
-                        let _ = app_handle.emit(&session_channel, sid);
-                    }
-                }
-                let _ = app_handle.emit(&event_channel, &event);
-            } else {
-                // Fallback: emit raw line for unparsed output (already cleaned)
-                let _ = app_handle.emit(&raw_channel, trimmed);
-            }
-        }
-
-        // Emit completion status
-        let status_channel = format!("process-status-{}", process_id);
-        let status_event = ProcessStatusEvent {
-            process_id: process_id.clone(),
-            status: ProcessStatus::Completed,
-            exit_code: Some(0),
-        };
-        let _ = app_handle.emit(&status_channel, &status_event);
-    });
-}
-
-/// Extract tool name from permission prompt.
-fn extract_tool_name(prompt: &str) -> String {
-    // Common patterns: "Allow Claude to write", "Allow Claude to read", "Allow Claude to execute"
-    if prompt.contains("write") || prompt.contains("Write") {
-        "Write".to_string()
-    } else if prompt.contains("read") || prompt.contains("Read") {
-        "Read".to_string()
-    } else if prompt.contains("execute")
-        || prompt.contains("Execute")
-        || prompt.contains("bash")
-        || prompt.contains("Bash")
-    {
-        "Bash".to_string()
-    } else {
-        "Tool".to_string()
-    }
-}
-
-/// Extract file path from permission prompt.
-fn extract_file_path(prompt: &str) -> Option<String> {
-    // Look for path-like strings (starting with / or containing common path patterns)
-    // This is a simple heuristic - Claude Code prompts often include the file path
-    for word in prompt.split_whitespace() {
-        let word = word.trim_matches(|c| c == '?' || c == '"' || c == '\'' || c == '`');
-        if word.starts_with('/') || word.contains(":\\") {
-            return Some(word.to_string());
-        }
-    }
-    None
-}
-
-/// Regex pattern for ANSI escape sequences.
-/// Matches: CSI sequences, OSC sequences, and other escape sequences.
-static ANSI_REGEX: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(concat!(
-        r"\x1b\[[0-9;]*[A-Za-z]",            // CSI sequences (colors, cursor, etc.)
-        r"|\x1b\][^\x07]*\x07",              // OSC sequences (terminated by BEL)
-        r"|\x1b[PX^_][^\x1b]*\x1b\\",        // DCS/SOS/PM/APC sequences
-        r"|\x1b[\[\]()#;?]*[0-9;]*[A-Za-z]", // Other escape sequences
-        r"|\x1b.",                           // Simple escape sequences
-        r"|[\x00-\x08\x0b\x0c\x0e-\x1f]",    // Control characters (except newline, tab, CR)
-    ))
-    .expect("Invalid ANSI regex pattern")
-});
+    // Output broadcast before watching started is picked up from the snapshot
+    state.claude_events.watch(&process.id);
+    let snapshot = state.process_service.snapshot(&process.id).await.ok();
+    let outputs = state.claude_events.catch_up(
+        &process.id,
+        snapshot
+            .as_ref()
+            .map(|snapshot| (snapshot.content.as_str(), snapshot.offset as u64)),
+    );
+    claude_events::emit(&app_handle, &process.id, outputs);
 
-/// Strip ANSI escape codes and control characters from a string.
-fn strip_ansi_codes(s: &str) -> String {
-    ANSI_REGEX.replace_all(s, "").to_string()
+    Ok(process)
 }
@@ -28,6 +28,7 @@ pub mod messages;
 pub mod processes;
 pub mod projects;
 pub mod search;
+pub mod secrets;
 pub mod settings;
 pub mod system;
 pub mod tasks;
@@ -38,8 +39,10 @@ use sqlx::SqlitePool;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
+use crate::claude_events::ClaudeEventStreams;
 use openflow_core::events::EventBroadcaster;
 use openflow_core::services::process::ProcessService;
+use openflow_core::services::secret::SecretCipher;
 
 /// Application state shared across all Tauri commands.
 ///
@@ -61,6 +64,8 @@ pub struct AppState {
     pub process_service: Arc<ProcessService>,
     /// Event broadcaster for real-time updates to frontend.
     pub broadcaster: Arc<dyn EventBroadcaster>,
+    /// Claude runs whose broadcast output is parsed into Claude events.
+    pub claude_events: Arc<ClaudeEventStreams>,
 }
 
 impl AppState {
@@ -70,14 +75,25 @@ impl AppState {
     /// and one is kept for sharing with the embedded HTTP server.
     ///
     /// The broadcaster is passed to the ProcessService so it can emit
-    /// real-time events for process output and status changes.
-    pub fn new(pool: SqlitePool, broadcaster: Arc<dyn EventBroadcaster>) -> Self {
+    /// real-time events for process output and status changes. The secret
+    /// cipher lets it resolve `${secret:NAME}` references. `claude_events`
+    /// must be the streams the broadcaster feeds.
+    pub fn new(
+        pool: SqlitePool,
+        broadcaster: Arc<dyn EventBroadcaster>,
+        secret_cipher: SecretCipher,
+        claude_events: Arc<ClaudeEventStreams>,
+    ) -> Self {
         Self {
             db: Arc::new(Mutex::new(pool.clone())),
             pool,
             // Pass broadcaster to ProcessService so it can emit output/status events
-            process_service: Arc::new(ProcessService::with_broadcaster(Arc::clone(&broadcaster))),
+            process_service: Arc::new(
+                ProcessService::with_broadcaster(Arc::clone(&broadcaster))
+                    .with_secret_cipher(secret_cipher),
+            ),
             broadcaster,
+            claude_events,
         }
     }
 
@@ -107,6 +123,7 @@ pub use messages::*;
 pub use processes::*;
 pub use projects::*;
 pub use search::*;
+pub use secrets::*;
 pub use settings::*;
 pub use system::*;
 pub use tasks::*;
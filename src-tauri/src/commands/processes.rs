@@ -5,17 +5,20 @@
 //!
 //! Process management includes:
 //! - Retrieving process information
-//! - Listing processes (all, by chat, running)
+//! - Listing processes (all, by chat, running) and the run queue
+//! - Replaying persisted process output
 //! - Killing running processes
 //! - Sending input to PTY processes
 //! - Resizing PTY windows
+//! - Snapshotting PTY output for reattaching to a running terminal
+//! - Exporting asciicast recordings of PTY sessions
 //! - Deleting process records
 
 use tauri::{AppHandle, Emitter, State};
 
 use crate::commands::AppState;
-use openflow_contracts::ExecutionProcess;
-use openflow_core::services::process;
+use openflow_contracts::{ExecutionProcess, ProcessOutputChunk, ProcessSnapshot, QueuedProcess};
+use openflow_core::services::{process, recording};
 
 /// Get a process by ID.
 ///
@@ -81,6 +84,23 @@ pub async fn list_running_processes(
         .map_err(|e| e.to_string())
 }
 
+/// Get persisted output for a process.
+///
+/// Returns output chunks in arrival order. Pass the `id` of the last chunk
+/// received as `since` to fetch only newer output.
+#[tauri::command]
+pub async fn get_process_output(
+    state: State<'_, AppState>,
+    id: String,
+    since: Option<i64>,
+    limit: Option<i32>,
+) -> Result<Vec<ProcessOutputChunk>, String> {
+    let pool = state.db.lock().await;
+    process::list_output(&pool, &id, since, limit)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 /// Kill a running process.
 ///
 /// This will attempt to terminate the process (either PTY or standard process),
@@ -138,6 +158,37 @@ pub async fn resize_process(
         .map_err(|e| e.to_string())
 }
 
+/// Get the recent output and size of a running PTY process.
+///
+/// Lets a view opened mid-run render the terminal before it starts
+/// following live output events.
+#[tauri::command]
+pub async fn get_process_snapshot(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<ProcessSnapshot, String> {
+    state
+        .process_service
+        .snapshot(&id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Export the asciicast v2 recording of a PTY process.
+///
+/// Returns the contents of a `.cast` file. Fails if the process was not
+/// recorded.
+#[tauri::command]
+pub async fn export_process_recording(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<String, String> {
+    let pool = state.db.lock().await;
+    recording::export(&pool, &id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 /// Check if a process is currently running.
 ///
 /// Returns true if the process is being tracked as running by the ProcessService.
@@ -157,6 +208,14 @@ pub async fn running_process_count(state: State<'_, AppState>) -> Result<usize,
     Ok(state.process_service.running_count().await)
 }
 
+/// List coding agent runs waiting in the run queue.
+///
+/// Returns queued runs in the order they will start, with their positions.
+#[tauri::command]
+pub async fn list_run_queue(state: State<'_, AppState>) -> Result<Vec<QueuedProcess>, String> {
+    Ok(state.process_service.queue().await)
+}
+
 /// Delete a process record.
 ///
 /// Removes the process from the database. This only affects the database record,
@@ -0,0 +1,46 @@
+//! Tauri commands for secret operations.
+//!
+//! These commands provide the IPC interface for the encrypted secrets store.
+//! Secrets are write-only: names can be listed and values set or deleted,
+//! but values are never returned. Processes reference them as
+//! `${secret:NAME}`.
+
+use tauri::State;
+
+use crate::commands::AppState;
+use openflow_contracts::{Secret, SetSecretRequest};
+use openflow_core::services::secret;
+
+/// List all secrets by name.
+#[tauri::command]
+pub async fn list_secrets(state: State<'_, AppState>) -> Result<Vec<Secret>, String> {
+    let pool = state.db.lock().await;
+    secret::list(&pool).await.map_err(|e| e.to_string())
+}
+
+/// Create or replace a secret.
+///
+/// Only the name and timestamps are returned.
+#[tauri::command]
+pub async fn set_secret(
+    state: State<'_, AppState>,
+    request: SetSecretRequest,
+) -> Result<Secret, String> {
+    let pool = state.db.lock().await;
+    let cipher = state
+        .process_service
+        .secret_cipher()
+        .map_err(|e| e.to_string())?;
+    secret::set(&pool, cipher, request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Delete a secret by name.
+#[tauri::command]
+pub async fn delete_secret(state: State<'_, AppState>, name: String) -> Result<(), String> {
+    let pool = state.db.lock().await;
+    secret::delete(&pool, &name)
+        .await
+        .map_err(|e| e.to_string())
+}
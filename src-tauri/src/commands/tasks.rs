@@ -3,11 +3,16 @@
 //! These commands provide the IPC interface for task CRUD operations.
 //! Each command is a thin wrapper around openflow_core::services::task functions.
 
+use std::sync::Arc;
+
 use tauri::State;
 
 use crate::commands::AppState;
-use openflow_contracts::{CreateTaskRequest, Task, TaskStatus, TaskWithChats, UpdateTaskRequest};
-use openflow_core::services::task;
+use openflow_contracts::{
+    Chat, CleanupWorktreeRequest, CreateTaskRequest, ExecutionProcess, Task, TaskStatus,
+    TaskWithChats, UpdateTaskRequest, WorkflowRun,
+};
+use openflow_core::services::{task, workflow_runner, worktree};
 
 /// List tasks for a project with optional filters.
 ///
@@ -44,13 +49,17 @@ pub async fn get_task(state: State<'_, AppState>, id: String) -> Result<TaskWith
 
 /// Create a new task.
 ///
-/// Returns the newly created task with generated ID and timestamps.
+/// Values for the workflow template's variables are checked first. Returns
+/// the newly created task with generated ID and timestamps.
 #[tauri::command]
 pub async fn create_task(
     state: State<'_, AppState>,
     request: CreateTaskRequest,
 ) -> Result<Task, String> {
     let pool = state.db.lock().await;
+    workflow_runner::check_new_task(&pool, &request)
+        .await
+        .map_err(|e| e.to_string())?;
     task::create(&pool, request)
         .await
         .map_err(|e| e.to_string())
@@ -66,7 +75,85 @@ pub async fn update_task(
     request: UpdateTaskRequest,
 ) -> Result<Task, String> {
     let pool = state.db.lock().await;
-    task::update(&pool, &id, request)
+    let finishing = request.status.as_ref().is_some_and(TaskStatus::is_terminal);
+    let task = task::update(&pool, &id, request)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    // Reclaim the task's worktrees in the background, keeping branches
+    if finishing {
+        let pool = pool.clone();
+        let process_service = Arc::clone(&state.process_service);
+        let task_id = task.id.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = worktree::cleanup_task(&pool, &process_service, &task_id, false).await {
+                eprintln!("Failed to clean up worktrees for task {}: {}", task_id, e);
+            }
+        });
+    }
+
+    Ok(task)
+}
+
+/// Clean up the worktrees of all chats of a task.
+///
+/// Runs the project's cleanup script in each worktree and removes it.
+/// Branches are kept unless `delete_branch` is set in the request.
+#[tauri::command]
+pub async fn cleanup_task_worktrees(
+    state: State<'_, AppState>,
+    id: String,
+    request: Option<CleanupWorktreeRequest>,
+) -> Result<Vec<Chat>, String> {
+    // Cleanup scripts can run for a while, so don't hold the `db` guard
+    let pool = state.get_pool().clone();
+    let request = request.unwrap_or_default();
+    worktree::cleanup_task(&pool, &state.process_service, &id, request.delete_branch)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Get the workflow run of a task.
+///
+/// Returns an error if the task's workflow has not been started.
+#[tauri::command]
+pub async fn get_task_workflow(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<WorkflowRun, String> {
+    let pool = state.db.lock().await;
+    workflow_runner::get(&pool, &id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Start or resume the workflow of a task.
+///
+/// Creates the step chats on first use and runs the current step.
+#[tauri::command]
+pub async fn start_task_workflow(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<WorkflowRun, String> {
+    // Starting steps provisions worktrees and runs setup scripts, so don't
+    // hold the `db` guard
+    let pool = state.get_pool().clone();
+    workflow_runner::start(&pool, &state.process_service, &id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Run a step of a task's workflow in its chat.
+#[tauri::command]
+pub async fn run_task_workflow_step(
+    state: State<'_, AppState>,
+    id: String,
+    step_index: i32,
+) -> Result<ExecutionProcess, String> {
+    // The step's worktree may need provisioning first, so don't hold the
+    // `db` guard
+    let pool = state.get_pool().clone();
+    workflow_runner::run_step(&pool, &state.process_service, &id, step_index)
         .await
         .map_err(|e| e.to_string())
 }
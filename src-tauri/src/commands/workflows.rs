@@ -9,7 +9,9 @@ use std::path::Path;
 
 use tauri::State;
 
-use openflow_contracts::{WorkflowContext, WorkflowStep, WorkflowTemplate};
+use openflow_contracts::{
+    WorkflowContext, WorkflowDiagnostic, WorkflowStep, WorkflowTemplate, WorkflowVariableInfo,
+};
 use openflow_core::services::{project, workflow};
 
 use super::AppState;
@@ -96,6 +98,11 @@ pub async fn get_workflow_template(
 #[tauri::command]
 pub async fn parse_workflow_content(content: String) -> Result<WorkflowTemplate, String> {
     let steps = workflow::parse(&content).map_err(|e| e.to_string())?;
+    let error_count = workflow::lint(&content)
+        .map_err(|e| e.to_string())?
+        .iter()
+        .filter(|d| d.is_error())
+        .count();
 
     // Create a temporary template for the parsed content
     let now = chrono::Utc::now().to_rfc3339();
@@ -106,6 +113,7 @@ pub async fn parse_workflow_content(content: String) -> Result<WorkflowTemplate,
         content,
         is_builtin: false,
         steps,
+        error_count: error_count as i32,
         created_at: now.clone(),
         updated_at: now,
     })
@@ -120,6 +128,24 @@ pub async fn parse_workflow_steps(content: String) -> Result<Vec<WorkflowStep>,
     workflow::parse(&content).map_err(|e| e.to_string())
 }
 
+/// List the variables of workflow markdown content.
+///
+/// Reports the variables declared in the front-matter and every
+/// `{@variable_name}` placeholder the content uses.
+#[tauri::command]
+pub async fn list_workflow_variables(content: String) -> Result<Vec<WorkflowVariableInfo>, String> {
+    workflow::list_variables(&content).map_err(|e| e.to_string())
+}
+
+/// Lint workflow markdown content.
+///
+/// Returns errors and warnings with their line and column, such as step
+/// headers with typos that parsing would silently skip.
+#[tauri::command]
+pub async fn lint_workflow(content: String) -> Result<Vec<WorkflowDiagnostic>, String> {
+    workflow::lint(&content).map_err(|e| e.to_string())
+}
+
 /// Substitute variables in workflow content using a HashMap.
 ///
 /// Variables are in format: {@variable_name}
@@ -61,11 +61,14 @@ fn cleanup_mcp_socket() {
 }
 
 pub mod broadcaster;
+pub mod claude_events;
 pub mod commands;
 
 use broadcaster::TauriBroadcaster;
+use claude_events::ClaudeEventStreams;
 use commands::AppState;
-use openflow_core::services::process::ProcessService;
+use openflow_core::services::process::{OrphanPolicy, ProcessService};
+use openflow_core::services::secret::SecretCipher;
 use openflow_db::{init_db_with_seeder, DbConfig};
 use openflow_server::ws::WsBroadcaster;
 use openflow_server::{ClientManager, ServerConfig};
@@ -131,11 +134,44 @@ pub fn run() {
                 .expect("Failed to initialize database")
             });
 
-            // Create the event broadcaster using Tauri's event system
-            let broadcaster = TauriBroadcaster::arc(app.handle().clone());
+            // Create the event broadcaster using Tauri's event system. It also
+            // parses the output of Claude runs into Claude events.
+            let claude_events = Arc::new(ClaudeEventStreams::new());
+            let broadcaster = Arc::new(
+                TauriBroadcaster::new(app.handle().clone())
+                    .with_claude_events(Arc::clone(&claude_events)),
+            );
+
+            // Load (or create) the secrets key in the app data directory
+            let secret_cipher =
+                SecretCipher::from_directory(&app_data_dir).expect("Failed to load secrets key");
 
             // Create the application state
-            let state = AppState::new(pool, broadcaster);
+            let state = AppState::new(pool, broadcaster, secret_cipher.clone(), claude_events);
+
+            // Reconcile processes left running by a previous session (e.g. after a crash).
+            // This runs once here, before the embedded HTTP server starts its own
+            // ProcessService, so its processes are not mistaken for orphans.
+            tauri::async_runtime::block_on(async {
+                let pool = state.get_pool();
+                let orphan_policy = OrphanPolicy::from_settings(pool).await.unwrap_or_default();
+                match state
+                    .process_service
+                    .reconcile_orphans(pool, orphan_policy)
+                    .await
+                {
+                    Ok(report) if report.total() > 0 => println!(
+                        "Reconciled {} orphaned process(es): failed={} killed={} adopted={} cancelled={}",
+                        report.total(),
+                        report.failed.len(),
+                        report.killed.len(),
+                        report.adopted.len(),
+                        report.cancelled.len()
+                    ),
+                    Ok(_) => {}
+                    Err(e) => eprintln!("Failed to reconcile orphaned processes: {}", e),
+                }
+            });
 
             // Extract shared resources for the HTTP server
             let http_pool = state.get_pool().clone();
@@ -170,8 +206,10 @@ pub fn run() {
                 let ws_broadcaster = WsBroadcaster::arc(client_manager.clone());
 
                 // Create ProcessService with WsBroadcaster for HTTP/WebSocket clients
-                let http_process_service =
-                    Arc::new(ProcessService::with_broadcaster(ws_broadcaster.clone()));
+                let http_process_service = Arc::new(
+                    ProcessService::with_broadcaster(ws_broadcaster.clone())
+                        .with_secret_cipher(secret_cipher),
+                );
 
                 if let Err(e) = openflow_server::start_embedded_server(
                     http_pool,
@@ -215,6 +253,10 @@ pub fn run() {
             commands::unarchive_task,
             commands::delete_task,
             commands::duplicate_task,
+            commands::cleanup_task_worktrees,
+            commands::get_task_workflow,
+            commands::start_task_workflow,
+            commands::run_task_workflow_step,
             // Chat commands
             commands::list_chats,
             commands::list_standalone_chats,
@@ -227,6 +269,12 @@ pub fn run() {
             commands::archive_chat,
             commands::unarchive_chat,
             commands::toggle_step_complete,
+            commands::cleanup_chat_worktree,
+            commands::verify_chat,
+            commands::list_chat_verifications,
+            commands::get_dev_server,
+            commands::start_dev_server,
+            commands::stop_dev_server,
             commands::start_workflow_step,
             // Message commands
             commands::list_messages,
@@ -259,6 +307,10 @@ pub fn run() {
             commands::get_settings_by_prefix,
             commands::delete_all_settings,
             commands::set_many_settings,
+            // Secret commands
+            commands::list_secrets,
+            commands::set_secret,
+            commands::delete_secret,
             // Search commands
             commands::search,
             commands::search_simple,
@@ -267,6 +319,7 @@ pub fn run() {
             commands::search_in_project,
             // Process commands
             commands::get_process,
+            commands::get_process_output,
             commands::list_all_processes,
             commands::list_processes,
             commands::list_all_running_processes,
@@ -274,8 +327,11 @@ pub fn run() {
             commands::kill_process,
             commands::send_process_input,
             commands::resize_process,
+            commands::get_process_snapshot,
+            commands::export_process_recording,
             commands::is_process_running,
             commands::running_process_count,
+            commands::list_run_queue,
             commands::delete_process,
             // Git commands
             commands::create_worktree,
@@ -310,6 +366,8 @@ pub fn run() {
             commands::get_workflow_template,
             commands::parse_workflow_content,
             commands::parse_workflow_steps,
+            commands::list_workflow_variables,
+            commands::lint_workflow,
             commands::substitute_workflow_variables,
             commands::substitute_workflow_with_context,
             // System commands